@@ -0,0 +1,300 @@
+// LogSleuth - app/alerts.rs
+//
+// Live alert rule storage and actions.
+//
+// Design principles:
+// - Rules (`core::alert::AlertRule`) live in `alert_rules.toml` next to
+//   `config.toml`, as an array of `[[rule]]` tables, so they can be written
+//   or shared by hand.  Saves are atomic (write temp, rename) like presets.
+// - Every firing is appended to `alerts.log` next to the rules file, one
+//   tab-separated line per firing, so alerts that fired while nobody was
+//   watching can be reviewed later.
+// - A rule's command is run directly (no shell) on a background thread with
+//   the entry as JSON on stdin; its output is discarded.
+//
+// Rule 11 compliance:
+//   - At most MAX_ALERT_RULES rules are loaded; extra rules are ignored.
+//   - The alert log is moved to `alerts.log.1` once it reaches
+//     MAX_ALERT_LOG_BYTES, so at most two logs' worth is kept.
+//   - At most MAX_ALERT_COMMANDS_IN_FLIGHT commands run at once, and each is
+//     killed after ALERT_COMMAND_TIMEOUT_SECS.
+
+use crate::core::alert::AlertRule;
+use crate::core::model::LogEntry;
+use crate::util::constants::{
+    ALERT_COMMAND_TIMEOUT_SECS, ALERT_LOG_FILE_NAME, ALERT_RULES_FILE_NAME,
+    MAX_ALERT_COMMANDS_IN_FLIGHT, MAX_ALERT_LOG_BYTES, MAX_ALERT_RULES,
+};
+use crate::util::error::ConfigError;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// On-disk shape: an array of `[[rule]]` tables.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RuleFile {
+    #[serde(default, rename = "rule")]
+    rules: Vec<AlertRule>,
+}
+
+/// Commands currently running.
+static COMMANDS_IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// Resolve the rules file path (next to `config.toml`, like the presets file).
+pub fn rules_path(config_dir: &Path) -> PathBuf {
+    config_dir
+        .parent()
+        .unwrap_or(config_dir)
+        .join(ALERT_RULES_FILE_NAME)
+}
+
+/// Resolve the alert log path (next to the rules file).
+pub fn log_path(config_dir: &Path) -> PathBuf {
+    config_dir
+        .parent()
+        .unwrap_or(config_dir)
+        .join(ALERT_LOG_FILE_NAME)
+}
+
+/// Load rules from a TOML file.  A missing file yields an empty list (first
+/// run).  Rules are normalised; rules beyond `MAX_ALERT_RULES` are dropped.
+pub fn load(path: &Path) -> Result<Vec<AlertRule>, ConfigError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(ConfigError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+    let file: RuleFile = toml::from_str(&content).map_err(|source| ConfigError::TomlParse {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut rules = file.rules;
+    if rules.len() > MAX_ALERT_RULES {
+        tracing::warn!(
+            path = %path.display(),
+            dropped = rules.len() - MAX_ALERT_RULES,
+            max = MAX_ALERT_RULES,
+            "Alert rule file exceeds the rule limit; extra rules ignored"
+        );
+        rules.truncate(MAX_ALERT_RULES);
+    }
+    rules.iter_mut().for_each(AlertRule::normalize);
+    Ok(rules)
+}
+
+/// Save `rules` to `path` atomically (write temp, rename), creating parent
+/// directories as needed.
+pub fn save(path: &Path, rules: &[AlertRule]) -> Result<(), ConfigError> {
+    let io_err = |source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let file = RuleFile {
+        rules: rules.to_vec(),
+    };
+    let text = toml::to_string_pretty(&file).map_err(|source| ConfigError::TomlSerialize {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(io_err)?;
+    }
+    let tmp = path.with_extension("toml.tmp");
+    std::fs::write(&tmp, text.as_bytes()).map_err(io_err)?;
+    std::fs::rename(&tmp, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        io_err(e)
+    })?;
+    tracing::debug!(path = %path.display(), count = rules.len(), "Alert rules saved");
+    Ok(())
+}
+
+/// Append one firing to the alert log at `path`:
+/// `time <TAB> rule <TAB> threshold <TAB> file:line <TAB> first message line`.
+pub fn append_log(
+    path: &Path,
+    rule: &AlertRule,
+    entry: &LogEntry,
+    fired_at: DateTime<Utc>,
+) -> std::io::Result<()> {
+    if std::fs::metadata(path).is_ok_and(|m| m.len() >= MAX_ALERT_LOG_BYTES) {
+        let mut rotated = path.as_os_str().to_owned();
+        rotated.push(".1");
+        std::fs::rename(path, rotated)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    let message = entry.message().lines().next().unwrap_or("");
+    writeln!(
+        file,
+        "{}\t{}\t{}\t{}:{}\t{}",
+        fired_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        rule.name.replace('\t', " "),
+        rule.describe_threshold(),
+        entry.source_file.display(),
+        entry.line_number,
+        message.replace('\t', " "),
+    )
+}
+
+/// Run `command` (program and arguments) on a background thread with
+/// `entry` as JSON on stdin.  Returns `false` without starting it when
+/// MAX_ALERT_COMMANDS_IN_FLIGHT commands are still running.
+pub fn run_command(rule_name: &str, command: &[String], entry: &LogEntry) -> bool {
+    let Some((program, args)) = command.split_first() else {
+        return true;
+    };
+    let running = COMMANDS_IN_FLIGHT.fetch_add(1, Ordering::SeqCst);
+    if running >= MAX_ALERT_COMMANDS_IN_FLIGHT {
+        COMMANDS_IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+        tracing::warn!(
+            rule = rule_name,
+            max = MAX_ALERT_COMMANDS_IN_FLIGHT,
+            "Alert command skipped: too many still running"
+        );
+        return false;
+    }
+    let json = serde_json::to_vec(entry).unwrap_or_default();
+    let rule_name = rule_name.to_string();
+    let program = program.clone();
+    let args = args.to_vec();
+    std::thread::spawn(move || {
+        run_to_completion(&rule_name, &program, &args, &json);
+        COMMANDS_IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+    });
+    true
+}
+
+/// Spawn the command, feed it `stdin`, and wait for it to exit, killing it
+/// after ALERT_COMMAND_TIMEOUT_SECS.  Failures are logged, never fatal.
+fn run_to_completion(rule_name: &str, program: &str, args: &[String], stdin: &[u8]) {
+    let mut child = match Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::warn!(rule = rule_name, program, error = %e, "Alert command failed to start");
+            return;
+        }
+    };
+    if let Some(mut pipe) = child.stdin.take() {
+        // A command that ignores its input may close stdin early.
+        let _ = pipe.write_all(stdin);
+    }
+    let deadline = Instant::now() + Duration::from_secs(ALERT_COMMAND_TIMEOUT_SECS);
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                tracing::debug!(rule = rule_name, program, %status, "Alert command finished");
+                return;
+            }
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Ok(None) => {
+                tracing::warn!(rule = rule_name, program, "Alert command timed out; killed");
+                let _ = child.kill();
+                let _ = child.wait();
+                return;
+            }
+            Err(e) => {
+                tracing::warn!(rule = rule_name, program, error = %e, "Alert command wait failed");
+                return;
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Unit tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::model::{MessageText, Severity};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    /// A hand-written rules file only needs a name and query; save and load
+    /// round-trip every field.
+    #[test]
+    fn test_rules_load_minimal_file_and_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(ALERT_RULES_FILE_NAME);
+        std::fs::write(
+            &path,
+            "[[rule]]\nname = \" DB timeouts \"\nquery = \"severity>=error AND timeout\"\n",
+        )
+        .unwrap();
+        let mut rules = load(&path).expect("minimal file must parse");
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].name, "DB timeouts");
+        assert_eq!(rules[0].threshold, 3);
+        assert_eq!(rules[0].window_secs, 60);
+        assert!(rules[0].enabled);
+
+        rules[0].command = vec!["notify-send".to_string(), "LogSleuth".to_string()];
+        rules[0].enabled = false;
+        save(&path, &rules).expect("save should succeed");
+        assert_eq!(load(&path).expect("reload"), rules);
+        assert!(load(&dir.path().join("none.toml")).unwrap().is_empty());
+    }
+
+    /// Firings are appended one per line and the log is rotated at the cap.
+    #[test]
+    fn test_alert_log_appends_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(ALERT_LOG_FILE_NAME);
+        let text = "connection timeout\n\tat Pool.get";
+        let entry = LogEntry {
+            id: 7,
+            timestamp: None,
+            severity: Severity::Error,
+            source_file: Arc::from(Path::new("/logs/app.log")),
+            line_number: 42,
+            thread: None,
+            component: None,
+            message: MessageText::slice_of(text, 0..text.len()),
+            raw_text: text.to_string(),
+            profile_id: Arc::from("plain-text"),
+            fields: Default::default(),
+            file_modified: None,
+        };
+        let rule = AlertRule::new("Timeouts", "timeout");
+        let at = chrono::TimeZone::with_ymd_and_hms(&Utc, 2024, 1, 15, 14, 30, 0).unwrap();
+        append_log(&path, &rule, &entry, at).unwrap();
+        append_log(&path, &rule, &entry, at).unwrap();
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "2024-01-15T14:30:00Z\tTimeouts\t\u{2265}3 in 60 s\t/logs/app.log:42\tconnection timeout"
+        );
+
+        std::fs::write(&path, vec![b'x'; MAX_ALERT_LOG_BYTES as usize]).unwrap();
+        append_log(&path, &rule, &entry, at).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+        assert!(dir.path().join("alerts.log.1").exists());
+    }
+}
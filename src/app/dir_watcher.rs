@@ -1,11 +1,14 @@
 // LogSleuth - app/dir_watcher.rs
 //
-// Recursive directory watcher: polls the scan directory on a background thread
-// and reports new log files that appear after the initial scan.
+// Recursive directory watcher: watches the scan directory on a background
+// thread and reports new log files that appear after the initial scan.
 //
 // Architecture:
 //   - `DirWatcher` lives on the UI thread; `run_dir_watcher` executes on a
-//     background thread polling the directory on a fixed interval.
+//     background thread.  New files and writes are reported by native change
+//     events (`app::fs_watch`); the tree is still walked on a fixed interval:
+//     the poll interval when events are unavailable (network paths), a slow
+//     safety-net interval otherwise.
 //   - An `Arc<AtomicBool>` cancel flag allows the UI to stop the watcher.
 //   - New file paths are sent as `DirWatchProgress::NewFiles` over an mpsc channel.
 //   - mtime changes to existing files are sent as `DirWatchProgress::FileMtimeUpdates`
@@ -19,6 +22,7 @@
 //   - `known_paths` is updated immediately after each `NewFiles` send so
 //     subsequent polls do not re-report the same files.
 
+use crate::app::fs_watch::{FsChanges, FsWatcher};
 use crate::core::model::DirWatchProgress;
 use crate::util::constants::{
     DIR_WATCH_CANCEL_CHECK_INTERVAL_MS, DIR_WATCH_WALK_TIMEOUT_SECS,
@@ -30,7 +34,7 @@ use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc};
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 // =============================================================================
 // Watch configuration
@@ -201,10 +205,10 @@ impl Default for DirWatcher {
 // Background thread
 // =============================================================================
 
-/// Entry point for the background directory polling thread.
+/// Entry point for the background directory watcher thread.
 ///
-/// Polls `root` every `DIR_WATCH_POLL_INTERVAL_MS` ms, looking for files that
-/// match the include patterns and are **not** in `known_paths` yet.
+/// Looks for files that match the include patterns and are **not** in
+/// `known_paths` yet, from change events and periodic tree walks.
 /// New files are sent via `tx` and immediately added to `known_paths` so they
 /// are not reported again on the next poll cycle.
 fn run_dir_watcher(
@@ -228,16 +232,20 @@ fn run_dir_watcher(
 
     let poll_interval = Duration::from_millis(config.poll_interval_ms);
     let cancel_check = Duration::from_millis(DIR_WATCH_CANCEL_CHECK_INTERVAL_MS);
-    // Number of cancel-check sub-sleeps that make up one full poll interval.
-    // The quotient is a u128; clamp to u32::MAX before casting so an extreme
-    // (misconfigured) poll interval cannot silently truncate to a tiny loop
-    // count (Rule 2: no silent truncation via `as` on fallible casts).
-    let sub_iters: u32 = u32::try_from(
-        (poll_interval.as_millis() / cancel_check.as_millis())
-            .max(1)
-            .min(u32::MAX as u128),
-    )
-    .unwrap_or(u32::MAX);
+
+    // Native change events report new files and writes as they happen; the
+    // tree walk and the mtime rotation below then only run as a safety net
+    // (FS_WATCH_RESCAN_INTERVAL_MS).  When polling (network paths) both run
+    // on their configured intervals as before.
+    let mut watcher = FsWatcher::new(std::slice::from_ref(&root), true);
+    // FSEvents reports canonical paths; map them back under `root` as given.
+    let canonical_root = std::fs::canonicalize(&root).ok().filter(|c| *c != root);
+    let walk_interval = watcher.full_check_interval(poll_interval);
+    let mut next_walk = Instant::now() + poll_interval;
+    // Known files reported written since the last mtime flush: stat'ed at
+    // most once per poll interval, however often they are written.
+    let mut written: HashSet<PathBuf> = HashSet::new();
+    let mut next_written_flush = Instant::now() + poll_interval;
 
     tracing::debug!(
         root = %root.display(),
@@ -274,10 +282,11 @@ fn run_dir_watcher(
     // delayed by one interval, which is fine).
     let mut mtime_file_list: Vec<PathBuf> = known_paths.iter().cloned().collect();
     let mut mtime_cursor: usize = 0;
-    let mtime_poll_duration = Duration::from_millis(config.mtime_poll_interval_ms);
-    let mut last_mtime_check = std::time::Instant::now()
+    let mtime_poll_duration =
+        watcher.full_check_interval(Duration::from_millis(config.mtime_poll_interval_ms));
+    let mut last_mtime_check = Instant::now()
         .checked_sub(mtime_poll_duration)
-        .unwrap_or_else(std::time::Instant::now);
+        .unwrap_or_else(Instant::now);
 
     // Receiver + start-time for the currently in-flight walk sub-thread.
     //
@@ -295,28 +304,89 @@ fn run_dir_watcher(
     // Channel closure (Disconnected) signals that the walk is finished.
     // `walk_new_count` accumulates the total new files found across all
     // batches so WalkComplete{new_count} can be sent when the walk finishes.
-    let mut walk_in_flight: Option<(mpsc::Receiver<Vec<PathBuf>>, std::time::Instant)> = None;
+    let mut walk_in_flight: Option<(mpsc::Receiver<Vec<PathBuf>>, Instant)> = None;
     let mut walk_new_count: usize = 0;
 
     loop {
-        // Sleep in small sub-intervals so cancellation is detected promptly.
-        for _ in 0..sub_iters {
-            if cancel.load(Ordering::Relaxed) {
-                tracing::debug!("Directory watcher thread: cancel flag set, exiting");
-                return;
-            }
-            std::thread::sleep(cancel_check);
-        }
-
+        // Wait in small sub-intervals so cancellation is detected promptly.
+        let changes = watcher.wait(cancel_check);
         if cancel.load(Ordering::Relaxed) {
+            tracing::debug!("Directory watcher thread: cancel flag set, exiting");
             return;
         }
 
+        // ---------------------------------------------------------------
+        // Change events: unknown paths that pass the scan's file rules are
+        // new files, reported at once; writes to known files are collected
+        // for the next mtime flush.  A rescan request walks the tree now.
+        // ---------------------------------------------------------------
+        match changes {
+            Some(FsChanges::Rescan) => next_walk = Instant::now(),
+            Some(FsChanges::Paths(paths)) => {
+                let mut new_files: Vec<PathBuf> = Vec::new();
+                for path in paths {
+                    let path = match canonical_root
+                        .as_ref()
+                        .and_then(|c| path.strip_prefix(c).ok())
+                    {
+                        Some(rest) => root.join(rest),
+                        None => path,
+                    };
+                    if known_paths.contains(&path) {
+                        written.insert(path);
+                    } else if is_new_candidate(
+                        &root,
+                        &path,
+                        &include_pats,
+                        &exclude_pats,
+                        config.max_depth,
+                        config.modified_since,
+                    ) {
+                        known_paths.insert(path.clone());
+                        mtime_file_list.push(path.clone());
+                        new_files.push(path);
+                    }
+                }
+                if !new_files.is_empty() {
+                    tracing::debug!(
+                        count = new_files.len(),
+                        "Directory watcher: new files reported"
+                    );
+                    if tx.send(DirWatchProgress::NewFiles(new_files)).is_err() {
+                        return;
+                    }
+                }
+            }
+            None => {}
+        }
+        if !written.is_empty() && Instant::now() >= next_written_flush {
+            next_written_flush = Instant::now() + poll_interval;
+            let mut mtime_updates: Vec<(PathBuf, DateTime<Utc>)> = Vec::new();
+            for path in written.drain() {
+                let Ok(mtime) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+                    continue;
+                };
+                if tracked_mtimes.insert(path.clone(), mtime) != Some(mtime) {
+                    mtime_updates.push((path, DateTime::<Utc>::from(mtime)));
+                }
+            }
+            if !mtime_updates.is_empty()
+                && tx
+                    .send(DirWatchProgress::FileMtimeUpdates(mtime_updates))
+                    .is_err()
+            {
+                return;
+            }
+        }
+
         // ---------------------------------------------------------------
         // New-file discovery via a persistent walk sub-thread.
         //
         // Pattern:
-        //   1. If no walk is in flight, snapshot known_paths and spawn one.
+        //   1. If no walk is in flight and one is due (every poll interval
+        //      when polling, every FS_WATCH_RESCAN_INTERVAL_MS with native
+        //      events, or at once on a rescan request), snapshot known_paths
+        //      and spawn one.
         //   2. Poll the in-flight receiver non-blockingly (try_recv).
         //   3. If the result is ready, process it; if not, skip new-file
         //      detection this cycle and continue to the mtime loop below.
@@ -343,7 +413,8 @@ fn run_dir_watcher(
             }
         }
 
-        if walk_in_flight.is_none() {
+        if walk_in_flight.is_none() && Instant::now() >= next_walk {
+            next_walk = Instant::now() + walk_interval;
             let known_snap = known_paths.clone();
             let root_owned = root.clone();
             let include_owned = include_pats.clone();
@@ -367,7 +438,7 @@ fn run_dir_watcher(
                 // walk_tx dropped here → channel Disconnected → main thread
                 // knows the walk is complete.
             });
-            walk_in_flight = Some((walk_rx, std::time::Instant::now()));
+            walk_in_flight = Some((walk_rx, Instant::now()));
             walk_new_count = 0;
             // Notify the UI that a walk cycle has started so it can show a
             // "scanning for new files..." indicator in the status bar.
@@ -390,20 +461,25 @@ fn run_dir_watcher(
         loop {
             match walk_in_flight.as_ref().map(|(rx, _)| rx.try_recv()) {
                 Some(Ok(batch)) => {
-                    walk_new_count += batch.len();
-                    new_files.extend(batch);
+                    // Skip files a change event reported since the walk began.
+                    let before = new_files.len();
+                    new_files.extend(batch.into_iter().filter(|p| !known_paths.contains(p)));
+                    walk_new_count += new_files.len() - before;
                 }
                 Some(Err(mpsc::TryRecvError::Empty)) => {
                     // Walk still running; come back next cycle.
                     break;
                 }
-                Some(Err(mpsc::TryRecvError::Disconnected)) | None => {
-                    // Walk thread exited (channel closed) or slot unexpectedly
-                    // empty: treat as walk complete.
+                Some(Err(mpsc::TryRecvError::Disconnected)) => {
+                    // Walk thread exited (channel closed): walk complete.
                     walk_in_flight = None;
                     walk_just_finished = true;
                     break;
                 }
+                None => {
+                    // No walk in flight (not due yet).
+                    break;
+                }
             }
         }
 
@@ -466,7 +542,7 @@ fn run_dir_watcher(
         // Per-file stat errors are silently skipped (Rule 11: non-fatal).
         // ------------------------------------------------------------------
         if last_mtime_check.elapsed() >= mtime_poll_duration {
-            last_mtime_check = std::time::Instant::now();
+            last_mtime_check = Instant::now();
             let total = mtime_file_list.len();
             if total > 0 {
                 if mtime_cursor >= total {
@@ -514,6 +590,51 @@ fn run_dir_watcher(
     }
 }
 
+/// Whether `path`, reported by a change event, is a file the tree walk would
+/// report: a regular file under `root` within `max_depth`, with no path
+/// component matching an exclude pattern, matching an include pattern, and
+/// modified on or after `modified_since` (fail-open when mtime is unreadable).
+fn is_new_candidate(
+    root: &Path,
+    path: &Path,
+    include_pats: &[Pattern],
+    exclude_pats: &[Pattern],
+    max_depth: usize,
+    modified_since: Option<DateTime<Utc>>,
+) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+    let names: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    if names.is_empty() || names.len() > max_depth {
+        return false;
+    }
+    if names
+        .iter()
+        .any(|name| exclude_pats.iter().any(|p| p.matches(name)))
+    {
+        return false;
+    }
+    let file_name = &names[names.len() - 1];
+    if !include_pats.is_empty() && !include_pats.iter().any(|p| p.matches(file_name)) {
+        return false;
+    }
+    // symlink_metadata: the walk does not follow links either.
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return false;
+    };
+    if !meta.is_file() {
+        return false;
+    }
+    match (modified_since, meta.modified()) {
+        (Some(since), Ok(mtime)) => DateTime::<Utc>::from(mtime) >= since,
+        _ => true,
+    }
+}
+
 /// Walk `root` up to `max_depth` levels and stream newly-discovered files to
 /// `batch_tx` in batches of [`WALK_BATCH_SIZE`].  Files are streamed as they
 /// are found so callers see partial results within a single poll cycle rather
@@ -676,11 +797,8 @@ mod tests {
         );
         drop(tx); // close sender so the loop below terminates on Disconnected
         let mut out = Vec::new();
-        loop {
-            match rx.try_recv() {
-                Ok(batch) => out.extend(batch),
-                Err(mpsc::TryRecvError::Empty | mpsc::TryRecvError::Disconnected) => break,
-            }
+        while let Ok(batch) = rx.try_recv() {
+            out.extend(batch);
         }
         out
     }
@@ -765,6 +883,71 @@ mod tests {
         assert_eq!(found3.len(), 1, "no filter: file must be included");
     }
 
+    /// Event-path checks apply the walk's rules: depth, excluded directory
+    /// names, include patterns, and regular files only.
+    #[test]
+    fn test_is_new_candidate_matches_walk_rules() {
+        let dir = TempDir::new().expect("tmpdir");
+        let root = dir.path();
+        fs::create_dir_all(root.join("sub/deeper")).expect("mkdir");
+        fs::create_dir_all(root.join("node_modules")).expect("mkdir");
+        for f in [
+            "a.log",
+            "a.txt",
+            "sub/b.log",
+            "sub/deeper/c.log",
+            "node_modules/d.log",
+        ] {
+            fs::write(root.join(f), b"x").expect("write");
+        }
+        let include = vec![Pattern::new("*.log").unwrap()];
+        let exclude = vec![Pattern::new("node_modules").unwrap()];
+        let check =
+            |rel: &str| is_new_candidate(root, &root.join(rel), &include, &exclude, 2, None);
+
+        assert!(check("a.log"));
+        assert!(check("sub/b.log"));
+        assert!(!check("a.txt"), "include pattern");
+        assert!(!check("sub/deeper/c.log"), "beyond max depth");
+        assert!(!check("node_modules/d.log"), "excluded directory");
+        assert!(!check("sub"), "directory");
+        assert!(!check("gone.log"), "missing file");
+    }
+
+    /// With native change events a new file is reported long before the
+    /// (maximum) poll interval elapses.
+    #[test]
+    fn test_dir_watcher_reports_new_file_on_change_event() {
+        let dir = TempDir::new().expect("tmpdir");
+        let mut watcher = DirWatcher::new();
+        watcher.start_watch(
+            dir.path().to_path_buf(),
+            HashSet::new(),
+            DirWatchConfig {
+                poll_interval_ms: crate::util::constants::MAX_DIR_WATCH_POLL_INTERVAL_MS,
+                ..DirWatchConfig::default()
+            },
+        );
+        std::thread::sleep(Duration::from_millis(300));
+        let path = dir.path().join("late.log");
+        fs::write(&path, b"line\n").expect("write");
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut reported = false;
+        while !reported && Instant::now() < deadline {
+            reported = watcher
+                .poll_progress(16)
+                .iter()
+                .any(|m| matches!(m, DirWatchProgress::NewFiles(files) if files.contains(&path)));
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        watcher.stop_watch();
+        assert!(
+            reported,
+            "new file was not reported before the poll interval"
+        );
+    }
+
     /// DirWatcher: start and stop without panicking.
     #[test]
     fn test_dir_watcher_start_stop() {
@@ -0,0 +1,225 @@
+// LogSleuth - app/fs_watch.rs
+//
+// Change notification shared by live tail and the directory watcher.
+//
+// `FsWatcher` wraps the platform's native backend (inotify on Linux, FSEvents
+// on macOS, ReadDirectoryChangesW on Windows, via the `notify` crate) behind
+// one blocking `wait` call, so both watchers keep their loop shape: wait one
+// cancel-check slice, act on the paths reported changed, and re-check
+// everything whenever `full_check_interval` has elapsed.
+//
+// Polling fallback: network shares do not deliver reliable change events,
+// and a backend can fail to start (inotify watch limit, missing directory).
+// In either case the watcher runs in polling mode: `wait` only sleeps and the
+// full check runs every poll interval, exactly as before native watching.
+//
+// In native mode the full check still runs every FS_WATCH_RESCAN_INTERVAL_MS,
+// so an event the OS drops delays a change rather than losing it.
+//
+// Rule 11 compliance:
+//   - Events are queued in a channel bounded by MAX_PENDING_FS_EVENTS; when
+//     it is full further events are dropped and the next `wait` reports
+//     `FsChanges::Rescan` instead.
+//   - Backend errors are non-fatal: they are logged and reported as `Rescan`.
+
+use crate::util::constants::{FS_WATCH_RESCAN_INTERVAL_MS, MAX_PENDING_FS_EVENTS};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// Changes reported by one `FsWatcher::wait`.
+#[derive(Debug)]
+pub enum FsChanges {
+    /// Paths created, modified, renamed or removed.
+    Paths(HashSet<PathBuf>),
+    /// Events were lost or the backend failed: re-check everything.
+    Rescan,
+}
+
+/// Native change watcher with a polling fallback.
+pub struct FsWatcher {
+    native: Option<NativeWatch>,
+}
+
+struct NativeWatch {
+    // Kept alive for its drop: dropping it stops the backend.
+    _watcher: RecommendedWatcher,
+    events: mpsc::Receiver<notify::Result<Event>>,
+    overflowed: Arc<AtomicBool>,
+}
+
+impl FsWatcher {
+    /// Watch each of `targets` (recursively when `recursive`).
+    ///
+    /// Falls back to polling when any target is a network path or the native
+    /// backend cannot watch every target.
+    pub fn new(targets: &[PathBuf], recursive: bool) -> Self {
+        if let Some(path) = targets.iter().find(|t| is_network_path(t)) {
+            tracing::info!(path = %path.display(), "Change watcher: network path, polling");
+            return Self::polling();
+        }
+        match NativeWatch::start(targets, recursive) {
+            Ok(native) => Self {
+                native: Some(native),
+            },
+            Err(e) => {
+                tracing::warn!(error = %e, "Change watcher: native backend unavailable, polling");
+                Self::polling()
+            }
+        }
+    }
+
+    /// A watcher that never reports changes: callers poll on their interval.
+    pub fn polling() -> Self {
+        Self { native: None }
+    }
+
+    /// Whether change events come from the OS rather than polling.
+    pub fn is_native(&self) -> bool {
+        self.native.is_some()
+    }
+
+    /// How often the caller should still re-check everything, given its
+    /// configured poll interval.
+    pub fn full_check_interval(&self, poll_interval: Duration) -> Duration {
+        if self.is_native() {
+            poll_interval.max(Duration::from_millis(FS_WATCH_RESCAN_INTERVAL_MS))
+        } else {
+            poll_interval
+        }
+    }
+
+    /// Block for at most `timeout` until changes arrive.
+    ///
+    /// Returns `None` when nothing changed (always, in polling mode).  If the
+    /// backend stops, the watcher switches to polling and reports `Rescan`.
+    pub fn wait(&mut self, timeout: Duration) -> Option<FsChanges> {
+        let Some(native) = &self.native else {
+            std::thread::sleep(timeout);
+            return None;
+        };
+        let first = match native.events.recv_timeout(timeout) {
+            Ok(event) => event,
+            Err(mpsc::RecvTimeoutError::Timeout) => return None,
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                tracing::warn!("Change watcher: backend stopped, polling");
+                self.native = None;
+                return Some(FsChanges::Rescan);
+            }
+        };
+
+        let mut paths = HashSet::new();
+        let mut rescan = native.overflowed.swap(false, Ordering::Relaxed);
+        for event in std::iter::once(first).chain(native.events.try_iter()) {
+            match event {
+                Ok(event) if event.need_rescan() => rescan = true,
+                // Opens and reads (including our own) change nothing.
+                Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+                Ok(event) => paths.extend(event.paths),
+                Err(e) => {
+                    tracing::debug!(error = %e, "Change watcher: backend error");
+                    rescan = true;
+                }
+            }
+        }
+        if rescan {
+            Some(FsChanges::Rescan)
+        } else if paths.is_empty() {
+            None
+        } else {
+            Some(FsChanges::Paths(paths))
+        }
+    }
+}
+
+impl NativeWatch {
+    fn start(targets: &[PathBuf], recursive: bool) -> notify::Result<Self> {
+        let (tx, events) = mpsc::sync_channel(MAX_PENDING_FS_EVENTS);
+        let overflowed = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&overflowed);
+        let mut watcher = notify::recommended_watcher(move |event| {
+            if tx.try_send(event).is_err() {
+                flag.store(true, Ordering::Relaxed);
+            }
+        })?;
+        let mode = if recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        for target in targets {
+            watcher.watch(target, mode)?;
+        }
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            overflowed,
+        })
+    }
+}
+
+/// Returns `true` when `path` resolves to a network location.  Network paths
+/// can cause multi-second hangs during directory enumeration (slow links,
+/// VPN, offline DFS targets) and do not deliver reliable change events, so
+/// the UI refuses to scan them and `FsWatcher` polls them.
+///
+/// Detected on Windows:
+///   - UNC paths             (`\\\\server\\share\\...`)
+///   - Verbatim UNC paths    (`\\\\?\\UNC\\server\\share\\...`)
+///   - Device-namespace paths (`\\\\.\\ ...`)
+///
+/// On non-Windows the function always returns `false` — network path
+/// conventions differ and are enforced at the OS level instead.
+pub fn is_network_path(path: &Path) -> bool {
+    #[cfg(windows)]
+    {
+        use std::path::{Component, Prefix};
+        if let Some(Component::Prefix(p)) = path.components().next() {
+            return matches!(
+                p.kind(),
+                Prefix::UNC(_, _) | Prefix::VerbatimUNC(_, _) | Prefix::DeviceNS(_)
+            );
+        }
+    }
+    let _ = path;
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// A write inside a watched directory is reported by path; a directory
+    /// that does not exist falls back to polling.
+    #[test]
+    fn test_native_watch_reports_changed_path() {
+        let dir = tempfile::TempDir::new().expect("tmpdir");
+        let root = dir.path().canonicalize().expect("canonical tmpdir");
+        let mut watcher = FsWatcher::new(std::slice::from_ref(&root), false);
+        assert!(watcher.is_native());
+
+        let file = root.join("app.log");
+        std::fs::write(&file, b"line\n").expect("write");
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut seen = false;
+        while !seen && Instant::now() < deadline {
+            seen = match watcher.wait(Duration::from_millis(100)) {
+                Some(FsChanges::Paths(paths)) => paths.contains(&file),
+                Some(FsChanges::Rescan) => true,
+                None => false,
+            };
+        }
+        assert!(seen, "write was not reported");
+
+        let missing = FsWatcher::new(&[root.join("missing")], false);
+        assert!(!missing.is_native());
+        assert_eq!(
+            missing.full_check_interval(Duration::from_millis(500)),
+            Duration::from_millis(500)
+        );
+    }
+}
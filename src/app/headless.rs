@@ -0,0 +1,483 @@
+// LogSleuth - app/headless.rs
+//
+// Headless scan mode for `logsleuth scan <path> ...`.
+//
+// Runs the same discovery -> auto-detect -> parse pipeline as the GUI (via
+// `ScanManager`, blocking on its progress channel instead of polling it once
+// per frame), applies a `FilterState` built from the CLI options, and writes
+// the matching entries to a CSV / JSON export or to stdout.
+//
+// Nothing here touches egui or eframe, so the mode works over SSH sessions
+// and in scripts without a display.
+
+use crate::app::scan::ScanManager;
+use crate::core::discovery::DiscoveryConfig;
+use crate::core::export::{self, ExportMetadata};
+use crate::core::filter::{self, FilterState};
+use crate::core::model::{FormatProfile, LogEntry, ScanProgress, Severity};
+use crate::util::constants::MAX_CUSTOM_TIME_MINUTES;
+use crate::util::error::{DiscoveryError, ExportError, LogSleuthError};
+use chrono::{DateTime, Duration, Utc};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Export file format, chosen from the `--export` file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Infer the format from a path's extension (`.csv` / `.json`, any case).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?;
+        if ext.eq_ignore_ascii_case("csv") {
+            Some(Self::Csv)
+        } else if ext.eq_ignore_ascii_case("json") {
+            Some(Self::Json)
+        } else {
+            None
+        }
+    }
+}
+
+/// Options for one headless scan, already validated by the CLI layer.
+#[derive(Debug, Clone)]
+pub struct HeadlessOptions {
+    /// Directory to scan, or a single log file / archive.
+    pub path: PathBuf,
+    /// Show this severity and every more-severe level.
+    pub min_severity: Option<Severity>,
+    /// Only entries at or after this instant.
+    pub since: Option<DateTime<Utc>>,
+    /// Case-insensitive regex applied to the message text.
+    pub regex: Option<String>,
+    /// Write matches to this file instead of stdout.
+    pub export: Option<(PathBuf, ExportFormat)>,
+    /// Discovery limits (from config.toml).
+    pub max_files: usize,
+    pub max_depth: usize,
+    /// Read chunk size in bytes (from config.toml).
+    pub chunk_size: usize,
+    /// Entry cap; entries beyond it are dropped and the report is marked
+    /// `truncated` (there is no on-disk spill store in headless mode).
+    pub max_entries: usize,
+}
+
+/// Outcome of a successful headless scan.
+#[derive(Debug, Default)]
+pub struct HeadlessReport {
+    pub files_scanned: usize,
+    pub total_entries: usize,
+    pub matched_entries: usize,
+    /// Non-fatal warnings reported by discovery and parsing.
+    pub warnings: Vec<String>,
+    /// The entry cap was reached, so entries (and possibly matches) are
+    /// missing from the output.
+    pub truncated: bool,
+}
+
+/// Parse a `--since` value.
+///
+/// Accepts a relative window (`30s`, `15m`, `2h`, `7d`, `1w`; a bare number
+/// means minutes, like the Filters panel custom input) or an absolute local
+/// date/time in the Filters panel formats (`YYYY-MM-DD[ HH:MM[:SS]]`), or an
+/// RFC 3339 timestamp.  Relative windows are capped at
+/// `MAX_CUSTOM_TIME_MINUTES`.
+pub fn parse_since(input: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let s = input.trim();
+    if s.is_empty() {
+        return None;
+    }
+    if let Some(secs) = relative_secs(s) {
+        if secs == 0 || secs > MAX_CUSTOM_TIME_MINUTES * 60 {
+            return None;
+        }
+        return Some(now - Duration::seconds(secs as i64));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.to_utc());
+    }
+    crate::core::filter::parse_filter_datetime(s)
+}
+
+/// Convert `15m` / `2h` / `90` style input to seconds.  Returns `None` when
+/// `s` is not a relative window (or overflows).
+fn relative_secs(s: &str) -> Option<u64> {
+    let (digits, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(pos) => s.split_at(pos),
+        None => (s, "m"),
+    };
+    let n: u64 = digits.parse().ok()?;
+    let per_unit = match unit.to_ascii_lowercase().as_str() {
+        "s" => 1,
+        "m" | "min" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        "w" => 604_800,
+        _ => return None,
+    };
+    n.checked_mul(per_unit)
+}
+
+/// Run a headless scan: discover and parse `opts.path`, filter, then export.
+///
+/// Warnings are returned in the report for the caller to print; the matching
+/// entries go to the export file, or to stdout one line per entry when no
+/// export is requested.
+pub fn run(
+    opts: &HeadlessOptions,
+    profiles: Vec<FormatProfile>,
+) -> Result<HeadlessReport, LogSleuthError> {
+    // Build the filter first so a bad regex fails before any I/O.
+    let mut filter_state = FilterState::default();
+    if let Some(min) = opts.min_severity {
+        filter_state.set_min_severity(min);
+    }
+    if let Some(ref pattern) = opts.regex {
+        filter_state.set_regex(pattern)?;
+    }
+    filter_state.time_start = opts.since;
+
+    let metadata = std::fs::metadata(&opts.path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            DiscoveryError::PermissionDenied {
+                path: opts.path.clone(),
+                source: e,
+            }
+        } else {
+            DiscoveryError::RootNotFound {
+                path: opts.path.clone(),
+            }
+        }
+    })?;
+
+    let mut scan_manager = ScanManager::new();
+    scan_manager.set_chunk_size(opts.chunk_size);
+    if metadata.is_dir() {
+        scan_manager.start_scan(
+            opts.path.clone(),
+            profiles,
+            DiscoveryConfig {
+                max_files: opts.max_files,
+                max_depth: opts.max_depth,
+                // A file last written before `since` cannot hold newer entries.
+                modified_since: opts.since,
+                max_total_entries: opts.max_entries,
+                ..DiscoveryConfig::default()
+            },
+            None,
+        );
+    } else {
+        scan_manager.start_scan_files(vec![opts.path.clone()], profiles, opts.max_entries, 0, None);
+    }
+
+    let mut report = HeadlessReport::default();
+    let entries = collect_entries(&scan_manager, &mut report)?;
+    report.total_entries = entries.len();
+
+    let matched = filter::apply_filters(&entries, &filter_state);
+    report.matched_entries = matched.len();
+    let matched_entries = matched.iter().filter_map(|&i| entries.get(i));
+
+    match opts.export {
+        Some((ref dest, format)) => {
+            let description = describe_filter(opts);
+            let metadata = ExportMetadata {
+                scan_path: Some(&opts.path),
+                filter_description: &description,
+                entry_count: matched.len(),
+                clock_adjustments: &[],
+            };
+            write_export(matched_entries, dest, format, &metadata)?;
+        }
+        None => {
+            let stdout = std::io::stdout();
+            let mut out = std::io::BufWriter::new(stdout.lock());
+            for entry in matched_entries {
+                writeln!(out, "{}", format_entry_line(entry)).map_err(stdout_error)?;
+            }
+            out.flush().map_err(stdout_error)?;
+        }
+    }
+
+    Ok(report)
+}
+
+/// Block on the scan channel until the pipeline finishes, collecting every
+/// entry batch and warning.
+fn collect_entries(
+    scan_manager: &ScanManager,
+    report: &mut HeadlessReport,
+) -> Result<Vec<LogEntry>, LogSleuthError> {
+    let Some(ref rx) = scan_manager.progress_rx else {
+        return Err(LogSleuthError::Scan {
+            reason: "scan did not start".to_string(),
+        });
+    };
+    let mut entries = Vec::new();
+    loop {
+        let Ok(msg) = rx.recv() else {
+            return Err(LogSleuthError::Scan {
+                reason: "scan thread exited before completing".to_string(),
+            });
+        };
+        match msg {
+            ScanProgress::EntriesBatch { entries: batch } => entries.extend(batch),
+            ScanProgress::Warning { message } => {
+                tracing::debug!("{message}");
+                report.warnings.push(message);
+            }
+            ScanProgress::ParsingStarted { total_files } => {
+                tracing::info!(total_files, "Parsing files");
+            }
+            ScanProgress::ParsingCompleted { summary } => {
+                report.files_scanned = summary.files_matched;
+                report.truncated = summary.entry_cap_reached;
+                return Ok(entries);
+            }
+            ScanProgress::Failed { error } => {
+                return Err(LogSleuthError::Scan { reason: error });
+            }
+            ScanProgress::Cancelled => {
+                return Err(LogSleuthError::Scan {
+                    reason: "scan was cancelled".to_string(),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Write `entries` to `dest` atomically (temp file + rename, as the GUI
+/// export does per SEC-04).
+fn write_export<'a>(
+    entries: impl Iterator<Item = &'a LogEntry> + Clone,
+    dest: &Path,
+    format: ExportFormat,
+    metadata: &ExportMetadata<'_>,
+) -> Result<usize, ExportError> {
+    let tmp = dest.with_extension(match format {
+        ExportFormat::Csv => "csv.tmp",
+        ExportFormat::Json => "json.tmp",
+    });
+    let io_err = |source| ExportError::Io {
+        path: dest.to_path_buf(),
+        source,
+    };
+    let file = std::fs::File::create(&tmp).map_err(io_err)?;
+    let writer = std::io::BufWriter::new(file);
+    let result = match format {
+        ExportFormat::Csv => export::export_csv(entries, writer, dest, metadata),
+        ExportFormat::Json => export::export_json(entries, writer, dest, metadata),
+    };
+    match result {
+        Ok(n) => std::fs::rename(&tmp, dest).map(|_| n).map_err(|e| {
+            let _ = std::fs::remove_file(&tmp);
+            io_err(e)
+        }),
+        Err(e) => {
+            let _ = std::fs::remove_file(&tmp);
+            Err(e)
+        }
+    }
+}
+
+/// One stdout line per entry: timestamp, severity, `file:line`, message.
+/// Multi-line messages are flattened so each entry stays greppable.
+fn format_entry_line(entry: &LogEntry) -> String {
+    let ts = entry
+        .timestamp
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let message = entry.message().replace(['\r', '\n'], " ");
+    format!(
+        "{ts}  {:<4}  {}:{}  {message}",
+        entry.severity.short_label(),
+        crate::core::archive::display_name(&entry.source_file),
+        entry.line_number,
+    )
+}
+
+/// Human-readable filter summary for the export metadata header.
+fn describe_filter(opts: &HeadlessOptions) -> String {
+    let mut parts = Vec::new();
+    if let Some(min) = opts.min_severity {
+        parts.push(format!("Severity >= {}", min.label()));
+    }
+    if let Some(since) = opts.since {
+        parts.push(format!("Since {}", since.format("%Y-%m-%d %H:%M:%S UTC")));
+    }
+    if let Some(ref re) = opts.regex {
+        parts.push(format!("Regex: {re}"));
+    }
+    if parts.is_empty() {
+        "None".to_string()
+    } else {
+        parts.join("; ")
+    }
+}
+
+fn stdout_error(source: std::io::Error) -> LogSleuthError {
+    LogSleuthError::Io {
+        path: PathBuf::from("<stdout>"),
+        operation: "write",
+        source,
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_since_relative_windows() {
+        assert_eq!(parse_since("2h", now()), Some(now() - Duration::hours(2)));
+        assert_eq!(
+            parse_since("15m", now()),
+            Some(now() - Duration::minutes(15))
+        );
+        assert_eq!(
+            parse_since("90", now()),
+            Some(now() - Duration::minutes(90))
+        );
+        assert_eq!(
+            parse_since("30s", now()),
+            Some(now() - Duration::seconds(30))
+        );
+        assert_eq!(parse_since("7d", now()), Some(now() - Duration::days(7)));
+        assert_eq!(parse_since("0m", now()), None);
+        assert_eq!(parse_since("2y", now()), None);
+        assert_eq!(parse_since("600w", now()), None, "beyond the one-year cap");
+    }
+
+    #[test]
+    fn test_parse_since_absolute() {
+        assert_eq!(
+            parse_since("2024-05-31T08:00:00Z", now()),
+            Some(Utc.with_ymd_and_hms(2024, 5, 31, 8, 0, 0).unwrap())
+        );
+        assert!(parse_since("2024-05-31 08:00", now()).is_some());
+        assert_eq!(parse_since("yesterday", now()), None);
+    }
+
+    #[test]
+    fn test_export_format_from_extension() {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("out.CSV")),
+            Some(ExportFormat::Csv)
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("out.json")),
+            Some(ExportFormat::Json)
+        );
+        assert_eq!(ExportFormat::from_path(Path::new("out.txt")), None);
+    }
+
+    /// End-to-end: scan a directory, keep errors matching the regex, and
+    /// export them as CSV.
+    #[test]
+    fn test_run_filters_and_exports_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let logs = dir.path().join("logs");
+        std::fs::create_dir(&logs).unwrap();
+        std::fs::write(
+            logs.join("app.log"),
+            "2024-01-15 10:00:00,100 ERROR [main] com.app.Db - connection refused\n\
+             2024-01-15 10:00:01,200 INFO  [main] com.app.Db - retrying connection\n\
+             2024-01-15 10:00:02,300 ERROR [main] com.app.Disk - disk full\n",
+        )
+        .unwrap();
+        let dest = dir.path().join("out.csv");
+
+        let opts = HeadlessOptions {
+            path: logs,
+            min_severity: Some(Severity::Error),
+            since: None,
+            regex: Some("connection".to_string()),
+            export: Some((dest.clone(), ExportFormat::Csv)),
+            max_files: 10,
+            max_depth: 2,
+            chunk_size: crate::util::constants::DEFAULT_CHUNK_SIZE,
+            max_entries: crate::util::constants::MAX_TOTAL_ENTRIES,
+        };
+        let report = run(&opts, crate::core::profile::load_builtin_profiles()).unwrap();
+        assert_eq!(report.total_entries, 3);
+        assert_eq!(report.matched_entries, 1);
+
+        let csv = std::fs::read_to_string(&dest).unwrap();
+        assert!(csv.contains("# Filter: Severity >= Error; Regex: connection"));
+        assert!(csv.contains("connection refused"));
+        assert!(!csv.contains("disk full"));
+        assert!(!report.truncated);
+    }
+
+    /// Reaching the entry cap is reported, not silently dropped.
+    #[test]
+    fn test_run_reports_entry_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("app.log");
+        let lines: String = (0..5)
+            .map(|i| format!("2024-01-15 10:00:0{i},100 INFO  [main] com.app.Job - step {i}\n"))
+            .collect();
+        std::fs::write(&log, lines).unwrap();
+
+        let opts = HeadlessOptions {
+            path: log,
+            min_severity: None,
+            since: None,
+            regex: None,
+            export: Some((dir.path().join("out.json"), ExportFormat::Json)),
+            max_files: 10,
+            max_depth: 2,
+            chunk_size: crate::util::constants::DEFAULT_CHUNK_SIZE,
+            max_entries: 3,
+        };
+        let report = run(&opts, crate::core::profile::load_builtin_profiles()).unwrap();
+        assert_eq!(report.total_entries, 3);
+        assert!(report.truncated);
+        assert!(report
+            .warnings
+            .iter()
+            .any(|w| w.starts_with("Entry limit reached")));
+    }
+
+    #[test]
+    fn test_run_rejects_bad_regex_and_missing_path() {
+        let opts = HeadlessOptions {
+            path: PathBuf::from("/definitely/not/here"),
+            min_severity: None,
+            since: None,
+            regex: Some("(unclosed".to_string()),
+            export: None,
+            max_files: 10,
+            max_depth: 2,
+            chunk_size: crate::util::constants::DEFAULT_CHUNK_SIZE,
+            max_entries: crate::util::constants::MAX_TOTAL_ENTRIES,
+        };
+        assert!(matches!(
+            run(&opts, Vec::new()),
+            Err(LogSleuthError::Filter(_))
+        ));
+        let opts = HeadlessOptions {
+            regex: None,
+            ..opts
+        };
+        assert!(matches!(
+            run(&opts, Vec::new()),
+            Err(LogSleuthError::Discovery(
+                DiscoveryError::RootNotFound { .. }
+            ))
+        ));
+    }
+}
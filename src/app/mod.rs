@@ -4,10 +4,15 @@
 // Dependencies: core layer.
 // Must NOT depend on: ui, platform specifics.
 
+pub mod alerts;
 pub mod dir_watcher;
+pub mod fs_watch;
+pub mod headless;
+pub mod presets;
 pub mod profile_mgr;
 pub mod scan;
 pub mod session;
+#[cfg(feature = "gui")]
 pub mod state;
 pub mod tail;
 pub mod windows_event_logs;
@@ -0,0 +1,315 @@
+// LogSleuth - app/presets.rs
+//
+// Named filter presets: user-saved snapshots of the filter state, shown as
+// buttons in the Filters sidebar.
+//
+// Design principles:
+// - A preset is a name plus a `session::PersistedFilter`, so it captures
+//   exactly what a session restores (severity, text, regex, exclusion,
+//   components, multi-search, dedup mode, relative window, fields, query).
+// - Session-specific selections (source-file whitelist, bookmarks-only) are
+//   stripped: they refer to files and entry IDs of one particular scan.
+// - Presets live in `filter_presets.toml` next to `config.toml`.  Import and
+//   export use the same TOML format so presets can be shared as plain files.
+// - Saves are atomic (write temp, rename) like the session file.
+
+use crate::app::session::PersistedFilter;
+use crate::util::constants::{
+    FILTER_PRESETS_FILE_NAME, MAX_FILTER_PRESETS, MAX_FILTER_PRESET_NAME_LEN,
+};
+use crate::util::error::ConfigError;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A named, saved filter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterPreset {
+    /// Button label.  Unique among presets (ASCII case-insensitive).
+    pub name: String,
+
+    /// Filter snapshot applied when the preset is clicked.
+    #[serde(default)]
+    pub filter: PersistedFilter,
+}
+
+/// On-disk shape: an array of `[[preset]]` tables.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PresetFile {
+    #[serde(default, rename = "preset")]
+    presets: Vec<FilterPreset>,
+}
+
+impl FilterPreset {
+    /// Build a preset from a filter snapshot, trimming the name and dropping
+    /// session-specific selections.
+    pub fn new(name: &str, filter: PersistedFilter) -> Self {
+        let mut preset = Self {
+            name: normalize_name(name),
+            filter,
+        };
+        preset.strip_session_fields();
+        preset
+    }
+
+    fn strip_session_fields(&mut self) {
+        self.filter.source_files.clear();
+        self.filter.hide_all_sources = false;
+        self.filter.bookmarks_only = false;
+    }
+
+    /// One-line summary of the active parts of the preset for hover text.
+    pub fn describe(&self) -> String {
+        let f = &self.filter;
+        let mut parts: Vec<String> = Vec::new();
+        if !f.severity_levels.is_empty() {
+            let labels: Vec<&str> = f.severity_levels.iter().map(|s| s.label()).collect();
+            parts.push(format!("Severity: {}", labels.join("+")));
+        }
+        if let Some(secs) = f.relative_time_secs {
+            parts.push(format!("Last {}m", secs / 60));
+        }
+        if !f.text_search.is_empty() {
+            parts.push(format!("Text: \"{}\"", f.text_search));
+        }
+        if !f.regex_pattern.is_empty() {
+            parts.push(format!("Regex: /{}/", f.regex_pattern));
+        }
+        if !f.exclude_text.is_empty() {
+            parts.push(format!("Exclude: \"{}\"", f.exclude_text));
+        }
+        if !f.component_filter.is_empty() {
+            parts.push(format!("Components: {}", f.component_filter.join(", ")));
+        }
+        if !f.multi_search_input.trim().is_empty() {
+            parts.push(format!(
+                "Multi-search: {}",
+                f.multi_search_input.trim().replace('\n', ", ")
+            ));
+        }
+        if !f.field_filter.is_empty() {
+            parts.push(format!("Fields: {}", f.field_filter));
+        }
+        if !f.query.trim().is_empty() {
+            parts.push(format!("Query: {}", f.query.trim()));
+        }
+        if f.hide_no_timestamp {
+            parts.push("Timestamped only".to_string());
+        }
+        if f.dedup_mode != crate::core::filter::DedupMode::Off {
+            parts.push(format!("Dedup: {}", f.dedup_mode.label()));
+        }
+        if parts.is_empty() {
+            "No filter (all entries)".to_string()
+        } else {
+            parts.join("\n")
+        }
+    }
+}
+
+/// Trim a preset name and bound it to `MAX_FILTER_PRESET_NAME_LEN` characters.
+pub fn normalize_name(name: &str) -> String {
+    name.trim()
+        .chars()
+        .take(MAX_FILTER_PRESET_NAME_LEN)
+        .collect()
+}
+
+/// Resolve the presets file path (next to `config.toml`, one level above the
+/// platform config directory -- see `platform::config::load_config`).
+pub fn presets_path(config_dir: &Path) -> PathBuf {
+    config_dir
+        .parent()
+        .unwrap_or(config_dir)
+        .join(FILTER_PRESETS_FILE_NAME)
+}
+
+/// Add `incoming` presets to `presets`, replacing any preset with the same
+/// name (ASCII case-insensitive).  Presets with a blank name are skipped.
+/// New presets beyond `MAX_FILTER_PRESETS` are dropped; the number dropped is
+/// returned.
+pub fn merge(presets: &mut Vec<FilterPreset>, incoming: Vec<FilterPreset>) -> usize {
+    let mut dropped = 0;
+    for mut preset in incoming {
+        preset.name = normalize_name(&preset.name);
+        if preset.name.is_empty() {
+            tracing::warn!("Skipping filter preset with an empty name");
+            continue;
+        }
+        preset.strip_session_fields();
+        if let Some(existing) = presets
+            .iter_mut()
+            .find(|p| p.name.eq_ignore_ascii_case(&preset.name))
+        {
+            *existing = preset;
+        } else if presets.len() < MAX_FILTER_PRESETS {
+            presets.push(preset);
+        } else {
+            dropped += 1;
+        }
+    }
+    dropped
+}
+
+/// Load presets from a TOML file.  A missing file yields an empty list (first
+/// run).  Names are normalised and duplicates merged as in [`merge`].
+pub fn load(path: &Path) -> Result<Vec<FilterPreset>, ConfigError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => {
+            return Err(ConfigError::Io {
+                path: path.to_path_buf(),
+                source,
+            })
+        }
+    };
+    let file: PresetFile = toml::from_str(&content).map_err(|source| ConfigError::TomlParse {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut presets = Vec::new();
+    let dropped = merge(&mut presets, file.presets);
+    if dropped > 0 {
+        tracing::warn!(
+            path = %path.display(),
+            dropped,
+            max = MAX_FILTER_PRESETS,
+            "Filter preset file exceeds the preset limit; extra presets ignored"
+        );
+    }
+    Ok(presets)
+}
+
+/// Save `presets` to `path` atomically (write temp, rename), creating parent
+/// directories as needed.
+pub fn save(path: &Path, presets: &[FilterPreset]) -> Result<(), ConfigError> {
+    let io_err = |source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let file = PresetFile {
+        presets: presets.to_vec(),
+    };
+    let text = toml::to_string_pretty(&file).map_err(|source| ConfigError::TomlSerialize {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(io_err)?;
+    }
+    let tmp = path.with_extension("toml.tmp");
+    std::fs::write(&tmp, text.as_bytes()).map_err(io_err)?;
+    std::fs::rename(&tmp, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        io_err(e)
+    })?;
+    tracing::debug!(path = %path.display(), count = presets.len(), "Filter presets saved");
+    Ok(())
+}
+
+// =============================================================================
+// Unit tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::filter::DedupMode;
+    use crate::core::model::Severity;
+    use tempfile::TempDir;
+
+    fn triage_preset() -> FilterPreset {
+        FilterPreset::new(
+            "  VBR job failure triage ",
+            PersistedFilter {
+                severity_levels: vec![Severity::Critical, Severity::Error],
+                regex_pattern: "job .* failed".to_string(),
+                component_filter: vec!["Agent".to_string()],
+                multi_search_input: "timeout\nrefused".to_string(),
+                dedup_mode: DedupMode::Normalized,
+                relative_time_secs: Some(900),
+                query: "NOT msg:retry".to_string(),
+                source_files: vec![PathBuf::from("/var/log/a.log")],
+                bookmarks_only: true,
+                multi_search_case_insensitive: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Save and load must round-trip the filter snapshot; session-specific
+    /// selections are stripped when the preset is created.
+    #[test]
+    fn test_presets_save_load_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join(FILTER_PRESETS_FILE_NAME);
+        save(&path, &[triage_preset()]).expect("save should succeed");
+
+        let loaded = load(&path).expect("load should succeed");
+        assert_eq!(loaded.len(), 1);
+        let p = &loaded[0];
+        assert_eq!(p.name, "VBR job failure triage");
+        assert_eq!(
+            p.filter.severity_levels,
+            vec![Severity::Critical, Severity::Error]
+        );
+        assert_eq!(p.filter.regex_pattern, "job .* failed");
+        assert_eq!(p.filter.component_filter, vec!["Agent".to_string()]);
+        assert_eq!(p.filter.multi_search_input, "timeout\nrefused");
+        assert_eq!(p.filter.dedup_mode, DedupMode::Normalized);
+        assert_eq!(p.filter.relative_time_secs, Some(900));
+        assert_eq!(p.filter.query, "NOT msg:retry");
+        assert!(p.filter.source_files.is_empty());
+        assert!(!p.filter.bookmarks_only);
+    }
+
+    /// A hand-written preset file only needs the keys it uses.
+    #[test]
+    fn test_presets_load_minimal_hand_written_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("shared.toml");
+        std::fs::write(
+            &path,
+            "[[preset]]\nname = \"Errors\"\n[preset.filter]\nseverity_levels = [\"Error\"]\n\n\
+             [[preset]]\nname = \"Everything\"\n",
+        )
+        .unwrap();
+
+        let loaded = load(&path).expect("minimal file must parse");
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].filter.severity_levels, vec![Severity::Error]);
+        assert!(loaded[0].filter.multi_search_case_insensitive);
+        assert!(loaded[1].filter.severity_levels.is_empty());
+    }
+
+    #[test]
+    fn test_presets_load_missing_file_is_empty_and_malformed_is_error() {
+        let dir = TempDir::new().unwrap();
+        assert!(load(&dir.path().join("none.toml")).unwrap().is_empty());
+
+        let bad = dir.path().join("bad.toml");
+        std::fs::write(&bad, "[[preset]\nname = ").unwrap();
+        assert!(matches!(load(&bad), Err(ConfigError::TomlParse { .. })));
+    }
+
+    /// Merging replaces presets with the same name (case-insensitive), skips
+    /// blank names and enforces `MAX_FILTER_PRESETS`.
+    #[test]
+    fn test_presets_merge_replaces_by_name_and_caps() {
+        let mut presets = vec![FilterPreset::new("Errors", PersistedFilter::default())];
+        let mut replacement = FilterPreset::new("ERRORS", PersistedFilter::default());
+        replacement.filter.text_search = "disk".to_string();
+        let blank = FilterPreset::new("   ", PersistedFilter::default());
+
+        assert_eq!(merge(&mut presets, vec![replacement, blank]), 0);
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].name, "ERRORS");
+        assert_eq!(presets[0].filter.text_search, "disk");
+
+        let many: Vec<FilterPreset> = (0..MAX_FILTER_PRESETS + 3)
+            .map(|i| FilterPreset::new(&format!("p{i}"), PersistedFilter::default()))
+            .collect();
+        assert_eq!(merge(&mut presets, many), 4);
+        assert_eq!(presets.len(), MAX_FILTER_PRESETS);
+    }
+}
@@ -22,13 +22,11 @@ pub fn load_all_profiles(
     let mut profiles = profile::load_builtin_profiles();
     let mut errors = Vec::new();
 
-    // On Windows, register the built-in EVTX profile for Event Viewer files.
-    #[cfg(target_os = "windows")]
-    {
-        let evtx_profile = profile::create_evtx_profile();
-        tracing::info!(profile_id = %evtx_profile.id, "Registered Windows Event Log (.evtx) profile");
-        profiles.push(evtx_profile);
-    }
+    // Register the built-in EVTX profile for Event Viewer files.  Parsing is
+    // pure Rust, so exported .evtx files open on every platform.
+    let evtx_profile = profile::create_evtx_profile();
+    tracing::info!(profile_id = %evtx_profile.id, "Registered Windows Event Log (.evtx) profile");
+    profiles.push(evtx_profile);
 
     tracing::info!(builtin_count = profiles.len(), "Loaded built-in profiles");
 
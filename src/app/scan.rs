@@ -15,15 +15,18 @@
 //   - Entry batching via ENTRY_BATCH_SIZE caps memory usage between flushes.
 //   - Cancel is checked before each file operation to enable prompt termination.
 
+use crate::core::clock::ClockRule;
 use crate::core::discovery::{self, DiscoveryConfig};
+use crate::core::encoding::{LineReader, TextEncoding, Utf8Reader};
+use crate::core::entry_store::EntryStore;
 use crate::core::model::{FileSummary, FormatProfile, LogEntry, ScanProgress, ScanSummary};
 use crate::core::parser::{self, ParseConfig};
 use crate::core::profile;
 use rayon::prelude::*;
-use std::io;
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::{mpsc, Arc, OnceLock};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 // =============================================================================
@@ -40,13 +43,24 @@ const SAMPLE_LINES: usize = 20;
 const MAX_RETRIES: usize = 3;
 const RETRY_DELAYS_MS: [u64; MAX_RETRIES] = [50, 100, 200];
 
-/// Maximum seconds to wait for a full file read.
+/// Maximum seconds to wait for a file to open and yield its detection sample.
 ///
 /// Protects the scan against I/O stalls (slow drives, antivirus, etc.) so
 /// Cancel takes effect promptly.  The spawned I/O thread is not forcibly
 /// killed but exits on its own once the OS I/O timeout fires.
 const FILE_READ_TIMEOUT_SECS: u64 = 30;
 
+/// Lines streamed between checks of the cancel flag while parsing one file.
+const CANCEL_CHECK_LINES: usize = 4096;
+
+/// Smallest segment worth parsing on its own thread.  Files under twice this
+/// size are parsed on one thread.
+const SEGMENT_MIN_BYTES: u64 = 32 * 1024 * 1024;
+
+/// How far past a segment's nominal start to look for a line that starts a
+/// record before giving up on that split point.
+const SEGMENT_SEARCH_BYTES: u64 = 4 * 1024 * 1024;
+
 // =============================================================================
 // ScanManager
 // =============================================================================
@@ -58,6 +72,13 @@ pub struct ScanManager {
 
     /// Cancel flag shared with the background thread.
     cancel_flag: Option<Arc<AtomicBool>>,
+
+    /// Parsing limits and timezone overrides handed to every scan.
+    parse_config: ParseConfig,
+
+    /// Directory for the on-disk entry store.  When set, entries beyond
+    /// `max_total_entries` are spilled there instead of dropped.
+    spill_dir: Option<PathBuf>,
 }
 
 impl ScanManager {
@@ -65,9 +86,30 @@ impl ScanManager {
         Self {
             progress_rx: None,
             cancel_flag: None,
+            parse_config: ParseConfig::default(),
+            spill_dir: None,
         }
     }
 
+    /// Use `rules` for the timezone overrides of subsequent scans.
+    pub fn set_clock_rules(&mut self, rules: &[ClockRule]) {
+        if self.parse_config.clock_rules != rules {
+            self.parse_config.clock_rules = rules.to_vec();
+        }
+    }
+
+    /// Read files in `chunk_size`-byte chunks in subsequent scans
+    /// (`[parsing] chunk_size_bytes`).
+    pub fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.parse_config.chunk_size = chunk_size;
+    }
+
+    /// Spill entries beyond the entry cap to an `EntryStore` in `dir` in
+    /// subsequent scans.  `None` (the default) drops them.
+    pub fn set_spill_dir(&mut self, dir: Option<PathBuf>) {
+        self.spill_dir = dir;
+    }
+
     /// Start a scan of `root` using the given format profiles and discovery config.
     ///
     /// Spawns a background thread immediately; progress is sent over the channel.
@@ -95,7 +137,8 @@ impl ScanManager {
         self.progress_rx = Some(rx);
         self.cancel_flag = Some(Arc::clone(&cancel));
 
-        let parse_config = ParseConfig::default();
+        let parse_config = self.parse_config.clone();
+        let spill_dir = self.spill_dir.clone();
 
         std::thread::spawn(move || {
             let tx_guard = tx.clone();
@@ -112,6 +155,7 @@ impl ScanManager {
                         entry_id_start: 0,
                         parse_path_filter,
                         append_exclude_paths: None,
+                        spill_dir,
                     },
                 );
             }));
@@ -147,7 +191,8 @@ impl ScanManager {
         self.progress_rx = Some(rx);
         self.cancel_flag = Some(Arc::clone(&cancel));
 
-        let parse_config = ParseConfig::default();
+        let parse_config = self.parse_config.clone();
+        let spill_dir = self.spill_dir.clone();
 
         std::thread::spawn(move || {
             let tx_guard = tx.clone();
@@ -164,6 +209,7 @@ impl ScanManager {
                         entry_id_start,
                         parse_path_filter,
                         append_exclude_paths: Some(exclude_paths),
+                        spill_dir,
                     },
                 );
             }));
@@ -212,7 +258,8 @@ impl ScanManager {
         self.progress_rx = Some(rx);
         self.cancel_flag = Some(Arc::clone(&cancel));
 
-        let parse_config = ParseConfig::default();
+        let parse_config = self.parse_config.clone();
+        let spill_dir = self.spill_dir.clone();
 
         std::thread::spawn(move || {
             let tx_guard = tx.clone();
@@ -226,6 +273,7 @@ impl ScanManager {
                     max_total_entries,
                     entry_id_start,
                     parse_path_filter,
+                    spill_dir,
                 );
             }));
             if result.is_err() {
@@ -324,6 +372,7 @@ struct RunScanOptions {
     entry_id_start: u64,
     parse_path_filter: Option<std::collections::HashSet<PathBuf>>,
     append_exclude_paths: Option<std::collections::HashSet<PathBuf>>,
+    spill_dir: Option<PathBuf>,
 }
 
 fn run_scan(
@@ -340,6 +389,7 @@ fn run_scan(
         entry_id_start,
         parse_path_filter,
         append_exclude_paths,
+        spill_dir,
     } = options;
 
     macro_rules! send {
@@ -516,6 +566,7 @@ fn run_scan(
         config.max_total_entries,
         entry_id_start,
         parse_path_filter.as_ref(),
+        spill_dir.as_deref(),
     );
 }
 
@@ -593,6 +644,9 @@ fn run_parse_pipeline(
     entry_id_start: u64,
     // When Some, only files in this set are parsed; others are skipped.
     parse_path_filter: Option<&std::collections::HashSet<PathBuf>>,
+    // When Some, entries beyond `max_total_entries` are spilled to an
+    // `EntryStore` in this directory instead of dropped.
+    spill_dir: Option<&Path>,
 ) {
     macro_rules! send {
         ($msg:expr) => {
@@ -630,8 +684,9 @@ fn run_parse_pipeline(
     // Key gains:
     //   - Overlaps I/O and parse CPU across files via rayon parallelism
     //   - Timeout-guarded reads protect rayon workers from I/O stalls
-    //   - Entry IDs are assigned sequentially after collection to maintain
-    //     global uniqueness without cross-thread coordination
+    //   - Entry IDs are assigned sequentially, in file order, as results
+    //     arrive, to maintain global uniqueness without cross-thread
+    //     coordination
     //   - Files are sorted by parent directory to improve OS metadata prefetch
 
     let total_files = discovered_files.len();
@@ -647,37 +702,17 @@ fn run_parse_pipeline(
             .then_with(|| a.path.cmp(&b.path))
     });
 
-    /// Per-file result collected from the parallel processing phase.
-    ///
-    /// Entry IDs inside `entries` are temporary (start from 0 within each
-    /// file).  The sequential post-processing step reassigns globally-unique
-    /// IDs before streaming to the UI.
-    struct FileResult {
-        /// Index into `discovered_files` for updating profile info.
-        idx: usize,
-        /// Detected or fallback profile ID (None if file was unreadable/skipped).
-        profile_id: Option<String>,
-        /// Detection confidence score.
-        detection_confidence: f64,
-        /// Parsed entries with temporary IDs.
-        entries: Vec<LogEntry>,
-        /// File summary for the scan report.
-        summary: Option<FileSummary>,
-        /// Warning messages to surface to the user.
-        warnings: Vec<String>,
-        /// Number of parse errors in this file.
-        error_count: usize,
-        /// Whether this file was skipped due to an active `parse_path_filter`.
-        /// Propagated to `DiscoveredFile::parsing_skipped` so the UI can show
-        /// a "(not parsed)" indicator and offer a follow-up parse action.
-        parsing_skipped: bool,
-    }
-
-    // Shared atomic counter so parallel workers can report per-file progress
-    // in real time (the UI sees FileParsed messages streaming in as files
-    // complete, even though the order is non-deterministic).
-    let files_completed_counter = Arc::new(AtomicUsize::new(0));
-    let progress_tx = tx.clone();
+    let files_completed_counter = AtomicUsize::new(0);
+    let job = ParseJob {
+        files: &discovered_files,
+        profiles: &profiles,
+        parse_config: &parse_config,
+        cancel: &cancel,
+        progress_tx: &tx,
+        files_completed: &files_completed_counter,
+        total_files,
+        parse_path_filter,
+    };
 
     // Obtain the process-scoped rayon thread pool (built once, reused for all
     // subsequent scans).  Building a new ThreadPool on each scan call spawns
@@ -686,420 +721,204 @@ fn run_parse_pipeline(
     // threads drain.  A static pool avoids all of that.
     let pool = scan_thread_pool();
 
-    let file_results: Vec<FileResult> = pool.install(|| {
-        discovered_files
-        .par_iter()
-        .enumerate()
-        .map(|(idx, file)| {
-            // Early exit on cancel -- each rayon worker checks independently.
-            if cancel.load(Ordering::SeqCst) {
-                return FileResult {
-                    idx,
-                    profile_id: None,
-                    detection_confidence: 0.0,
-                    entries: Vec::new(),
-                    summary: None,
-                    warnings: Vec::new(),
-                    error_count: 0,
-                    parsing_skipped: false,
-                };
-            }
+    // Process results in a fixed file order so entry IDs are deterministic:
+    // each rotation family's segments together and oldest first, so entries
+    // the chronological sort cannot tell apart (equal or missing timestamps)
+    // keep the order they were written in across segments.
+    let mut rank = vec![0usize; discovered_files.len()];
+    let streams = discovery::group_streams(discovered_files.iter().map(|f| f.path.as_path()));
+    for (pos, &idx) in streams.iter().flat_map(|s| &s.members).enumerate() {
+        rank[idx] = pos;
+    }
+    let mut units = WorkUnit::plan(&discovered_files);
+    units.sort_by_cached_key(|unit| unit.files().iter().map(|&idx| rank[idx]).min());
+
+    // Workers claim units in rank order but may run at most `lookahead`
+    // files ahead of the assembler, and hand each result over a bounded
+    // channel as soon as it is ready.  The assembler below takes them in
+    // rank order from a reorder buffer, so only the files in flight are
+    // held in memory besides the resident entries.  (A tarball's members
+    // arrive in archive order; any that rank after one not yet read wait
+    // in the buffer.)
+    let lookahead = pool.current_num_threads() * 2;
+    let assembled = Mutex::new(0usize);
+    let advanced = std::sync::Condvar::new();
+    let next_unit = AtomicUsize::new(0);
+    // Set when the assembler wants no more results: the cap was reached
+    // without a spill directory, or the UI hung up.
+    let stop = AtomicBool::new(false);
+    let halted = || stop.load(Ordering::SeqCst) || cancel.load(Ordering::SeqCst);
+    let (result_tx, result_rx) = mpsc::sync_channel::<FileResult>(lookahead);
 
-            #[cfg(target_os = "windows")]
-            let is_evtx = file
-                .path
-                .extension()
-                .and_then(|e| e.to_str())
-                .map(|e| e.eq_ignore_ascii_case("evtx"))
-                == Some(true);
-            #[cfg(not(target_os = "windows"))]
-            let is_evtx = false;
-
-            // Parse-path filter: if a filter is active and this file is not in
-            // it, skip full I/O and parsing.  The profile is still assigned via
-            // filename-only detection (no file read) so the discovery panel shows
-            // a sensible profile label.  The caller can trigger a follow-up
-            // `start_scan_files` to parse skipped files on demand.
-            //
-            // Windows EVTX files bypass this gate so Event Viewer logs are
-            // always parsed when readable.
-            if let Some(filter) = parse_path_filter {
-                if !filter.contains(&file.path) && !is_evtx {
-                    let file_name =
-                        file.path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                    let (profile_id, detection_confidence) = if let Some(detection) =
-                        profile::auto_detect(file_name, &[], &profiles)
-                    {
-                        (Some(detection.profile_id), detection.confidence)
-                    } else if profiles.iter().any(|p| p.id == "plain-text") {
-                        (Some("plain-text".to_string()), 0.0)
-                    } else {
-                        (None, 0.0)
-                    };
-                    let completed =
-                        files_completed_counter.fetch_add(1, Ordering::SeqCst) + 1;
-                    let _ = progress_tx.send(ScanProgress::FileParsed {
-                        path: file.path.clone(),
-                        entries: 0,
-                        errors: 0,
-                        files_completed: completed,
-                        total_files,
-                    });
-                    return FileResult {
-                        idx,
-                        profile_id,
-                        detection_confidence,
-                        entries: Vec::new(),
-                        summary: None,
-                        warnings: Vec::new(),
-                        error_count: 0,
-                        parsing_skipped: true,
-                    };
+    // Assign globally-unique entry IDs, enforce the hard entry cap, and
+    // collect summaries -- all on this thread, in rank order, as results
+    // arrive.
+    let mut total_errors: usize = 0;
+    let mut files_with_entries: usize = 0;
+    let mut entry_id: u64 = entry_id_start;
+    let mut all_entries: Vec<LogEntry> = Vec::new();
+    let mut file_summaries: Vec<FileSummary> = Vec::new();
+    let mut entry_cap_reached = false;
+    let mut profile_updates: Vec<(usize, Option<String>, f64, bool)> = Vec::new();
+
+    // With a spill directory, the first `max_total_entries` entries in file
+    // order stay resident and the rest go to disk as each file is
+    // assembled.  The split follows rank order, not the order the workers
+    // finished in.
+    let mut spill = spill_dir.map(SpillStore::new);
+
+    let completed = pool.in_place_scope(|scope| {
+        // Owned here so that returning early hangs up on the workers.
+        let result_rx = result_rx;
+        for _ in 0..pool.current_num_threads() {
+            let result_tx = result_tx.clone();
+            let (job, units, rank, assembled, advanced, next_unit, halted) = (
+                &job, &units, &rank, &assembled, &advanced, &next_unit, &halted,
+            );
+            scope.spawn(move |_| loop {
+                let k = next_unit.fetch_add(1, Ordering::SeqCst);
+                let Some(unit) = units.get(k) else { break };
+                let first = unit.files().iter().map(|&idx| rank[idx]).min().unwrap_or(0);
+                let mut done = assembled.lock().unwrap_or_else(|e| e.into_inner());
+                while first >= *done + lookahead && !halted() {
+                    done = advanced
+                        .wait_timeout(done, Duration::from_millis(100))
+                        .unwrap_or_else(|e| e.into_inner())
+                        .0;
                 }
-            }
-
-            // -----------------------------------------------------------------
-            // EVTX binary format: bypass text parser (Windows only)
-            // -----------------------------------------------------------------
-            // .evtx files are binary and cannot be parsed by the regex-based
-            // text parser.  Detect by extension and route to the dedicated
-            // evtx_parser module which uses the `evtx` crate.
-            #[cfg(target_os = "windows")]
-            {
-                if is_evtx {
-                    let evtx_result = crate::core::evtx_parser::parse_evtx_file(
-                        &file.path,
-                        parse_config.max_entry_size,
-                        parse_config.max_parse_errors_per_file,
-                        0, // temporary IDs -- reassigned sequentially after collection
-                    );
-
-                    // Stamp file mtime on every entry (same as the text path).
-                    let file_mtime = file.modified;
-                    let mut evtx_entries = evtx_result.entries;
-                    for entry in &mut evtx_entries {
-                        entry.file_modified = file_mtime;
-                    }
-
-                    let entry_count = evtx_entries.len();
-                    let error_count = evtx_result.errors.len();
-
-                    // Build per-file summary.
-                    let mut earliest: Option<chrono::DateTime<chrono::Utc>> = None;
-                    let mut latest: Option<chrono::DateTime<chrono::Utc>> = None;
-                    for entry in &evtx_entries {
-                        if let Some(ts) = entry.timestamp {
-                            earliest = Some(match earliest {
-                                Some(e) if e <= ts => e,
-                                _ => ts,
-                            });
-                            latest = Some(match latest {
-                                Some(l) if l >= ts => l,
-                                _ => ts,
-                            });
-                        }
-                    }
-
-                    for err in &evtx_result.errors {
-                        tracing::debug!(error = %err, "EVTX parse error");
-                    }
-
-                    let completed =
-                        files_completed_counter.fetch_add(1, Ordering::SeqCst) + 1;
-                    let _ = progress_tx.send(ScanProgress::FileParsed {
-                        path: file.path.clone(),
-                        entries: entry_count,
-                        errors: error_count,
-                        files_completed: completed,
-                        total_files,
-                    });
-
-                    return FileResult {
-                        idx,
-                        profile_id: Some(
-                            crate::util::constants::EVTX_PROFILE_ID.to_string(),
-                        ),
-                        detection_confidence: 1.0,
-                        entries: evtx_entries,
-                        summary: Some(FileSummary {
-                            path: file.path.clone(),
-                            profile_id: crate::util::constants::EVTX_PROFILE_ID
-                                .to_string(),
-                            entry_count,
-                            error_count,
-                            earliest,
-                            latest,
-                        }),
-                        warnings: Vec::new(),
-                        error_count,
-                        parsing_skipped: false,
-                    };
+                drop(done);
+                if halted() {
+                    break;
                 }
-            }
-
-            let mut warnings: Vec<String> = Vec::new();
+                job.run(unit, &mut |result| {
+                    let _ = result_tx.send(result);
+                });
+            });
+        }
+        drop(result_tx);
 
-            // --- Single I/O pass: read full file content (timeout-guarded) ---
-            let content = match read_file_content_timed(&file.path, file.is_large) {
-                Ok(c) => c,
-                Err(e) => {
-                    let msg = format!("Cannot read '{}': {e}", file.path.display());
-                    tracing::warn!(warning = %msg, "File read failed");
-                    let _ = progress_tx.send(ScanProgress::Warning {
-                        message: msg.clone(),
-                    });
-                    let completed = files_completed_counter.fetch_add(1, Ordering::SeqCst) + 1;
-                    let _ = progress_tx.send(ScanProgress::FileParsed {
-                        path: file.path.clone(),
-                        entries: 0,
-                        errors: 0,
-                        files_completed: completed,
-                        total_files,
-                    });
-                    return FileResult {
-                        idx,
-                        profile_id: None,
-                        detection_confidence: 0.0,
-                        entries: Vec::new(),
-                        summary: None,
-                        warnings: vec![msg],
-                        error_count: 0,
-                        parsing_skipped: false,
-                    };
+        macro_rules! send_or_stop {
+            ($msg:expr) => {
+                if tx.send($msg).is_err() {
+                    stop.store(true, Ordering::SeqCst);
+                    return false;
                 }
             };
+        }
 
+        let mut pending: std::collections::BTreeMap<usize, FileResult> =
+            std::collections::BTreeMap::new();
+        let mut next_rank = 0usize;
+        let mut channel_open = true;
+        while channel_open || !pending.is_empty() {
             if cancel.load(Ordering::SeqCst) {
-                return FileResult {
-                    idx,
-                    profile_id: None,
-                    detection_confidence: 0.0,
-                    entries: Vec::new(),
-                    summary: None,
-                    warnings: Vec::new(),
-                    error_count: 0,
-                    parsing_skipped: false,
-                };
+                return false;
             }
-
-            // --- Auto-detect from first N lines of already-read content ---
-            // Avoids a second file open that the old sequential Phase 2 required.
-            let sample_lines: Vec<String> = content
-                .lines()
-                .take(SAMPLE_LINES)
-                .map(String::from)
-                .collect();
-            let file_name = file.path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-
-            let (mut detected_profile_id, detection_confidence) = if let Some(detection) =
-                profile::auto_detect(file_name, &sample_lines, &profiles)
-            {
-                tracing::debug!(
-                    file = %file.path.display(),
-                    profile = %detection.profile_id,
-                    confidence = detection.confidence,
-                    "Auto-detected profile"
-                );
-                (Some(detection.profile_id), detection.confidence)
-            } else if profiles.iter().any(|p| p.id == "plain-text") {
-                tracing::debug!(
-                    file = %file.path.display(),
-                    "No structured profile matched; falling back to plain-text"
-                );
-                (Some("plain-text".to_string()), 0.0)
-            } else {
-                (None, 0.0)
-            };
-
-            // Resolve profile or skip the file.
-            let pid = match &detected_profile_id {
-                Some(id) => id.clone(),
-                None => {
-                    let msg = format!(
-                        "'{}': no format profile could be assigned \
-                         (even plain-text was unavailable), file skipped",
-                        file.path.display()
-                    );
-                    tracing::debug!(file = %file.path.display(), "No profile assigned, skipping");
-                    let _ = progress_tx.send(ScanProgress::Warning {
-                        message: msg.clone(),
-                    });
-                    warnings.push(msg);
-                    let completed = files_completed_counter.fetch_add(1, Ordering::SeqCst) + 1;
-                    let _ = progress_tx.send(ScanProgress::FileParsed {
-                        path: file.path.clone(),
-                        entries: 0,
-                        errors: 0,
-                        files_completed: completed,
-                        total_files,
-                    });
-                    return FileResult {
-                        idx,
-                        profile_id: None,
-                        detection_confidence: 0.0,
-                        entries: Vec::new(),
-                        summary: None,
-                        warnings,
-                        error_count: 0,
-                        parsing_skipped: false,
-                    };
+            if channel_open {
+                match result_rx.recv_timeout(Duration::from_millis(100)) {
+                    Ok(result) => {
+                        pending.insert(rank[result.idx], result);
+                    }
+                    Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                    Err(mpsc::RecvTimeoutError::Disconnected) => channel_open = false,
                 }
-            };
-
-            let matched_profile = match profiles.iter().find(|p| p.id == pid) {
-                Some(p) => p,
-                None => {
-                    tracing::warn!(profile = %pid, "Profile not found in loaded profiles");
-                    let completed = files_completed_counter.fetch_add(1, Ordering::SeqCst) + 1;
-                    let _ = progress_tx.send(ScanProgress::FileParsed {
-                        path: file.path.clone(),
-                        entries: 0,
-                        errors: 0,
-                        files_completed: completed,
-                        total_files,
-                    });
-                    return FileResult {
-                        idx,
-                        profile_id: Some(pid),
-                        detection_confidence,
-                        entries: Vec::new(),
-                        summary: None,
-                        warnings,
-                        error_count: 0,
-                        parsing_skipped: false,
-                    };
+            }
+            // Once every worker is done, files that never arrived (cap
+            // reached, or cancelled) no longer hold back the ones after them.
+            if !channel_open {
+                if let Some(&first) = pending.keys().next() {
+                    next_rank = next_rank.max(first);
                 }
-            };
-
-            if cancel.load(Ordering::SeqCst) {
-                return FileResult {
-                    idx,
-                    profile_id: Some(pid),
-                    detection_confidence,
-                    entries: Vec::new(),
-                    summary: None,
-                    warnings,
-                    error_count: 0,
-                    parsing_skipped: false,
-                };
             }
 
-            // --- Parse (reuses already-read content -- zero additional I/O) ---
-            let mut parse_result = parser::parse_content(
-                &content,
-                &file.path,
-                matched_profile,
-                &parse_config,
-                0, // temporary IDs -- reassigned sequentially after collection
-            );
-
-            // Fallback: if the assigned profile produced zero entries but the
-            // file has content, re-parse with plain-text so every non-empty
-            // file contributes at least its raw line content to the timeline.
-            let mut final_profile_id = pid;
-            if parse_result.entries.is_empty() && !content.trim().is_empty() {
-                if let Some(plain_profile) = profiles.iter().find(|p| p.id == "plain-text") {
-                    if plain_profile.id != final_profile_id {
-                        tracing::debug!(
-                            file = %file.path.display(),
-                            assigned_profile = %final_profile_id,
-                            "Assigned profile yielded 0 entries; \
-                             falling back to plain-text"
-                        );
-                        parse_result = parser::parse_content(
-                            &content,
-                            &file.path,
-                            plain_profile,
-                            &parse_config,
-                            0,
-                        );
-                        final_profile_id = plain_profile.id.clone();
-                    }
+            while let Some(mut result) = pending.remove(&next_rank) {
+                next_rank += 1;
+                *assembled.lock().unwrap_or_else(|e| e.into_inner()) = next_rank;
+                advanced.notify_all();
+
+                profile_updates.push((
+                    result.idx,
+                    result.profile_id.take(),
+                    result.detection_confidence,
+                    result.parsing_skipped,
+                ));
+                if entry_cap_reached || result.summary.is_none() {
+                    continue;
                 }
-            }
 
-            // Stamp the source file's OS last-modified time on every entry.
-            let file_mtime = file.modified;
-            for entry in &mut parse_result.entries {
-                entry.file_modified = file_mtime;
-            }
+                let entry_count = result.entries.len();
 
-            let entry_count = parse_result.entries.len();
-            let error_count = parse_result.errors.len();
-
-            // Build per-file summary.
-            let mut earliest: Option<chrono::DateTime<chrono::Utc>> = None;
-            let mut latest: Option<chrono::DateTime<chrono::Utc>> = None;
-            for entry in &parse_result.entries {
-                if let Some(ts) = entry.timestamp {
-                    earliest = Some(match earliest {
-                        Some(e) if e <= ts => e,
-                        _ => ts,
-                    });
-                    latest = Some(match latest {
-                        Some(l) if l >= ts => l,
-                        _ => ts,
-                    });
+                // Reassign entry IDs to maintain global uniqueness.
+                for entry in &mut result.entries {
+                    entry.id = entry_id;
+                    entry_id += 1;
                 }
-            }
-            let summary = FileSummary {
-                path: file.path.clone(),
-                profile_id: final_profile_id.clone(),
-                entry_count,
-                error_count,
-                earliest,
-                latest,
-            };
 
-            for err in &parse_result.errors {
-                tracing::debug!(error = %err, "Parse error");
-            }
+                total_errors += result.error_count;
+                if entry_count > 0 {
+                    files_with_entries += 1;
+                }
 
-            // Send per-file progress (order is non-deterministic in parallel
-            // mode but the UI only uses files_completed / total_files for its
-            // progress bar, so arrival order does not matter).
-            let completed = files_completed_counter.fetch_add(1, Ordering::SeqCst) + 1;
-            let _ = progress_tx.send(ScanProgress::FileParsed {
-                path: file.path.clone(),
-                entries: entry_count,
-                errors: error_count,
-                files_completed: completed,
-                total_files,
-            });
+                if let Some(summary) = result.summary {
+                    file_summaries.push(summary);
+                }
 
-            detected_profile_id = Some(final_profile_id);
+                for w in &result.warnings {
+                    send_or_stop!(ScanProgress::Warning { message: w.clone() });
+                }
 
-            FileResult {
-                idx,
-                profile_id: detected_profile_id,
-                detection_confidence,
-                entries: parse_result.entries,
-                summary: Some(summary),
-                warnings,
-                error_count,
-                parsing_skipped: false,
+                // Enforce the hard entry cap (Rule 11: bounded collections).
+                let remaining_capacity = max_total_entries.saturating_sub(all_entries.len());
+                if result.entries.len() <= remaining_capacity {
+                    all_entries.extend(result.entries);
+                } else if let Some(ref mut spill) = spill {
+                    let overflow = result.entries.split_off(remaining_capacity);
+                    all_entries.extend(result.entries);
+                    if let Err(msg) = spill.append(overflow, &parse_config.clock_rules) {
+                        // Nothing more fits in memory, so the rest is dropped.
+                        entry_cap_reached = true;
+                        stop.store(true, Ordering::SeqCst);
+                        tracing::warn!(warning = %msg, "Entry store write failed");
+                        send_or_stop!(ScanProgress::Warning { message: msg });
+                    }
+                } else {
+                    all_entries.extend(result.entries.into_iter().take(remaining_capacity));
+                    entry_cap_reached = true;
+                    stop.store(true, Ordering::SeqCst);
+                    let cap = max_total_entries;
+                    let msg = format!(
+                        "Entry limit reached: {cap} entries loaded. \
+                         Remaining files in this scan have been skipped to \
+                         prevent an out-of-memory crash. Use the date filter \
+                         or reduce the max-files limit to target a smaller dataset."
+                    );
+                    tracing::warn!("{}", msg);
+                    send_or_stop!(ScanProgress::Warning { message: msg });
+                }
             }
-        })
-        .collect()
-    }); // end pool.install
-
+        }
+        true
+    });
     check_cancel!();
+    if !completed {
+        return;
+    }
 
     // -------------------------------------------------------------------------
-    // Post-parallel: assemble results sequentially
+    // Post-parallel: report the discovered files and stream the entries
     // -------------------------------------------------------------------------
 
     // Update discovered_files with the profile info detected during the
     // parallel phase so the UI file list shows the correct profile assignment.
     // Also propagate the parsing_skipped flag so the UI can show a "(not
     // parsed)" indicator for files excluded by the parse-path filter.
-    for result in &file_results {
-        if let Some(pid) = &result.profile_id {
-            discovered_files[result.idx].profile_id = Some(pid.clone());
-            discovered_files[result.idx].detection_confidence = result.detection_confidence;
+    for (idx, profile_id, detection_confidence, parsing_skipped) in profile_updates {
+        if let Some(pid) = profile_id {
+            discovered_files[idx].profile_id = Some(pid);
+            discovered_files[idx].detection_confidence = detection_confidence;
         }
-        if result.parsing_skipped {
-            discovered_files[result.idx].parsing_skipped = true;
+        if parsing_skipped {
+            discovered_files[idx].parsing_skipped = true;
         }
     }
 
@@ -1124,72 +943,6 @@ fn run_parse_pipeline(
     // UI saw incremental progress.
     send!(ScanProgress::ParsingStarted { total_files });
 
-    // Assign globally-unique entry IDs, enforce the hard entry cap, and
-    // collect summaries -- all sequential to avoid cross-thread coordination.
-    let mut total_errors: usize = 0;
-    let mut files_with_entries: usize = 0;
-    let mut entry_id: u64 = entry_id_start;
-    let mut all_entries: Vec<LogEntry> = Vec::new();
-    let mut file_summaries: Vec<FileSummary> = Vec::new();
-    let mut entry_cap_reached = false;
-
-    // Process results in original file order so entry IDs are deterministic.
-    let mut sorted_results: Vec<FileResult> = file_results
-        .into_iter()
-        .filter(|r| r.summary.is_some())
-        .collect();
-    sorted_results.sort_by_key(|r| r.idx);
-
-    for mut result in sorted_results {
-        check_cancel!();
-
-        if entry_cap_reached {
-            break;
-        }
-
-        let entry_count = result.entries.len();
-        let error_count = result.error_count;
-
-        // Reassign entry IDs to maintain global uniqueness.
-        for entry in &mut result.entries {
-            entry.id = entry_id;
-            entry_id += 1;
-        }
-
-        total_errors += error_count;
-        if entry_count > 0 {
-            files_with_entries += 1;
-        }
-
-        if let Some(summary) = result.summary {
-            file_summaries.push(summary);
-        }
-
-        for w in &result.warnings {
-            send!(ScanProgress::Warning { message: w.clone() });
-        }
-
-        // Enforce the hard entry cap (Rule 11: bounded collections).
-        let remaining_capacity = max_total_entries.saturating_sub(all_entries.len());
-        if remaining_capacity == 0 {
-            // Defensive guard -- should not be reached with the break above.
-        } else if result.entries.len() <= remaining_capacity {
-            all_entries.extend(result.entries);
-        } else {
-            all_entries.extend(result.entries.into_iter().take(remaining_capacity));
-            entry_cap_reached = true;
-            let cap = max_total_entries;
-            let msg = format!(
-                "Entry limit reached: {cap} entries loaded. \
-                 Remaining files in this scan have been skipped to \
-                 prevent an out-of-memory crash. Use the date filter \
-                 or reduce the max-files limit to target a smaller dataset."
-            );
-            tracing::warn!("{}", msg);
-            send!(ScanProgress::Warning { message: msg });
-        }
-    }
-
     // Sort all collected entries chronologically on the background thread.
     // Entries with timestamps come first ordered by time; timestamp-less entries
     // retain their relative parse order at the end.  This prevents the UI thread
@@ -1215,7 +968,7 @@ fn run_parse_pipeline(
     // accumulated `total_entries` counter can overcount when the entry cap
     // truncates a file mid-way (it includes the full file's entry count).
     // Using the concrete Vec length gives the accurate number.
-    let actual_total_entries = all_entries.len();
+    let mut actual_total_entries = all_entries.len();
     check_cancel!();
     while !all_entries.is_empty() {
         let batch_end = ENTRY_BATCH_SIZE.min(all_entries.len());
@@ -1224,10 +977,28 @@ fn run_parse_pipeline(
         check_cancel!();
     }
 
+    // Hand the spilled entries over after the resident ones, so the UI can
+    // fold both into one store.
+    if let Some(store) = spill.and_then(SpillStore::into_store) {
+        actual_total_entries += store.len();
+        let msg = format!(
+            "Entry limit reached: {} entries beyond the {max_total_entries} held in \
+             memory were written to the on-disk entry store. The timeline shows \
+             one window of the session; use Earlier / Later or Search all.",
+            store.len()
+        );
+        tracing::info!("{}", msg);
+        send!(ScanProgress::Warning { message: msg });
+        send!(ScanProgress::EntriesSpilled {
+            store: Arc::new(Mutex::new(store)),
+        });
+    }
+
     let files_with_errors = file_summaries
         .iter()
         .filter(|fs| fs.error_count > 0)
         .count();
+    let file_summaries = discovery::merge_stream_summaries(file_summaries);
     let summary = ScanSummary {
         total_files_discovered: total_files,
         total_entries: actual_total_entries,
@@ -1235,6 +1006,7 @@ fn run_parse_pipeline(
         files_matched: files_with_entries,
         files_with_errors,
         file_summaries,
+        entry_cap_reached,
         duration: scan_start.elapsed(),
     };
 
@@ -1249,6 +1021,572 @@ fn run_parse_pipeline(
     );
 }
 
+// =============================================================================
+// Per-file parse workers
+// =============================================================================
+
+/// Per-file result collected from the parallel processing phase.
+///
+/// Entry IDs inside `entries` are temporary (start from 0 within each
+/// file).  The sequential post-processing step reassigns globally-unique
+/// IDs before streaming to the UI.
+struct FileResult {
+    /// Index into `discovered_files` for updating profile info.
+    idx: usize,
+    /// Detected or fallback profile ID (None if file was unreadable/skipped).
+    profile_id: Option<String>,
+    /// Detection confidence score.
+    detection_confidence: f64,
+    /// Parsed entries with temporary IDs.
+    entries: Vec<LogEntry>,
+    /// File summary for the scan report.
+    summary: Option<FileSummary>,
+    /// Warning messages to surface to the user.
+    warnings: Vec<String>,
+    /// Number of parse errors in this file.
+    error_count: usize,
+    /// Whether this file was skipped due to an active `parse_path_filter`.
+    /// Propagated to `DiscoveredFile::parsing_skipped` so the UI can show
+    /// a "(not parsed)" indicator and offer a follow-up parse action.
+    parsing_skipped: bool,
+}
+
+impl FileResult {
+    /// Result for a file that produced no entries and no summary.
+    fn empty(idx: usize, profile_id: Option<String>, detection_confidence: f64) -> Self {
+        Self {
+            idx,
+            profile_id,
+            detection_confidence,
+            entries: Vec::new(),
+            summary: None,
+            warnings: Vec::new(),
+            error_count: 0,
+            parsing_skipped: false,
+        }
+    }
+}
+
+/// One task for a parse worker: a single file, or every wanted member of
+/// one tarball.
+///
+/// A tar.gz has no index, so reaching a member means decompressing
+/// everything before it.  Its members are therefore parsed together, in
+/// archive order, during one walk of the archive
+/// (`archive::walk_tar_members`): one decompression per tarball instead of
+/// one per member.  The members of a tarball share a worker as a result.
+enum WorkUnit {
+    File(usize),
+    Tarball {
+        archive: PathBuf,
+        members: Vec<usize>,
+    },
+}
+
+impl WorkUnit {
+    /// Indices of the files this unit parses.
+    fn files(&self) -> &[usize] {
+        match self {
+            WorkUnit::File(idx) => std::slice::from_ref(idx),
+            WorkUnit::Tarball { members, .. } => members,
+        }
+    }
+
+    /// Group `files` into work units, in file order (a tarball where its
+    /// first member is).
+    fn plan(files: &[crate::core::model::DiscoveredFile]) -> Vec<WorkUnit> {
+        let mut units = Vec::new();
+        let mut tarballs: std::collections::HashMap<PathBuf, usize> =
+            std::collections::HashMap::new();
+        for (idx, file) in files.iter().enumerate() {
+            let archive = crate::core::archive::split_member_path(&file.path)
+                .map(|(archive, _)| archive)
+                .filter(|archive| {
+                    crate::core::archive::ArchiveKind::from_path(archive)
+                        == Some(crate::core::archive::ArchiveKind::TarGz)
+                });
+            let Some(archive) = archive else {
+                units.push(WorkUnit::File(idx));
+                continue;
+            };
+            match tarballs.get(&archive) {
+                Some(&slot) => {
+                    if let WorkUnit::Tarball { members, .. } = &mut units[slot] {
+                        members.push(idx);
+                    }
+                }
+                None => {
+                    tarballs.insert(archive.clone(), units.len());
+                    units.push(WorkUnit::Tarball {
+                        archive,
+                        members: vec![idx],
+                    });
+                }
+            }
+        }
+        units
+    }
+}
+
+/// Everything the parse workers of one pipeline run share.
+struct ParseJob<'a> {
+    files: &'a [crate::core::model::DiscoveredFile],
+    profiles: &'a [FormatProfile],
+    parse_config: &'a ParseConfig,
+    cancel: &'a AtomicBool,
+    progress_tx: &'a mpsc::Sender<ScanProgress>,
+    /// Shared counter so parallel workers can report per-file progress in
+    /// real time (the UI sees FileParsed messages streaming in as files
+    /// complete, even though the order is non-deterministic).
+    files_completed: &'a AtomicUsize,
+    total_files: usize,
+    parse_path_filter: Option<&'a std::collections::HashSet<PathBuf>>,
+}
+
+impl ParseJob<'_> {
+    /// Parse every file of `unit`, handing each result to `emit` as soon as
+    /// it is ready.
+    fn run(&self, unit: &WorkUnit, emit: &mut dyn FnMut(FileResult)) {
+        match unit {
+            WorkUnit::File(idx) => {
+                let result = match self.parse_without_text(*idx) {
+                    Some(result) => result,
+                    None => self.parse_opened(
+                        *idx,
+                        open_log_lines_timed(&self.files[*idx].path, self.parse_config),
+                    ),
+                };
+                emit(result);
+            }
+            WorkUnit::Tarball { archive, members } => self.parse_tarball(archive, members, emit),
+        }
+    }
+
+    /// Parse the wanted `members` of the tarball `archive` in one walk of
+    /// the archive.  A member the walk does not reach (a read error, or the
+    /// member vanished since discovery) is reported like an unreadable file.
+    fn parse_tarball(&self, archive: &Path, members: &[usize], emit: &mut dyn FnMut(FileResult)) {
+        let mut pending: std::collections::HashMap<String, usize> =
+            std::collections::HashMap::new();
+        for &idx in members {
+            match self.parse_without_text(idx) {
+                Some(result) => emit(result),
+                None => {
+                    if let Some((_, name)) =
+                        crate::core::archive::split_member_path(&self.files[idx].path)
+                    {
+                        pending.insert(name, idx);
+                    }
+                }
+            }
+        }
+        if pending.is_empty() {
+            return;
+        }
+
+        let chunk_size = self.parse_config.chunk_size;
+        let max_line = parser::max_line_len(self.parse_config);
+        let walked = open_timed(archive).and_then(|file| {
+            crate::core::archive::walk_tar_members(file, |name, member| {
+                if let Some(idx) = pending.remove(name) {
+                    let mut lines = log_lines(member, chunk_size, max_line);
+                    let opened = read_sample(&mut lines).map(|sample| (lines, sample));
+                    emit(self.parse_opened(idx, opened));
+                }
+                !pending.is_empty() && !self.cancel.load(Ordering::SeqCst)
+            })
+        });
+
+        let mut missed: Vec<usize> = pending.into_values().collect();
+        missed.sort_unstable();
+        for idx in missed {
+            if self.cancel.load(Ordering::SeqCst) {
+                emit(FileResult::empty(idx, None, 0.0));
+                continue;
+            }
+            let error = match &walked {
+                Err(e) => io::Error::new(e.kind(), e.to_string()),
+                Ok(()) => io::Error::new(io::ErrorKind::NotFound, "member not found in archive"),
+            };
+            emit(self.parse_opened::<io::Empty>(idx, Err(error)));
+        }
+    }
+
+    /// Result for file `idx` when it needs no text read: the scan was
+    /// cancelled, the parse-path filter skips it, or it is an .evtx file.
+    /// `None` when the file must be opened and parsed as text.
+    fn parse_without_text(&self, idx: usize) -> Option<FileResult> {
+        let file = &self.files[idx];
+
+        // Early exit on cancel -- each rayon worker checks independently.
+        if self.cancel.load(Ordering::SeqCst) {
+            return Some(FileResult::empty(idx, None, 0.0));
+        }
+
+        let is_evtx = file
+            .path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.eq_ignore_ascii_case("evtx"))
+            == Some(true);
+
+        // Parse-path filter: if a filter is active and this file is not in
+        // it, skip full I/O and parsing.  The profile is still assigned via
+        // filename-only detection (no file read) so the discovery panel shows
+        // a sensible profile label.  The caller can trigger a follow-up
+        // `start_scan_files` to parse skipped files on demand.
+        //
+        // EVTX files bypass this gate so Event Viewer logs are
+        // always parsed when readable.
+        if let Some(filter) = self.parse_path_filter {
+            if !filter.contains(&file.path) && !is_evtx {
+                let file_name = file.path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                let (profile_id, detection_confidence) =
+                    if let Some(detection) = profile::auto_detect(file_name, &[], self.profiles) {
+                        (Some(detection.profile_id), detection.confidence)
+                    } else if self.profiles.iter().any(|p| p.id == "plain-text") {
+                        (Some("plain-text".to_string()), 0.0)
+                    } else {
+                        (None, 0.0)
+                    };
+                self.file_parsed(&file.path, 0, 0);
+                return Some(FileResult {
+                    parsing_skipped: true,
+                    ..FileResult::empty(idx, profile_id, detection_confidence)
+                });
+            }
+        }
+
+        // ---------------------------------------------------------------------
+        // EVTX binary format: bypass text parser
+        // ---------------------------------------------------------------------
+        // .evtx files are binary and cannot be parsed by the regex-based
+        // text parser.  Detect by extension and route to the dedicated
+        // evtx_parser module which uses the `evtx` crate.
+        if !is_evtx {
+            return None;
+        }
+        let evtx_result = crate::core::evtx_parser::parse_evtx_file(
+            &file.path,
+            self.parse_config.max_entry_size,
+            self.parse_config.max_parse_errors_per_file,
+            0, // temporary IDs -- reassigned sequentially during assembly
+        );
+
+        // Stamp file mtime on every entry (same as the text path).
+        let mut evtx_entries = evtx_result.entries;
+        for entry in &mut evtx_entries {
+            entry.file_modified = file.modified;
+        }
+
+        let entry_count = evtx_entries.len();
+        let error_count = evtx_result.errors.len();
+        let (earliest, latest) = time_range(&evtx_entries);
+
+        for err in &evtx_result.errors {
+            tracing::debug!(error = %err, "EVTX parse error");
+        }
+
+        self.file_parsed(&file.path, entry_count, error_count);
+
+        Some(FileResult {
+            idx,
+            profile_id: Some(crate::util::constants::EVTX_PROFILE_ID.to_string()),
+            detection_confidence: 1.0,
+            entries: evtx_entries,
+            summary: Some(FileSummary {
+                path: file.path.clone(),
+                profile_id: crate::util::constants::EVTX_PROFILE_ID.to_string(),
+                entry_count,
+                error_count,
+                earliest,
+                latest,
+                segments: 1,
+            }),
+            warnings: Vec::new(),
+            error_count,
+            parsing_skipped: false,
+        })
+    }
+
+    /// Auto-detect and parse file `idx` from its opened lines and detection
+    /// sample, or report why it could not be opened.
+    fn parse_opened<R: Read>(
+        &self,
+        idx: usize,
+        opened: io::Result<(LogLines<R>, Vec<String>)>,
+    ) -> FileResult {
+        let file = &self.files[idx];
+        let mut warnings: Vec<String> = Vec::new();
+
+        // The rest of the file is streamed through the parser below, so
+        // memory is bounded by the read chunk, not the file size.
+        let (mut lines, sample_lines) = match opened {
+            Ok(opened) => opened,
+            Err(e) => {
+                let msg = format!("Cannot read '{}': {e}", file.path.display());
+                tracing::warn!(warning = %msg, "File read failed");
+                let _ = self.progress_tx.send(ScanProgress::Warning {
+                    message: msg.clone(),
+                });
+                self.file_parsed(&file.path, 0, 0);
+                return FileResult {
+                    warnings: vec![msg],
+                    ..FileResult::empty(idx, None, 0.0)
+                };
+            }
+        };
+
+        if self.cancel.load(Ordering::SeqCst) {
+            return FileResult::empty(idx, None, 0.0);
+        }
+
+        // --- Auto-detect from the first N lines ---
+        // The sample lines are fed to the parser first, so the file is
+        // still read only once.
+        let file_name = file.path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+
+        let (detected_profile_id, detection_confidence) = if let Some(detection) =
+            profile::auto_detect(file_name, &sample_lines, self.profiles)
+        {
+            tracing::debug!(
+                file = %file.path.display(),
+                profile = %detection.profile_id,
+                confidence = detection.confidence,
+                "Auto-detected profile"
+            );
+            (Some(detection.profile_id), detection.confidence)
+        } else if self.profiles.iter().any(|p| p.id == "plain-text") {
+            tracing::debug!(
+                file = %file.path.display(),
+                "No structured profile matched; falling back to plain-text"
+            );
+            (Some("plain-text".to_string()), 0.0)
+        } else {
+            (None, 0.0)
+        };
+
+        // Resolve profile or skip the file.
+        let pid = match detected_profile_id {
+            Some(id) => id,
+            None => {
+                let msg = format!(
+                    "'{}': no format profile could be assigned \
+                     (even plain-text was unavailable), file skipped",
+                    file.path.display()
+                );
+                tracing::debug!(file = %file.path.display(), "No profile assigned, skipping");
+                let _ = self.progress_tx.send(ScanProgress::Warning {
+                    message: msg.clone(),
+                });
+                warnings.push(msg);
+                self.file_parsed(&file.path, 0, 0);
+                return FileResult {
+                    warnings,
+                    ..FileResult::empty(idx, None, 0.0)
+                };
+            }
+        };
+
+        let matched_profile = match self.profiles.iter().find(|p| p.id == pid) {
+            Some(p) => p,
+            None => {
+                tracing::warn!(profile = %pid, "Profile not found in loaded profiles");
+                self.file_parsed(&file.path, 0, 0);
+                return FileResult {
+                    warnings,
+                    ..FileResult::empty(idx, Some(pid), detection_confidence)
+                };
+            }
+        };
+
+        if self.cancel.load(Ordering::SeqCst) {
+            return FileResult {
+                warnings,
+                ..FileResult::empty(idx, Some(pid), detection_confidence)
+            };
+        }
+
+        // --- Parse: the sample lines, then the rest of the stream ---
+        let (mut parse_result, has_text) = parse_log_lines(
+            &mut lines,
+            &sample_lines,
+            &file.path,
+            matched_profile,
+            self.parse_config,
+            self.cancel,
+            self.progress_tx,
+            &mut warnings,
+        );
+        drop(lines);
+
+        // Fallback: if the assigned profile produced zero entries but the
+        // file has content, re-parse with plain-text so every non-empty
+        // file contributes at least its raw line content to the timeline.
+        // The file is opened again: nothing but the sample was kept.  (A
+        // tarball member is found again with `open_member`, which walks the
+        // archive from the start; this fallback is rare.)
+        let mut final_profile_id = pid;
+        if parse_result.entries.is_empty() && has_text {
+            if let Some(plain_profile) = self.profiles.iter().find(|p| p.id == "plain-text") {
+                if plain_profile.id != final_profile_id {
+                    tracing::debug!(
+                        file = %file.path.display(),
+                        assigned_profile = %final_profile_id,
+                        "Assigned profile yielded 0 entries; \
+                         falling back to plain-text"
+                    );
+                    match open_log_lines_timed(&file.path, self.parse_config) {
+                        Ok((mut lines, sample)) => {
+                            (parse_result, _) = parse_log_lines(
+                                &mut lines,
+                                &sample,
+                                &file.path,
+                                plain_profile,
+                                self.parse_config,
+                                self.cancel,
+                                self.progress_tx,
+                                &mut warnings,
+                            );
+                            final_profile_id = plain_profile.id.clone();
+                        }
+                        Err(e) => {
+                            let msg = format!("Cannot re-read '{}': {e}", file.path.display());
+                            tracing::warn!(warning = %msg, "File read failed");
+                            let _ = self.progress_tx.send(ScanProgress::Warning {
+                                message: msg.clone(),
+                            });
+                            warnings.push(msg);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Stamp the source file's OS last-modified time on every entry.
+        for entry in &mut parse_result.entries {
+            entry.file_modified = file.modified;
+        }
+
+        let entry_count = parse_result.entries.len();
+        let error_count = parse_result.errors.len();
+        let (earliest, latest) = time_range(&parse_result.entries);
+        let summary = FileSummary {
+            path: file.path.clone(),
+            profile_id: final_profile_id.clone(),
+            entry_count,
+            error_count,
+            earliest,
+            latest,
+            segments: 1,
+        };
+
+        for err in &parse_result.errors {
+            tracing::debug!(error = %err, "Parse error");
+        }
+
+        self.file_parsed(&file.path, entry_count, error_count);
+
+        FileResult {
+            idx,
+            profile_id: Some(final_profile_id),
+            detection_confidence,
+            entries: parse_result.entries,
+            summary: Some(summary),
+            warnings,
+            error_count,
+            parsing_skipped: false,
+        }
+    }
+
+    /// Send per-file progress.  The order is non-deterministic in parallel
+    /// mode but the UI only uses files_completed / total_files for its
+    /// progress bar, so arrival order does not matter.
+    fn file_parsed(&self, path: &Path, entries: usize, errors: usize) {
+        let completed = self.files_completed.fetch_add(1, Ordering::SeqCst) + 1;
+        let _ = self.progress_tx.send(ScanProgress::FileParsed {
+            path: path.to_path_buf(),
+            entries,
+            errors,
+            files_completed: completed,
+            total_files: self.total_files,
+        });
+    }
+}
+
+/// Earliest and latest timestamp among `entries`, for a file summary.
+fn time_range(
+    entries: &[LogEntry],
+) -> (
+    Option<chrono::DateTime<chrono::Utc>>,
+    Option<chrono::DateTime<chrono::Utc>>,
+) {
+    let mut earliest: Option<chrono::DateTime<chrono::Utc>> = None;
+    let mut latest: Option<chrono::DateTime<chrono::Utc>> = None;
+    for entry in entries {
+        if let Some(ts) = entry.timestamp {
+            earliest = Some(match earliest {
+                Some(e) if e <= ts => e,
+                _ => ts,
+            });
+            latest = Some(match latest {
+                Some(l) if l >= ts => l,
+                _ => ts,
+            });
+        }
+    }
+    (earliest, latest)
+}
+
+/// On-disk store for the entries of one pipeline run beyond the resident cap.
+struct SpillStore {
+    dir: PathBuf,
+    /// Created on first overflow.
+    store: Option<EntryStore>,
+}
+
+impl SpillStore {
+    fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            store: None,
+        }
+    }
+
+    /// Append `overflow` (IDs already assigned) to the store.  On a write
+    /// error the entries are dropped, as they are without a spill directory.
+    fn append(
+        &mut self,
+        mut overflow: Vec<LogEntry>,
+        clock_rules: &[ClockRule],
+    ) -> Result<(), String> {
+        // Resident entries get their clock offsets on the UI thread when
+        // ingested; spilled entries never pass through it.
+        crate::core::clock::apply_rules(clock_rules, &mut overflow);
+
+        let result = match self.store.as_mut() {
+            Some(store) => store.append(&overflow),
+            None => EntryStore::create(&self.dir).and_then(|mut store| {
+                store.append(&overflow)?;
+                self.store = Some(store);
+                Ok(())
+            }),
+        };
+        result.map_err(|e| {
+            format!(
+                "Cannot write the on-disk entry store in '{}': {e}. \
+                 Entries beyond the limit are dropped.",
+                self.dir.display()
+            )
+        })
+    }
+
+    /// The store, if any entry was spilled.
+    fn into_store(self) -> Option<EntryStore> {
+        self.store.filter(|s| !s.is_empty())
+    }
+}
+
 // =============================================================================
 // Add-files scan (no directory walk, append to existing session)
 // =============================================================================
@@ -1268,6 +1606,7 @@ fn run_files_scan(
     max_total_entries: usize,
     entry_id_start: u64,
     parse_path_filter: Option<std::collections::HashSet<PathBuf>>,
+    spill_dir: Option<PathBuf>,
 ) {
     use crate::util::constants::DEFAULT_LARGE_FILE_THRESHOLD;
     use chrono::DateTime;
@@ -1292,6 +1631,27 @@ fn run_files_scan(
         if cancel.load(std::sync::atomic::Ordering::SeqCst) {
             return;
         }
+        // Archives added directly are expanded into their members using the
+        // default include patterns.
+        if crate::core::archive::ArchiveKind::from_path(path).is_some() {
+            let (members, warnings) = crate::core::discovery::discover_archive_members(
+                path,
+                &crate::core::discovery::DiscoveryConfig::default(),
+            );
+            for message in warnings {
+                send!(ScanProgress::Warning { message });
+            }
+            if members.is_empty() {
+                send!(ScanProgress::Warning {
+                    message: format!(
+                        "Archive '{}' contains no files matching the log include patterns",
+                        path.display()
+                    ),
+                });
+            }
+            discovered.extend(members);
+            continue;
+        }
         let path_owned = path.clone();
         let (meta_tx, meta_rx) = std::sync::mpsc::channel();
         std::thread::spawn(move || {
@@ -1346,6 +1706,7 @@ fn run_files_scan(
         max_total_entries,
         entry_id_start,
         parse_path_filter.as_ref(),
+        spill_dir.as_deref(),
     );
 }
 
@@ -1353,16 +1714,44 @@ fn run_files_scan(
 // File reading helpers
 // =============================================================================
 
-/// Timeout-guarded wrapper around `read_file_content`.
+/// Lines of a log file or archive member, decoded to UTF-8 as they are read.
+type LogLines<R = Box<dyn Read + Send>> = LineReader<BufReader<Utf8Reader<R>>>;
+
+/// Timeout-guarded `open_log_lines` plus the first `SAMPLE_LINES` lines for
+/// profile auto-detection.
 ///
-/// Runs the I/O on a separate thread; if no result arrives within
-/// `FILE_READ_TIMEOUT_SECS` returns a `TimedOut` error so the caller logs a
-/// warning and skips the file (Rule 11 resilience).
-fn read_file_content_timed(path: &Path, is_large: bool) -> io::Result<String> {
-    let (tx, rx) = std::sync::mpsc::channel();
+/// Runs the open and the first reads on a separate thread; if no result
+/// arrives within `FILE_READ_TIMEOUT_SECS` returns a `TimedOut` error so the
+/// caller logs a warning and skips the file (Rule 11 resilience).  A stalled
+/// share or locked file almost always stalls here, before the first chunk.
+fn open_log_lines_timed(path: &Path, config: &ParseConfig) -> io::Result<(LogLines, Vec<String>)> {
+    let path_owned = path.to_path_buf();
+    let chunk_size = config.chunk_size;
+    let max_line = parser::max_line_len(config);
+    with_read_timeout(path, move || {
+        let mut lines = open_log_lines(&path_owned, chunk_size, max_line)?;
+        let sample = read_sample(&mut lines)?;
+        Ok((lines, sample))
+    })
+}
+
+/// Timeout-guarded `open_with_retry`, for a tarball whose members are then
+/// read in one pass (`ParseJob::parse_tarball`).
+fn open_timed(path: &Path) -> io::Result<std::fs::File> {
     let path_owned = path.to_path_buf();
+    with_read_timeout(path, move || open_with_retry(&path_owned))
+}
+
+/// Run `read` on a separate thread and wait at most
+/// `FILE_READ_TIMEOUT_SECS` for its result.
+fn with_read_timeout<T, F>(path: &Path, read: F) -> io::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+{
+    let (tx, rx) = std::sync::mpsc::channel();
     std::thread::spawn(move || {
-        let _ = tx.send(read_file_content(&path_owned, is_large));
+        let _ = tx.send(read());
     });
     match rx.recv_timeout(Duration::from_secs(FILE_READ_TIMEOUT_SECS)) {
         Ok(result) => result,
@@ -1380,43 +1769,55 @@ fn read_file_content_timed(path: &Path, is_large: bool) -> io::Result<String> {
     }
 }
 
-/// Read the full content of a file as a UTF-8 string.
+/// Open a log file for streaming: `chunk_size`-byte reads, BOM-sniffed and
+/// transcoded to UTF-8 (`core::encoding`), split into lines of at most
+/// `max_line` bytes.
 ///
-/// Large files use retrying byte reads followed by conservative decoding.
-/// Small UTF-8 files use `fs::read_to_string` with retry.
+/// Archive member paths (`bundle.zip!/logs/app.log`) are streamed through
+/// their decompressor (`core::archive::open_member`) and decoded like any
+/// other file, so memory stays bounded by `chunk_size` here too.
 ///
-/// Transient I/O errors (WouldBlock, Interrupted, TimedOut) are retried with
-/// capped exponential backoff (Rule 11). Permanent errors are returned immediately.
-fn read_file_content(path: &Path, is_large: bool) -> io::Result<String> {
-    if is_large {
-        read_large_file(path)
+/// This deliberately avoids a memory map. Log files may still be growing
+/// while LogSleuth scans them, and mapping a file that can be modified by
+/// another process is not a sound assumption for a production log viewer.
+fn open_log_lines(path: &Path, chunk_size: usize, max_line: usize) -> io::Result<LogLines> {
+    let source: Box<dyn Read + Send> = if crate::core::archive::is_member_path(path) {
+        crate::core::archive::open_member(path)?
     } else {
-        read_small_file_with_retry(path)
-    }
+        Box::new(open_with_retry(path)?)
+    };
+    Ok(log_lines(source, chunk_size, max_line))
 }
 
-/// Read a large file via retrying byte reads.
-///
-/// This deliberately avoids a live memory map. Log files may still be growing
-/// while LogSleuth scans them, and mapping a file that can be modified by
-/// another process is not a sound assumption for a production log viewer.
-fn read_large_file(path: &Path) -> io::Result<String> {
-    let bytes = read_bytes_with_retry(path)?;
-    decode_owned_bytes(bytes, path)
+/// Decode `source` as `open_log_lines` does.
+fn log_lines<R: Read>(source: R, chunk_size: usize, max_line: usize) -> LogLines<R> {
+    let decoded = BufReader::with_capacity(chunk_size, Utf8Reader::new(source, chunk_size));
+    LineReader::new(decoded, max_line)
 }
 
-/// Read a small file with transient-error retries.
-fn read_small_file_with_retry(path: &Path) -> io::Result<String> {
+/// Read the first `SAMPLE_LINES` lines of `lines` for profile
+/// auto-detection.
+fn read_sample<R: BufRead>(lines: &mut LineReader<R>) -> io::Result<Vec<String>> {
+    let mut sample = Vec::with_capacity(SAMPLE_LINES);
+    while sample.len() < SAMPLE_LINES {
+        match lines.next_line()? {
+            Some(line) => sample.push(line.to_string()),
+            None => break,
+        }
+    }
+    Ok(sample)
+}
+
+/// Open the file with transient-error retries.
+///
+/// Transient I/O errors (WouldBlock, Interrupted, TimedOut) are retried with
+/// capped exponential backoff (Rule 11). Permanent errors are returned immediately.
+fn open_with_retry(path: &Path) -> io::Result<std::fs::File> {
     let mut last_err: Option<io::Error> = None;
 
     for (attempt, &delay_ms) in RETRY_DELAYS_MS.iter().enumerate() {
-        match std::fs::read_to_string(path) {
-            Ok(content) => return Ok(content),
-            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
-                // File is not valid UTF-8 (e.g. UTF-16 LE Windows system logs).
-                // Switch to encoding-aware decoding rather than retrying.
-                return decode_non_utf8_file(path);
-            }
+        match std::fs::File::open(path) {
+            Ok(file) => return Ok(file),
             Err(e) if is_transient_error(&e) => {
                 tracing::debug!(
                     file = %path.display(),
@@ -1434,83 +1835,251 @@ fn read_small_file_with_retry(path: &Path) -> io::Result<String> {
     Err(last_err.unwrap_or_else(|| io::Error::other("Unknown read error")))
 }
 
-/// Decode a file whose bytes are not valid UTF-8.
+/// Stream a file through `parser::StreamParser`: the `sample` lines already
+/// read for auto-detection first, then the rest of `lines`.
 ///
-/// Checks for UTF-16 LE BOM (0xFF 0xFE) and UTF-16 BE BOM (0xFE 0xFF) —
-/// both are used by Windows system logs such as CBS.log and WindowsUpdate.log.
-/// Falls back to lossy UTF-8 for ANSI / Windows-1252 encoded files.
-fn decode_non_utf8_file(path: &Path) -> io::Result<String> {
-    let bytes = read_bytes_with_retry(path)?;
-    decode_owned_bytes(bytes, path)
-}
+/// A large UTF-8 file is instead split into segments parsed in parallel
+/// (`parse_segmented`).  Returns the parse result and whether the file had
+/// any non-blank line.  A read error part-way through keeps the entries
+/// parsed so far and adds a warning; cancellation stops the read early.
+#[allow(clippy::too_many_arguments)]
+fn parse_log_lines<R: Read>(
+    lines: &mut LogLines<R>,
+    sample: &[String],
+    path: &Path,
+    profile: &FormatProfile,
+    config: &ParseConfig,
+    cancel: &AtomicBool,
+    progress_tx: &mpsc::Sender<ScanProgress>,
+    warnings: &mut Vec<String>,
+) -> (parser::ParseResult, bool) {
+    let encoding = lines.get_ref().get_ref().encoding();
+    if let Some(encoding) = encoding {
+        tracing::debug!(file = %path.display(), encoding = encoding.label(), "Decoding file");
+    }
 
-/// Read the file as raw bytes with transient-error retries.
-///
-/// Used by the large-file path and the non-UTF-8 fallback so both benefit
-/// from the same retry policy.
-fn read_bytes_with_retry(path: &Path) -> io::Result<Vec<u8>> {
-    let mut last_err: Option<io::Error> = None;
+    // Only plain UTF-8 files are split.  A byte offset in a UTF-16 file or
+    // inside a compressed archive member cannot be mapped to a line start
+    // without decoding everything before it, so those are always streamed
+    // on one thread below, however large (same result, only slower).
+    let segments = segment_count(path);
+    let splittable =
+        encoding == Some(TextEncoding::Utf8) && !crate::core::archive::is_member_path(path);
+    if segments > 1 && !splittable {
+        tracing::debug!(
+            file = %path.display(),
+            encoding = encoding.map(|e| e.label()),
+            "Large file is UTF-16 or an archive member; parsing on one thread"
+        );
+    }
+    if segments > 1 && splittable {
+        match parse_segmented(path, profile, config, segments, cancel) {
+            Ok((result, has_text, read_errors)) => {
+                for msg in read_errors {
+                    tracing::warn!(warning = %msg, "File read failed part-way");
+                    let _ = progress_tx.send(ScanProgress::Warning {
+                        message: msg.clone(),
+                    });
+                    warnings.push(msg);
+                }
+                return (result, has_text);
+            }
+            Err(e) => tracing::debug!(
+                file = %path.display(),
+                error = %e,
+                "Segmented parse unavailable; streaming on one thread"
+            ),
+        }
+    }
 
-    for (attempt, &delay_ms) in RETRY_DELAYS_MS.iter().enumerate() {
-        match std::fs::read(path) {
-            Ok(bytes) => return Ok(bytes),
-            Err(e) if is_transient_error(&e) => {
-                tracing::debug!(
-                    file = %path.display(),
-                    attempt = attempt + 1,
-                    error = %e,
-                    "Transient byte-read I/O error, retrying"
-                );
-                std::thread::sleep(Duration::from_millis(delay_ms));
-                last_err = Some(e);
+    let mut stream = parser::StreamParser::new(path, profile, config, 0);
+    let mut has_text = false;
+    for line in sample {
+        has_text |= !line.trim().is_empty();
+        stream.push_line(line);
+    }
+    let (rest_has_text, read_error) = feed_lines(lines, &mut stream, cancel);
+    if let Some(e) = read_error {
+        let msg = format!(
+            "Read of '{}' stopped after line {}: {e}",
+            path.display(),
+            stream.lines_processed()
+        );
+        tracing::warn!(warning = %msg, "File read failed part-way");
+        let _ = progress_tx.send(ScanProgress::Warning {
+            message: msg.clone(),
+        });
+        warnings.push(msg);
+    }
+    (stream.finish(), has_text || rest_has_text)
+}
+
+/// Feed every remaining line of `lines` to `stream`, checking `cancel` every
+/// `CANCEL_CHECK_LINES` lines.  Returns whether any line was non-blank, and
+/// the read error that ended the input early, if any.
+fn feed_lines<R: BufRead>(
+    lines: &mut LineReader<R>,
+    stream: &mut parser::StreamParser<'_>,
+    cancel: &AtomicBool,
+) -> (bool, Option<io::Error>) {
+    let mut has_text = false;
+    let mut read_lines: usize = 0;
+    loop {
+        read_lines += 1;
+        if read_lines % CANCEL_CHECK_LINES == 0 && cancel.load(Ordering::Relaxed) {
+            return (has_text, None);
+        }
+        match lines.next_line() {
+            Ok(Some(line)) => {
+                has_text |= !line.trim().is_empty();
+                stream.push_line(line);
             }
-            Err(e) => return Err(e),
+            Ok(None) => return (has_text, None),
+            Err(e) => return (has_text, Some(e)),
         }
     }
+}
 
-    Err(last_err.unwrap_or_else(|| io::Error::other("Unknown read error")))
+// =============================================================================
+// Parallel parsing of one large file
+// =============================================================================
+
+/// Number of segments to split `path` into: one per rayon worker, but no
+/// segment smaller than `SEGMENT_MIN_BYTES`.  1 means "do not split".
+fn segment_count(path: &Path) -> usize {
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let by_size = usize::try_from(size / SEGMENT_MIN_BYTES).unwrap_or(usize::MAX);
+    by_size.min(rayon::current_num_threads()).max(1)
 }
 
-/// Detect encoding from owned `bytes` and return the decoded string.
+/// Parse a UTF-8 file as up to `segments` byte ranges in parallel and stitch
+/// the results (`parser::stitch_segments`).
 ///
-/// Checks BOM markers only; does not do statistical charset detection.
-/// This is intentionally conservative: it correctly handles the most common
-/// non-UTF-8 encodings found in Windows system log directories while avoiding
-/// the complexity and potential false-positives of full charset detection.
-fn decode_owned_bytes(bytes: Vec<u8>, path: &Path) -> io::Result<String> {
-    // UTF-16 LE BOM: 0xFF 0xFE — used by CBS.log, WindowsUpdate.log, etc.
-    if bytes.len() >= 2 && bytes[0] == 0xFF && bytes[1] == 0xFE {
-        let utf16: Vec<u16> = bytes[2..]
-            .chunks_exact(2)
-            .map(|c| u16::from_le_bytes([c[0], c[1]]))
-            .collect();
-        tracing::debug!(file = %path.display(), "Decoded file as UTF-16 LE");
-        return Ok(String::from_utf16_lossy(&utf16));
+/// Each range starts at a line where a record starts
+/// (`parser::starts_record`), so every segment parses exactly as it would
+/// have sequentially; line numbers and entry IDs continue across segments.
+/// Returns the result, whether any line was non-blank, and one warning per
+/// segment whose read failed part-way.  Errors only when the file cannot be
+/// opened or planned; the caller then parses it on one thread.
+fn parse_segmented(
+    path: &Path,
+    profile: &FormatProfile,
+    config: &ParseConfig,
+    segments: usize,
+    cancel: &AtomicBool,
+) -> io::Result<(parser::ParseResult, bool, Vec<String>)> {
+    let file = open_with_retry(path)?;
+    let size = file.metadata()?.len();
+    let mut starts = vec![0u64];
+    for i in 1..segments as u64 {
+        let target = size / segments as u64 * i;
+        if target <= *starts.last().unwrap_or(&0) {
+            continue;
+        }
+        if let Some(start) = find_record_start(&file, target, profile)? {
+            if start > *starts.last().unwrap_or(&0) && start < size {
+                starts.push(start);
+            }
+        }
     }
+    tracing::debug!(
+        file = %path.display(),
+        size,
+        segments = starts.len(),
+        "Parsing file in parallel segments"
+    );
 
-    // UTF-16 BE BOM: 0xFE 0xFF — uncommon but valid.
-    if bytes.len() >= 2 && bytes[0] == 0xFE && bytes[1] == 0xFF {
-        let utf16: Vec<u16> = bytes[2..]
-            .chunks_exact(2)
-            .map(|c| u16::from_be_bytes([c[0], c[1]]))
-            .collect();
-        tracing::debug!(file = %path.display(), "Decoded file as UTF-16 BE");
-        return Ok(String::from_utf16_lossy(&utf16));
-    }
+    let ranges: Vec<(u64, u64)> = starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| (start, starts.get(i + 1).copied().unwrap_or(size)))
+        .collect();
+    let parsed: Vec<(parser::ParseResult, bool, Option<String>)> = ranges
+        .par_iter()
+        .map(|&(start, end)| parse_segment(path, start, end, profile, config, cancel))
+        .collect();
 
-    // No recognised BOM: try zero-copy UTF-8 first (most log files are
-    // valid UTF-8), falling back to lossy conversion only for genuinely
-    // invalid bytes.  The owned Vec→String path avoids a full buffer copy
-    // that from_utf8_lossy().into_owned() would always perform.
-    match String::from_utf8(bytes) {
-        Ok(s) => {
-            tracing::debug!(file = %path.display(), "Decoded file as UTF-8 (zero-copy)");
-            Ok(s)
+    let has_text = parsed.iter().any(|(_, has_text, _)| *has_text);
+    let read_errors: Vec<String> = parsed.iter().filter_map(|(_, _, e)| e.clone()).collect();
+    let result = parser::stitch_segments(
+        parsed.into_iter().map(|(result, _, _)| result).collect(),
+        0,
+        config.max_parse_errors_per_file,
+    );
+    Ok((result, has_text, read_errors))
+}
+
+/// Parse bytes `start..end` of `path` on its own file handle.  The segment at
+/// offset 0 goes through `Utf8Reader` so a UTF-8 BOM is stripped.
+fn parse_segment(
+    path: &Path,
+    start: u64,
+    end: u64,
+    profile: &FormatProfile,
+    config: &ParseConfig,
+    cancel: &AtomicBool,
+) -> (parser::ParseResult, bool, Option<String>) {
+    let mut stream = parser::StreamParser::new(path, profile, config, 0);
+    let opened = open_with_retry(path).and_then(|mut file| {
+        file.seek(SeekFrom::Start(start))?;
+        Ok(file)
+    });
+    let (has_text, read_error) = match opened {
+        Ok(file) => {
+            let range = file.take(end - start);
+            let source: Box<dyn Read + Send> = if start == 0 {
+                Box::new(Utf8Reader::new(range, config.chunk_size))
+            } else {
+                Box::new(range)
+            };
+            let mut lines = LineReader::new(
+                BufReader::with_capacity(config.chunk_size, source),
+                parser::max_line_len(config),
+            );
+            feed_lines(&mut lines, &mut stream, cancel)
         }
-        Err(e) => {
-            tracing::debug!(file = %path.display(), "Decoded file as lossy UTF-8 (no BOM)");
-            Ok(String::from_utf8_lossy(e.as_bytes()).into_owned())
+        Err(e) => (false, Some(e)),
+    };
+    let warning = read_error.map(|e| {
+        format!(
+            "Read of '{}' stopped in bytes {start}-{end}: {e}",
+            path.display()
+        )
+    });
+    (stream.finish(), has_text, warning)
+}
+
+/// Byte offset of the first line at or after `from` that starts a record,
+/// searching at most `SEGMENT_SEARCH_BYTES`.  The line containing `from` is
+/// skipped since it may be partial.
+fn find_record_start(
+    file: &std::fs::File,
+    from: u64,
+    profile: &FormatProfile,
+) -> io::Result<Option<u64>> {
+    let mut handle = file;
+    handle.seek(SeekFrom::Start(from))?;
+    let mut reader = BufReader::new(handle.take(SEGMENT_SEARCH_BYTES));
+    let mut buf: Vec<u8> = Vec::new();
+    let mut pos = from;
+    let mut prev = String::new();
+    let mut first = true;
+    loop {
+        buf.clear();
+        let n = reader.read_until(b'\n', &mut buf)?;
+        if n == 0 || buf.last() != Some(&b'\n') {
+            return Ok(None); // end of file or of the search window
         }
+        let line = String::from_utf8_lossy(&buf);
+        let line = line.trim_end_matches(['\n', '\r']);
+        if !first && parser::starts_record(profile, &prev, line) {
+            return Ok(Some(pos));
+        }
+        if !first && !line.trim().is_empty() {
+            prev = line.to_string();
+        }
+        first = false;
+        pos += n as u64;
     }
 }
 
@@ -1524,35 +2093,283 @@ fn is_transient_error(e: &io::Error) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use super::read_file_content;
+    use super::{open_log_lines, parse_segmented, LogLines};
+    use crate::core::parser::{self, ParseConfig};
     use std::fs;
+    use std::path::PathBuf;
+    use std::sync::atomic::AtomicBool;
+
+    fn read_all(mut lines: LogLines) -> Vec<String> {
+        let mut out = Vec::new();
+        while let Some(line) = lines.next_line().expect("read line") {
+            out.push(line.to_string());
+        }
+        out
+    }
 
     #[test]
-    fn test_read_file_content_large_utf8_file() {
+    fn test_open_log_lines_utf8_file() {
         let dir = tempfile::tempdir().expect("tempdir");
         let path = dir.path().join("large.log");
-        let expected = "2026-04-04 12:00:00 hello\nsecond line\n";
-        fs::write(&path, expected).expect("write utf8 log");
+        fs::write(&path, "2026-04-04 12:00:00 hello\nsecond line\n").expect("write utf8 log");
 
-        let actual = read_file_content(&path, true).expect("read large utf8 file");
+        let lines = open_log_lines(&path, 8, 1024).expect("open utf8 file");
 
-        assert_eq!(actual, expected);
+        assert_eq!(
+            read_all(lines),
+            vec!["2026-04-04 12:00:00 hello", "second line"]
+        );
     }
 
     #[test]
-    fn test_read_file_content_large_utf16le_file() {
+    fn test_open_log_lines_utf16le_file() {
         let dir = tempfile::tempdir().expect("tempdir");
         let path = dir.path().join("large-utf16.log");
-        let expected = "2026-04-04 12:00:00 hello\n";
+        let text = "2026-04-04 12:00:00 hello\r\nsecond line\r\n";
 
         let mut bytes = vec![0xFF, 0xFE];
-        for unit in expected.encode_utf16() {
+        for unit in text.encode_utf16() {
             bytes.extend_from_slice(&unit.to_le_bytes());
         }
         fs::write(&path, bytes).expect("write utf16 log");
 
-        let actual = read_file_content(&path, true).expect("read large utf16 file");
+        let lines = open_log_lines(&path, 7, 1024).expect("open utf16 file");
+
+        assert_eq!(
+            read_all(lines),
+            vec!["2026-04-04 12:00:00 hello", "second line"]
+        );
+    }
+
+    /// A file split into segments parses to exactly the sequential result:
+    /// splits land on record starts (never inside a stack trace), and line
+    /// numbers and IDs run on across segments.
+    #[test]
+    fn test_parse_segmented_matches_sequential() {
+        let toml = r#"
+[profile]
+id = "seg"
+name = "Segmented"
+
+[detection]
+content_match = '^\['
+
+[parsing]
+line_pattern = '^\[(?P<timestamp>[^\]]+)\]\s(?P<level>\w+)\s+(?P<message>.+)$'
+timestamp_format = "%Y-%m-%d %H:%M:%S"
+multiline_mode = "continuation"
+"#;
+        let def_path = PathBuf::from("seg.toml");
+        let profile = crate::core::profile::validate_and_compile(
+            crate::core::profile::parse_profile_toml(toml, &def_path).unwrap(),
+            &def_path,
+            false,
+        )
+        .unwrap();
+
+        let mut content = String::from("\u{feff}");
+        for i in 0..3000 {
+            content.push_str(&format!(
+                "[2024-01-15 10:{:02}:{:02}] Info step {i}\n",
+                i / 60 % 60,
+                i % 60
+            ));
+            if i % 7 == 0 {
+                content.push_str("\tat com.example.Worker.run(Worker.java:12)\r\n\n");
+            }
+        }
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("big.log");
+        fs::write(&path, &content).expect("write log");
+
+        let config = ParseConfig::default();
+        let (segmented, has_text, read_errors) =
+            parse_segmented(&path, &profile, &config, 5, &AtomicBool::new(false))
+                .expect("segmented parse");
+        let whole = parser::parse_content(
+            content.trim_start_matches('\u{feff}'),
+            &path,
+            &profile,
+            &config,
+            0,
+        );
+
+        assert!(has_text);
+        assert!(read_errors.is_empty());
+        assert_eq!(segmented.lines_processed, whole.lines_processed);
+        assert_eq!(segmented.entries.len(), 3000);
+        for (s, w) in segmented.entries.iter().zip(&whole.entries) {
+            assert_eq!(
+                (s.id, s.line_number, s.message(), s.timestamp),
+                (w.id, w.line_number, w.message(), w.timestamp)
+            );
+        }
+    }
+
+    /// Results are assembled while the workers run, so once the entry cap
+    /// is reached without a spill directory the files after it are not
+    /// parsed at all.
+    #[test]
+    fn test_pipeline_stops_parsing_once_cap_reached() {
+        use crate::core::model::ScanProgress;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let files: Vec<PathBuf> = (0..300)
+            .map(|f| {
+                let path = dir.path().join(format!("app{f:03}.log"));
+                fs::write(&path, "one\ntwo\n").expect("write log");
+                path
+            })
+            .collect();
 
-        assert_eq!(actual, expected);
+        let mut manager = super::ScanManager::new();
+        manager.start_scan_files(
+            files,
+            crate::core::profile::load_builtin_profiles(),
+            5,
+            0,
+            None,
+        );
+        let rx = manager.progress_rx.take().expect("scan started");
+        let mut parsed = 0;
+        let summary = loop {
+            match rx.recv().expect("scan message") {
+                ScanProgress::FileParsed { .. } => parsed += 1,
+                ScanProgress::ParsingCompleted { summary } => break summary,
+                ScanProgress::Failed { error } => panic!("{error}"),
+                _ => {}
+            }
+        };
+
+        assert!(summary.entry_cap_reached);
+        assert_eq!(summary.total_entries, 5);
+        assert!(parsed < 300, "all {parsed} files were parsed");
+    }
+
+    /// Every member of a tarball is parsed, each under its own virtual
+    /// path, from one walk of the archive.
+    #[test]
+    fn test_pipeline_parses_every_tarball_member() {
+        use crate::core::model::ScanProgress;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let tgz = dir.path().join("bundle.tar.gz");
+        {
+            let file = fs::File::create(&tgz).expect("create tarball");
+            let mut builder =
+                tar::Builder::new(flate2::write::GzEncoder::new(file, Default::default()));
+            for (name, count) in [("c.log", 3), ("logs/a.log", 5), ("b.log", 4)] {
+                let data: String = (0..count).map(|i| format!("{name} line {i}\n")).collect();
+                let mut header = tar::Header::new_gnu();
+                header.set_size(data.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder
+                    .append_data(&mut header, name, data.as_bytes())
+                    .expect("append member");
+            }
+            builder
+                .into_inner()
+                .expect("finish tar")
+                .finish()
+                .expect("finish gzip");
+        }
+
+        let mut manager = super::ScanManager::new();
+        manager.start_scan_files(
+            vec![tgz.clone()],
+            crate::core::profile::load_builtin_profiles(),
+            1000,
+            0,
+            None,
+        );
+        let rx = manager.progress_rx.take().expect("scan started");
+        let mut parsed = Vec::new();
+        let mut entries = Vec::new();
+        loop {
+            match rx.recv().expect("scan message") {
+                ScanProgress::FileParsed { path, entries, .. } => parsed.push((path, entries)),
+                ScanProgress::EntriesBatch { entries: batch } => entries.extend(batch),
+                ScanProgress::ParsingCompleted { .. } => break,
+                ScanProgress::Failed { error } => panic!("{error}"),
+                _ => {}
+            }
+        }
+
+        parsed.sort();
+        let member = |name| crate::core::archive::member_path(&tgz, name);
+        assert_eq!(
+            parsed,
+            vec![
+                (member("b.log"), 4),
+                (member("c.log"), 3),
+                (member("logs/a.log"), 5)
+            ]
+        );
+        assert_eq!(entries.len(), 12);
+        assert!(
+            entries
+                .iter()
+                .any(|e| *e.source_file == *member("logs/a.log")
+                    && e.message() == "logs/a.log line 4")
+        );
+    }
+
+    /// With a spill directory, entries beyond the cap reach the UI through
+    /// an entry store instead of being dropped, with IDs that do not collide
+    /// with the resident ones.  The resident entries are the first ones in
+    /// file order, whichever worker finished first.
+    #[test]
+    fn test_pipeline_spills_entries_beyond_cap() {
+        use crate::core::model::ScanProgress;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut files = Vec::new();
+        for f in 0..3 {
+            let path = dir.path().join(format!("app{f}.log"));
+            let lines: String = (0..50).map(|i| format!("file {f} line {i}\n")).collect();
+            fs::write(&path, lines).expect("write log");
+            files.push(path);
+        }
+        let spill = dir.path().join("entry-store");
+
+        let mut manager = super::ScanManager::new();
+        manager.set_spill_dir(Some(spill.clone()));
+        manager.start_scan_files(
+            files,
+            crate::core::profile::load_builtin_profiles(),
+            40,
+            0,
+            None,
+        );
+        let rx = manager.progress_rx.take().expect("scan started");
+        let mut resident = Vec::new();
+        let mut stored = None;
+        let total = loop {
+            match rx.recv().expect("scan message") {
+                ScanProgress::EntriesBatch { entries } => resident.extend(entries),
+                ScanProgress::EntriesSpilled { store } => stored = Some(store),
+                ScanProgress::ParsingCompleted { summary } => break summary.total_entries,
+                ScanProgress::Failed { error } => panic!("{error}"),
+                _ => {}
+            }
+        };
+        let store = stored.expect("entries spilled");
+        let mut store = store.lock().unwrap();
+
+        assert_eq!(total, 150);
+        assert_eq!(resident.len(), 40);
+        assert_eq!(store.len(), 110);
+        let mut ids: Vec<u64> = resident.iter().map(|e| e.id).collect();
+        ids.extend(store.window(0, 200).unwrap().iter().map(|e| e.id));
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), 150);
+
+        let first = &resident[0].source_file;
+        assert!(resident.iter().all(|e| &e.source_file == first));
+        let mut resident_ids: Vec<u64> = resident.iter().map(|e| e.id).collect();
+        resident_ids.sort_unstable();
+        assert_eq!(resident_ids, (0..40).collect::<Vec<u64>>());
     }
 }
@@ -111,6 +111,10 @@ pub struct SessionData {
     #[serde(default = "default_tail_poll_interval_ms")]
     pub tail_poll_interval_ms: u64,
 
+    /// Live tail start mode for files whose content is not loaded.
+    #[serde(default)]
+    pub tail_replay: crate::app::tail::TailReplay,
+
     /// Directory watcher poll interval in milliseconds.
     #[serde(default = "default_dir_watch_poll_interval_ms")]
     pub dir_watch_poll_interval_ms: u64,
@@ -125,6 +129,10 @@ pub struct SessionData {
     /// Error entries are ingested on the next scan / tail session.
     #[serde(default)]
     pub troubleshoot_mode: bool,
+
+    /// Per-file / per-directory clock offsets and timezone assumptions.
+    #[serde(default)]
+    pub clock_rules: Vec<crate::core::clock::ClockRule>,
 }
 
 fn default_correlation_window() -> i64 {
@@ -172,7 +180,7 @@ fn default_max_tail_buffer_entries() -> usize {
 /// Only the user-visible, stable fields are persisted.  Runtime-only state
 /// (`regex_search`, `bookmarked_ids`, `time_start`/`time_end`) is excluded
 /// and re-derived on restore.
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PersistedFilter {
     /// Active severity level filter.  Empty = all severities shown.
     #[serde(default)]
@@ -249,6 +257,16 @@ pub struct PersistedFilter {
     /// Multi-term search: regex mode flag.
     #[serde(default)]
     pub multi_search_regex_mode: bool,
+
+    /// Structured-field filter raw input (`key=value, key`).  Re-parsed on
+    /// restore.  Empty = no field filter.
+    #[serde(default)]
+    pub field_filter: String,
+
+    /// Filter query-language text (e.g. `component:Agent AND
+    /// severity>=warning`).  Re-parsed on restore.  Empty = no query.
+    #[serde(default)]
+    pub query: String,
 }
 
 fn default_true() -> bool {
@@ -356,6 +374,7 @@ mod tests {
                 fuzzy: true,
                 relative_time_secs: Some(3600),
                 dedup_mode: DedupMode::Normalized,
+                query: "component:Agent AND severity>=warning".to_string(),
                 ..Default::default()
             },
             file_colours: vec![(PathBuf::from("/tmp/logs/app.log"), [255, 128, 0, 255])],
@@ -370,9 +389,17 @@ mod tests {
             max_total_entries: 500_000,
             max_scan_depth: 5,
             tail_poll_interval_ms: 1_000,
+            tail_replay: crate::app::tail::TailReplay::Lines(500),
             dir_watch_poll_interval_ms: 4_000,
             max_tail_buffer_entries: default_max_tail_buffer_entries(),
             troubleshoot_mode: false,
+            clock_rules: vec![crate::core::clock::ClockRule {
+                path: PathBuf::from("/tmp/logs/sql"),
+                offset_secs: -90,
+                zone: Some(crate::core::clock::AssumedZone::Named(
+                    chrono_tz::Europe::Berlin,
+                )),
+            }],
         }
     }
 
@@ -417,6 +444,7 @@ mod tests {
         assert_eq!(loaded.max_total_entries, 500_000);
         assert_eq!(loaded.max_scan_depth, 5);
         assert_eq!(loaded.tail_poll_interval_ms, 1_000);
+        assert_eq!(loaded.tail_replay, crate::app::tail::TailReplay::Lines(500));
         assert_eq!(loaded.dir_watch_poll_interval_ms, 4_000);
         // Dedup mode must round-trip.
         assert_eq!(
@@ -424,6 +452,14 @@ mod tests {
             DedupMode::Normalized,
             "dedup_mode must survive round-trip"
         );
+        assert_eq!(
+            loaded.filter.query, "component:Agent AND severity>=warning",
+            "filter query must survive round-trip"
+        );
+        assert_eq!(
+            loaded.clock_rules, original.clock_rules,
+            "clock rules must survive round-trip"
+        );
     }
 
     /// Load must return None when the file does not exist (first run).
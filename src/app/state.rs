@@ -4,11 +4,12 @@
 // filter state, selection, and profile list.
 // Owned by the eframe::App implementation.
 
-use crate::core::filter::{DedupInfo, DedupMode, FilterState};
+use crate::core::filter::{parse_filter_datetime, DedupInfo, DedupMode, FilterState};
 use crate::core::model::{DiscoveredFile, FormatProfile, LogEntry, ScanSummary};
 use crate::util::constants::{DEFAULT_CORRELATION_WINDOW_SECS, MAX_CLIPBOARD_ENTRIES};
 use std::collections::{BTreeSet, HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Top-level application state.
 #[derive(Debug)]
@@ -128,6 +129,11 @@ pub struct AppState {
     /// Populated by `assign_file_colour` when files are discovered.
     pub file_colours: HashMap<PathBuf, egui::Color32>,
 
+    /// Palette colour of each logical stream, keyed by the stream path from
+    /// `discovery::rotation_segment`, so every segment of a rotation family
+    /// (`app.log`, `app.log.1`, `app.log.2.gz`) shares one colour.
+    pub stream_colours: HashMap<PathBuf, egui::Color32>,
+
     /// Set by the UI to request parsing a specific list of files in append
     /// mode (adds to the current session without clearing existing entries).
     /// Consumed and cleared by `gui.rs` in the update loop each frame.
@@ -230,6 +236,29 @@ pub struct AppState {
     /// those hidden by the current filter) so context is never silently missing.
     pub correlated_ids: HashSet<u64>,
 
+    // -------------------------------------------------------------------------
+    // Key correlation (stitched transactions)
+    // -------------------------------------------------------------------------
+    /// Identifier (trace / request / job ID, GUID, thread) the timeline is
+    /// correlated by, or `None` when key correlation is off.
+    pub correlation_key: Option<crate::core::correlation::CorrelationKey>,
+
+    /// UI text buffer for the key input in the filters panel.
+    pub correlation_key_input: String,
+
+    /// IDs of every entry (in every file, regardless of the current filter)
+    /// carrying `correlation_key`.  Used by `timeline.rs` for the blue
+    /// highlight overlay.
+    pub key_correlated_ids: HashSet<u64>,
+
+    /// Indices into `entries` of the key-correlated entries, ordered by
+    /// time: the stitched transaction.  Rebuilt by `refresh_key_correlation()`
+    /// on every `apply_filters()` and extended on the tail fast path.
+    pub transaction: Vec<usize>,
+
+    /// Whether to show the Transaction window.
+    pub show_transaction: bool,
+
     // -------------------------------------------------------------------------
     // Session persistence
     // -------------------------------------------------------------------------
@@ -286,6 +315,16 @@ pub struct AppState {
     /// Applied when a new tail session is started.
     pub tail_poll_interval_ms: u64,
 
+    /// Where Live Tail starts in files whose content is not loaded: their
+    /// end, or a replay of the last N lines / minutes.
+    /// User-configurable via the Options dialog; applied when a new tail
+    /// session is started.
+    pub tail_replay: crate::app::tail::TailReplay,
+
+    /// Options dialog input buffer for `TailReplay::Since` (local time,
+    /// "YYYY-MM-DD HH:MM[:SS]").  Pure UI state, not persisted.
+    pub tail_replay_since_input: String,
+
     /// How often the directory watcher polls for new files (ms).
     /// User-configurable via the Options dialog; defaults to DIR_WATCH_POLL_INTERVAL_MS.
     /// Applied when a new directory watch session is started.
@@ -393,6 +432,11 @@ pub struct AppState {
     /// `self.entries`.  Rebuilt by `apply_filters()` and cleared by `clear()`.
     pub unique_component_values: Vec<String>,
 
+    /// Sorted, deduplicated list of all structured-field keys observed across
+    /// `self.entries` (`LogEntry::fields`).  Shown as a hint next to the
+    /// field filter input.  Rebuilt alongside `unique_component_values`.
+    pub unique_field_keys: Vec<String>,
+
     // -------------------------------------------------------------------------
     // Live-tail ring-buffer state
     // -------------------------------------------------------------------------
@@ -429,6 +473,34 @@ pub struct AppState {
     /// time-range-filter fallback, and the O(n) scan can be avoided entirely.
     pub notimestamp_entry_count: usize,
 
+    // -------------------------------------------------------------------------
+    // Out-of-core entry store (core::entry_store)
+    // -------------------------------------------------------------------------
+    /// Every entry of the session, once it outgrew `max_total_entries`.
+    /// The filters then run over the whole store and `entries` holds the
+    /// page of matching rows around the timeline's scroll position.  `None`
+    /// while the session fits in memory.
+    pub entry_store: Option<crate::core::entry_store::EntryStore>,
+
+    /// Directory the entry store is created in (`<data dir>/entry-store`).
+    /// `None` disables spilling: entries beyond the cap are dropped.
+    pub spill_dir: Option<PathBuf>,
+
+    /// Store positions of every entry matching the current filters, in time
+    /// order.  These are the timeline rows while `entry_store` is set.
+    pub store_rows: Vec<u32>,
+
+    /// Index into `store_rows` of the first entry in `entries`.
+    pub store_window_start: usize,
+
+    /// Occurrence count and earliest timestamp of each dedup survivor in
+    /// `store_rows`, keyed by store position.  Empty when dedup is off.
+    store_dedup: HashMap<u32, (usize, Option<chrono::DateTime<chrono::Utc>>)>,
+
+    /// Set when entries were added to the store since the filters last ran.
+    /// `ParsingCompleted` re-runs them instead of sorting.
+    pub store_window_stale: bool,
+
     /// Set to `Some(Instant::now())` by text-input handlers when the user edits
     /// the text-search or regex fields.  Cleared by `apply_filters()`.  The UI
     /// loop fires `apply_filters()` once this age exceeds `FILTER_DEBOUNCE_MS`,
@@ -441,6 +513,99 @@ pub struct AppState {
     /// `MultiSearch` stores parsed term vectors, not the raw input.
     pub multi_search_input: String,
 
+    // -------------------------------------------------------------------------
+    // Filter presets
+    // -------------------------------------------------------------------------
+    /// Named filter presets shown as buttons in the Filters sidebar.  Loaded
+    /// at startup from `presets_path`; never cleared by `clear()`.
+    pub filter_presets: Vec<crate::app::presets::FilterPreset>,
+
+    /// Absolute path to the presets file (`FILTER_PRESETS_FILE_NAME` next to
+    /// `config.toml`).  Set once at startup; `None` in tests, in which case
+    /// presets are kept in memory only.
+    pub presets_path: Option<PathBuf>,
+
+    /// UI text buffer for the "Save as preset" name input.
+    pub preset_name_input: String,
+
+    // -------------------------------------------------------------------------
+    // Live alert rules
+    // -------------------------------------------------------------------------
+    /// Alert rules evaluated against live-tail batches.  Loaded at startup
+    /// from `alert_rules_path`; never cleared by `clear()`.
+    pub alert_rules: Vec<crate::core::alert::AlertRule>,
+
+    /// Absolute path to the rules file (`ALERT_RULES_FILE_NAME` next to
+    /// `config.toml`).  `None` in tests: rules are kept in memory only.
+    pub alert_rules_path: Option<PathBuf>,
+
+    /// Absolute path to the alert log (`ALERT_LOG_FILE_NAME`).  `None` in
+    /// tests: firings are not logged.
+    pub alert_log_path: Option<PathBuf>,
+
+    /// Compiled enabled rules with their match counts.  Rebuilt whenever the
+    /// rules change and when a tail session starts.
+    pub alert_engine: crate::core::alert::AlertEngine,
+
+    /// Last alert shown in the status bar and when it fired; the status bar
+    /// flashes for ALERT_FLASH_SECS after that.
+    pub alert_flash: Option<(String, std::time::Instant)>,
+
+    /// Whether to show the Alert Rules window.
+    pub show_alert_rules: bool,
+
+    /// Rule being edited in the Alert Rules window.
+    pub alert_draft: crate::core::alert::AlertRule,
+
+    /// Index of the rule the draft replaces when saved (`None` = new rule).
+    pub alert_draft_index: Option<usize>,
+
+    /// Command text buffer of the draft (program and arguments separated by
+    /// spaces).
+    pub alert_draft_command: String,
+
+    /// Why the draft could not be saved, shown under the editor.
+    pub alert_draft_error: Option<String>,
+
+    // -------------------------------------------------------------------------
+    // Patterns view (log template mining)
+    // -------------------------------------------------------------------------
+    /// Whether to show the Patterns window.
+    pub show_patterns: bool,
+
+    /// Templates mined from `entries` by `refresh_patterns()`.  `None` until
+    /// the Patterns window is first opened after a scan; not updated
+    /// automatically as entries arrive (the window offers a Refresh).
+    pub patterns: Option<crate::core::patterns::PatternSummary>,
+
+    // -------------------------------------------------------------------------
+    // Anomalies view (spike / silence detection)
+    // -------------------------------------------------------------------------
+    /// Whether to show the Anomalies window.
+    pub show_anomalies: bool,
+
+    /// Findings computed from `entries` by `refresh_anomalies()`.  `None`
+    /// until the Anomalies window is first opened after a scan.
+    pub anomalies: Option<crate::core::anomaly::AnomalyReport>,
+
+    // -------------------------------------------------------------------------
+    // Compare view (diff two entry sets)
+    // -------------------------------------------------------------------------
+    /// Whether to show the Compare window.
+    pub show_compare: bool,
+
+    /// Input buffers for side A (the baseline, e.g. yesterday's run).
+    pub compare_a: CompareSideInput,
+
+    /// Input buffers for side B (e.g. today's failing run).
+    pub compare_b: CompareSideInput,
+
+    /// Result of the last `run_compare()`.
+    pub compare: Option<crate::core::compare::CompareReport>,
+
+    /// Why the last `run_compare()` could not run (missing / invalid input).
+    pub compare_error: Option<String>,
+
     // -------------------------------------------------------------------------
     // Troubleshoot mode
     // -------------------------------------------------------------------------
@@ -458,50 +623,156 @@ pub struct AppState {
     /// activation flow; consumed and cleared by the `ParsingCompleted`
     /// handler.
     pub request_start_tail_after_scan: bool,
+
+    // -------------------------------------------------------------------------
+    // Clock adjustment (cross-host skew)
+    // -------------------------------------------------------------------------
+    /// Per-file / per-directory clock offsets, applied to
+    /// `LogEntry::timestamp` at ingest (`apply_clock_rules`), and timezone
+    /// overrides, applied by the parser (`ParseConfig::clock_rules`).
+    ///
+    /// Persists across `clear()` (the same files are usually re-scanned) and
+    /// in the session file.  Reset by `new_session()`.
+    pub clock_rules: Vec<crate::core::clock::ClockRule>,
+
+    /// Clock-rule editor in the Files tab; `None` when closed.
+    pub clock_edit: Option<ClockRuleInput>,
 }
 
 // =============================================================================
 // Standalone helpers
 // =============================================================================
 
-/// Parse a user-supplied datetime string into a UTC bound for a time filter.
-///
-/// Accepted formats (most-specific first):
-///   `YYYY-MM-DD HH:MM:SS`  -- second precision
-///   `YYYY-MM-DD HH:MM`     -- minute precision
-///   `YYYY-MM-DD`           -- day precision (midnight in local time)
-///
-/// All formats are interpreted as **local** time and converted to UTC, matching
-/// the behaviour of `AppState::discovery_modified_since`.  An empty or
-/// non-parseable input returns `None`.
-///
-/// Used by `ui/panels/filters.rs` to commit absolute date range inputs directly
-/// to `FilterState::time_start` / `FilterState::time_end`.
-pub fn parse_filter_datetime(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
-    use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone as _};
-    let trimmed = s.trim();
-    if trimmed.is_empty() {
-        return None;
+/// How one side of the Compare window selects its entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompareMode {
+    /// One loaded source file.
+    #[default]
+    File,
+    /// Every loaded file under a directory (a scan root).
+    Directory,
+    /// A local-time From / To range over all loaded files.
+    TimeRange,
+}
+
+impl CompareMode {
+    /// All modes in display order.
+    pub fn all() -> &'static [CompareMode] {
+        &[Self::File, Self::Directory, Self::TimeRange]
+    }
+
+    /// Human-readable label for UI display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::File => "File",
+            Self::Directory => "Directory",
+            Self::TimeRange => "Time range",
+        }
+    }
+}
+
+/// UI input buffers for one side of the Compare window.
+#[derive(Debug, Clone, Default)]
+pub struct CompareSideInput {
+    pub mode: CompareMode,
+    pub file: Option<PathBuf>,
+    pub directory: String,
+    pub from: String,
+    pub to: String,
+}
+
+impl CompareSideInput {
+    /// Build the entry selector for this side, or explain what is missing.
+    /// Times are parsed with `parse_filter_datetime` (local time).
+    pub fn selector(&self) -> Result<crate::core::compare::EntrySelector, String> {
+        use crate::core::compare::EntrySelector;
+        match self.mode {
+            CompareMode::File => self
+                .file
+                .clone()
+                .map(EntrySelector::File)
+                .ok_or_else(|| "choose a file".to_string()),
+            CompareMode::Directory => {
+                let dir = self.directory.trim();
+                if dir.is_empty() {
+                    Err("enter a directory".to_string())
+                } else {
+                    Ok(EntrySelector::Directory(PathBuf::from(dir)))
+                }
+            }
+            CompareMode::TimeRange => {
+                let start = parse_filter_datetime(&self.from)
+                    .ok_or_else(|| format!("invalid From time '{}'", self.from.trim()))?;
+                let end = parse_filter_datetime(&self.to)
+                    .ok_or_else(|| format!("invalid To time '{}'", self.to.trim()))?;
+                if end < start {
+                    return Err("To is before From".to_string());
+                }
+                Ok(EntrySelector::TimeRange { start, end })
+            }
+        }
+    }
+}
+
+/// UI input buffers for the clock-rule editor opened from a file row.
+#[derive(Debug, Clone, Default)]
+pub struct ClockRuleInput {
+    /// The file whose row opened the editor.
+    pub file: PathBuf,
+
+    /// Apply the rule to the file's whole directory instead of the file.
+    pub whole_directory: bool,
+
+    /// Offset text, e.g. `+90`, `-5m`, `+00:01:30`.
+    pub offset: String,
+
+    /// Timezone text: `UTC`, `Local`, `+HH:MM` or an IANA name; empty keeps
+    /// the profile's `timezone`.
+    pub zone: String,
+
+    /// Why the last Apply failed.
+    pub error: Option<String>,
+}
+
+impl ClockRuleInput {
+    /// Editor for `file`, pre-filled from the rule currently covering it.
+    pub fn for_file(file: &Path, rules: &[crate::core::clock::ClockRule]) -> Self {
+        let current = crate::core::clock::rule_for(rules, file);
+        Self {
+            file: file.to_path_buf(),
+            whole_directory: current.is_some_and(|r| r.path.as_path() != file),
+            offset: current
+                .filter(|r| r.offset_secs != 0)
+                .map(|r| crate::core::clock::format_offset(r.offset_secs))
+                .unwrap_or_default(),
+            zone: current
+                .and_then(|r| r.zone)
+                .map(|z| z.label())
+                .unwrap_or_default(),
+            error: None,
+        }
+    }
+
+    /// The path the rule will target.
+    pub fn target(&self) -> PathBuf {
+        match self.file.parent() {
+            Some(dir) if self.whole_directory => dir.to_path_buf(),
+            _ => self.file.clone(),
+        }
+    }
+
+    /// Build the rule, or explain what is wrong with the input.
+    pub fn rule(&self) -> Result<crate::core::clock::ClockRule, String> {
+        Ok(crate::core::clock::ClockRule {
+            path: self.target(),
+            offset_secs: crate::core::clock::parse_offset(&self.offset)?,
+            zone: if self.zone.trim().is_empty() {
+                None
+            } else {
+                Some(crate::core::clock::AssumedZone::parse(&self.zone)?)
+            },
+        })
     }
-    // Interpret as LOCAL time, convert to UTC.  Falls back to treating the
-    // naive datetime as UTC on DST ambiguity (e.g. clock-back hour).
-    let local_to_utc = |ndt: NaiveDateTime| -> chrono::DateTime<chrono::Utc> {
-        Local
-            .from_local_datetime(&ndt)
-            .single()
-            .map(|dt| dt.to_utc())
-            .unwrap_or_else(|| ndt.and_utc())
-    };
-    if let Ok(ndt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M:%S") {
-        return Some(local_to_utc(ndt));
-    }
-    if let Ok(ndt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M") {
-        return Some(local_to_utc(ndt));
-    }
-    NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
-        .ok()
-        .and_then(|d| d.and_hms_opt(0, 0, 0))
-        .map(local_to_utc)
 }
 
 impl AppState {
@@ -535,6 +806,7 @@ impl AppState {
             shortcut_open_directory: false,
             file_list_search: String::new(),
             file_colours: HashMap::new(),
+            stream_colours: HashMap::new(),
             pending_single_files: None,
             pending_parse_skipped: false,
             activity_window_secs: None,
@@ -550,6 +822,11 @@ impl AppState {
             correlation_window_secs: DEFAULT_CORRELATION_WINDOW_SECS,
             correlation_window_input: DEFAULT_CORRELATION_WINDOW_SECS.to_string(),
             correlated_ids: HashSet::new(),
+            correlation_key: None,
+            correlation_key_input: String::new(),
+            key_correlated_ids: HashSet::new(),
+            transaction: Vec::new(),
+            show_transaction: false,
             session_path: None,
             initial_scan: None,
             manually_added_files: Vec::new(),
@@ -558,6 +835,8 @@ impl AppState {
             max_total_entries: crate::util::constants::MAX_TOTAL_ENTRIES,
             max_scan_depth: crate::util::constants::DEFAULT_MAX_DEPTH,
             tail_poll_interval_ms: crate::util::constants::TAIL_POLL_INTERVAL_MS,
+            tail_replay: crate::app::tail::TailReplay::Off,
+            tail_replay_since_input: String::new(),
             dir_watch_poll_interval_ms: crate::util::constants::DIR_WATCH_POLL_INTERVAL_MS,
             show_options: false,
             scroll_top_requested: false,
@@ -575,13 +854,44 @@ impl AppState {
             tail_base_count: 0,
             max_tail_buffer_entries: crate::util::constants::DEFAULT_MAX_TAIL_BUFFER_ENTRIES,
             notimestamp_entry_count: 0,
+            entry_store: None,
+            spill_dir: None,
+            store_rows: Vec::new(),
+            store_window_start: 0,
+            store_dedup: HashMap::new(),
+            store_window_stale: false,
             filter_dirty_at: None,
             fresh_scan_in_progress: false,
             unique_component_values: Vec::new(),
+            unique_field_keys: Vec::new(),
             dedup_info: HashMap::new(),
             multi_search_input: String::new(),
+            filter_presets: Vec::new(),
+            show_patterns: false,
+            patterns: None,
+            show_anomalies: false,
+            anomalies: None,
+            show_compare: false,
+            compare_a: CompareSideInput::default(),
+            compare_b: CompareSideInput::default(),
+            compare: None,
+            compare_error: None,
+            presets_path: None,
+            preset_name_input: String::new(),
+            alert_rules: Vec::new(),
+            alert_rules_path: None,
+            alert_log_path: None,
+            alert_engine: crate::core::alert::AlertEngine::default(),
+            alert_flash: None,
+            show_alert_rules: false,
+            alert_draft: crate::core::alert::AlertRule::new("", ""),
+            alert_draft_index: None,
+            alert_draft_command: String::new(),
+            alert_draft_error: None,
             troubleshoot_mode: false,
             request_start_tail_after_scan: false,
+            clock_rules: Vec::new(),
+            clock_edit: None,
         }
     }
 
@@ -612,6 +922,7 @@ impl AppState {
             .and_then(|n| n.to_str())
             .unwrap_or("?")
             .to_string();
+        self.remove_store_files(|f| f == path.as_path());
         let before = self.entries.len();
         // Keep the live-tail ring-buffer boundary consistent: removing entries
         // positioned below `tail_base_count` shifts all later entries down, so
@@ -621,9 +932,9 @@ impl AppState {
         let base = self.tail_base_count.min(before);
         let removed_below_base = self.entries[..base]
             .iter()
-            .filter(|e| &e.source_file == path)
+            .filter(|e| *e.source_file == **path)
             .count();
-        self.entries.retain(|e| &e.source_file != path);
+        self.entries.retain(|e| *e.source_file != **path);
         self.tail_base_count = self.tail_base_count.saturating_sub(removed_below_base);
         let removed = before - self.entries.len();
 
@@ -666,6 +977,40 @@ impl AppState {
         }
     }
 
+    /// Request an on-demand parse of `paths` (files whose entries are not
+    /// loaded).  Goes to `pending_single_files`, or to `queued_parse_files`
+    /// while a scan is running (drained when that scan completes).
+    pub fn queue_parse_requests(&mut self, mut paths: Vec<PathBuf>) {
+        if paths.is_empty() {
+            return;
+        }
+        paths.sort();
+        paths.dedup();
+
+        if self.scan_in_progress {
+            let mut existing: std::collections::HashSet<PathBuf> =
+                self.queued_parse_files.iter().cloned().collect();
+            for p in paths {
+                if existing.insert(p.clone()) {
+                    self.queued_parse_files.push(p);
+                }
+            }
+            return;
+        }
+
+        if let Some(ref mut pending) = self.pending_single_files {
+            let mut existing: std::collections::HashSet<PathBuf> =
+                pending.iter().cloned().collect();
+            for p in paths {
+                if existing.insert(p.clone()) {
+                    pending.push(p);
+                }
+            }
+        } else {
+            self.pending_single_files = Some(paths);
+        }
+    }
+
     /// Remove all parsed entries for a set of source files from memory and
     /// mark those files as `parsing_skipped`.
     ///
@@ -678,14 +1023,15 @@ impl AppState {
             return 0;
         }
 
+        self.remove_store_files(|f| paths.contains(f));
         let before = self.entries.len();
         // Same boundary adjustment as remove_entries_for_file: see comment there.
         let base = self.tail_base_count.min(before);
         let removed_below_base = self.entries[..base]
             .iter()
-            .filter(|e| paths.contains(&e.source_file))
+            .filter(|e| paths.contains(&*e.source_file))
             .count();
-        self.entries.retain(|e| !paths.contains(&e.source_file));
+        self.entries.retain(|e| !paths.contains(&*e.source_file));
         self.tail_base_count = self.tail_base_count.saturating_sub(removed_below_base);
         let removed = before.saturating_sub(self.entries.len());
 
@@ -770,52 +1116,12 @@ impl AppState {
             })
             .collect();
 
-        self.filtered_indices =
-            crate::core::filter::apply_filters(&self.entries, &self.filter_state);
-
-        // Activity window: further filter to only entries whose source file has
-        // been modified within the rolling window.  Applied *after* all other
-        // filters so it combines with severity, text search, etc.  Uses the live
-        // mtime from `discovered_files` (updated by the dir-watcher) rather than
-        // any parsed log timestamp, so it works for plain-text and files with
-        // unreliable embedded timestamps.
-        //
-        // Fail-open: a file with no known mtime is treated as active so that a
-        // metadata failure never silently hides entries.
-        //
-        // Performance: only build the active-file HashSet when the feature is
-        // enabled and at least one file is discovered.  Building a HashSet of
-        // PathBuf references on every filter call was O(discovered_files) and
-        // allocated even when the filter matched all files.
-        if let Some(cutoff) = self.activity_cutoff() {
-            if !self.discovered_files.is_empty() {
-                let active_files: std::collections::HashSet<&std::path::PathBuf> = self
-                    .discovered_files
-                    .iter()
-                    .filter(|f| f.modified.map_or(true, |t| t >= cutoff))
-                    .map(|f| &f.path)
-                    .collect();
-                self.filtered_indices.retain(|&idx| {
-                    self.entries
-                        .get(idx)
-                        .is_some_and(|e| active_files.contains(&e.source_file))
-                });
-            }
-        }
-
-        // Deduplication post-filter: collapse duplicate messages (per source file)
-        // to show only the latest occurrence of each unique message.  Applied after
-        // all other filters so it operates on the already-narrowed result set.
-        if self.filter_state.dedup_mode != DedupMode::Off {
-            let (deduped, info) = crate::core::filter::apply_dedup(
-                &self.entries,
-                &self.filtered_indices,
-                self.filter_state.dedup_mode,
-            );
-            self.filtered_indices = deduped;
-            self.dedup_info = info;
+        if self.entry_store.is_some() {
+            // The session lives in the entry store: filter all of it and
+            // reload the page of matching rows at the current position.
+            self.filter_entry_store();
         } else {
-            self.dedup_info.clear();
+            self.filter_resident_entries();
         }
 
         // Restore selection: find the new display position of the previously
@@ -851,6 +1157,10 @@ impl AppState {
         // entry's identity or position.
         self.update_correlation();
 
+        // Entries may have been removed or re-sorted since the transaction was
+        // built, so its indices are rebuilt too.
+        self.refresh_key_correlation();
+
         // Rebuild the unique-thread and unique-component caches so the filter
         // panel checkboxes always reflect the current entry set.  Runs after
         // update_correlation to share the apply_filters call as the natural
@@ -858,6 +1168,45 @@ impl AppState {
         self.rebuild_unique_values();
     }
 
+    /// Filter the resident `entries` into `filtered_indices` (and `dedup_info`).
+    fn filter_resident_entries(&mut self) {
+        self.filtered_indices =
+            crate::core::filter::apply_filters(&self.entries, &self.filter_state);
+
+        // Activity window: further filter to only entries whose source file has
+        // been modified within the rolling window.  Applied *after* all other
+        // filters so it combines with severity, text search, etc.  Uses the live
+        // mtime from `discovered_files` (updated by the dir-watcher) rather than
+        // any parsed log timestamp, so it works for plain-text and files with
+        // unreliable embedded timestamps.
+        //
+        // Fail-open: a file with no known mtime is treated as active so that a
+        // metadata failure never silently hides entries.
+        if let Some(active_files) = active_file_set(&self.discovered_files, self.activity_cutoff())
+        {
+            self.filtered_indices.retain(|&idx| {
+                self.entries
+                    .get(idx)
+                    .is_some_and(|e| active_files.contains(&*e.source_file))
+            });
+        }
+
+        // Deduplication post-filter: collapse duplicate messages (per source file)
+        // to show only the latest occurrence of each unique message.  Applied after
+        // all other filters so it operates on the already-narrowed result set.
+        if self.filter_state.dedup_mode != DedupMode::Off {
+            let (deduped, info) = crate::core::filter::apply_dedup(
+                &self.entries,
+                &self.filtered_indices,
+                self.filter_state.dedup_mode,
+            );
+            self.filtered_indices = deduped;
+            self.dedup_info = info;
+        } else {
+            self.dedup_info.clear();
+        }
+    }
+
     /// Recompute the correlation overlay from the currently selected entry.
     ///
     /// Iterates **all** entries (not just filtered ones) so context entries
@@ -919,14 +1268,19 @@ impl AppState {
     /// so the cache is always consistent with the loaded data.
     fn rebuild_unique_values(&mut self) {
         let mut components: HashSet<String> = HashSet::new();
+        let mut field_keys: HashSet<&str> = HashSet::new();
         for entry in &self.entries {
             if let Some(c) = &entry.component {
-                components.insert(c.clone());
+                components.insert(c.to_string());
             }
+            field_keys.extend(entry.fields.keys().map(String::as_str));
         }
         let mut cv: Vec<String> = components.into_iter().collect();
         cv.sort_unstable();
+        let mut fk: Vec<String> = field_keys.into_iter().map(str::to_string).collect();
+        fk.sort_unstable();
         self.unique_component_values = cv;
+        self.unique_field_keys = fk;
     }
 
     /// Return the next available monotonic entry ID.
@@ -988,101 +1342,449 @@ impl AppState {
         &self,
         entries: &mut Vec<crate::core::model::LogEntry>,
     ) -> usize {
-        if !self.troubleshoot_mode || self.scan_path.is_none() {
+        if !self.troubleshoot_filtering() {
             return 0;
         }
         let before = entries.len();
-        entries.retain(|e| {
-            matches!(
-                e.severity,
-                crate::core::model::Severity::Critical | crate::core::model::Severity::Error
-            )
-        });
+        entries.retain(troubleshoot_keeps);
         before - entries.len()
     }
 
-    /// Record the current entry count as the live-tail baseline.
-    ///
-    /// Called from `gui.rs` when the `request_start_tail` flag is processed,
-    /// immediately before the `TailManager::start_tail()` call.  All entries
-    /// added before this point are from the initial scan and will never be
-    /// evicted by the ring-buffer logic.
-    pub fn set_tail_base(&mut self) {
-        self.tail_base_count = self.entries.len();
+    /// Whether troubleshoot mode drops non-Critical/Error entries: only for
+    /// directory sessions.
+    fn troubleshoot_filtering(&self) -> bool {
+        self.troubleshoot_mode && self.scan_path.is_some()
     }
 
-    /// Evict the oldest `count` entries from the live-tail section
-    /// (`entries[tail_base_count..]`).
-    ///
-    /// Entries from the initial scan (`entries[..tail_base_count]`) are
-    /// NEVER touched.  After draining, `notimestamp_entry_count` is
-    /// recounted from scratch (because some evicted entries may have had
-    /// `timestamp == None`).
-    ///
-    /// Returns the number of entries actually removed (may be less than
-    /// `count` if the tail section is smaller).  The caller MUST rebuild
-    /// `filtered_indices` after calling this (via `apply_filters()` or
-    /// `sort_entries_chronologically()`).
-    pub fn evict_tail_entries(&mut self, count: usize) -> usize {
-        let tail_start = self.tail_base_count;
-        let available = self.entries.len().saturating_sub(tail_start);
-        let to_evict = count.min(available);
-        if to_evict == 0 {
-            return 0;
-        }
-        self.entries.drain(tail_start..tail_start + to_evict);
-        // Recount no-timestamp entries after draining, because some evicted
-        // entries may have had timestamp == None.  O(n) but infrequent.
-        self.notimestamp_entry_count = self
-            .entries
-            .iter()
-            .filter(|e| e.timestamp.is_none())
-            .count();
-        to_evict
-    }
+    // -------------------------------------------------------------------------
+    // Out-of-core entry store
+    // -------------------------------------------------------------------------
 
-    /// Incrementally extend `filtered_indices` by evaluating the current
-    /// filter against `entries[start..]` only.
-    ///
-    /// This is the fast path used by the live-tail handler when incoming
-    /// entries are appended in strictly ascending timestamp order (the
-    /// common case).  It costs O(N) where N = new entries, instead of the
-    /// O(M) full rebuild that `apply_filters()` performs.
-    ///
-    /// **Pre-conditions the caller MUST guarantee:**
-    /// 1. All entries in `[start..]` have timestamps ≥ the last entry in
-    ///    `[..start]`, preserving the ascending `filtered_indices` invariant.
-    /// 2. No entries were removed or reordered below `start` since the last
-    ///    full `apply_filters()` call.
+    /// Add a scan batch to the session, capped at `max_total_entries`.
     ///
-    /// **Does NOT call `update_correlation()`.**  The correlation set only
-    /// needs updating when the *selected* entry changes, which never happens
-    /// on a pure tail append.  The caller is responsible for calling
-    /// `update_correlation()` separately if it detects a selection change.
-    pub fn extend_filtered_for_range(&mut self, start: usize) {
-        if start >= self.entries.len() {
-            return;
+    /// The cap is enforced here as well as on the background thread because
+    /// append scans cannot know how many entries the UI already holds.
+    /// Entries beyond it go to the entry store (created on first overflow,
+    /// seeded with the resident entries) when `spill_dir` is set, and are
+    /// dropped otherwise.  Once a store exists every batch goes to it and
+    /// `ParsingCompleted` reloads the window.
+    pub fn ingest_entries(&mut self, mut entries: Vec<LogEntry>) {
+        if self.entry_store.is_none() {
+            let remaining = self.max_total_entries.saturating_sub(self.entries.len());
+            let overflow = entries.split_off(remaining.min(entries.len()));
+            // Track max entry ID incrementally (O(1) in next_entry_id) and
+            // entries without parsed timestamps so the mtime-update path in
+            // FileMtimeUpdates can be skipped when unneeded.
+            self.track_max_entry_id(&entries);
+            self.track_notimestamp_entries(&entries);
+            self.entries.extend(entries);
+            if overflow.is_empty() || !self.start_entry_store() {
+                return;
+            }
+            entries = overflow;
         }
-
-        // When dedup is active, incremental extension cannot correctly maintain
-        // the dedup groups (a new entry may supersede an existing survivor).
-        // Fall back to a full rebuild which is correct in all cases.
-        if self.filter_state.dedup_mode != DedupMode::Off {
-            self.apply_filters();
-            return;
+        self.track_max_entry_id(&entries);
+        if let Some(store) = self.entry_store.as_mut() {
+            if let Err(e) = store.append(&entries) {
+                self.entry_store_error(&e);
+                return;
+            }
         }
+        self.store_window_stale = true;
+    }
 
-        // Mirror the pre-conditions of apply_filters: recompute the rolling
-        // time bound and bookmark set so the incremental result is identical
-        // to what a full rebuild would produce for the new entries.
-        if let Some(secs) = self.filter_state.relative_time_secs {
-            let clamped = i64::try_from(secs).unwrap_or(i64::MAX);
-            self.filter_state.time_start =
-                chrono::Utc::now().checked_sub_signed(chrono::Duration::seconds(clamped));
-            self.filter_state.time_end = None;
+    /// Fold entries the scan pipeline spilled to disk into the session store.
+    /// Without a store yet, `store` becomes it and the resident entries are
+    /// added to it.
+    pub fn absorb_entry_store(&mut self, mut store: crate::core::entry_store::EntryStore) {
+        if let Some(max) = store.max_id() {
+            self.max_entry_id = self.max_entry_id.max(max);
         }
-        if self.filter_state.bookmarks_only {
-            self.filter_state.bookmarked_ids = self.bookmarks.keys().copied().collect();
+        let result = match self.entry_store.as_mut() {
+            Some(existing) => existing.append_store(&mut store),
+            None => store.append(&self.entries).map(|()| {
+                self.entry_store = Some(store);
+            }),
+        };
+        match result {
+            Ok(()) => self.store_window_stale = true,
+            Err(e) => self.entry_store_error(&e),
+        }
+    }
+
+    /// Create the entry store holding the resident entries.  Returns `false`
+    /// (after a warning) when there is no spill directory or it fails.
+    fn start_entry_store(&mut self) -> bool {
+        let Some(dir) = self.spill_dir.clone() else {
+            return false;
+        };
+        let result = crate::core::entry_store::EntryStore::create(&dir).and_then(|mut store| {
+            store.append(&self.entries)?;
+            Ok(store)
+        });
+        match result {
+            Ok(store) => {
+                tracing::info!(path = %store.path().display(), "Entry store started");
+                self.entry_store = Some(store);
+                true
+            }
+            Err(e) => {
+                self.entry_store_error(&e);
+                false
+            }
+        }
+    }
+
+    fn entry_store_error(&mut self, e: &std::io::Error) {
+        let msg = format!("Entry store error: {e}");
+        tracing::warn!("{}", msg);
+        self.status_message = msg.clone();
+        self.warnings.push(msg);
+    }
+
+    /// Total entries in the session: the store when there is one, otherwise
+    /// the loaded entries.
+    pub fn session_entry_count(&self) -> usize {
+        self.entry_store
+            .as_ref()
+            .map_or(self.entries.len(), |s| s.len())
+    }
+
+    /// Number of timeline rows: every matching entry of the store when there
+    /// is one, otherwise the filtered resident entries.
+    pub fn filtered_count(&self) -> usize {
+        if self.entry_store.is_some() {
+            self.store_rows.len()
+        } else {
+            self.filtered_indices.len()
+        }
+    }
+
+    /// Export every timeline row as CSV: with an entry store, all matching
+    /// stored entries, read in batches as they are written.
+    pub fn export_csv(
+        &mut self,
+        writer: impl std::io::Write,
+        export_path: &Path,
+        metadata: &crate::core::export::ExportMetadata<'_>,
+    ) -> Result<usize, crate::util::error::ExportError> {
+        use crate::core::export;
+        let Some(store) = self.entry_store.as_mut() else {
+            let entries = self
+                .filtered_indices
+                .iter()
+                .filter_map(|&i| self.entries.get(i));
+            return export::export_csv(entries, writer, export_path, metadata);
+        };
+        // A first pass over the rows finds the field columns.
+        let mut field_keys = BTreeSet::new();
+        for entry in store_row_entries(store, &self.store_rows) {
+            field_keys.extend(entry?.fields.into_keys());
+        }
+        export::write_csv(
+            store_row_entries(store, &self.store_rows),
+            &field_keys,
+            writer,
+            export_path,
+            metadata,
+        )
+    }
+
+    /// Export every timeline row as JSON; see [`Self::export_csv`].
+    pub fn export_json(
+        &mut self,
+        writer: impl std::io::Write,
+        export_path: &Path,
+        metadata: &crate::core::export::ExportMetadata<'_>,
+    ) -> Result<usize, crate::util::error::ExportError> {
+        use crate::core::export;
+        let Some(store) = self.entry_store.as_mut() else {
+            let entries = self
+                .filtered_indices
+                .iter()
+                .filter_map(|&i| self.entries.get(i));
+            return export::export_json(entries, writer, export_path, metadata);
+        };
+        export::write_json(
+            store_row_entries(store, &self.store_rows),
+            writer,
+            export_path,
+            metadata,
+        )
+    }
+
+    /// Run the filters, the activity window and dedup over every entry in
+    /// the store, batch by batch, into `store_rows`, then reload the page at
+    /// the current position.
+    fn filter_entry_store(&mut self) {
+        /// A dedup group: the surviving (latest) position, its sort key, the
+        /// occurrence count and the earliest timestamp.
+        struct Group {
+            best: u32,
+            best_key: (Option<chrono::DateTime<chrono::Utc>>, u64),
+            count: usize,
+            first: Option<chrono::DateTime<chrono::Utc>>,
+        }
+
+        let active_files = active_file_set(&self.discovered_files, self.activity_cutoff());
+        // Spilled scan entries skip the troubleshoot filter on ingest.
+        let troubleshoot = self.troubleshoot_filtering();
+        let Some(store) = self.entry_store.as_mut() else {
+            return;
+        };
+        let filter = &self.filter_state;
+        let mode = filter.dedup_mode;
+        let mut rows: Vec<u32> = Vec::new();
+        let mut groups: HashMap<(Arc<Path>, String), Group> = HashMap::new();
+        let mut first_position = 0u32;
+        let result = store.for_each_batch(|batch| {
+            for (idx, entry) in matching_entries(&batch, filter, active_files.as_ref()) {
+                if troubleshoot && !troubleshoot_keeps(entry) {
+                    continue;
+                }
+                let position = first_position + idx as u32;
+                let Some(key) = crate::core::filter::dedup_key(entry, mode) else {
+                    rows.push(position);
+                    continue;
+                };
+                let entry_key = (entry.timestamp, entry.id);
+                let group = groups
+                    .entry((Arc::clone(&entry.source_file), key))
+                    .or_insert(Group {
+                        best: position,
+                        best_key: entry_key,
+                        count: 0,
+                        first: None,
+                    });
+                group.count += 1;
+                if entry_key > group.best_key {
+                    group.best = position;
+                    group.best_key = entry_key;
+                }
+                group.first = match (group.first, entry.timestamp) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+            }
+            first_position += batch.len() as u32;
+            Ok(())
+        });
+        if let Err(e) = result {
+            self.entry_store_error(&e);
+            return;
+        }
+        self.store_dedup = groups
+            .into_values()
+            .map(|g| (g.best, (g.count, g.first)))
+            .collect();
+        rows.extend(self.store_dedup.keys().copied());
+        store.sort_by_time(&mut rows);
+        self.store_rows = rows;
+        self.store_window_stale = false;
+        self.load_store_page(self.store_window_start);
+    }
+
+    /// Load the page of up to `max_total_entries` rows starting at `start`
+    /// in `store_rows` into `entries`.  Called by the timeline as it scrolls
+    /// outside the loaded page.  The selection follows its entry when the
+    /// entry is on the new page.
+    pub fn load_store_page(&mut self, start: usize) {
+        let count = self.max_total_entries.max(1);
+        let start = start.min(self.store_rows.len().saturating_sub(count));
+        let end = (start + count).min(self.store_rows.len());
+        let selected_id = self.selected_entry().map(|e| e.id);
+        let Some(store) = self.entry_store.as_mut() else {
+            return;
+        };
+        let page = match store.read(&self.store_rows[start..end]) {
+            Ok(page) => page,
+            Err(e) => {
+                self.entry_store_error(&e);
+                return;
+            }
+        };
+        self.store_window_start = start;
+        self.replace_entries(page);
+        self.filtered_indices = (0..self.entries.len()).collect();
+        self.dedup_info = self.store_rows[start..end]
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, p)| {
+                let &(count, first_timestamp) = self.store_dedup.get(p)?;
+                Some((
+                    idx,
+                    DedupInfo {
+                        count,
+                        first_timestamp,
+                        all_indices: vec![idx],
+                    },
+                ))
+            })
+            .collect();
+        self.selected_index =
+            selected_id.and_then(|id| self.entries.iter().position(|e| e.id == id));
+        self.update_correlation();
+    }
+
+    /// Add live-tail entries to the entry store.  Without dedup their
+    /// matching rows join `store_rows` (and the loaded page, when it ends
+    /// at the newest row); with dedup the debounced filter pass recounts
+    /// the groups.
+    pub fn append_tail_to_store(&mut self, entries: Vec<LogEntry>) {
+        self.track_max_entry_id(&entries);
+        let active_files = active_file_set(&self.discovered_files, self.activity_cutoff());
+        let Some(store) = self.entry_store.as_mut() else {
+            return;
+        };
+        let first_position = store.len() as u32;
+        let hits: Vec<u32> = matching_entries(&entries, &self.filter_state, active_files.as_ref())
+            .map(|(idx, _)| first_position + idx as u32)
+            .collect();
+        if let Err(e) = store.append(&entries) {
+            self.entry_store_error(&e);
+            return;
+        }
+        if self.filter_state.dedup_mode != DedupMode::Off {
+            self.filter_dirty_at
+                .get_or_insert_with(std::time::Instant::now);
+            return;
+        }
+        let old_len = self.store_rows.len();
+        let page_end = self.store_window_start + self.entries.len();
+        self.store_rows.extend(hits);
+        store.sort_by_time(&mut self.store_rows);
+        if self.store_rows[old_len..]
+            .iter()
+            .any(|&p| p < first_position)
+        {
+            // Some new rows sort before older ones: row numbers moved.
+            self.load_store_page(self.store_window_start);
+            return;
+        }
+        if page_end == old_len {
+            let room = self
+                .max_total_entries
+                .max(1)
+                .saturating_sub(self.entries.len());
+            for &p in self.store_rows[old_len..].iter().take(room) {
+                self.filtered_indices.push(self.entries.len());
+                self.entries
+                    .push(entries[(p - first_position) as usize].clone());
+            }
+        }
+    }
+
+    /// Replace `entries` with a page of store rows (already filtered and in
+    /// time order).  Index-based derived state is reset; bookmarks and the
+    /// correlation overlay are ID-based and carry over.
+    fn replace_entries(&mut self, entries: Vec<LogEntry>) {
+        self.entries = entries;
+        self.tail_base_count = self.entries.len();
+        self.notimestamp_entry_count = self
+            .entries
+            .iter()
+            .filter(|e| e.timestamp.is_none())
+            .count();
+        self.selected_index = None;
+        self.selected_indices.clear();
+        self.transaction.clear();
+        self.show_transaction = false;
+        self.patterns = None;
+        self.anomalies = None;
+        self.compare = None;
+    }
+
+    /// Drop the stored entries of files matching `remove`.  Only the store
+    /// index changes, so unloading files stays cheap however large it is.
+    fn remove_store_files(&mut self, remove: impl Fn(&Path) -> bool) {
+        if let Some(store) = self.entry_store.as_mut() {
+            store.remove_files(remove);
+            self.store_window_stale = true;
+        }
+    }
+
+    /// Record the current entry count as the live-tail baseline.
+    ///
+    /// Called from `gui.rs` when the `request_start_tail` flag is processed,
+    /// immediately before the `TailManager::start_tail()` call.  All entries
+    /// added before this point are from the initial scan and will never be
+    /// evicted by the ring-buffer logic.
+    pub fn set_tail_base(&mut self) {
+        self.tail_base_count = self.entries.len();
+    }
+
+    /// Evict the oldest `count` entries from the live-tail section
+    /// (`entries[tail_base_count..]`).
+    ///
+    /// Entries from the initial scan (`entries[..tail_base_count]`) are
+    /// NEVER touched.  After draining, `notimestamp_entry_count` is
+    /// recounted from scratch (because some evicted entries may have had
+    /// `timestamp == None`).
+    ///
+    /// Returns the number of entries actually removed (may be less than
+    /// `count` if the tail section is smaller).  The caller MUST rebuild
+    /// `filtered_indices` after calling this (via `apply_filters()` or
+    /// `sort_entries_chronologically()`).
+    pub fn evict_tail_entries(&mut self, count: usize) -> usize {
+        let tail_start = self.tail_base_count;
+        let available = self.entries.len().saturating_sub(tail_start);
+        let to_evict = count.min(available);
+        if to_evict == 0 {
+            return 0;
+        }
+        self.entries.drain(tail_start..tail_start + to_evict);
+        // Recount no-timestamp entries after draining, because some evicted
+        // entries may have had timestamp == None.  O(n) but infrequent.
+        self.notimestamp_entry_count = self
+            .entries
+            .iter()
+            .filter(|e| e.timestamp.is_none())
+            .count();
+        to_evict
+    }
+
+    /// Incrementally extend `filtered_indices` by evaluating the current
+    /// filter against `entries[start..]` only.
+    ///
+    /// This is the fast path used by the live-tail handler when incoming
+    /// entries are appended in strictly ascending timestamp order (the
+    /// common case).  It costs O(N) where N = new entries, instead of the
+    /// O(M) full rebuild that `apply_filters()` performs.
+    ///
+    /// **Pre-conditions the caller MUST guarantee:**
+    /// 1. All entries in `[start..]` have timestamps ≥ the last entry in
+    ///    `[..start]`, preserving the ascending `filtered_indices` invariant.
+    /// 2. No entries were removed or reordered below `start` since the last
+    ///    full `apply_filters()` call.
+    ///
+    /// **Does NOT call `update_correlation()`.**  The correlation set only
+    /// needs updating when the *selected* entry changes, which never happens
+    /// on a pure tail append.  The caller is responsible for calling
+    /// `update_correlation()` separately if it detects a selection change.
+    pub fn extend_filtered_for_range(&mut self, start: usize) {
+        if start >= self.entries.len() {
+            return;
+        }
+
+        // When dedup is active, incremental extension cannot correctly maintain
+        // the dedup groups (a new entry may supersede an existing survivor).
+        // Fall back to a full rebuild which is correct in all cases.
+        if self.filter_state.dedup_mode != DedupMode::Off {
+            self.apply_filters();
+            return;
+        }
+
+        // Mirror the pre-conditions of apply_filters: recompute the rolling
+        // time bound and bookmark set so the incremental result is identical
+        // to what a full rebuild would produce for the new entries.
+        if let Some(secs) = self.filter_state.relative_time_secs {
+            let clamped = i64::try_from(secs).unwrap_or(i64::MAX);
+            self.filter_state.time_start =
+                chrono::Utc::now().checked_sub_signed(chrono::Duration::seconds(clamped));
+            self.filter_state.time_end = None;
+        }
+        if self.filter_state.bookmarks_only {
+            self.filter_state.bookmarked_ids = self.bookmarks.keys().copied().collect();
         }
 
         // Pre-compute the lowercased text-search needle once for the batch
@@ -1091,14 +1793,14 @@ impl AppState {
 
         // Activity-window cutoff: build the active-file set once for the
         // entire batch (same logic as the post-filter in apply_filters).
-        let active_files: Option<std::collections::HashSet<&std::path::PathBuf>> =
+        let active_files: Option<std::collections::HashSet<&std::path::Path>> =
             if let Some(cutoff) = self.activity_cutoff() {
                 if !self.discovered_files.is_empty() {
                     Some(
                         self.discovered_files
                             .iter()
                             .filter(|f| f.modified.map_or(true, |t| t >= cutoff))
-                            .map(|f| &f.path)
+                            .map(|f| f.path.as_path())
                             .collect(),
                     )
                 } else {
@@ -1115,23 +1817,41 @@ impl AppState {
                 continue;
             }
             if let Some(ref active) = active_files {
-                if !active.contains(&entry.source_file) {
+                if !active.contains(&*entry.source_file) {
                     continue;
                 }
             }
             self.filtered_indices.push(start + local_idx);
         }
+
+        // Key correlation: appended entries that carry the key join the
+        // transaction (which stays time-ordered because the new entries are).
+        if let Some(ref key) = self.correlation_key {
+            for (local_idx, entry) in self.entries[start..].iter().enumerate() {
+                if key.matches(entry) {
+                    self.key_correlated_ids.insert(entry.id);
+                    self.transaction.push(start + local_idx);
+                }
+            }
+        }
     }
 
     /// Assign a palette colour to `path` if it does not already have one.
-    /// Uses a round-robin index over the theme palette so each new file gets
-    /// a distinct colour (wrapping after 12 files).
+    /// Uses a round-robin index over the theme palette so each new stream
+    /// gets a distinct colour (wrapping after 12 streams); the segments of a
+    /// rotation family all take the family's colour.
     pub fn assign_file_colour(&mut self, path: &std::path::Path) {
-        if !self.file_colours.contains_key(path) {
-            let idx = self.file_colours.len();
-            let colour = crate::ui::theme::file_colour(idx);
-            self.file_colours.insert(path.to_path_buf(), colour);
-        }
+        let (stream, _) = crate::core::discovery::rotation_segment(path);
+        // A colour restored from the session seeds its stream's colour.
+        let colour = match (
+            self.stream_colours.get(&stream),
+            self.file_colours.get(path),
+        ) {
+            (Some(&colour), _) | (None, Some(&colour)) => colour,
+            (None, None) => crate::ui::theme::file_colour(self.stream_colours.len()),
+        };
+        self.stream_colours.insert(stream, colour);
+        self.file_colours.insert(path.to_path_buf(), colour);
     }
 
     /// Return the palette colour for `path`, or a neutral grey if not found.
@@ -1203,11 +1923,7 @@ impl AppState {
                     .timestamp
                     .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
                     .unwrap_or_else(|| "no timestamp".to_string());
-                let src = entry
-                    .source_file
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("?");
+                let src = crate::core::archive::display_name(&entry.source_file);
                 let label = self
                     .bookmarks
                     .get(id)
@@ -1260,6 +1976,18 @@ impl AppState {
         if self.filter_state.regex_search.is_some() {
             filter_parts.push(format!("Regex: /{}/", self.filter_state.regex_pattern));
         }
+        if !self.filter_state.field_filters.is_empty() {
+            filter_parts.push(format!(
+                "Fields: {}",
+                self.filter_state.field_filter_input.trim()
+            ));
+        }
+        if self.filter_state.query.is_some() {
+            filter_parts.push(format!("Query: {}", self.filter_state.query_input.trim()));
+        }
+        if self.filter_state.pattern_ids.is_some() {
+            filter_parts.push(format!("Pattern: {}", self.filter_state.pattern_label));
+        }
         if self.filter_state.hide_all_sources {
             filter_parts.push("Files: none (all hidden)".to_string());
         } else if !self.filter_state.source_files.is_empty() {
@@ -1308,11 +2036,7 @@ impl AppState {
                     .timestamp
                     .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
                     .unwrap_or_else(|| "no timestamp         ".to_string());
-                let src = entry
-                    .source_file
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("?");
+                let src = crate::core::archive::display_name(&entry.source_file);
                 let sev = entry.severity.label();
                 // Use raw_text (the verbatim original log line) so the clipboard
                 // contains the actual file contents, not a parsed sub-field.
@@ -1413,6 +2137,13 @@ impl AppState {
         self.warnings.clear();
         self.show_summary = false;
         self.show_log_summary = false;
+        self.show_patterns = false;
+        self.patterns = None;
+        self.show_anomalies = false;
+        self.anomalies = None;
+        self.show_compare = false;
+        self.compare = None;
+        self.compare_error = None;
         self.status_message = "Ready.".to_string();
         self.scan_in_progress = false;
         self.pending_scan = None;
@@ -1420,6 +2151,7 @@ impl AppState {
         self.request_cancel = false;
         self.file_list_search.clear();
         self.file_colours.clear();
+        self.stream_colours.clear();
         self.pending_single_files = None;
         self.pending_parse_skipped = false;
         // Stop tail and dir watcher on clear — a new scan starts fresh.
@@ -1440,6 +2172,11 @@ impl AppState {
         self.correlated_ids.clear();
         self.correlation_window_secs = DEFAULT_CORRELATION_WINDOW_SECS;
         self.correlation_window_input = DEFAULT_CORRELATION_WINDOW_SECS.to_string();
+        self.correlation_key = None;
+        self.correlation_key_input.clear();
+        self.key_correlated_ids.clear();
+        self.transaction.clear();
+        self.show_transaction = false;
         // tail_auto_scroll preference is intentionally preserved across clears.
         // initial_scan is cleared on each new scan; session_path is never cleared.
         self.initial_scan = None;
@@ -1460,6 +2197,12 @@ impl AppState {
         // Reset live-tail ring-buffer state.
         self.tail_base_count = 0;
         self.notimestamp_entry_count = 0;
+        // Dropping the store deletes its segment file.
+        self.entry_store = None;
+        self.store_rows.clear();
+        self.store_window_start = 0;
+        self.store_dedup.clear();
+        self.store_window_stale = false;
         // max_tail_buffer_entries is a user preference — do not reset.
         // Cancel any pending debounced filter rebuild.
         self.filter_dirty_at = None;
@@ -1467,6 +2210,7 @@ impl AppState {
         // Clear derived caches so stale values from the previous session are
         // not shown in the component filter panel.
         self.unique_component_values.clear();
+        self.unique_field_keys.clear();
         // Clear dedup metadata.
         self.dedup_info.clear();
         // Clear multi-search input buffer.
@@ -1485,9 +2229,56 @@ impl AppState {
         self.clear();
         self.scan_path = None;
         self.troubleshoot_mode = false;
+        self.set_clock_rules(Vec::new());
+        self.clock_edit = None;
         self.status_message = "Ready. Open a directory to begin scanning.".to_string();
     }
 
+    /// Queue paths passed on the command line for the first frame.
+    ///
+    /// The first directory is opened like "Open Directory..." (`pending_scan`);
+    /// when there is no directory, the files are opened like "Open Log(s)..."
+    /// (`pending_replace_files`).  Files given alongside a directory are
+    /// queued in `queued_parse_files` and parsed once the directory scan
+    /// completes.  Only one directory can be scanned per session, so further
+    /// directories and paths that do not exist are reported in the status bar.
+    ///
+    /// `tail` requests Live Tail as soon as the initial scan completes.
+    pub fn open_startup_paths(&mut self, paths: &[PathBuf], tail: bool) {
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        let mut files: Vec<PathBuf> = Vec::new();
+        let mut skipped: Vec<String> = Vec::new();
+        for path in paths {
+            match std::fs::metadata(path) {
+                Ok(meta) if meta.is_dir() => dirs.push(path.clone()),
+                Ok(_) => files.push(path.clone()),
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "CLI path not found");
+                    skipped.push(format!("{} (not found)", path.display()));
+                }
+            }
+        }
+        for extra in dirs.iter().skip(1) {
+            skipped.push(format!(
+                "{} (only one directory per session)",
+                extra.display()
+            ));
+        }
+
+        if let Some(dir) = dirs.into_iter().next() {
+            self.scan_path = Some(dir.clone());
+            self.pending_scan = Some(dir);
+            self.queued_parse_files.extend(files);
+            self.request_start_tail_after_scan = tail;
+        } else if !files.is_empty() {
+            self.pending_replace_files = Some(files);
+            self.request_start_tail_after_scan = tail;
+        }
+        if !skipped.is_empty() {
+            self.status_message = format!("\u{26a0} Not opened: {}", skipped.join(", "));
+        }
+    }
+
     /// Parse `discovery_date_input` into a UTC `DateTime`.
     ///
     /// Accepts three levels of precision (most-specific first):
@@ -1532,17 +2323,688 @@ impl AppState {
         if let Ok(ndt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M") {
             return Some(local_to_utc(ndt));
         }
-        // 3. Date only: "YYYY-MM-DD" — treat as start of day in local time.
-        NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
-            .ok()
-            .and_then(|d| d.and_hms_opt(0, 0, 0))
-            .map(local_to_utc)
+        // 3. Date only: "YYYY-MM-DD" — treat as start of day in local time.
+        NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+            .ok()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(local_to_utc)
+    }
+
+    // =========================================================================
+    // Filter presets
+    // =========================================================================
+
+    /// Load `filter_presets` from `presets_path`.  Errors are reported in the
+    /// status bar and leave the list empty.
+    pub fn load_filter_presets(&mut self) {
+        let Some(path) = self.presets_path.clone() else {
+            return;
+        };
+        match crate::app::presets::load(&path) {
+            Ok(presets) => {
+                tracing::debug!(count = presets.len(), "Filter presets loaded");
+                self.filter_presets = presets;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load filter presets");
+                self.status_message = format!("\u{26a0} Filter presets not loaded: {e}");
+            }
+        }
+    }
+
+    /// Write `filter_presets` to `presets_path`.  Returns false (with a
+    /// status-bar message) when the write fails.
+    fn store_filter_presets(&mut self) -> bool {
+        let Some(path) = self.presets_path.clone() else {
+            return true;
+        };
+        match crate::app::presets::save(&path, &self.filter_presets) {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to save filter presets");
+                self.status_message = format!("\u{26a0} Filter presets not saved: {e}");
+                false
+            }
+        }
+    }
+
+    /// Save the current filter under `name`, replacing a preset with the same
+    /// name.  Blank names are ignored.
+    pub fn save_filter_preset(&mut self, name: &str) {
+        let preset = crate::app::presets::FilterPreset::new(name, self.persisted_filter());
+        if preset.name.is_empty() {
+            return;
+        }
+        let name = preset.name.clone();
+        let dropped = crate::app::presets::merge(&mut self.filter_presets, vec![preset]);
+        if dropped > 0 {
+            self.status_message = format!(
+                "Preset limit reached ({}); delete a preset first.",
+                crate::util::constants::MAX_FILTER_PRESETS
+            );
+            return;
+        }
+        if self.store_filter_presets() {
+            self.status_message = format!("Saved filter preset \u{201c}{name}\u{201d}.");
+        }
+    }
+
+    /// Replace the filter state with preset `index`.  The source-file
+    /// selection is kept, as with the built-in quick filters.
+    pub fn apply_filter_preset(&mut self, index: usize) {
+        let Some(preset) = self.filter_presets.get(index).cloned() else {
+            return;
+        };
+        let source_files = std::mem::take(&mut self.filter_state.source_files);
+        let hide_all = self.filter_state.hide_all_sources;
+        self.filter_state = FilterState::default();
+        self.multi_search_input.clear();
+        self.apply_persisted_filter(&preset.filter);
+        self.filter_state.source_files = source_files;
+        self.filter_state.hide_all_sources = hide_all;
+        self.apply_filters();
+        self.status_message = format!("Applied filter preset \u{201c}{}\u{201d}.", preset.name);
+    }
+
+    /// Delete preset `index`.
+    pub fn delete_filter_preset(&mut self, index: usize) {
+        if index >= self.filter_presets.len() {
+            return;
+        }
+        let removed = self.filter_presets.remove(index);
+        if self.store_filter_presets() {
+            self.status_message =
+                format!("Deleted filter preset \u{201c}{}\u{201d}.", removed.name);
+        }
+    }
+
+    /// Merge presets from a TOML file; presets with an existing name replace
+    /// the saved one.
+    pub fn import_filter_presets(&mut self, path: &std::path::Path) {
+        let incoming = match crate::app::presets::load(path) {
+            Ok(p) => p,
+            Err(e) => {
+                self.status_message = format!("\u{26a0} Preset import failed: {e}");
+                return;
+            }
+        };
+        let count = incoming.len();
+        let dropped = crate::app::presets::merge(&mut self.filter_presets, incoming);
+        if self.store_filter_presets() {
+            let word = if count == 1 { "preset" } else { "presets" };
+            self.status_message = if dropped > 0 {
+                format!(
+                    "Imported {} of {count} {word}; limit of {} reached.",
+                    count - dropped,
+                    crate::util::constants::MAX_FILTER_PRESETS
+                )
+            } else {
+                format!("Imported {count} filter {word}.")
+            };
+        }
+    }
+
+    /// Export preset `index` (or all presets when `None`) to a TOML file.
+    pub fn export_filter_presets(&mut self, path: &std::path::Path, index: Option<usize>) {
+        let selected: Vec<crate::app::presets::FilterPreset> = match index {
+            Some(i) => self.filter_presets.get(i).cloned().into_iter().collect(),
+            None => self.filter_presets.clone(),
+        };
+        if selected.is_empty() {
+            return;
+        }
+        self.status_message = match crate::app::presets::save(path, &selected) {
+            Ok(()) => format!(
+                "Exported {} filter preset(s) to {}.",
+                selected.len(),
+                path.display()
+            ),
+            Err(e) => format!("\u{26a0} Preset export failed: {e}"),
+        };
+    }
+
+    // =========================================================================
+    // Live alert rules
+    // =========================================================================
+
+    /// Load `alert_rules` from `alert_rules_path` and compile them.  Errors
+    /// are reported in the status bar and leave the list empty.
+    pub fn load_alert_rules(&mut self) {
+        let Some(path) = self.alert_rules_path.clone() else {
+            return;
+        };
+        match crate::app::alerts::load(&path) {
+            Ok(rules) => {
+                tracing::debug!(count = rules.len(), "Alert rules loaded");
+                self.alert_rules = rules;
+                self.rebuild_alert_engine();
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load alert rules");
+                self.status_message = format!("\u{26a0} Alert rules not loaded: {e}");
+            }
+        }
+    }
+
+    /// Recompile the enabled rules, resetting their match counts.  Rules that
+    /// do not compile are reported in the status bar.
+    pub fn rebuild_alert_engine(&mut self) {
+        let (engine, errors) = crate::core::alert::AlertEngine::new(&self.alert_rules);
+        self.alert_engine = engine;
+        if let Some((index, error)) = errors.first() {
+            let name = self.alert_rules[*index].name.clone();
+            tracing::warn!(rule = %name, error = %error, "Alert rule skipped");
+            self.status_message =
+                format!("\u{26a0} Alert rule \u{201c}{name}\u{201d} skipped: {error}");
+        }
+    }
+
+    /// Write `alert_rules` to `alert_rules_path` and recompile them.
+    fn store_alert_rules(&mut self) {
+        self.rebuild_alert_engine();
+        let Some(path) = self.alert_rules_path.clone() else {
+            return;
+        };
+        if let Err(e) = crate::app::alerts::save(&path, &self.alert_rules) {
+            tracing::warn!(error = %e, "Failed to save alert rules");
+            self.status_message = format!("\u{26a0} Alert rules not saved: {e}");
+        }
+    }
+
+    /// Load rule `index` (or a new rule when `None`) into the editor draft.
+    pub fn edit_alert_rule(&mut self, index: Option<usize>) {
+        let rule = index
+            .and_then(|i| self.alert_rules.get(i).cloned())
+            .unwrap_or_else(|| crate::core::alert::AlertRule::new("", ""));
+        self.alert_draft_command = crate::core::alert::format_command(&rule.command);
+        self.alert_draft = rule;
+        self.alert_draft_index = index.filter(|&i| i < self.alert_rules.len());
+        self.alert_draft_error = None;
+    }
+
+    /// Validate the editor draft and save it, replacing the rule it was
+    /// loaded from.  On error the draft stays in the editor with the reason.
+    pub fn save_alert_draft(&mut self) {
+        let mut rule = self.alert_draft.clone();
+        rule.command = match crate::core::alert::parse_command(&self.alert_draft_command) {
+            Ok(command) => command,
+            Err(e) => {
+                self.alert_draft_error = Some(e);
+                return;
+            }
+        };
+        rule.normalize();
+        if let Err(e) = rule.validate() {
+            self.alert_draft_error = Some(e);
+            return;
+        }
+        match self.alert_draft_index {
+            Some(i) if i < self.alert_rules.len() => self.alert_rules[i] = rule.clone(),
+            _ if self.alert_rules.len() >= crate::util::constants::MAX_ALERT_RULES => {
+                self.alert_draft_error = Some(format!(
+                    "rule limit reached ({}); delete a rule first",
+                    crate::util::constants::MAX_ALERT_RULES
+                ));
+                return;
+            }
+            _ => self.alert_rules.push(rule.clone()),
+        }
+        self.store_alert_rules();
+        self.edit_alert_rule(None);
+        self.status_message = format!("Saved alert rule \u{201c}{}\u{201d}.", rule.name);
+    }
+
+    /// Enable or disable rule `index`.
+    pub fn set_alert_rule_enabled(&mut self, index: usize, enabled: bool) {
+        if let Some(rule) = self.alert_rules.get_mut(index) {
+            rule.enabled = enabled;
+            self.store_alert_rules();
+        }
+    }
+
+    /// Delete rule `index`.
+    pub fn delete_alert_rule(&mut self, index: usize) {
+        if index >= self.alert_rules.len() {
+            return;
+        }
+        let removed = self.alert_rules.remove(index);
+        match self.alert_draft_index {
+            Some(i) if i == index => self.edit_alert_rule(None),
+            Some(i) if i > index => self.alert_draft_index = Some(i - 1),
+            _ => {}
+        }
+        self.store_alert_rules();
+        self.status_message = format!("Deleted alert rule \u{201c}{}\u{201d}.", removed.name);
+    }
+
+    /// Evaluate the alert rules against a batch of live-tail entries (before
+    /// they are added to `entries`) and act on every rule that fires: pin the
+    /// entry as a bookmark, flash the status bar, append to the alert log
+    /// and run the rule's command.  Only batches of new content are passed
+    /// in; replayed history is skipped by the caller.
+    pub fn evaluate_alerts(&mut self, entries: &[LogEntry]) {
+        if self.alert_engine.is_empty() {
+            return;
+        }
+        let now = chrono::Utc::now();
+        let batch = self.alert_engine.evaluate(entries, now);
+        if batch.suppressed > 0 {
+            tracing::warn!(
+                suppressed = batch.suppressed,
+                "Alert firings beyond the per-batch limit were not acted on"
+            );
+        }
+        for firing in &batch.firings {
+            let Some(rule) = self.alert_rules.get(firing.rule) else {
+                continue;
+            };
+            tracing::info!(rule = %rule.name, entry = firing.entry.id, "Alert rule fired");
+            self.bookmarks
+                .entry(firing.entry.id)
+                .or_insert_with(|| format!("Alert: {}", rule.name));
+            if let Some(path) = &self.alert_log_path {
+                if let Err(e) = crate::app::alerts::append_log(path, rule, &firing.entry, now) {
+                    tracing::warn!(path = %path.display(), error = %e, "Alert log write failed");
+                }
+            }
+            if !rule.command.is_empty() {
+                crate::app::alerts::run_command(&rule.name, &rule.command, &firing.entry);
+            }
+        }
+        if let Some(firing) = batch.firings.last() {
+            let rule = &self.alert_rules[firing.rule];
+            let message = format!(
+                "\u{1f514} Alert \u{201c}{}\u{201d} ({}): {}",
+                rule.name,
+                rule.describe_threshold(),
+                firing.entry.message().lines().next().unwrap_or("")
+            );
+            self.status_message = message.clone();
+            self.alert_flash = Some((message, std::time::Instant::now()));
+        }
+    }
+
+    // =========================================================================
+    // Patterns view
+    // =========================================================================
+
+    /// Mine message templates from all loaded entries into `patterns`.
+    pub fn refresh_patterns(&mut self) {
+        let summary = crate::core::patterns::mine_patterns(&self.entries);
+        tracing::debug!(
+            entries = summary.entries_analysed,
+            patterns = summary.patterns.len(),
+            unclustered = summary.unclustered,
+            "Log patterns mined"
+        );
+        self.patterns = Some(summary);
+    }
+
+    /// Filter the timeline to the members of pattern `index` (an index into
+    /// `patterns.patterns`).  Other active filters stay in effect.
+    pub fn apply_pattern_filter(&mut self, index: usize) {
+        let Some(pattern) = self.patterns.as_ref().and_then(|p| p.patterns.get(index)) else {
+            return;
+        };
+        self.filter_state.pattern_ids = Some(pattern.member_ids.iter().copied().collect());
+        self.filter_state.pattern_label = pattern.template.clone();
+        self.apply_filters();
+    }
+
+    /// Remove the pattern filter.
+    pub fn clear_pattern_filter(&mut self) {
+        self.filter_state.pattern_ids = None;
+        self.filter_state.pattern_label.clear();
+        self.apply_filters();
+    }
+
+    // =========================================================================
+    // Anomalies view
+    // =========================================================================
+
+    /// Detect error-rate spikes and silences in all loaded entries.
+    pub fn refresh_anomalies(&mut self) {
+        let report = crate::core::anomaly::detect_anomalies(&self.entries);
+        tracing::debug!(
+            entries = report.entries_analysed,
+            findings = report.findings.len(),
+            bucket_secs = report.bucket_secs,
+            "Anomaly detection complete"
+        );
+        self.anomalies = Some(report);
+    }
+
+    /// Pin the absolute time filter to `start..=end` (the "jump to window"
+    /// action of an anomaly finding).  Clears the rolling time window like
+    /// the From / To inputs do, and fills those inputs in local time.
+    pub fn jump_to_window(
+        &mut self,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) {
+        let local = |t: chrono::DateTime<chrono::Utc>| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        };
+        self.filter_state.relative_time_secs = None;
+        self.filter_state.relative_time_input.clear();
+        self.filter_state.time_start = Some(start);
+        self.filter_state.time_end = Some(end);
+        self.filter_state.abs_time_start_input = local(start);
+        self.filter_state.abs_time_end_input = local(end);
+        self.apply_filters();
+    }
+
+    // =========================================================================
+    // Compare view
+    // =========================================================================
+
+    /// Compare the entries selected by `compare_a` with those of `compare_b`.
+    /// Input problems are reported in `compare_error`.
+    pub fn run_compare(&mut self) {
+        let sides = self
+            .compare_a
+            .selector()
+            .map_err(|e| format!("Side A: {e}"))
+            .and_then(|a| {
+                self.compare_b
+                    .selector()
+                    .map(|b| (a, b))
+                    .map_err(|e| format!("Side B: {e}"))
+            });
+        match sides {
+            Ok((a, b)) => {
+                let report = crate::core::compare::compare(&self.entries, &a, &b);
+                tracing::debug!(
+                    a = %report.label_a,
+                    b = %report.label_b,
+                    rows = report.rows.len(),
+                    "Compare complete"
+                );
+                self.compare = Some(report);
+                self.compare_error = None;
+            }
+            Err(e) => {
+                self.compare = None;
+                self.compare_error = Some(e);
+            }
+        }
+    }
+
+    /// Show the occurrences of compare row `index` in the timeline: filters
+    /// to them (via the pattern filter) and selects the first occurrence,
+    /// preferring side B.
+    pub fn show_compare_row(&mut self, index: usize) {
+        let Some(row) = self.compare.as_ref().and_then(|c| c.rows.get(index)) else {
+            return;
+        };
+        let first_id = row.ids_b.first().or(row.ids_a.first()).copied();
+        self.filter_state.pattern_ids = Some(row.ids_a.iter().chain(&row.ids_b).copied().collect());
+        self.filter_state.pattern_label = format!("Compare: {}", row.template);
+        self.apply_filters();
+        if let Some(id) = first_id {
+            self.select_entry_by_id(id);
+        }
+    }
+
+    // =========================================================================
+    // Key correlation
+    // =========================================================================
+
+    /// Correlate the timeline by `key` (or turn key correlation off with
+    /// `None`) and rebuild the highlight set and transaction.  Opens the
+    /// Transaction window when a key is set.
+    pub fn set_correlation_key(&mut self, key: Option<crate::core::correlation::CorrelationKey>) {
+        self.correlation_key_input = key.as_ref().map(|k| k.token.clone()).unwrap_or_default();
+        self.show_transaction = key.is_some();
+        self.correlation_key = key;
+        self.refresh_key_correlation();
+        if let Some(ref key) = self.correlation_key {
+            tracing::debug!(
+                key = %key.label(),
+                entries = self.transaction.len(),
+                "Key correlation set"
+            );
+        }
+    }
+
+    /// Rebuild `key_correlated_ids` and `transaction` from all entries.
+    pub fn refresh_key_correlation(&mut self) {
+        self.key_correlated_ids.clear();
+        self.transaction.clear();
+        let Some(ref key) = self.correlation_key else {
+            return;
+        };
+        self.transaction = crate::core::correlation::find_transaction(&self.entries, key);
+        self.key_correlated_ids = self
+            .transaction
+            .iter()
+            .filter_map(|&i| self.entries.get(i).map(|e| e.id))
+            .collect();
+    }
+
+    /// Filter the timeline to the transaction's entries (via the pattern
+    /// filter).
+    pub fn filter_to_transaction(&mut self) {
+        let Some(ref key) = self.correlation_key else {
+            return;
+        };
+        self.filter_state.pattern_ids = Some(self.key_correlated_ids.clone());
+        self.filter_state.pattern_label = format!("Key: {}", key.token);
+        self.apply_filters();
+    }
+
+    /// Select the entry with `id` in the timeline.  Returns `false` (and
+    /// leaves the selection unchanged) when the current filter hides it.
+    pub fn select_entry_by_id(&mut self, id: u64) -> bool {
+        let Some(pos) = self
+            .filtered_indices
+            .iter()
+            .position(|&i| self.entries.get(i).is_some_and(|e| e.id == id))
+        else {
+            return false;
+        };
+        self.selected_indices.clear();
+        self.selected_index = Some(pos);
+        self.update_correlation();
+        true
+    }
+
+    // =========================================================================
+    // Clock adjustment
+    // =========================================================================
+
+    /// Apply `clock_rules` to freshly parsed entries before they are appended
+    /// to `entries` (scan batches and live tail).
+    pub fn apply_clock_rules(&self, entries: &mut [LogEntry]) {
+        crate::core::clock::apply_rules(&self.clock_rules, entries);
+    }
+
+    /// Add `rule`, replacing any rule for the same path; an identity rule
+    /// (no offset, profile timezone) just removes it.  Loaded entries are
+    /// re-timed, or re-parsed when their zone changes.
+    pub fn set_clock_rule(&mut self, rule: crate::core::clock::ClockRule) {
+        let mut rules: Vec<_> = self
+            .clock_rules
+            .iter()
+            .filter(|r| r.path != rule.path)
+            .cloned()
+            .collect();
+        if !rule.is_identity() {
+            tracing::info!(rule = %rule.describe(), "Clock rule set");
+            rules.push(rule);
+            rules.sort_by(|a, b| a.path.cmp(&b.path));
+        }
+        self.set_clock_rules(rules);
+    }
+
+    /// Remove the rule for exactly `path`.  Loaded entries are re-timed, or
+    /// re-parsed when their zone changes.
+    pub fn remove_clock_rule(&mut self, path: &Path) {
+        let rules = self
+            .clock_rules
+            .iter()
+            .filter(|r| r.path != path)
+            .cloned()
+            .collect();
+        self.set_clock_rules(rules);
+    }
+
+    /// Replace all rules, re-time loaded entries from the old rules to the
+    /// new ones and re-sort the timeline.  Files whose zone changed are
+    /// queued for re-parsing.
+    fn set_clock_rules(&mut self, rules: Vec<crate::core::clock::ClockRule>) {
+        let old = std::mem::replace(&mut self.clock_rules, rules);
+        if old == self.clock_rules {
+            return;
+        }
+        crate::core::clock::retime(&old, &self.clock_rules, &mut self.entries);
+        if let Some(store) = self.entry_store.as_mut() {
+            // Only entries of files whose offset changed are rewritten.
+            let new = &self.clock_rules;
+            let offset = |rules: &[crate::core::clock::ClockRule], f: &Path| {
+                crate::core::clock::rule_for(rules, f).map(|r| r.offset_secs)
+            };
+            let result = store.rewrite_files(
+                |f| offset(&old, f) != offset(new, f),
+                |batch| crate::core::clock::retime(&old, new, batch),
+            );
+            if let Err(e) = result {
+                self.entry_store_error(&e);
+            }
+            self.apply_filters();
+        } else if !self.entries.is_empty() {
+            self.sort_entries_chronologically();
+        }
+
+        // Zones are applied at parse time: re-parse loaded files whose zone
+        // override changed.
+        let rezoned: std::collections::HashSet<PathBuf> = self
+            .discovered_files
+            .iter()
+            .filter(|f| !f.parsing_skipped)
+            .filter(|f| {
+                crate::core::clock::zone_for(&old, &f.path)
+                    != crate::core::clock::zone_for(&self.clock_rules, &f.path)
+            })
+            .map(|f| f.path.clone())
+            .collect();
+        if !rezoned.is_empty() {
+            self.remove_entries_for_paths(&rezoned);
+            self.queue_parse_requests(rezoned.into_iter().collect());
+            self.apply_filters();
+        }
+    }
+
+    /// One description per clock rule, for export metadata.
+    pub fn clock_rule_descriptions(&self) -> Vec<String> {
+        self.clock_rules.iter().map(|r| r.describe()).collect()
+    }
+
+    // -------------------------------------------------------------------------
+    // Session persistence helpers
+    // -------------------------------------------------------------------------
+
+    /// Snapshot the serialisable subset of the filter state (session save and
+    /// filter presets).
+    pub fn persisted_filter(&self) -> crate::app::session::PersistedFilter {
+        crate::app::session::PersistedFilter {
+            severity_levels: self.filter_state.severity_levels.iter().copied().collect(),
+            source_files: self.filter_state.source_files.iter().cloned().collect(),
+            hide_all_sources: self.filter_state.hide_all_sources,
+            text_search: self.filter_state.text_search.clone(),
+            regex_pattern: self.filter_state.regex_pattern.clone(),
+            fuzzy: self.filter_state.fuzzy,
+            relative_time_secs: self.filter_state.relative_time_secs,
+            bookmarks_only: self.filter_state.bookmarks_only,
+            exclude_text: self.filter_state.exclude_text.clone(),
+            component_filter: {
+                let mut v: Vec<String> =
+                    self.filter_state.component_filter.iter().cloned().collect();
+                v.sort_unstable();
+                v
+            },
+            hide_no_timestamp: self.filter_state.hide_no_timestamp,
+            dedup_mode: self.filter_state.dedup_mode,
+            multi_search_input: self.multi_search_input.clone(),
+            multi_search_mode: self.filter_state.multi_search.mode,
+            multi_search_min_match: self.filter_state.multi_search.min_match,
+            multi_search_case_insensitive: self.filter_state.multi_search.case_insensitive,
+            multi_search_whole_word: self.filter_state.multi_search.whole_word,
+            multi_search_regex_mode: self.filter_state.multi_search.regex_mode,
+            field_filter: self.filter_state.field_filter_input.clone(),
+            query: self.filter_state.query_input.clone(),
+        }
+    }
+
+    /// Apply a persisted filter snapshot on top of the current filter state
+    /// (session restore and filter presets).  Invalid regex / query /
+    /// multi-search input is logged and left inactive.
+    fn apply_persisted_filter(&mut self, f: &crate::app::session::PersistedFilter) {
+        self.filter_state.severity_levels = f.severity_levels.iter().copied().collect();
+        // NOTE: source_files and hide_all_sources are intentionally NOT
+        // restored from the session.  File-path whitelists are tightly coupled
+        // to a particular scan directory and become silently stale when a new
+        // scan runs over a different (or updated) directory.  Restoring them
+        // caused confusing states where only one file was visible after restart
+        // even though 400+ files had been discovered.
+        // The values ARE still serialised by PersistedFilter so old sessions
+        // round-trip without schema breakage, but we simply discard them here.
+        self.filter_state.text_search = f.text_search.clone();
+        self.filter_state.fuzzy = f.fuzzy;
+        self.filter_state.relative_time_secs = f.relative_time_secs;
+        self.filter_state.relative_time_input = f
+            .relative_time_secs
+            .map(|s| (s / 60).to_string())
+            .unwrap_or_default();
+        self.filter_state.bookmarks_only = f.bookmarks_only;
+        // Restore the component filter.  component_filter references component
+        // strings that are re-discovered on the next scan parse, so it remains
+        // semantically valid on restore.
+        self.filter_state.exclude_text = f.exclude_text.clone();
+        self.filter_state.component_filter = f.component_filter.iter().cloned().collect();
+        self.filter_state.hide_no_timestamp = f.hide_no_timestamp;
+        self.filter_state.dedup_mode = f.dedup_mode;
+        self.filter_state.set_field_filter(&f.field_filter);
+        if let Err(e) = self.filter_state.set_query(&f.query) {
+            // Keep the text in the query bar so the user can fix it.
+            tracing::warn!(
+                query = %f.query,
+                error = %e,
+                "Restored filter: saved query is invalid, not applied"
+            );
+        }
+        if !f.regex_pattern.is_empty() && self.filter_state.set_regex(&f.regex_pattern).is_err() {
+            tracing::warn!(
+                pattern = %f.regex_pattern,
+                "Restored filter: saved regex pattern is invalid, discarding"
+            );
+        }
+        // Restore multi-term search state.
+        self.multi_search_input = f.multi_search_input.clone();
+        if !self.multi_search_input.is_empty() {
+            let (include, exclude) =
+                crate::core::multi_search::MultiSearch::parse_terms(&self.multi_search_input);
+            self.filter_state.multi_search.include_terms = include;
+            self.filter_state.multi_search.exclude_terms = exclude;
+            self.filter_state.multi_search.mode = f.multi_search_mode;
+            self.filter_state.multi_search.min_match = f.multi_search_min_match;
+            self.filter_state.multi_search.case_insensitive = f.multi_search_case_insensitive;
+            self.filter_state.multi_search.whole_word = f.multi_search_whole_word;
+            self.filter_state.multi_search.regex_mode = f.multi_search_regex_mode;
+            self.filter_state.multi_search.compile();
+            if let Some(ref err) = self.filter_state.multi_search.compile_error {
+                tracing::warn!(
+                    error = %err,
+                    "Restored filter: saved multi-search terms failed to compile, discarding"
+                );
+                self.multi_search_input.clear();
+                self.filter_state.multi_search = crate::core::multi_search::MultiSearch::default();
+            }
+        }
     }
 
-    // -------------------------------------------------------------------------
-    // Session persistence helpers
-    // -------------------------------------------------------------------------
-
     /// Snapshot the current state into a session file on disk.
     ///
     /// Silently does nothing if `session_path` has not been set (e.g. in tests).
@@ -1551,31 +3013,7 @@ impl AppState {
         let Some(session_path) = &self.session_path else {
             return;
         };
-        let filter = crate::app::session::PersistedFilter {
-            severity_levels: self.filter_state.severity_levels.iter().copied().collect(),
-            source_files: self.filter_state.source_files.iter().cloned().collect(),
-            hide_all_sources: self.filter_state.hide_all_sources,
-            text_search: self.filter_state.text_search.clone(),
-            regex_pattern: self.filter_state.regex_pattern.clone(),
-            fuzzy: self.filter_state.fuzzy,
-            relative_time_secs: self.filter_state.relative_time_secs,
-            bookmarks_only: self.filter_state.bookmarks_only,
-            exclude_text: self.filter_state.exclude_text.clone(),
-            component_filter: {
-                let mut v: Vec<String> =
-                    self.filter_state.component_filter.iter().cloned().collect();
-                v.sort_unstable();
-                v
-            },
-            hide_no_timestamp: self.filter_state.hide_no_timestamp,
-            dedup_mode: self.filter_state.dedup_mode,
-            multi_search_input: self.multi_search_input.clone(),
-            multi_search_mode: self.filter_state.multi_search.mode,
-            multi_search_min_match: self.filter_state.multi_search.min_match,
-            multi_search_case_insensitive: self.filter_state.multi_search.case_insensitive,
-            multi_search_whole_word: self.filter_state.multi_search.whole_word,
-            multi_search_regex_mode: self.filter_state.multi_search.regex_mode,
-        };
+        let filter = self.persisted_filter();
         let file_colours = self
             .file_colours
             .iter()
@@ -1603,9 +3041,11 @@ impl AppState {
             max_total_entries: self.max_total_entries,
             max_scan_depth: self.max_scan_depth,
             tail_poll_interval_ms: self.tail_poll_interval_ms,
+            tail_replay: self.tail_replay,
             dir_watch_poll_interval_ms: self.dir_watch_poll_interval_ms,
             max_tail_buffer_entries: self.max_tail_buffer_entries,
             troubleshoot_mode: self.troubleshoot_mode,
+            clock_rules: self.clock_rules.clone(),
         };
         if let Err(e) = crate::app::session::save(&data, session_path) {
             tracing::warn!(error = %e, "Failed to save session");
@@ -1619,37 +3059,7 @@ impl AppState {
     /// the view always reflects current on-disk state.
     pub fn restore_from_session(&mut self, data: crate::app::session::SessionData) {
         self.scan_path = data.scan_path;
-        let f = &data.filter;
-        self.filter_state.severity_levels = f.severity_levels.iter().copied().collect();
-        // NOTE: source_files and hide_all_sources are intentionally NOT
-        // restored from the session.  File-path whitelists are tightly coupled
-        // to a particular scan directory and become silently stale when a new
-        // scan runs over a different (or updated) directory.  Restoring them
-        // caused confusing states where only one file was visible after restart
-        // even though 400+ files had been discovered.
-        // The values ARE still serialised by PersistedFilter so old sessions
-        // round-trip without schema breakage, but we simply discard them here.
-        self.filter_state.text_search = f.text_search.clone();
-        self.filter_state.fuzzy = f.fuzzy;
-        self.filter_state.relative_time_secs = f.relative_time_secs;
-        self.filter_state.relative_time_input = f
-            .relative_time_secs
-            .map(|s| (s / 60).to_string())
-            .unwrap_or_default();
-        self.filter_state.bookmarks_only = f.bookmarks_only;
-        // Restore the component filter.  component_filter references component
-        // strings that are re-discovered on the next scan parse, so it remains
-        // semantically valid on restore.
-        self.filter_state.exclude_text = f.exclude_text.clone();
-        self.filter_state.component_filter = f.component_filter.iter().cloned().collect();
-        self.filter_state.hide_no_timestamp = f.hide_no_timestamp;
-        self.filter_state.dedup_mode = f.dedup_mode;
-        if !f.regex_pattern.is_empty() && self.filter_state.set_regex(&f.regex_pattern).is_err() {
-            tracing::warn!(
-                pattern = %f.regex_pattern,
-                "Session restore: saved regex pattern is invalid, discarding"
-            );
-        }
+        self.apply_persisted_filter(&data.filter);
         // Queue extra files for a secondary append scan after the initial
         // scan_path scan completes (handled in gui.rs::ParsingCompleted).
         self.extra_files_to_restore = data.extra_files;
@@ -1677,33 +3087,77 @@ impl AppState {
         self.max_total_entries = data.max_total_entries;
         self.max_scan_depth = data.max_scan_depth;
         self.tail_poll_interval_ms = data.tail_poll_interval_ms;
+        self.tail_replay = data.tail_replay;
         self.dir_watch_poll_interval_ms = data.dir_watch_poll_interval_ms;
         self.max_tail_buffer_entries = data.max_tail_buffer_entries;
         self.troubleshoot_mode = data.troubleshoot_mode;
+        // Entries are not restored, so the rules are set without re-timing.
+        self.clock_rules = data.clock_rules;
+    }
+}
 
-        // Restore multi-term search state.
-        self.multi_search_input = f.multi_search_input.clone();
-        if !self.multi_search_input.is_empty() {
-            let (include, exclude) =
-                crate::core::multi_search::MultiSearch::parse_terms(&self.multi_search_input);
-            self.filter_state.multi_search.include_terms = include;
-            self.filter_state.multi_search.exclude_terms = exclude;
-            self.filter_state.multi_search.mode = f.multi_search_mode;
-            self.filter_state.multi_search.min_match = f.multi_search_min_match;
-            self.filter_state.multi_search.case_insensitive = f.multi_search_case_insensitive;
-            self.filter_state.multi_search.whole_word = f.multi_search_whole_word;
-            self.filter_state.multi_search.regex_mode = f.multi_search_regex_mode;
-            self.filter_state.multi_search.compile();
-            if let Some(ref err) = self.filter_state.multi_search.compile_error {
-                tracing::warn!(
-                    error = %err,
-                    "Session restore: saved multi-search terms failed to compile, discarding"
-                );
-                self.multi_search_input.clear();
-                self.filter_state.multi_search = crate::core::multi_search::MultiSearch::default();
-            }
-        }
+/// Store entries read per batch when exporting.
+const STORE_EXPORT_BATCH: usize = 10_000;
+
+/// The entries at `rows` of `store`, read a batch at a time as the iterator
+/// advances.
+fn store_row_entries<'a>(
+    store: &'a mut crate::core::entry_store::EntryStore,
+    rows: &'a [u32],
+) -> impl Iterator<Item = Result<LogEntry, crate::util::error::ExportError>> + 'a {
+    rows.chunks(STORE_EXPORT_BATCH).flat_map(move |chunk| {
+        let batch: Vec<_> = match store.read(chunk) {
+            Ok(entries) => entries.into_iter().map(Ok).collect(),
+            Err(source) => vec![Err(crate::util::error::ExportError::Store { source })],
+        };
+        batch
+    })
+}
+
+/// Whether troubleshoot mode keeps `entry` (Critical and Error only).
+fn troubleshoot_keeps(entry: &LogEntry) -> bool {
+    matches!(
+        entry.severity,
+        crate::core::model::Severity::Critical | crate::core::model::Severity::Error
+    )
+}
+
+/// Entries of `batch` (with their index) that pass `filter` and, when set,
+/// come from a file in `active_files`.
+fn matching_entries<'a>(
+    batch: &'a [LogEntry],
+    filter: &FilterState,
+    active_files: Option<&'a HashSet<&Path>>,
+) -> impl Iterator<Item = (usize, &'a LogEntry)> {
+    crate::core::filter::apply_filters(batch, filter)
+        .into_iter()
+        .map(move |idx| (idx, &batch[idx]))
+        .filter(move |(_, e)| active_files.map_or(true, |a| a.contains(&*e.source_file)))
+}
+
+/// The files modified since `cutoff` (the activity window), or `None` when
+/// the window is off or no files are discovered.  A file with no known mtime
+/// counts as active.
+///
+/// Performance: the set is only built when the feature is enabled and at
+/// least one file is discovered.  Building a HashSet of path references on
+/// every filter call was O(discovered_files) and allocated even when the
+/// filter matched all files.
+fn active_file_set(
+    files: &[DiscoveredFile],
+    cutoff: Option<chrono::DateTime<chrono::Utc>>,
+) -> Option<HashSet<&Path>> {
+    let cutoff = cutoff?;
+    if files.is_empty() {
+        return None;
     }
+    Some(
+        files
+            .iter()
+            .filter(|f| f.modified.map_or(true, |t| t >= cutoff))
+            .map(|f| f.path.as_path())
+            .collect(),
+    )
 }
 
 #[cfg(test)]
@@ -1720,17 +3174,110 @@ mod tests {
             id,
             timestamp: Some(ts),
             severity: Severity::Info,
-            source_file: std::path::PathBuf::from("test.log"),
+            source_file: std::path::PathBuf::from("test.log").into(),
             line_number: 1,
             thread: None,
             component: None,
-            message: String::new(),
+            message: String::new().into(),
             raw_text: String::new(),
-            profile_id: "test".to_string(),
+            profile_id: "test".to_string().into(),
+            fields: Default::default(),
             file_modified: None,
         }
     }
 
+    /// Entries beyond the cap spill to the entry store; the filters, dedup,
+    /// paging, export and live tail all cover every stored entry.
+    #[test]
+    fn test_ingest_spills_to_entry_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = AppState::new(vec![], false);
+        state.max_total_entries = 4;
+        state.spill_dir = Some(dir.path().to_path_buf());
+
+        // Arrive newest first, in two batches.
+        state.ingest_entries((0..6).map(|i| make_entry(i, 100 - i as i64)).collect());
+        state.ingest_entries((6..10).map(|i| make_entry(i, 100 - i as i64)).collect());
+        assert_eq!(state.session_entry_count(), 10);
+        assert!(state.store_window_stale);
+        assert_eq!(state.next_entry_id(), 10);
+
+        let page_ids = |state: &AppState| -> Vec<u64> {
+            state
+                .filtered_indices
+                .iter()
+                .map(|&i| state.entries[i].id)
+                .collect()
+        };
+        state.apply_filters();
+        assert!(!state.store_window_stale);
+        assert_eq!(state.filtered_count(), 10);
+        assert_eq!(page_ids(&state), vec![9, 8, 7, 6]);
+
+        // Pages are clamped to end at the last row.
+        state.load_store_page(8);
+        assert_eq!(state.store_window_start, 6);
+        assert_eq!(page_ids(&state), vec![3, 2, 1, 0]);
+
+        state.filter_state.time_start = Some(make_entry(0, 95).timestamp.unwrap());
+        state.apply_filters();
+        assert_eq!(state.filtered_count(), 6);
+        assert_eq!(page_ids(&state), vec![3, 2, 1, 0]);
+
+        let metadata = crate::core::export::ExportMetadata {
+            scan_path: None,
+            filter_description: "",
+            entry_count: state.filtered_count(),
+            clock_adjustments: &[],
+        };
+        let mut out = Vec::new();
+        let n = state
+            .export_csv(&mut out, Path::new("export.csv"), &metadata)
+            .unwrap();
+        assert_eq!(n, 6);
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 6 + 7);
+
+        // Identical messages from one file collapse to the latest entry.
+        state.filter_state.dedup_mode = DedupMode::Exact;
+        state.apply_filters();
+        assert_eq!(state.filtered_count(), 1);
+        assert_eq!(page_ids(&state), vec![0]);
+        assert_eq!(state.dedup_info[&0].count, 6);
+
+        state.filter_state.dedup_mode = DedupMode::Off;
+        state.apply_filters();
+        state.append_tail_to_store(vec![make_entry(10, 200)]);
+        assert_eq!(state.session_entry_count(), 11);
+        assert_eq!(state.filtered_count(), 7);
+        state.load_store_page(7);
+        assert_eq!(page_ids(&state), vec![2, 1, 0, 10]);
+
+        state.clear();
+        assert!(state.entry_store.is_none());
+        assert!(state.store_rows.is_empty());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    /// Every segment of a rotation family takes the family's colour; other
+    /// files get the next palette colour.
+    #[test]
+    fn test_assign_file_colour_shares_colour_across_rotation_family() {
+        let mut state = AppState::new(vec![], false);
+        for name in [
+            "app.log",
+            "other.log",
+            "app.log.1",
+            "app.log.2.gz!/app.log.2",
+        ] {
+            state.assign_file_colour(&PathBuf::from("/logs").join(name));
+        }
+        let colour = |name: &str| state.colour_for_file(&PathBuf::from("/logs").join(name));
+        assert_eq!(colour("app.log.1"), colour("app.log"));
+        assert_eq!(colour("app.log.2.gz!/app.log.2"), colour("app.log"));
+        assert_ne!(colour("other.log"), colour("app.log"));
+        assert_eq!(colour("other.log"), crate::ui::theme::file_colour(1));
+    }
+
     /// Correlation must identify all entries within the window and exclude
     /// those outside it, across all loaded entries (not just filtered ones).
     #[test]
@@ -2141,9 +3688,11 @@ mod tests {
             max_total_entries: crate::util::constants::MAX_TOTAL_ENTRIES,
             max_scan_depth: crate::util::constants::DEFAULT_MAX_DEPTH,
             tail_poll_interval_ms: crate::util::constants::TAIL_POLL_INTERVAL_MS,
+            tail_replay: crate::app::tail::TailReplay::Off,
             dir_watch_poll_interval_ms: crate::util::constants::DIR_WATCH_POLL_INTERVAL_MS,
             max_tail_buffer_entries: crate::util::constants::DEFAULT_MAX_TAIL_BUFFER_ENTRIES,
             troubleshoot_mode: false,
+            clock_rules: Vec::new(),
         };
 
         state.restore_from_session(data);
@@ -2205,7 +3754,7 @@ mod tests {
         // 2 tail entries from b.log.
         let e = |id: u64, file: &str| {
             let mut entry = make_entry(id, id as i64);
-            entry.source_file = std::path::PathBuf::from(file);
+            entry.source_file = std::path::PathBuf::from(file).into();
             entry
         };
         state.entries = vec![e(0, "a.log"), e(1, "b.log"), e(2, "a.log"), e(3, "b.log")];
@@ -2254,4 +3803,403 @@ mod tests {
             "extra_files_to_restore must be empty after clear()"
         );
     }
+
+    /// A directory plus files on the command line: the directory becomes the
+    /// scan root, the files wait in the parse queue, and --tail is recorded.
+    #[test]
+    fn test_open_startup_paths_directory_and_files() {
+        use tempfile::TempDir;
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("extra.log");
+        std::fs::write(&file, "line\n").unwrap();
+
+        let mut state = AppState::new(vec![], false);
+        state.open_startup_paths(&[dir.path().to_path_buf(), file.clone()], true);
+
+        assert_eq!(state.pending_scan.as_deref(), Some(dir.path()));
+        assert_eq!(state.scan_path.as_deref(), Some(dir.path()));
+        assert_eq!(state.queued_parse_files, vec![file]);
+        assert!(state.pending_replace_files.is_none());
+        assert!(state.request_start_tail_after_scan);
+    }
+
+    /// Files without a directory open as a file-only session (like
+    /// "Open Log(s)..."); without --tail no tail request is recorded.
+    #[test]
+    fn test_open_startup_paths_files_only() {
+        use tempfile::TempDir;
+        let dir = TempDir::new().unwrap();
+        let a = dir.path().join("a.log");
+        let b = dir.path().join("b.log");
+        std::fs::write(&a, "x\n").unwrap();
+        std::fs::write(&b, "y\n").unwrap();
+
+        let mut state = AppState::new(vec![], false);
+        state.open_startup_paths(&[a.clone(), b.clone()], false);
+
+        assert!(state.pending_scan.is_none());
+        assert_eq!(state.pending_replace_files, Some(vec![a, b]));
+        assert!(!state.request_start_tail_after_scan);
+    }
+
+    /// Missing paths and second directories are skipped and named in the
+    /// status bar rather than silently dropped.
+    #[test]
+    fn test_open_startup_paths_reports_skipped_paths() {
+        use tempfile::TempDir;
+        let first = TempDir::new().unwrap();
+        let second = TempDir::new().unwrap();
+        let missing = first.path().join("does-not-exist.log");
+
+        let mut state = AppState::new(vec![], false);
+        state.open_startup_paths(
+            &[
+                first.path().to_path_buf(),
+                missing.clone(),
+                second.path().to_path_buf(),
+            ],
+            false,
+        );
+
+        assert_eq!(state.pending_scan.as_deref(), Some(first.path()));
+        assert!(state.queued_parse_files.is_empty());
+        assert!(state.status_message.contains("not found"));
+        assert!(state
+            .status_message
+            .contains(&missing.display().to_string()));
+        assert!(state.status_message.contains("only one directory"));
+    }
+
+    /// Nothing valid on the command line leaves the app idle.
+    #[test]
+    fn test_open_startup_paths_all_missing_queues_nothing() {
+        let mut state = AppState::new(vec![], false);
+        state.open_startup_paths(&[PathBuf::from("/no/such/dir/or/file")], true);
+
+        assert!(state.pending_scan.is_none());
+        assert!(state.pending_replace_files.is_none());
+        assert!(!state.request_start_tail_after_scan);
+    }
+
+    /// Applying a preset replaces the whole filter (including fields the
+    /// preset leaves empty) but keeps the current source-file selection.
+    #[test]
+    fn test_filter_preset_save_and_apply_round_trip() {
+        let mut state = AppState::new(vec![], false);
+        state.filter_state.set_min_severity(Severity::Warning);
+        state.filter_state.exclude_text = "heartbeat".to_string();
+        state.filter_state.relative_time_secs = Some(900);
+        state.multi_search_input = "timeout".to_string();
+        state.filter_state.set_query("component:Agent").unwrap();
+        state.save_filter_preset("  Triage ");
+        assert_eq!(state.filter_presets.len(), 1);
+        assert_eq!(state.filter_presets[0].name, "Triage");
+
+        state.filter_state = FilterState::default();
+        state.filter_state.text_search = "unrelated".to_string();
+        let file = std::path::PathBuf::from("/var/log/a.log");
+        state.filter_state.source_files.insert(file.clone());
+        state.apply_filter_preset(0);
+
+        let f = &state.filter_state;
+        assert_eq!(f.severity_levels.len(), 3);
+        assert_eq!(f.exclude_text, "heartbeat");
+        assert_eq!(f.relative_time_secs, Some(900));
+        assert!(
+            f.text_search.is_empty(),
+            "preset must replace the text search"
+        );
+        assert!(f.query.is_some());
+        assert_eq!(state.multi_search_input, "timeout");
+        assert!(
+            f.source_files.contains(&file),
+            "file selection must be kept"
+        );
+    }
+
+    /// Picking a template in the Patterns view filters to its members; the
+    /// filter is cleared again by `clear_pattern_filter()` and by `clear()`.
+    #[test]
+    fn test_pattern_filter_apply_and_clear() {
+        let mut state = AppState::new(vec![], false);
+        let messages = [
+            "Backup job 17 started",
+            "Disk full on C:",
+            "Backup job 18 started",
+        ];
+        state.entries = messages
+            .iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let mut e = make_entry(i as u64, i as i64);
+                e.message = m.to_string().into();
+                e
+            })
+            .collect();
+        state.apply_filters();
+        state.refresh_patterns();
+        let summary = state.patterns.as_ref().unwrap();
+        assert_eq!(summary.patterns[0].template, "Backup job <NUM> started");
+
+        state.apply_pattern_filter(0);
+        assert_eq!(state.filtered_indices, vec![0, 2]);
+        assert!(state.filter_description().contains("Pattern: Backup job"));
+
+        state.clear_pattern_filter();
+        assert_eq!(state.filtered_indices.len(), 3);
+
+        state.apply_pattern_filter(0);
+        state.show_patterns = true;
+        state.clear();
+        assert!(state.filter_state.pattern_ids.is_none());
+        assert!(state.patterns.is_none());
+        assert!(!state.show_patterns);
+    }
+
+    /// Jumping to an anomaly window pins the absolute time range, replaces
+    /// a rolling window and fills the From / To inputs.
+    #[test]
+    fn test_jump_to_window_sets_absolute_range() {
+        let mut state = AppState::new(vec![], false);
+        state.entries = (0..5).map(|i| make_entry(i, i as i64 * 60)).collect();
+        state.filter_state.relative_time_secs = Some(900);
+        state.filter_state.relative_time_input = "15".to_string();
+
+        let base = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        state.jump_to_window(
+            base + chrono::Duration::seconds(60),
+            base + chrono::Duration::seconds(179),
+        );
+
+        assert_eq!(state.filtered_indices, vec![1, 2]);
+        assert!(state.filter_state.relative_time_secs.is_none());
+        assert!(state.filter_state.relative_time_input.is_empty());
+        let parsed = parse_filter_datetime(&state.filter_state.abs_time_start_input);
+        assert_eq!(parsed, state.filter_state.time_start);
+        assert!(!state.filter_state.abs_time_end_input.is_empty());
+    }
+
+    /// Compare inputs are validated per side, and a result row links back
+    /// into the timeline by filtering to its occurrences.
+    #[test]
+    fn test_run_compare_and_show_row() {
+        let mut state = AppState::new(vec![], false);
+        state.run_compare();
+        assert_eq!(
+            state.compare_error.as_deref(),
+            Some("Side A: choose a file")
+        );
+
+        let mut entries: Vec<LogEntry> = (0..4).map(|i| make_entry(i, i as i64)).collect();
+        entries[0].source_file = PathBuf::from("/runs/ok/job.log").into();
+        entries[0].message = "Job finished".to_string().into();
+        for e in &mut entries[1..] {
+            e.source_file = PathBuf::from("/runs/bad/job.log").into();
+            e.message = "Job failed".to_string().into();
+        }
+        state.entries = entries;
+        state.apply_filters();
+
+        state.compare_a.file = Some(PathBuf::from("/runs/ok/job.log"));
+        state.compare_b.mode = CompareMode::Directory;
+        state.compare_b.directory = "/runs/bad".to_string();
+        state.run_compare();
+        assert!(state.compare_error.is_none());
+        let report = state.compare.as_ref().unwrap();
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[1].template, "Job failed");
+
+        state.show_compare_row(1);
+        assert_eq!(state.filtered_indices, vec![1, 2, 3]);
+        assert_eq!(state.selected_index, Some(0));
+        assert!(state.filter_description().contains("Compare: Job failed"));
+    }
+
+    /// A key stitches entries from several files into one time-ordered
+    /// transaction that survives filter changes and tail appends.
+    #[test]
+    fn test_key_correlation_transaction() {
+        let mut state = AppState::new(vec![], false);
+        let mut entries: Vec<LogEntry> = (0..4).map(|i| make_entry(i, 10 - i as i64)).collect();
+        entries[0].message = "GET /orders requestId=req-42".to_string().into();
+        entries[1].message = "unrelated".to_string().into();
+        entries[2].source_file = PathBuf::from("db.log").into();
+        entries[2].message = "query for req-42".to_string().into();
+        entries[3].message = "req-420 is a different request".to_string().into();
+        state.entries = entries;
+        state.apply_filters();
+
+        state.set_correlation_key(crate::core::correlation::CorrelationKey::custom("req-42"));
+        assert!(state.show_transaction);
+        assert_eq!(
+            state.transaction,
+            vec![2, 0],
+            "time-ordered, whole token only"
+        );
+        assert_eq!(state.key_correlated_ids, HashSet::from([0, 2]));
+
+        state.filter_to_transaction();
+        assert_eq!(state.filtered_indices.len(), 2);
+        assert!(state.filter_description().contains("Key: req-42"));
+        assert!(state.select_entry_by_id(0));
+        assert!(
+            !state.select_entry_by_id(1),
+            "hidden by the transaction filter"
+        );
+
+        let mut tail = make_entry(4, 20);
+        tail.message = "done req-42".to_string().into();
+        state.entries.push(tail);
+        state.extend_filtered_for_range(4);
+        assert_eq!(state.transaction, vec![2, 0, 4]);
+
+        state.set_correlation_key(None);
+        assert!(state.transaction.is_empty() && state.key_correlated_ids.is_empty());
+    }
+
+    /// A clock rule shifts ingested entries, re-times loaded ones and
+    /// re-sorts the merged timeline; removing it restores parsed times.
+    #[test]
+    fn test_clock_rule_retimes_and_resorts() {
+        use crate::core::clock::{AssumedZone, ClockRule};
+        let mut state = AppState::new(vec![], false);
+        let mut entries: Vec<LogEntry> = (0..3).map(|i| make_entry(i, i as i64 * 10)).collect();
+        entries[1].source_file = PathBuf::from("/hosts/sql/errorlog").into();
+        state.entries = entries;
+        state.apply_filters();
+
+        let mut input = ClockRuleInput::for_file(Path::new("/hosts/sql/errorlog"), &[]);
+        input.whole_directory = true;
+        input.offset = "+30".to_string();
+        state.set_clock_rule(input.rule().unwrap());
+        assert_eq!(state.clock_rules[0].path, PathBuf::from("/hosts/sql"));
+        let order: Vec<u64> = state.entries.iter().map(|e| e.id).collect();
+        assert_eq!(
+            order,
+            vec![0, 2, 1],
+            "the SQL entry moved from +10s to +40s"
+        );
+
+        let mut tail = vec![make_entry(3, 0)];
+        tail[0].source_file = PathBuf::from("/hosts/sql/agent.log").into();
+        state.apply_clock_rules(&mut tail);
+        assert_eq!(tail[0].timestamp, make_entry(0, 30).timestamp);
+        assert_eq!(
+            state.clock_rule_descriptions(),
+            vec!["/hosts/sql: +00:00:30"]
+        );
+
+        state.set_clock_rule(ClockRule {
+            path: PathBuf::from("/hosts/sql"),
+            offset_secs: 0,
+            zone: None,
+        });
+        assert!(state.clock_rules.is_empty(), "identity rule removes");
+        assert_eq!(state.entries[1].timestamp, make_entry(1, 10).timestamp);
+
+        // A zone change cannot be re-timed: the file is re-parsed.
+        state
+            .discovered_files
+            .push(crate::core::model::DiscoveredFile {
+                path: PathBuf::from("/hosts/sql/errorlog"),
+                size: 1,
+                modified: None,
+                profile_id: Some("test".to_string()),
+                detection_confidence: 1.0,
+                is_large: false,
+                parsing_skipped: false,
+            });
+        state.set_clock_rule(ClockRule {
+            path: PathBuf::from("/hosts/sql"),
+            offset_secs: 0,
+            zone: Some(AssumedZone::Local),
+        });
+        assert_eq!(state.entries.len(), 2);
+        assert!(state.discovered_files[0].parsing_skipped);
+        assert_eq!(
+            state.pending_single_files,
+            Some(vec![PathBuf::from("/hosts/sql/errorlog")])
+        );
+        state.remove_clock_rule(Path::new("/hosts/sql"));
+
+        state.set_clock_rule(input.rule().unwrap());
+        state.clear();
+        assert_eq!(state.clock_rules.len(), 1, "rules survive clear()");
+        state.new_session();
+        assert!(state.clock_rules.is_empty());
+    }
+
+    /// Saving an editor draft validates it; a saved rule that fires during
+    /// tail pins the entry, flashes the status bar and writes the alert log.
+    #[test]
+    fn test_alert_rule_fires_pins_entry_and_logs() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut state = AppState::new(vec![], false);
+        state.alert_rules_path = Some(dir.path().join("alert_rules.toml"));
+        state.alert_log_path = Some(dir.path().join("alerts.log"));
+
+        state.alert_draft.name = "Failures".to_string();
+        state.save_alert_draft();
+        assert!(state.alert_draft_error.is_some(), "a query is required");
+        state.alert_draft.query = "severity>=error".to_string();
+        state.alert_draft.threshold = 2;
+        state.save_alert_draft();
+        assert!(state.alert_draft_error.is_none());
+        assert_eq!(state.alert_rules.len(), 1);
+        assert!(dir.path().join("alert_rules.toml").exists());
+
+        let batch: Vec<LogEntry> = (0..3)
+            .map(|i| {
+                let mut e = make_entry(i, i as i64);
+                e.severity = Severity::Error;
+                e
+            })
+            .collect();
+        state.evaluate_alerts(&batch);
+        assert_eq!(
+            state.bookmarks.get(&1).map(String::as_str),
+            Some("Alert: Failures")
+        );
+        assert!(state.alert_flash.is_some());
+        let log = std::fs::read_to_string(dir.path().join("alerts.log")).unwrap();
+        assert_eq!(log.lines().count(), 1);
+
+        state.set_alert_rule_enabled(0, false);
+        state.alert_flash = None;
+        state.evaluate_alerts(&batch);
+        assert!(state.alert_flash.is_none());
+    }
+
+    /// Opening a hand-written rule in the editor and saving it again keeps
+    /// arguments that contain spaces intact.
+    #[test]
+    fn test_alert_rule_command_survives_editor_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("alert_rules.toml");
+        std::fs::write(
+            &path,
+            "[[rule]]\nname = \"DB\"\nquery = \"timeout\"\ncommand = [\"notify-send\", \"DB alert\"]\n",
+        )
+        .unwrap();
+        let mut state = AppState::new(vec![], false);
+        state.alert_rules_path = Some(path.clone());
+        state.load_alert_rules();
+
+        state.edit_alert_rule(Some(0));
+        assert_eq!(state.alert_draft_command, "notify-send \"DB alert\"");
+        state.save_alert_draft();
+        assert!(state.alert_draft_error.is_none());
+        let expected = vec!["notify-send".to_string(), "DB alert".to_string()];
+        assert_eq!(state.alert_rules[0].command, expected);
+        assert_eq!(
+            crate::app::alerts::load(&path).unwrap()[0].command,
+            expected
+        );
+
+        state.edit_alert_rule(Some(0));
+        state.alert_draft_command = "notify-send \"unterminated".to_string();
+        state.save_alert_draft();
+        assert!(state.alert_draft_error.is_some());
+        assert_eq!(state.alert_rules[0].command, expected);
+    }
 }
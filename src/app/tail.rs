@@ -5,11 +5,31 @@
 //
 // Architecture:
 //   - `TailManager` lives on the UI thread; `run_tail_watcher` runs on a
-//     background thread polling each file for new content on a fixed interval.
+//     background thread that reads a file when the OS reports it changed
+//     (`app::fs_watch`), and re-checks every file on a fixed interval: the
+//     poll interval when change events are unavailable (network paths), a
+//     slow safety-net interval otherwise.
 //   - An `Arc<AtomicBool>` cancel flag allows the UI to stop the tail.
 //   - New entries are sent as `TailProgress::NewEntries` over an mpsc channel.
 //   - The UI thread polls the channel each frame (same pattern as ScanManager).
 //
+// Rotation: each file is followed through an open handle and identified by
+// device + inode (volume serial + file index on Windows).  When a different
+// file appears at the path (rename-create), the old handle is read to its
+// end before the new file is followed from its start.  When the file shrinks
+// or the bytes just before the read position change (copytruncate), the
+// sibling copy (`app.log.1`, `app.log-20240115`) holding those bytes is read
+// from the old position first.  Either way a `TailProgress::Rotated` carries
+// a marker entry for the timeline.
+//
+// Start modes: a file whose earlier content is not in the timeline can start
+// with a replay (`TailStart`): the last N lines, or the records stamped since
+// a given time.  The replay is found by reading back from the end of the file
+// and starts at a record boundary (`parser::starts_record`), so the first
+// replayed entry is never the tail of a stack trace.  Replayed entries are
+// delivered in batches of their own flagged `replay`, apart from content
+// appended after the start.
+//
 // Encoding: tail reads new bytes and decodes them as lossy UTF-8.
 // UTF-16 encoded files (Windows system logs) are generally not appended
 // line-by-line by the OS, so this limitation is acceptable and documented.
@@ -17,21 +37,33 @@
 // Rule 11 compliance:
 //   - File read/stat errors on a single file are non-fatal: logged as warnings,
 //     a FileError message is sent, and the watcher continues to the next file.
-//   - Truncated/rotated files (size < last offset) are handled by resetting the
-//     offset to 0 so the rewritten content is picked up cleanly.
-//   - The poll loop sleeps in small sub-intervals so cancel is checked promptly
-//     (within TAIL_CANCEL_CHECK_INTERVAL_MS of the cancel flag being set).
+//   - Truncated/rotated files are followed from offset 0 once the rotated
+//     content has been drained; if no copy is found the rotation is still
+//     reported, noting that unread lines may be missing.
+//   - Rotated content is drained MAX_TAIL_READ_BYTES_PER_TICK at a time, and
+//     at most MAX_TAIL_ROTATION_CANDIDATES siblings are examined to find it.
+//   - The loop waits for changes in small sub-intervals so cancel is checked
+//     promptly (within TAIL_CANCEL_CHECK_INTERVAL_MS of the flag being set).
 //   - MAX_TAIL_READ_BYTES_PER_TICK caps the bytes consumed per file per tick to
 //     prevent a burst of large writes from stalling the entire poll loop.
+//   - A replay looks back at most MAX_TAIL_REPLAY_BYTES from the end of a file.
 
-use crate::core::model::{FormatProfile, TailProgress};
+use crate::app::fs_watch::{FsChanges, FsWatcher};
+use crate::core::archive::ArchiveKind;
+use crate::core::clock::ClockRule;
+use crate::core::model::{
+    FormatProfile, LogEntry, MessageText, RotationKind, Severity, TailProgress,
+};
 use crate::core::parser::{self, ParseConfig};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc};
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
 // =============================================================================
 // Constants (Rule 11: named bounds — defined in util::constants and re-used
@@ -39,7 +71,9 @@ use std::time::Duration;
 // =============================================================================
 
 use crate::util::constants::{
-    MAX_TAIL_PARTIAL_BYTES, MAX_TAIL_READ_BYTES_PER_TICK, TAIL_CANCEL_CHECK_INTERVAL_MS,
+    MAX_TAIL_PARTIAL_BYTES, MAX_TAIL_READ_BYTES_PER_TICK, MAX_TAIL_REPLAY_BYTES,
+    MAX_TAIL_REPLAY_MINUTES, MAX_TAIL_ROTATION_CANDIDATES, TAIL_CANCEL_CHECK_INTERVAL_MS,
+    TAIL_ROTATION_FINGERPRINT_BYTES,
 };
 
 // =============================================================================
@@ -60,11 +94,67 @@ pub struct TailFileInfo {
     /// completing and Live Tail being activated are picked up on the first poll
     /// — eliminating the "gap" where new entries are silently skipped.
     ///
-    /// `None` falls back to the old behaviour of seeking to the current EOF at
-    /// tail-activation time (safe but lossy when tailing is started late).
+    /// `None` means none of the file's content is loaded: reading starts
+    /// where the tail's `TailStart` says -- the current EOF at
+    /// tail-activation time for `TailStart::End`.  `Some` always wins over
+    /// the start mode, so content already in the timeline is never replayed.
     pub initial_offset: Option<u64>,
 }
 
+/// Where tailing starts in files without an `initial_offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TailStart {
+    /// At the current end: only content written from now on.
+    End,
+    /// The last N lines, widened back to the start of the record the first
+    /// of them belongs to.
+    LastLines(usize),
+    /// The records stamped at or after this instant.
+    Since(DateTime<Utc>),
+}
+
+/// The "Start from" Live Tail option, saved with the session.  `Minutes` is
+/// relative, so a saved "last 15 minutes" means 15 minutes before each tail
+/// is started; `Since` is a fixed instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TailReplay {
+    /// No replay: new content only.
+    #[default]
+    Off,
+    /// Replay the last N lines.
+    Lines(usize),
+    /// Replay the last N minutes.
+    Minutes(u64),
+    /// Replay the records stamped at or after a fixed time.
+    Since(DateTime<Utc>),
+}
+
+impl TailReplay {
+    /// The start mode for a tail started at `now`.
+    pub fn start_at(self, now: DateTime<Utc>) -> TailStart {
+        match self {
+            Self::Off => TailStart::End,
+            Self::Lines(n) => TailStart::LastLines(n),
+            // Clamped: a hand-edited session cannot overflow the subtraction.
+            Self::Minutes(m) => TailStart::Since(
+                now - chrono::Duration::minutes(m.min(MAX_TAIL_REPLAY_MINUTES) as i64),
+            ),
+            Self::Since(at) => TailStart::Since(at),
+        }
+    }
+
+    /// Short label for the Options dialog.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "End of file (new lines only)",
+            Self::Lines(_) => "Last N lines",
+            Self::Minutes(_) => "Last N minutes",
+            Self::Since(_) => "Since a time",
+        }
+    }
+}
+
 // =============================================================================
 // TailManager
 // =============================================================================
@@ -78,6 +168,8 @@ pub struct TailManager {
     pub progress_rx: Option<mpsc::Receiver<TailProgress>>,
     /// Cancel flag shared with the background thread.
     cancel_flag: Option<Arc<AtomicBool>>,
+    /// Parsing limits and timezone overrides handed to each tail thread.
+    parse_config: ParseConfig,
 }
 
 impl TailManager {
@@ -85,22 +177,36 @@ impl TailManager {
         Self {
             progress_rx: None,
             cancel_flag: None,
+            parse_config: ParseConfig::default(),
+        }
+    }
+
+    /// Use `rules` for the timezone overrides of the next tail started.
+    pub fn set_clock_rules(&mut self, rules: &[ClockRule]) {
+        if self.parse_config.clock_rules != rules {
+            self.parse_config.clock_rules = rules.to_vec();
         }
     }
 
-    /// Start tailing the given files from their *current end* (new content only).
+    /// Start tailing the given files.
     ///
-    /// Spawns a background poll thread immediately. If a tail is already running
+    /// Each file continues from its `initial_offset`; files without one start
+    /// at `start`: their current end (new content only) or a replay of recent
+    /// content.
+    ///
+    /// Spawns a background watch thread immediately. If a tail is already running
     /// it is stopped first.
     ///
     /// `entry_id_start` is the next available monotonic entry ID so that tail
     /// entries do not collide with IDs assigned during the initial scan.
     /// `poll_interval_ms` controls how often each watched file is checked for
-    /// new content.  Lower values give faster tail updates at the cost of more
-    /// I/O; higher values reduce CPU/disk overhead.
+    /// new content when the OS cannot report changes (network paths); with
+    /// native change events new lines arrive as they are written.  Lower
+    /// values give faster polled updates at the cost of more I/O.
     pub fn start_tail(
         &mut self,
         files: Vec<TailFileInfo>,
+        start: TailStart,
         entry_id_start: u64,
         poll_interval_ms: u64,
     ) {
@@ -113,11 +219,25 @@ impl TailManager {
         self.cancel_flag = Some(Arc::clone(&cancel));
 
         let file_count = files.len();
+        let parse_config = self.parse_config.clone();
         std::thread::spawn(move || {
-            run_tail_watcher(files, entry_id_start, tx, cancel, poll_interval_ms);
+            run_tail_watcher(
+                files,
+                start,
+                entry_id_start,
+                parse_config,
+                tx,
+                cancel,
+                poll_interval_ms,
+            );
         });
 
-        tracing::info!(files = file_count, poll_interval_ms, "Live tail started");
+        tracing::info!(
+            files = file_count,
+            ?start,
+            poll_interval_ms,
+            "Live tail started"
+        );
     }
 
     /// Request the background tail thread to stop.
@@ -166,13 +286,32 @@ impl Default for TailManager {
 // Per-file state (private to the background thread)
 // =============================================================================
 
+/// Identity of an open file: device (volume) and inode (file index).
+///
+/// Survives a rename, so a rotated file is told apart from the new file
+/// created at its old path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileId(u64, u64);
+
 struct FileState {
     path: PathBuf,
     profile: FormatProfile,
+    /// Handle on the file being followed.  Reads go through it rather than
+    /// the path, so a file renamed away by rotation can still be finished.
+    /// `None` until the path first opens.
+    file: Option<File>,
+    /// Identity of `file`; `None` where the platform cannot tell (the
+    /// fingerprint below still detects rotation there).
+    identity: Option<FileId>,
     /// Byte position of the last byte examined in the file.
     /// Always advances by exactly the number of bytes read each tick,
     /// whether those bytes produced complete lines or not.
     offset: u64,
+    /// End of the replayed history (the file size when the tail started).
+    /// Reads stop here until it is reached, so replayed and new content are
+    /// never delivered in the same `NewEntries` batch.  Equal to the initial
+    /// offset when nothing is replayed.
+    replay_end: u64,
     /// Raw bytes from the most recent read that followed the final newline --
     /// they represent an in-progress (incomplete) log line. Prepended to the
     /// next tick's raw bytes before searching for newlines.
@@ -181,17 +320,473 @@ struct FileState {
     /// split across two reads are decoded correctly at the line boundary
     /// rather than being replaced with U+FFFD per fragment (Bug fix).
     partial: Vec<u8>,
+    /// The last TAIL_ROTATION_FINGERPRINT_BYTES bytes before `offset`.
+    /// Re-read in front of every read: a mismatch means the content was
+    /// replaced in place.  Also identifies the copy made by copytruncate.
+    fingerprint: Vec<u8>,
+    /// Rotated predecessor still being read before the new file is followed.
+    draining: Option<Draining>,
+}
+
+/// A rotated file whose unread lines are delivered before the tail moves on
+/// to the new file.
+struct Draining {
+    /// `None` when the rotated content could not be found (copytruncate
+    /// with no matching copy): the rotation is reported with nothing drained.
+    file: Option<File>,
+    offset: u64,
+    kind: RotationKind,
+    /// Where the rotated content now lives, when found.
+    predecessor: Option<PathBuf>,
+    /// Entries delivered from it so far, reported in the rotation marker.
+    entries: usize,
+}
+
+impl FileState {
+    /// Open `info.path` and position at its initial offset.
+    ///
+    /// Offset priority:
+    ///   1. `initial_offset` supplied by the caller (the file size at scan-end).
+    ///      Using the scan-end size means the first poll tick catches any bytes
+    ///      appended between scan completion and tail activation — the "gap".
+    ///   2. Fallback: the replay start for `start`, or the current EOF for
+    ///      `TailStart::End` (stat the file now).  Used when none of the
+    ///      file's content is loaded (e.g. a directory opened with `--tail`).
+    fn new(info: TailFileInfo, start: TailStart, parse_config: &ParseConfig) -> Self {
+        let mut file = File::open(&info.path).ok();
+        let identity = file.as_ref().and_then(file_id);
+        let (offset, replay_end) = match (info.initial_offset, file.as_mut()) {
+            (Some(off), _) => (off, off),
+            (None, None) => (0, 0),
+            (None, Some(f)) => {
+                let size = f.metadata().map_or(0, |m| m.len());
+                match replay_offset(f, size, &info.path, &info.profile, parse_config, start) {
+                    Ok(off) => (off, size),
+                    Err(e) => {
+                        tracing::warn!(
+                            file = %info.path.display(),
+                            error = %e,
+                            "Tail: cannot read back for replay -- starting at the end"
+                        );
+                        (size, size)
+                    }
+                }
+            }
+        };
+        // Seed the fingerprint so a rotation before the first read is
+        // recognised too.
+        let back = offset.min(TAIL_ROTATION_FINGERPRINT_BYTES as u64);
+        let fingerprint = file
+            .as_mut()
+            .and_then(|f| read_from(f, offset - back, back as usize).ok())
+            .filter(|bytes| bytes.len() as u64 == back)
+            .unwrap_or_default();
+        tracing::debug!(
+            file = %info.path.display(),
+            offset,
+            from_scan = info.initial_offset.is_some(),
+            "Tail: seeding initial offset"
+        );
+        Self {
+            path: info.path,
+            profile: info.profile,
+            file,
+            identity,
+            offset,
+            replay_end,
+            partial: Vec::new(),
+            fingerprint,
+            draining: None,
+        }
+    }
+
+    /// Check the file once, appending the resulting messages to `out`.
+    ///
+    /// Returns `true` when unread bytes remain -- the read hit
+    /// MAX_TAIL_READ_BYTES_PER_TICK or a rotated file is still draining -- so
+    /// the caller checks it again without waiting for a change event.
+    fn poll(
+        &mut self,
+        parse_config: &ParseConfig,
+        next_id: &mut u64,
+        out: &mut Vec<TailProgress>,
+    ) -> bool {
+        if self.draining.is_some() && self.drain(parse_config, next_id, out) {
+            return true;
+        }
+
+        // ---------------------------------------------------------------------
+        // 1. Open whatever is at the path now.  A different identity means
+        //    rename-create rotation: finish the old file first.
+        // ---------------------------------------------------------------------
+        match File::open(&self.path) {
+            Ok(current) => {
+                let id = file_id(&current);
+                match (self.identity, id) {
+                    (Some(old), Some(new)) if old != new && self.file.is_some() => {
+                        self.start_rename_drain(current, id);
+                        return true;
+                    }
+                    // Same file: keep the handle already open.
+                    (Some(_), Some(_)) if self.file.is_some() => {}
+                    _ => {
+                        self.file = Some(current);
+                        self.identity = id;
+                    }
+                }
+            }
+            Err(e) if self.file.is_none() => {
+                tracing::warn!(file = %self.path.display(), error = %e, "Tail: open error");
+                out.push(TailProgress::FileError {
+                    path: self.path.clone(),
+                    message: format!("Cannot open: {e}"),
+                });
+                return false;
+            }
+            // Renamed or removed mid-rotation: keep reading the open handle
+            // until a new file appears at the path.
+            Err(_) => {}
+        }
+        let Some(file) = self.file.as_mut() else {
+            return false;
+        };
+
+        // ---------------------------------------------------------------------
+        // 2. Check current file size and last-modified time.
+        //    Both come from a single metadata() call to avoid TOCTOU.
+        // ---------------------------------------------------------------------
+        let (current_size, file_mtime) = match file.metadata() {
+            Ok(m) => (m.len(), m.modified().ok().map(Into::into)),
+            Err(e) => {
+                tracing::warn!(file = %self.path.display(), error = %e, "Tail: stat error");
+                out.push(TailProgress::FileError {
+                    path: self.path.clone(),
+                    message: format!("Cannot stat: {e}"),
+                });
+                return false;
+            }
+        };
+
+        // ---------------------------------------------------------------------
+        // 3. Truncated in place (copytruncate), or nothing new.
+        // ---------------------------------------------------------------------
+        if current_size < self.offset {
+            self.start_truncate_drain();
+            return true;
+        }
+        if current_size == self.offset {
+            return false;
+        }
+
+        // ---------------------------------------------------------------------
+        // 4. Read new bytes (capped per tick), preceded by the fingerprint
+        //    bytes: if those changed, the file was rewritten in place.
+        // ---------------------------------------------------------------------
+        let back = self.fingerprint.len();
+        let replay = self.offset < self.replay_end;
+        let read_end = if replay {
+            self.replay_end.min(current_size)
+        } else {
+            current_size
+        };
+        let bytes_available = usize::try_from(read_end - self.offset).unwrap_or(usize::MAX);
+        let read_limit = bytes_available.min(MAX_TAIL_READ_BYTES_PER_TICK);
+        let mut new_bytes = match read_from(file, self.offset - back as u64, back + read_limit) {
+            Ok(b) => b,
+            Err(e) => {
+                tracing::warn!(file = %self.path.display(), error = %e, "Tail: read error");
+                out.push(TailProgress::FileError {
+                    path: self.path.clone(),
+                    message: format!("Read error: {e}"),
+                });
+                return false;
+            }
+        };
+        if new_bytes.get(..back) != Some(&self.fingerprint[..]) {
+            self.start_truncate_drain();
+            return true;
+        }
+        new_bytes.drain(..back);
+        if new_bytes.is_empty() {
+            return false;
+        }
+
+        // Advance offset unconditionally -- we have consumed these bytes
+        // whether they produce complete lines or not.
+        self.offset += new_bytes.len() as u64;
+        remember_tail(&mut self.fingerprint, &new_bytes);
+
+        self.ingest(&new_bytes, file_mtime, replay, parse_config, next_id, out);
+        self.offset < current_size
+    }
+
+    /// A new file is at the path: drain the old handle, then follow the new
+    /// file from its start.
+    fn start_rename_drain(&mut self, current: File, id: Option<FileId>) {
+        let predecessor = self.identity.and_then(|old| find_renamed(&self.path, old));
+        tracing::info!(
+            file = %self.path.display(),
+            renamed_to = ?predecessor,
+            offset = self.offset,
+            "Tail: file renamed by rotation -- draining it before following the new file"
+        );
+        self.draining = Some(Draining {
+            file: self.file.replace(current),
+            offset: self.offset,
+            kind: RotationKind::Renamed,
+            predecessor,
+            entries: 0,
+        });
+        self.identity = id;
+        self.offset = 0;
+        self.replay_end = 0;
+        self.fingerprint.clear();
+    }
+
+    /// The file shrank or changed in place: drain the copy holding its
+    /// unread lines when one can be found, then follow it from the start.
+    fn start_truncate_drain(&mut self) {
+        let copy = find_truncated_copy(&self.path, self.offset, &self.fingerprint);
+        tracing::info!(
+            file = %self.path.display(),
+            copy = ?copy.as_ref().map(|(p, _)| p),
+            old_offset = self.offset,
+            "Tail: file truncated in place -- resetting offset to 0"
+        );
+        let (predecessor, file) = copy.map_or((None, None), |(p, f)| (Some(p), Some(f)));
+        if file.is_none() {
+            // Its continuation is gone with the truncated content.
+            self.partial.clear();
+        }
+        self.draining = Some(Draining {
+            file,
+            offset: self.offset,
+            kind: RotationKind::Truncated,
+            predecessor,
+            entries: 0,
+        });
+        self.offset = 0;
+        self.replay_end = 0;
+        self.fingerprint.clear();
+    }
+
+    /// Deliver the next chunk of the rotated file.  Returns `true` while
+    /// bytes remain; once it is exhausted, flushes the last line and reports
+    /// the rotation.
+    fn drain(
+        &mut self,
+        parse_config: &ParseConfig,
+        next_id: &mut u64,
+        out: &mut Vec<TailProgress>,
+    ) -> bool {
+        let Some(mut draining) = self.draining.take() else {
+            return false;
+        };
+        if let Some(file) = draining.file.as_mut() {
+            let (size, mtime) = match file.metadata() {
+                Ok(m) => (m.len(), m.modified().ok().map(Into::into)),
+                Err(_) => (draining.offset, None),
+            };
+            let bytes_available =
+                usize::try_from(size.saturating_sub(draining.offset)).unwrap_or(usize::MAX);
+            let read_limit = bytes_available.min(MAX_TAIL_READ_BYTES_PER_TICK);
+            if read_limit > 0 {
+                match read_from(file, draining.offset, read_limit) {
+                    Ok(bytes) if !bytes.is_empty() => {
+                        draining.offset += bytes.len() as u64;
+                        draining.entries +=
+                            self.ingest(&bytes, mtime, false, parse_config, next_id, out);
+                        self.draining = Some(draining);
+                        return true;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        tracing::warn!(
+                            file = %self.path.display(),
+                            error = %e,
+                            "Tail: cannot finish reading rotated file"
+                        );
+                    }
+                }
+            }
+        }
+
+        // The rotated file's last line may lack its trailing newline.
+        if !self.partial.is_empty() {
+            self.partial.push(b'\n');
+            draining.entries += self.ingest(&[], None, false, parse_config, next_id, out);
+        }
+        let marker = self.rotation_marker(*next_id, &draining);
+        *next_id += 1;
+        out.push(TailProgress::Rotated {
+            path: self.path.clone(),
+            kind: draining.kind,
+            predecessor: draining.predecessor,
+            marker,
+        });
+        false
+    }
+
+    /// Parse `bytes` appended to the partial-line buffer and queue the
+    /// resulting entries, flagged as `replay` history or new content.
+    /// Returns the number of entries queued.
+    fn ingest(
+        &mut self,
+        bytes: &[u8],
+        file_mtime: Option<DateTime<Utc>>,
+        replay: bool,
+        parse_config: &ParseConfig,
+        next_id: &mut u64,
+        out: &mut Vec<TailProgress>,
+    ) -> usize {
+        // ---------------------------------------------------------------------
+        // 5. Append raw bytes to the partial-line buffer.
+        //    Kept as raw bytes so multi-byte UTF-8 sequences split across
+        //    two reads are decoded correctly at the line boundary (Bug fix:
+        //    previously decoded each fragment independently with lossy
+        //    UTF-8, replacing split code points with U+FFFD).
+        // ---------------------------------------------------------------------
+        self.partial.extend_from_slice(bytes);
+
+        // Bound the partial buffer (Rule 11 -- resource bounds on growing
+        // collections).  A file that never emits newlines -- binary content,
+        // an extremely long structured-log line, or a non-text file opened
+        // by mistake -- would otherwise cause the buffer to grow at
+        // MAX_TAIL_READ_BYTES_PER_TICK per tick without any upper limit,
+        // eventually exhausting heap.  When the bound is hit we discard the
+        // stale fragment, emit a warning, and skip to the next file; the
+        // watcher remains alive and will resume on the next newline.
+        if self.partial.len() > MAX_TAIL_PARTIAL_BYTES {
+            tracing::warn!(
+                file = %self.path.display(),
+                partial_bytes = self.partial.len(),
+                limit = MAX_TAIL_PARTIAL_BYTES,
+                "Tail: partial buffer exceeded limit -- discarding fragment \
+                 (binary content or extremely long line?)"
+            );
+            out.push(TailProgress::FileError {
+                path: self.path.clone(),
+                message: format!(
+                    "Partial-line buffer exceeded {MAX_TAIL_PARTIAL_BYTES}B; \
+                     content may be binary or contain very long lines."
+                ),
+            });
+            self.partial.clear();
+            return 0;
+        }
+
+        // ---------------------------------------------------------------------
+        // 6. Split at the last newline byte.
+        //    Everything up to and including the final '\n' can be parsed.
+        //    Bytes after the final '\n' are an in-progress line -- carry forward.
+        //    Decode to UTF-8 only *after* splitting so that multi-byte
+        //    sequences that straddle a read boundary are kept intact.
+        // ---------------------------------------------------------------------
+        let Some(complete_text) = take_complete_text(&mut self.partial) else {
+            // No newline yet -- the entire buffer is an in-progress line.
+            return 0;
+        };
+
+        // ---------------------------------------------------------------------
+        // 7. Parse complete lines through the file's format profile.
+        // ---------------------------------------------------------------------
+        let mut result = parser::parse_content(
+            &complete_text,
+            &self.path,
+            &self.profile,
+            parse_config,
+            *next_id,
+        );
+
+        if result.entries.is_empty() {
+            return 0;
+        }
+
+        // Stamp entries with the file's OS mtime and back-fill a parsed
+        // timestamp if the profile has no timestamp capture group.
+        //
+        // file_modified: used by the time-range filter (e.g. last 15 min).
+        //   Files being actively written have a fresh mtime, so they always
+        //   pass a relative-time filter regardless of log line content.
+        //
+        // timestamp: back-filled with now() only when the profile produced
+        //   None (plain-text fallback). This keeps timeline sort order
+        //   correct and prevents tail entries from sinking to the bottom.
+        let now = Utc::now();
+        let effective_mtime = file_mtime.unwrap_or(now);
+        for entry in &mut result.entries {
+            entry.file_modified = Some(effective_mtime);
+            if entry.timestamp.is_none() {
+                entry.timestamp = Some(now);
+            }
+        }
+
+        tracing::debug!(
+            file = %self.path.display(),
+            count = result.entries.len(),
+            "Tail: new entries"
+        );
+
+        let count = result.entries.len();
+        *next_id += count as u64;
+        out.push(TailProgress::NewEntries {
+            entries: result.entries,
+            replay,
+        });
+        count
+    }
+
+    /// Synthetic entry recording a finished rotation in the timeline.
+    fn rotation_marker(&self, id: u64, draining: &Draining) -> LogEntry {
+        let name = |p: &Path| {
+            p.file_name().map_or_else(
+                || p.display().to_string(),
+                |n| n.to_string_lossy().into_owned(),
+            )
+        };
+        let moved = match (draining.kind, &draining.predecessor) {
+            (RotationKind::Renamed, Some(p)) => format!("renamed to {}", name(p)),
+            (RotationKind::Renamed, None) => "renamed away".to_string(),
+            (RotationKind::Truncated, Some(p)) => format!("copied to {} and truncated", name(p)),
+            (RotationKind::Truncated, None) => {
+                "truncated in place (no copy found; unread lines may be missing)".to_string()
+            }
+        };
+        let text = format!(
+            "Log rotated: {} {moved}; {} entries read from the rotated file, now following the new file",
+            name(&self.path),
+            draining.entries,
+        );
+        let now = Utc::now();
+        LogEntry {
+            id,
+            timestamp: Some(now),
+            severity: Severity::Info,
+            source_file: Arc::from(self.path.as_path()),
+            line_number: 0,
+            thread: None,
+            component: None,
+            message: MessageText::slice_of(&text, 0..text.len()),
+            raw_text: text,
+            profile_id: Arc::from(self.profile.id.as_str()),
+            fields: BTreeMap::from([("rotation".to_string(), draining.kind.label().to_string())]),
+            file_modified: Some(now),
+        }
+    }
 }
 
 // =============================================================================
 // Background tail watcher
 // =============================================================================
 
-/// Background poll loop. Checks each file every `poll_interval_ms` ms for
-/// new content and sends parsed entries back to the UI via `tx`.
+/// Background watch loop. Reads each file when it is reported changed (and
+/// every file on the full-check interval) and sends parsed entries back to
+/// the UI via `tx`.
 fn run_tail_watcher(
     files: Vec<TailFileInfo>,
+    start: TailStart,
     entry_id_start: u64,
+    parse_config: ParseConfig,
     tx: mpsc::Sender<TailProgress>,
     cancel: Arc<AtomicBool>,
     poll_interval_ms: u64,
@@ -205,36 +800,9 @@ fn run_tail_watcher(
         };
     }
 
-    let parse_config = ParseConfig::default();
-
-    // Initialise per-file state.
-    //
-    // Offset priority:
-    //   1. `initial_offset` supplied by the caller (the file size at scan-end).
-    //      Using the scan-end size means the first poll tick catches any bytes
-    //      appended between scan completion and tail activation — the "gap".
-    //   2. Fallback: current EOF (stat the file now).  Used when no scan-end
-    //      offset is available (e.g. files opened via Open Log(s)...).
     let mut states: Vec<FileState> = files
         .into_iter()
-        .map(|info| {
-            let offset = match info.initial_offset {
-                Some(off) => off,
-                None => std::fs::metadata(&info.path).map(|m| m.len()).unwrap_or(0),
-            };
-            tracing::debug!(
-                file = %info.path.display(),
-                offset,
-                from_scan = info.initial_offset.is_some(),
-                "Tail: seeding initial offset"
-            );
-            FileState {
-                path: info.path,
-                profile: info.profile,
-                offset,
-                partial: Vec::new(),
-            }
-        })
+        .map(|info| FileState::new(info, start, &parse_config))
         .collect();
 
     let file_count = states.len();
@@ -243,205 +811,276 @@ fn run_tail_watcher(
     // Single monotonically increasing ID counter shared across all watched files.
     let mut next_id = entry_id_start;
 
-    // Sub-divide each poll interval into cancel-check slices.
-    // Total sleep = poll_interval_ms; wake every TAIL_CANCEL_CHECK_INTERVAL_MS.
-    let slices = (poll_interval_ms / TAIL_CANCEL_CHECK_INTERVAL_MS).max(1);
+    // Watch the files' directories rather than the files, so a file that is
+    // deleted and recreated keeps being reported.  Events are matched back
+    // to files by path as given and by canonical path (FSEvents reports the
+    // latter).
+    let watch_dirs: Vec<PathBuf> = states
+        .iter()
+        .map(|s| match s.path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let mut watcher = FsWatcher::new(&watch_dirs, false);
+    let mut by_path: HashMap<PathBuf, usize> = HashMap::new();
+    for (idx, state) in states.iter().enumerate() {
+        by_path.insert(state.path.clone(), idx);
+        if let Ok(canonical) = std::fs::canonicalize(&state.path) {
+            by_path.insert(canonical, idx);
+        }
+    }
+    tracing::debug!(native = watcher.is_native(), "Tail: change watcher ready");
+
+    // Every file is checked each poll interval when polling, and every
+    // FS_WATCH_RESCAN_INTERVAL_MS as a safety net when change events arrive
+    // natively.  Files with unread bytes left (the read hit
+    // MAX_TAIL_READ_BYTES_PER_TICK, or a rotated file is draining) are
+    // checked again on the next wake-up without waiting for an event.
+    let poll_interval = Duration::from_millis(poll_interval_ms);
+    let slice = Duration::from_millis(TAIL_CANCEL_CHECK_INTERVAL_MS);
+    let mut next_full_check = Instant::now() + poll_interval;
+    let mut backlog: HashSet<usize> = HashSet::new();
+    let mut out: Vec<TailProgress> = Vec::new();
 
     loop {
-        // Interruptible sleep: check cancel flag between slices.
-        for _ in 0..slices {
-            std::thread::sleep(Duration::from_millis(TAIL_CANCEL_CHECK_INTERVAL_MS));
-            if cancel.load(Ordering::SeqCst) {
-                send!(TailProgress::Stopped);
-                return;
-            }
+        // Interruptible wait: returns within one cancel-check slice.
+        let changes = watcher.wait(if backlog.is_empty() {
+            slice
+        } else {
+            Duration::ZERO
+        });
+        if cancel.load(Ordering::SeqCst) {
+            send!(TailProgress::Stopped);
+            return;
         }
 
-        for state in &mut states {
+        let mut due: Vec<usize> = std::mem::take(&mut backlog).into_iter().collect();
+        if Instant::now() >= next_full_check || matches!(changes, Some(FsChanges::Rescan)) {
+            next_full_check = Instant::now() + watcher.full_check_interval(poll_interval);
+            due = (0..states.len()).collect();
+        } else if let Some(FsChanges::Paths(paths)) = changes {
+            due.extend(paths.iter().filter_map(|p| by_path.get(p).copied()));
+            due.sort_unstable();
+            due.dedup();
+        }
+
+        for idx in due {
             if cancel.load(Ordering::SeqCst) {
                 send!(TailProgress::Stopped);
                 return;
             }
-
-            // -----------------------------------------------------------------
-            // 1. Check current file size and last-modified time.
-            //    Both come from a single metadata() call to avoid TOCTOU.
-            // -----------------------------------------------------------------
-            let meta_result = std::fs::metadata(&state.path);
-            let (current_size, file_mtime) = match meta_result {
-                Ok(m) => {
-                    let size = m.len();
-                    let mtime: Option<chrono::DateTime<Utc>> = m.modified().ok().map(|t| t.into());
-                    (size, mtime)
-                }
-                Err(e) => {
-                    let msg = format!("Cannot stat: {e}");
-                    tracing::warn!(file = %state.path.display(), error = %e, "Tail: stat error");
-                    send!(TailProgress::FileError {
-                        path: state.path.clone(),
-                        message: msg,
-                    });
-                    continue;
-                }
-            };
-
-            // -----------------------------------------------------------------
-            // 2. Handle rotation / truncation.
-            // -----------------------------------------------------------------
-            if current_size < state.offset {
-                tracing::info!(
-                    file = %state.path.display(),
-                    old_offset = state.offset,
-                    new_size = current_size,
-                    "Tail: file truncated or rotated -- resetting offset to 0"
-                );
-                state.offset = 0;
-                state.partial.clear();
+            if states[idx].poll(&parse_config, &mut next_id, &mut out) {
+                backlog.insert(idx);
             }
-
-            // -----------------------------------------------------------------
-            // 3. Nothing new.
-            // -----------------------------------------------------------------
-            if current_size == state.offset {
-                continue;
+            for msg in out.drain(..) {
+                send!(msg);
             }
+        }
+    }
+}
 
-            // -----------------------------------------------------------------
-            // 4. Read new bytes (capped per tick).
-            // -----------------------------------------------------------------
-            let bytes_available =
-                usize::try_from(current_size - state.offset).unwrap_or(usize::MAX);
-            let read_limit = bytes_available.min(MAX_TAIL_READ_BYTES_PER_TICK);
+/// Byte offset in `file` (of length `size`) at which tailing with `start`
+/// begins.
+///
+/// Reads back at most MAX_TAIL_REPLAY_BYTES and only starts at a line where
+/// `parser::starts_record` says a record begins:
+///   - `LastLines(n)`: the record holding the Nth-last line.  If that record
+///     began before the window read, the next record instead.
+///   - `Since(t)`: the earliest record such that every record from it on is
+///     stamped at or after `t` (or has no timestamp).
+fn replay_offset(
+    file: &mut File,
+    size: u64,
+    path: &Path,
+    profile: &FormatProfile,
+    parse_config: &ParseConfig,
+    start: TailStart,
+) -> std::io::Result<u64> {
+    if start == TailStart::End || start == TailStart::LastLines(0) {
+        return Ok(size);
+    }
+    let window_start = size.saturating_sub(MAX_TAIL_REPLAY_BYTES as u64);
+    let window = read_from(file, window_start, (size - window_start) as usize)?;
 
-            let read_result = read_bytes_at(&state.path, state.offset, read_limit);
-            let new_bytes = match read_result {
-                Ok(b) => b,
-                Err(e) => {
-                    let msg = format!("Read error: {e}");
-                    tracing::warn!(file = %state.path.display(), error = %e, "Tail: read error");
-                    send!(TailProgress::FileError {
-                        path: state.path.clone(),
-                        message: msg,
-                    });
-                    continue;
-                }
-            };
+    // Complete lines in the window with their offsets in it.  The first line
+    // is cut short unless the window starts the file.
+    let mut first = 0;
+    if window_start > 0 {
+        match window.iter().position(|&b| b == b'\n') {
+            Some(nl) => first = nl + 1,
+            None => return Ok(size),
+        }
+    }
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    let mut pos = first;
+    while pos < window.len() {
+        let end = window[pos..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(window.len(), |i| pos + i + 1);
+        let text = String::from_utf8_lossy(&window[pos..end]);
+        lines.push((pos, text.trim_end_matches(['\n', '\r']).to_string()));
+        pos = end;
+    }
 
-            let n = new_bytes.len();
-            if n == 0 {
-                continue;
-            }
+    let mut record_starts: Vec<usize> = Vec::new();
+    let mut prev = "";
+    for (idx, (_, line)) in lines.iter().enumerate() {
+        if parser::starts_record(profile, prev, line) {
+            record_starts.push(idx);
+        }
+        if !line.trim().is_empty() {
+            prev = line;
+        }
+    }
 
-            // Advance offset unconditionally -- we have consumed these bytes
-            // whether they produce complete lines or not.
-            state.offset += n as u64;
-
-            // -----------------------------------------------------------------
-            // 5. Append raw bytes to the partial-line buffer.
-            //    Kept as raw bytes so multi-byte UTF-8 sequences split across
-            //    two reads are decoded correctly at the line boundary (Bug fix:
-            //    previously decoded each fragment independently with lossy
-            //    UTF-8, replacing split code points with U+FFFD).
-            // -----------------------------------------------------------------
-            state.partial.extend_from_slice(&new_bytes);
-
-            // Bound the partial buffer (Rule 11 -- resource bounds on growing
-            // collections).  A file that never emits newlines -- binary content,
-            // an extremely long structured-log line, or a non-text file opened
-            // by mistake -- would otherwise cause the buffer to grow at
-            // MAX_TAIL_READ_BYTES_PER_TICK per tick without any upper limit,
-            // eventually exhausting heap.  When the bound is hit we discard the
-            // stale fragment, emit a warning, and skip to the next file; the
-            // watcher remains alive and will resume on the next newline.
-            if state.partial.len() > MAX_TAIL_PARTIAL_BYTES {
-                tracing::warn!(
-                    file = %state.path.display(),
-                    partial_bytes = state.partial.len(),
-                    limit = MAX_TAIL_PARTIAL_BYTES,
-                    "Tail: partial buffer exceeded limit -- discarding fragment \
-                     (binary content or extremely long line?)"
-                );
-                send!(TailProgress::FileError {
-                    path: state.path.clone(),
-                    message: format!(
-                        "Partial-line buffer exceeded {MAX_TAIL_PARTIAL_BYTES}B; \
-                         content may be binary or contain very long lines."
-                    ),
-                });
-                state.partial.clear();
-                continue;
+    let start_line = match start {
+        TailStart::End => None,
+        TailStart::LastLines(n) => {
+            let target = lines.len().saturating_sub(n);
+            record_starts
+                .iter()
+                .rev()
+                .find(|&&idx| idx <= target)
+                .or_else(|| record_starts.iter().find(|&&idx| idx > target))
+                .copied()
+        }
+        TailStart::Since(cutoff) => {
+            // Unstamped records only count next to stamped ones: a file with
+            // no timestamps at all has nothing to replay.
+            let mut earliest = None;
+            let mut any_stamped = false;
+            for (i, &idx) in record_starts.iter().enumerate().rev() {
+                let end = record_starts.get(i + 1).map_or(lines.len(), |&next| next);
+                let text: String = lines[idx..end]
+                    .iter()
+                    .map(|(_, line)| format!("{line}\n"))
+                    .collect();
+                let stamped = parser::parse_content(&text, path, profile, parse_config, 0)
+                    .entries
+                    .first()
+                    .and_then(|e| e.timestamp);
+                match stamped {
+                    Some(ts) if ts < cutoff => break,
+                    Some(_) => any_stamped = true,
+                    None => {}
+                }
+                earliest = Some(idx);
             }
+            earliest.filter(|_| any_stamped)
+        }
+    };
+    Ok(start_line.map_or(size, |idx| window_start + lines[idx].0 as u64))
+}
 
-            // -----------------------------------------------------------------
-            // 6. Split at the last newline byte.
-            //    Everything up to and including the final '\n' can be parsed.
-            //    Bytes after the final '\n' are an in-progress line -- carry forward.
-            //    Decode to UTF-8 only *after* splitting so that multi-byte
-            //    sequences that straddle a read boundary are kept intact.
-            // -----------------------------------------------------------------
-            let Some(complete_text) = take_complete_text(&mut state.partial) else {
-                // No newline yet -- the entire buffer is an in-progress line.
-                continue;
-            };
-
-            // -----------------------------------------------------------------
-            // 7. Parse complete lines through the file's format profile.
-            // -----------------------------------------------------------------
-            let mut result = parser::parse_content(
-                &complete_text,
-                &state.path,
-                &state.profile,
-                &parse_config,
-                next_id,
-            );
+/// Keep the last TAIL_ROTATION_FINGERPRINT_BYTES of the bytes consumed.
+fn remember_tail(fingerprint: &mut Vec<u8>, new_bytes: &[u8]) {
+    fingerprint.extend_from_slice(new_bytes);
+    let excess = fingerprint
+        .len()
+        .saturating_sub(TAIL_ROTATION_FINGERPRINT_BYTES);
+    fingerprint.drain(..excess);
+}
 
-            if result.entries.is_empty() {
-                continue;
+/// Files next to `path` whose names extend its name (`app.log.1`,
+/// `app.log-20240115`) -- where rotation moves a log's content -- newest
+/// first.  Compressed siblings are skipped: their bytes cannot be compared.
+fn rotated_siblings(path: &Path) -> Vec<PathBuf> {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut found: Vec<(SystemTime, PathBuf)> = read_dir
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let sibling = file_name.to_str()?;
+            if sibling.len() <= name.len()
+                || !sibling.starts_with(name)
+                || ArchiveKind::from_file_name(sibling).is_some()
+            {
+                return None;
             }
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    found.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    found.truncate(MAX_TAIL_ROTATION_CANDIDATES);
+    found.into_iter().map(|(_, p)| p).collect()
+}
 
-            // Stamp entries with the file's OS mtime and back-fill a parsed
-            // timestamp if the profile has no timestamp capture group.
-            //
-            // file_modified: used by the time-range filter (e.g. last 15 min).
-            //   Files being actively written have a fresh mtime, so they always
-            //   pass a relative-time filter regardless of log line content.
-            //
-            // timestamp: back-filled with now() only when the profile produced
-            //   None (plain-text fallback). This keeps timeline sort order
-            //   correct and prevents tail entries from sinking to the bottom.
-            let now = Utc::now();
-            let effective_mtime = file_mtime.unwrap_or(now);
-            for entry in &mut result.entries {
-                entry.file_modified = Some(effective_mtime);
-                if entry.timestamp.is_none() {
-                    entry.timestamp = Some(now);
-                }
-            }
+/// The sibling of `path` that is the file with identity `id`, i.e. where a
+/// rename-create rotation moved it.
+fn find_renamed(path: &Path, id: FileId) -> Option<PathBuf> {
+    rotated_siblings(path)
+        .into_iter()
+        .find(|p| File::open(p).ok().as_ref().and_then(file_id) == Some(id))
+}
 
-            tracing::debug!(
-                file = %state.path.display(),
-                count = result.entries.len(),
-                "Tail: new entries"
-            );
+/// The sibling of `path` that copytruncate copied it to: long enough to
+/// hold `offset` bytes and with `fingerprint` just before `offset`.
+fn find_truncated_copy(path: &Path, offset: u64, fingerprint: &[u8]) -> Option<(PathBuf, File)> {
+    if fingerprint.is_empty() {
+        // Nothing read yet, so nothing to recognise the copy by.
+        return None;
+    }
+    let start = offset - fingerprint.len() as u64;
+    rotated_siblings(path).into_iter().find_map(|p| {
+        let mut file = File::open(&p).ok()?;
+        if file.metadata().ok()?.len() < offset {
+            return None;
+        }
+        let bytes = read_from(&mut file, start, fingerprint.len()).ok()?;
+        (bytes == fingerprint).then_some((p, file))
+    })
+}
 
-            next_id += result.entries.len() as u64;
+#[cfg(unix)]
+fn file_id(file: &File) -> Option<FileId> {
+    use std::os::unix::fs::MetadataExt;
+    let meta = file.metadata().ok()?;
+    Some(FileId(meta.dev(), meta.ino()))
+}
 
-            send!(TailProgress::NewEntries {
-                entries: result.entries,
-            });
-        }
+#[cfg(windows)]
+fn file_id(file: &File) -> Option<FileId> {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION,
+    };
+    // SAFETY: the handle is valid for the lifetime of `file`, and `info` is
+    // a plain-data out parameter that the call fills in.
+    let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+    let ok = unsafe { GetFileInformationByHandle(file.as_raw_handle() as _, &mut info) };
+    if ok == 0 {
+        return None;
     }
+    let index = (u64::from(info.nFileIndexHigh) << 32) | u64::from(info.nFileIndexLow);
+    Some(FileId(u64::from(info.dwVolumeSerialNumber), index))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn file_id(_file: &File) -> Option<FileId> {
+    None
 }
 
-/// Read exactly `limit` bytes from `path` starting at byte position `offset`.
+/// Read up to `limit` bytes of `file` starting at byte position `offset`.
 ///
-/// Returns fewer bytes than `limit` if the file ends before `limit` is reached.
-fn read_bytes_at(path: &std::path::Path, offset: u64, limit: usize) -> std::io::Result<Vec<u8>> {
-    let mut file = std::fs::File::open(path)?;
+/// Returns fewer bytes than `limit` only if the file ends first.
+fn read_from(file: &mut File, offset: u64, limit: usize) -> std::io::Result<Vec<u8>> {
     file.seek(SeekFrom::Start(offset))?;
-    let mut buf = vec![0u8; limit];
-    let n = file.read(&mut buf)?;
-    buf.truncate(n);
+    let mut buf = Vec::with_capacity(limit);
+    file.take(limit as u64).read_to_end(&mut buf)?;
     Ok(buf)
 }
 
@@ -494,6 +1133,7 @@ info = ["Info"]
         profile: &FormatProfile,
         chunk_size: usize,
     ) -> (Duration, usize) {
+        let mut file = File::open(path).expect("open should succeed");
         let mut offset = 0u64;
         let parse_config = ParseConfig::default();
         let mut partial = Vec::new();
@@ -503,7 +1143,7 @@ info = ["Info"]
         let start = Instant::now();
         loop {
             let new_bytes =
-                read_bytes_at(path, offset, chunk_size).expect("chunk read should succeed");
+                read_from(&mut file, offset, chunk_size).expect("chunk read should succeed");
             if new_bytes.is_empty() {
                 break;
             }
@@ -521,6 +1161,347 @@ info = ["Info"]
         (start.elapsed(), total_entries)
     }
 
+    /// Poll `state` until nothing is left to read; returns the messages.
+    fn poll_until_idle(state: &mut FileState, next_id: &mut u64) -> Vec<TailProgress> {
+        let mut out = Vec::new();
+        while state.poll(&ParseConfig::default(), next_id, &mut out) {}
+        out
+    }
+
+    /// Messages reduced to entry messages and rotation markers, in order.
+    fn timeline(messages: &[TailProgress]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for msg in messages {
+            match msg {
+                TailProgress::NewEntries { entries, .. } => {
+                    lines.extend(entries.iter().map(|e| e.message().to_string()));
+                }
+                TailProgress::Rotated { kind, .. } => lines.push(format!("<{}>", kind.label())),
+                _ => {}
+            }
+        }
+        lines
+    }
+
+    fn append(path: &std::path::Path, text: &str) {
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .expect("open for append");
+        file.write_all(text.as_bytes()).expect("append");
+    }
+
+    /// Rename-create rotation: lines written to the old file after the last
+    /// read are delivered from the renamed file before the new file is
+    /// followed, and the rotation names where the old file went.
+    #[test]
+    fn test_tail_drains_renamed_file_before_following_new_one() {
+        let dir = tempfile::TempDir::new().expect("tmpdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "[2024-01-15 14:30:00] Info started\n").expect("write");
+        let mut state = FileState::new(
+            TailFileInfo {
+                path: path.clone(),
+                profile: make_tail_test_profile(),
+                initial_offset: None,
+            },
+            TailStart::End,
+            &ParseConfig::default(),
+        );
+        let mut next_id = 0;
+        assert!(poll_until_idle(&mut state, &mut next_id).is_empty());
+
+        // The last line has no newline yet when the file is rotated.
+        append(
+            &path,
+            "[2024-01-15 14:30:01] Info unread\n[2024-01-15 14:30:02] Info last",
+        );
+        std::fs::rename(&path, dir.path().join("app.log.1")).expect("rename");
+        std::fs::write(&path, "[2024-01-15 14:30:03] Info fresh\n").expect("write new");
+
+        let messages = poll_until_idle(&mut state, &mut next_id);
+        assert_eq!(
+            timeline(&messages),
+            ["unread", "last", "<renamed>", "fresh"]
+        );
+        let rotated = messages
+            .iter()
+            .find_map(|m| match m {
+                TailProgress::Rotated {
+                    predecessor,
+                    marker,
+                    ..
+                } => Some((predecessor.clone(), marker.clone())),
+                _ => None,
+            })
+            .expect("rotation reported");
+        assert_eq!(rotated.0, Some(dir.path().join("app.log.1")));
+        assert_eq!(*rotated.1.source_file, *path);
+        assert_eq!(
+            rotated.1.fields.get("rotation").map(String::as_str),
+            Some("renamed")
+        );
+        assert!(rotated.1.message().contains("renamed to app.log.1"));
+
+        // Entry IDs stay unique across the drain, the marker and the new file.
+        let mut ids: Vec<u64> = messages
+            .iter()
+            .flat_map(|m| match m {
+                TailProgress::NewEntries { entries, .. } => entries.iter().map(|e| e.id).collect(),
+                TailProgress::Rotated { marker, .. } => vec![marker.id],
+                _ => Vec::new(),
+            })
+            .collect();
+        ids.dedup();
+        assert_eq!(ids, [0, 1, 2, 3]);
+    }
+
+    /// Copytruncate rotation: lines missed before the truncation are read
+    /// from the copy, including when the file has regrown past the old
+    /// offset by the time it is checked.
+    #[test]
+    fn test_tail_drains_copytruncate_copy() {
+        let dir = tempfile::TempDir::new().expect("tmpdir");
+        let path = dir.path().join("app.log");
+        let copy = dir.path().join("app.log-20240115");
+        std::fs::write(&path, "[2024-01-15 14:30:00] Info started\n").expect("write");
+        let mut state = FileState::new(
+            TailFileInfo {
+                path: path.clone(),
+                profile: make_tail_test_profile(),
+                initial_offset: None,
+            },
+            TailStart::End,
+            &ParseConfig::default(),
+        );
+        let mut next_id = 0;
+
+        append(&path, "[2024-01-15 14:30:01] Info missed\n");
+        std::fs::copy(&path, &copy).expect("copy");
+        std::fs::write(dir.path().join("app.log.unrelated"), "other\n").expect("write");
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .expect("open")
+            .set_len(0)
+            .expect("truncate");
+        append(
+            &path,
+            "[2024-01-15 14:30:02] Info fresh after the truncation, longer than before\n",
+        );
+
+        let messages = poll_until_idle(&mut state, &mut next_id);
+        assert_eq!(
+            timeline(&messages),
+            [
+                "missed",
+                "<truncated>",
+                "fresh after the truncation, longer than before"
+            ]
+        );
+        assert!(messages.iter().any(|m| matches!(m,
+            TailProgress::Rotated { predecessor: Some(p), .. } if *p == copy)));
+
+        // Without a copy the truncation is still reported and followed.
+        std::fs::write(&path, "[2024-01-15 14:30:03] Info again\n").expect("rewrite");
+        let messages = poll_until_idle(&mut state, &mut next_id);
+        assert_eq!(timeline(&messages), ["<truncated>", "again"]);
+    }
+
+    /// Replays start at a record boundary: the last N lines widen back to the
+    /// start of a stack trace, and a time cutoff includes the whole trace of
+    /// the first record at or after it.  A loaded file is never replayed.
+    #[test]
+    fn test_tail_replay_starts_at_record_boundary() {
+        let dir = tempfile::TempDir::new().expect("tmpdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(
+            &path,
+            "[2024-01-15 14:30:00] Info started\n\
+             [2024-01-15 14:30:01] Error failed\n\
+             \tat Worker.run\n\
+             \tat Thread.run\n\
+             [2024-01-15 14:30:02] Info recovered\n",
+        )
+        .expect("write");
+        let replay = |start: TailStart, initial_offset: Option<u64>| {
+            let mut state = FileState::new(
+                TailFileInfo {
+                    path: path.clone(),
+                    profile: make_tail_test_profile(),
+                    initial_offset,
+                },
+                start,
+                &ParseConfig::default(),
+            );
+            timeline(&poll_until_idle(&mut state, &mut 0))
+        };
+        let at = |s: &str| {
+            chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+                .expect("time")
+                .and_utc()
+        };
+
+        // Line 3 of the last 3 is inside the trace: the whole trace replays.
+        assert_eq!(
+            replay(TailStart::LastLines(3), None),
+            ["failed\n\tat Worker.run\n\tat Thread.run", "recovered"]
+        );
+        assert_eq!(replay(TailStart::LastLines(1), None), ["recovered"]);
+        assert_eq!(
+            replay(TailStart::LastLines(100), None),
+            [
+                "started",
+                "failed\n\tat Worker.run\n\tat Thread.run",
+                "recovered"
+            ]
+        );
+        assert_eq!(
+            replay(TailStart::Since(at("2024-01-15 14:30:01")), None),
+            ["failed\n\tat Worker.run\n\tat Thread.run", "recovered"]
+        );
+        assert!(replay(TailStart::Since(at("2024-01-15 15:00:00")), None).is_empty());
+        assert!(replay(TailStart::End, None).is_empty());
+
+        let loaded = std::fs::metadata(&path).expect("stat").len();
+        assert!(replay(TailStart::LastLines(3), Some(loaded)).is_empty());
+    }
+
+    /// When the read-back window starts inside a record (a stack trace
+    /// longer than MAX_TAIL_REPLAY_BYTES), the replay skips the cut record
+    /// and starts at the next record start.  A fixed `Since` option replays
+    /// from its own instant.
+    #[test]
+    fn test_tail_replay_window_starting_mid_record_advances_to_next_record() {
+        let dir = tempfile::TempDir::new().expect("tmpdir");
+        let path = dir.path().join("app.log");
+        let frame = "\tat com.example.Worker.run(Worker.java:42)\n";
+        let mut content = String::from("[2024-01-15 14:30:00] Error huge trace\n");
+        while content.len() < MAX_TAIL_REPLAY_BYTES + 4096 {
+            content.push_str(frame);
+        }
+        content.push_str("[2024-01-15 14:30:05] Info after\n");
+        content.push_str("[2024-01-15 14:30:06] Info last\n");
+        std::fs::write(&path, &content).expect("write");
+
+        let replay = |start: TailStart| {
+            let mut state = FileState::new(
+                TailFileInfo {
+                    path: path.clone(),
+                    profile: make_tail_test_profile(),
+                    initial_offset: None,
+                },
+                start,
+                &ParseConfig::default(),
+            );
+            timeline(&poll_until_idle(&mut state, &mut 0))
+        };
+        assert_eq!(replay(TailStart::LastLines(1_000_000)), ["after", "last"]);
+
+        let early =
+            chrono::NaiveDateTime::parse_from_str("2024-01-15 14:00:00", "%Y-%m-%d %H:%M:%S")
+                .expect("time")
+                .and_utc();
+        let start = TailReplay::Since(early).start_at(Utc::now());
+        assert_eq!(start, TailStart::Since(early));
+        assert_eq!(replay(start), ["after", "last"]);
+    }
+
+    /// Replayed history and content appended after the tail started arrive
+    /// in separate batches, and only the history is flagged as replay, so
+    /// alert rules never fire on it.
+    #[test]
+    fn test_tail_replay_batches_are_flagged() {
+        let dir = tempfile::TempDir::new().expect("tmpdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(
+            &path,
+            "[2024-01-15 14:30:00] Error one\n\
+             [2024-01-15 14:30:01] Error two\n\
+             [2024-01-15 14:30:02] Error three\n",
+        )
+        .expect("write");
+        let mut state = FileState::new(
+            TailFileInfo {
+                path: path.clone(),
+                profile: make_tail_test_profile(),
+                initial_offset: None,
+            },
+            TailStart::LastLines(2),
+            &ParseConfig::default(),
+        );
+        let mut f = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open");
+        f.write_all(b"[2024-01-15 14:30:03] Error four\n")
+            .expect("append");
+        drop(f);
+
+        let batches: Vec<(bool, Vec<String>)> = poll_until_idle(&mut state, &mut 0)
+            .into_iter()
+            .filter_map(|m| match m {
+                TailProgress::NewEntries { entries, replay } => Some((
+                    replay,
+                    entries.iter().map(|e| e.message().to_string()).collect(),
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            batches,
+            vec![
+                (true, vec!["two".to_string(), "three".to_string()]),
+                (false, vec!["four".to_string()]),
+            ]
+        );
+    }
+
+    /// With native change events, an appended line is delivered well before
+    /// the (long) poll interval elapses.
+    #[test]
+    fn test_tail_delivers_appended_line_on_change_event() {
+        let dir = tempfile::TempDir::new().expect("tmpdir");
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "[2024-01-15 14:30:00] Info started\n").expect("write");
+
+        let mut manager = TailManager::new();
+        manager.start_tail(
+            vec![TailFileInfo {
+                path: path.clone(),
+                profile: make_tail_test_profile(),
+                initial_offset: None,
+            }],
+            TailStart::End,
+            0,
+            10_000,
+        );
+        // Let the watcher seed its offset before appending.
+        std::thread::sleep(Duration::from_millis(300));
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open for append");
+        writeln!(file, "[2024-01-15 14:30:01] Error appended").expect("append");
+        drop(file);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut delivered = false;
+        while !delivered && Instant::now() < deadline {
+            delivered = manager.poll_progress(16).iter().any(|m| {
+                matches!(m, TailProgress::NewEntries { entries, .. }
+                    if entries.iter().any(|e| e.message() == "appended"))
+            });
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        manager.stop_tail();
+        assert!(
+            delivered,
+            "appended line was not delivered before the poll interval"
+        );
+    }
+
     #[test]
     #[ignore = "performance benchmark"]
     fn bench_tail_chunk_pipeline_throughput() {
@@ -0,0 +1,426 @@
+// LogSleuth - core/alert.rs
+//
+// Live alert rules: a filter query plus a threshold ("at least 3 matches in
+// 60 s"), evaluated against each batch of live-tail entries.
+//
+// Counting: a rule remembers when each matching entry arrived (the time the
+// batch is evaluated, not the entry's own timestamp, so a file with a skewed
+// clock counts the same as any other).  Every entry in a batch arrives at
+// the same instant, so only batches of new content may be evaluated: the
+// history replayed when a tail starts (`TailStart`) arrives as one batch and
+// would look like a burst.  The app layer skips those batches
+// (`TailProgress::NewEntries::replay`).  When the matches inside the window
+// reach the threshold the rule fires on the entry that completed the count,
+// and counting starts again from zero: a steady stream of matches fires once
+// per `threshold` matches, not on every match.
+//
+// This module only decides what fires.  Pinning the entry, flashing the
+// status bar, running the rule's command and writing the alert log are done
+// by the app layer (`app::alerts`, `AppState::evaluate_alerts`).
+//
+// Core layer: pure logic, no I/O or UI dependencies.
+//
+// Rule 11 compliance:
+//   - A rule remembers at most `threshold` arrival times, and thresholds are
+//     capped at MAX_ALERT_THRESHOLD.
+//   - At most MAX_ALERT_FIRINGS_PER_BATCH firings are returned per batch;
+//     the rest are only counted.
+
+use crate::core::filter::QueryExpr;
+use crate::core::model::LogEntry;
+use crate::util::constants::{
+    DEFAULT_ALERT_THRESHOLD, DEFAULT_ALERT_WINDOW_SECS, MAX_ALERT_FIRINGS_PER_BATCH,
+    MAX_ALERT_RULE_NAME_LEN, MAX_ALERT_THRESHOLD, MAX_ALERT_WINDOW_SECS,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// A saved alert rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Shown in the status bar, the pin label and the alert log.
+    pub name: String,
+
+    /// Filter query (`core::filter::QueryExpr` syntax) an entry must match.
+    pub query: String,
+
+    /// Matches within `window_secs` that fire the rule.
+    #[serde(default = "default_threshold")]
+    pub threshold: usize,
+
+    /// Counting window in seconds.
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+
+    /// Program and arguments run when the rule fires, with the entry as JSON
+    /// on stdin.  Run directly, not through a shell.  Empty: no command.
+    #[serde(default)]
+    pub command: Vec<String>,
+
+    /// Disabled rules are kept but never evaluated.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_threshold() -> usize {
+    DEFAULT_ALERT_THRESHOLD
+}
+
+fn default_window_secs() -> u64 {
+    DEFAULT_ALERT_WINDOW_SECS
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl AlertRule {
+    /// A rule with the default threshold and window and no command.
+    pub fn new(name: &str, query: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            query: query.to_string(),
+            threshold: DEFAULT_ALERT_THRESHOLD,
+            window_secs: DEFAULT_ALERT_WINDOW_SECS,
+            command: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    /// Trim the name and query, bound the name to MAX_ALERT_RULE_NAME_LEN
+    /// characters, clamp the threshold and window to their limits and drop
+    /// a command with no program.
+    pub fn normalize(&mut self) {
+        self.name = self
+            .name
+            .trim()
+            .chars()
+            .take(MAX_ALERT_RULE_NAME_LEN)
+            .collect();
+        self.query = self.query.trim().to_string();
+        self.threshold = self.threshold.clamp(1, MAX_ALERT_THRESHOLD);
+        self.window_secs = self.window_secs.clamp(1, MAX_ALERT_WINDOW_SECS);
+        if self.command.first().map_or(true, String::is_empty) {
+            self.command.clear();
+        }
+    }
+
+    /// Check that the rule can be evaluated: it has a name and its query
+    /// parses.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("the rule needs a name".to_string());
+        }
+        if self.query.trim().is_empty() {
+            return Err("the rule needs a query".to_string());
+        }
+        QueryExpr::parse(&self.query)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Threshold in words, e.g. "≥3 in 60 s".
+    pub fn describe_threshold(&self) -> String {
+        format!("\u{2265}{} in {} s", self.threshold, self.window_secs)
+    }
+}
+
+/// Write a command as one line for editing: arguments separated by spaces,
+/// an argument that is empty or contains whitespace or quotes wrapped in
+/// double quotes with `"` and `\` escaped.  `parse_command` reverses it.
+pub fn format_command(command: &[String]) -> String {
+    command
+        .iter()
+        .map(|arg| {
+            let plain = !arg.is_empty()
+                && !arg
+                    .chars()
+                    .any(|c| c.is_whitespace() || c == '"' || c == '\'');
+            if plain {
+                return arg.clone();
+            }
+            let mut quoted = String::with_capacity(arg.len() + 2);
+            quoted.push('"');
+            for c in arg.chars() {
+                if c == '"' || c == '\\' {
+                    quoted.push('\\');
+                }
+                quoted.push(c);
+            }
+            quoted.push('"');
+            quoted
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split an edited command line into program and arguments.
+///
+/// Whitespace separates arguments.  `"..."` groups an argument, and inside
+/// it `\"` and `\\` stand for `"` and `\`; `'...'` groups an argument taken
+/// literally.  Backslashes elsewhere are kept, so Windows paths need no
+/// quoting.
+pub fn parse_command(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if let Some(arg) = current.take() {
+                    args.push(arg);
+                }
+            }
+            '"' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.peek(), Some('"' | '\\')) => {
+                            arg.extend(chars.next());
+                        }
+                        Some(c) => arg.push(c),
+                        None => return Err("unterminated \" in command".to_string()),
+                    }
+                }
+            }
+            '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => arg.push(c),
+                        None => return Err("unterminated ' in command".to_string()),
+                    }
+                }
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// A rule that fired.
+#[derive(Debug, Clone)]
+pub struct AlertFiring {
+    /// Index of the rule in the list the engine was built from.
+    pub rule: usize,
+    /// Matches counted in the window when it fired (the threshold).
+    pub matches: usize,
+    /// The entry that completed the count.
+    pub entry: LogEntry,
+}
+
+/// Result of evaluating one batch.
+#[derive(Debug, Default)]
+pub struct AlertBatch {
+    /// Firings to act on, in entry order.
+    pub firings: Vec<AlertFiring>,
+    /// Further firings beyond MAX_ALERT_FIRINGS_PER_BATCH, counted only.
+    pub suppressed: usize,
+}
+
+/// Evaluates the enabled rules against live-tail batches.
+#[derive(Debug, Default)]
+pub struct AlertEngine {
+    rules: Vec<CompiledRule>,
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    index: usize,
+    expr: QueryExpr,
+    threshold: usize,
+    window: Duration,
+    /// Arrival times of the matches counted so far, oldest first.
+    hits: VecDeque<DateTime<Utc>>,
+}
+
+impl AlertEngine {
+    /// Compile the enabled rules.  Rules that do not validate are skipped;
+    /// their indices and errors are returned.
+    pub fn new(rules: &[AlertRule]) -> (Self, Vec<(usize, String)>) {
+        let mut errors = Vec::new();
+        let mut compiled = Vec::new();
+        for (index, rule) in rules.iter().enumerate().filter(|(_, r)| r.enabled) {
+            if let Err(e) = rule.validate() {
+                errors.push((index, e));
+                continue;
+            }
+            let Ok(expr) = QueryExpr::parse(&rule.query) else {
+                continue;
+            };
+            compiled.push(CompiledRule {
+                index,
+                expr,
+                threshold: rule.threshold.clamp(1, MAX_ALERT_THRESHOLD),
+                window: Duration::seconds(rule.window_secs.clamp(1, MAX_ALERT_WINDOW_SECS) as i64),
+                hits: VecDeque::new(),
+            });
+        }
+        (Self { rules: compiled }, errors)
+    }
+
+    /// Whether no rule is being evaluated.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Count the matches in `entries`, which arrived at `now`, and return
+    /// the rules that fired.
+    pub fn evaluate(&mut self, entries: &[LogEntry], now: DateTime<Utc>) -> AlertBatch {
+        let mut batch = AlertBatch::default();
+        for entry in entries {
+            for rule in &mut self.rules {
+                if !rule.expr.matches(entry) {
+                    continue;
+                }
+                while rule.hits.front().is_some_and(|&t| now - t >= rule.window) {
+                    rule.hits.pop_front();
+                }
+                rule.hits.push_back(now);
+                if rule.hits.len() < rule.threshold {
+                    continue;
+                }
+                rule.hits.clear();
+                if batch.firings.len() < MAX_ALERT_FIRINGS_PER_BATCH {
+                    batch.firings.push(AlertFiring {
+                        rule: rule.index,
+                        matches: rule.threshold,
+                        entry: entry.clone(),
+                    });
+                } else {
+                    batch.suppressed += 1;
+                }
+            }
+        }
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::model::{MessageText, Severity};
+    use std::path::Path;
+    use std::sync::Arc;
+
+    fn entry(id: u64, severity: Severity, text: &str) -> LogEntry {
+        LogEntry {
+            id,
+            timestamp: None,
+            severity,
+            source_file: Arc::from(Path::new("/logs/app.log")),
+            line_number: id,
+            thread: None,
+            component: None,
+            message: MessageText::slice_of(text, 0..text.len()),
+            raw_text: text.to_string(),
+            profile_id: Arc::from("plain-text"),
+            fields: Default::default(),
+            file_modified: None,
+        }
+    }
+
+    /// A rule fires when the threshold is reached inside the window, on the
+    /// entry that completed the count, and then counts from zero again.
+    #[test]
+    fn test_rule_fires_at_threshold_within_window() {
+        let rules = vec![AlertRule::new("Timeouts", "severity>=error AND timeout")];
+        let (mut engine, errors) = AlertEngine::new(&rules);
+        assert!(errors.is_empty());
+        let t0 = Utc::now();
+
+        let fired = engine.evaluate(
+            &[
+                entry(1, Severity::Error, "db timeout"),
+                entry(2, Severity::Info, "timeout retried"),
+                entry(3, Severity::Error, "disk full"),
+            ],
+            t0,
+        );
+        assert!(fired.firings.is_empty());
+
+        // Two more matches, the first one after the window: 1 + 1 only.
+        let fired = engine.evaluate(
+            &[entry(4, Severity::Error, "timeout")],
+            t0 + Duration::seconds(60),
+        );
+        assert!(fired.firings.is_empty());
+        let fired = engine.evaluate(
+            &[
+                entry(5, Severity::Error, "timeout"),
+                entry(6, Severity::Error, "timeout"),
+                entry(7, Severity::Error, "timeout"),
+            ],
+            t0 + Duration::seconds(70),
+        );
+        assert_eq!(fired.firings.len(), 1);
+        assert_eq!(fired.firings[0].rule, 0);
+        assert_eq!(fired.firings[0].matches, 3);
+        assert_eq!(fired.firings[0].entry.id, 6);
+    }
+
+    /// Arguments with spaces, quotes or backslashes survive a trip through
+    /// the editor's one-line form unchanged.
+    #[test]
+    fn test_command_line_round_trip() {
+        let command: Vec<String> = [
+            "notify-send",
+            "DB alert",
+            "",
+            r#"say "hi""#,
+            r"C:\Program Files\hook.exe",
+            r"C:\Tools\x.exe",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let line = format_command(&command);
+        assert!(line.starts_with(r#"notify-send "DB alert" """#));
+        assert!(line.ends_with(r" C:\Tools\x.exe"));
+        assert_eq!(parse_command(&line), Ok(command));
+
+        assert_eq!(
+            parse_command(r#"  curl -d 'a "b"' C:\x  "#),
+            Ok(vec![
+                "curl".to_string(),
+                "-d".to_string(),
+                r#"a "b""#.to_string(),
+                r"C:\x".to_string(),
+            ])
+        );
+        assert!(parse_command(r#"notify-send "open"#).is_err());
+    }
+
+    /// Disabled and invalid rules are not evaluated; invalid ones are
+    /// reported by index.  Firings beyond the per-batch cap are counted.
+    #[test]
+    fn test_engine_skips_invalid_rules_and_caps_firings() {
+        let mut disabled = AlertRule::new("Off", "error");
+        disabled.enabled = false;
+        let mut every = AlertRule::new("Every", "error");
+        every.threshold = 1;
+        let rules = vec![
+            disabled,
+            AlertRule::new("Broken", "(error"),
+            AlertRule::new("", "error"),
+            every,
+        ];
+        let (mut engine, errors) = AlertEngine::new(&rules);
+        assert_eq!(
+            errors.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        let flood: Vec<LogEntry> = (0..MAX_ALERT_FIRINGS_PER_BATCH as u64 + 5)
+            .map(|id| entry(id, Severity::Error, "error"))
+            .collect();
+        let fired = engine.evaluate(&flood, Utc::now());
+        assert_eq!(fired.firings.len(), MAX_ALERT_FIRINGS_PER_BATCH);
+        assert!(fired.firings.iter().all(|f| f.rule == 3));
+        assert_eq!(fired.suppressed, 5);
+    }
+}
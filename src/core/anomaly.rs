@@ -0,0 +1,518 @@
+// LogSleuth - core/anomaly.rs
+//
+// Error-rate spike and silence detection across the merged timeline.
+//
+// Method:
+// - The span of all timestamped entries is divided into fixed-width buckets
+//   (about `ANOMALY_TARGET_BUCKETS` of them, at least
+//   `ANOMALY_MIN_BUCKET_SECS` wide).
+// - Each source file and each component is a series.  Per bucket it counts
+//   errors (Critical + Error), warnings and total volume.
+// - The baseline of a series is the median bucket count over the buckets it
+//   spans (empty buckets count as zero); spread is the median absolute
+//   deviation, floored at the Poisson spread `sqrt(median)` so a perfectly
+//   steady series does not flag tiny wobbles.  Buckets whose robust z-score
+//   reaches `ANOMALY_SPIKE_Z_THRESHOLD` are spikes; adjacent spike buckets
+//   merge into one finding.
+// - A file goes silent when one quiet period (a gap between entries, or the
+//   time from its last entry to the end of the merged timeline) is at least
+//   `ANOMALY_SILENCE_FACTOR` times its median gap and twice as long as any
+//   other quiet period in that file.
+//
+// Entries without a parsed timestamp are ignored.
+// Core layer: pure logic, no I/O or UI dependencies.
+
+use crate::core::model::{LogEntry, Severity};
+use crate::util::constants::{
+    ANOMALY_MIN_BUCKET_SECS, ANOMALY_MIN_SERIES_BUCKETS, ANOMALY_MIN_SILENCE_ENTRIES,
+    ANOMALY_MIN_SILENCE_SECS, ANOMALY_MIN_SPIKE_COUNT, ANOMALY_SILENCE_FACTOR,
+    ANOMALY_SPIKE_Z_THRESHOLD, ANOMALY_TARGET_BUCKETS, MAX_ANOMALY_FINDINGS,
+};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// What kind of deviation a finding describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyKind {
+    /// Critical + Error entries per bucket far above the baseline.
+    ErrorSpike,
+    /// Warning entries per bucket far above the baseline.
+    WarningSpike,
+    /// Total entries per bucket far above the baseline.
+    VolumeSpike,
+    /// A file stopped logging for much longer than usual.
+    Silence,
+}
+
+impl AnomalyKind {
+    /// Human-readable label for UI display.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ErrorSpike => "Error spike",
+            Self::WarningSpike => "Warning spike",
+            Self::VolumeSpike => "Volume spike",
+            Self::Silence => "Silence",
+        }
+    }
+
+    /// Severity used to colour the finding.
+    pub fn severity(self) -> Severity {
+        match self {
+            Self::ErrorSpike => Severity::Error,
+            Self::WarningSpike | Self::Silence => Severity::Warning,
+            Self::VolumeSpike => Severity::Info,
+        }
+    }
+}
+
+/// The series a finding was detected in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AnomalyScope {
+    /// All entries of one source file.
+    File(PathBuf),
+    /// All entries with one `LogEntry::component`, across files.
+    Component(String),
+}
+
+impl AnomalyScope {
+    /// Short label: file name or `component: name`.
+    pub fn label(&self) -> String {
+        match self {
+            Self::File(path) => crate::core::archive::display_name(path).into_owned(),
+            Self::Component(name) => format!("component: {name}"),
+        }
+    }
+}
+
+/// One detected anomaly.
+#[derive(Debug, Clone)]
+pub struct AnomalyFinding {
+    pub kind: AnomalyKind,
+    pub scope: AnomalyScope,
+
+    /// Start of the anomalous window (inclusive).
+    pub start: DateTime<Utc>,
+
+    /// End of the anomalous window (inclusive).
+    pub end: DateTime<Utc>,
+
+    /// Spikes: peak entries in one bucket.  Silence: always 0.
+    pub observed: usize,
+
+    /// Spikes: median entries per bucket.  Silence: median seconds between
+    /// entries of the file.
+    pub baseline: f64,
+
+    /// Strength of the deviation: robust z-score for spikes, quiet period
+    /// divided by the median gap for silences.
+    pub score: f64,
+}
+
+impl AnomalyFinding {
+    /// One-line explanation for the findings list.
+    pub fn describe(&self, bucket_secs: i64) -> String {
+        let what = match self.kind {
+            AnomalyKind::ErrorSpike => "errors",
+            AnomalyKind::WarningSpike => "warnings",
+            AnomalyKind::VolumeSpike => "entries",
+            AnomalyKind::Silence => {
+                let gap = (self.end - self.start).num_seconds();
+                return format!(
+                    "No entries for {} (usually one every {})",
+                    format_duration(gap),
+                    format_duration(self.baseline.round() as i64)
+                );
+            }
+        };
+        format!(
+            "{} {what} per {} at peak vs a typical {:.1}",
+            self.observed,
+            format_duration(bucket_secs),
+            self.baseline
+        )
+    }
+}
+
+/// Result of an analysis run.
+#[derive(Debug, Clone, Default)]
+pub struct AnomalyReport {
+    /// Findings in chronological order (the most significant
+    /// `MAX_ANOMALY_FINDINGS` when there are more).
+    pub findings: Vec<AnomalyFinding>,
+
+    /// Bucket width in seconds used for spike detection.
+    pub bucket_secs: i64,
+
+    /// Number of entries the report was computed from (including entries
+    /// without a timestamp, which are skipped).
+    pub entries_analysed: usize,
+}
+
+/// Bucket width for a timeline spanning `span_secs` seconds.
+pub fn bucket_width(span_secs: i64) -> i64 {
+    let raw = (span_secs + ANOMALY_TARGET_BUCKETS - 1) / ANOMALY_TARGET_BUCKETS;
+    let minutes = (raw + 59) / 60;
+    (minutes * 60).max(ANOMALY_MIN_BUCKET_SECS)
+}
+
+/// Compact duration text: `45s`, `12m`, `2h 5m`, `3d 4h`.
+pub fn format_duration(secs: i64) -> String {
+    let secs = secs.max(0);
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3_600 {
+        format!("{}m", secs / 60)
+    } else if secs < 86_400 {
+        format!("{}h {}m", secs / 3_600, (secs % 3_600) / 60)
+    } else {
+        format!("{}d {}h", secs / 86_400, (secs % 86_400) / 3_600)
+    }
+}
+
+/// Per-bucket counts of one series: `[errors, warnings, total]`.
+type SeriesCounts = BTreeMap<i64, [usize; 3]>;
+
+/// Detect spikes and silences in `entries`.
+pub fn detect_anomalies(entries: &[LogEntry]) -> AnomalyReport {
+    let mut report = AnomalyReport {
+        entries_analysed: entries.len(),
+        ..Default::default()
+    };
+
+    let (Some(first), Some(last)) = (
+        entries.iter().filter_map(|e| e.timestamp).min(),
+        entries.iter().filter_map(|e| e.timestamp).max(),
+    ) else {
+        return report;
+    };
+    let bucket_secs = bucket_width((last - first).num_seconds());
+    report.bucket_secs = bucket_secs;
+
+    let mut file_series: HashMap<&Path, SeriesCounts> = HashMap::new();
+    let mut component_series: HashMap<&str, SeriesCounts> = HashMap::new();
+    let mut file_times: HashMap<&Path, Vec<DateTime<Utc>>> = HashMap::new();
+    for entry in entries {
+        let Some(ts) = entry.timestamp else {
+            continue;
+        };
+        let bucket = ts.timestamp().div_euclid(bucket_secs);
+        let is_error = matches!(entry.severity, Severity::Critical | Severity::Error);
+        let is_warning = entry.severity == Severity::Warning;
+        let add = |counts: &mut [usize; 3]| {
+            counts[0] += usize::from(is_error);
+            counts[1] += usize::from(is_warning);
+            counts[2] += 1;
+        };
+        add(file_series
+            .entry(&*entry.source_file)
+            .or_default()
+            .entry(bucket)
+            .or_default());
+        if let Some(ref component) = entry.component {
+            add(component_series
+                .entry(&**component)
+                .or_default()
+                .entry(bucket)
+                .or_default());
+        }
+        file_times.entry(&*entry.source_file).or_default().push(ts);
+    }
+
+    let mut findings: Vec<AnomalyFinding> = Vec::new();
+    let series = file_series
+        .into_iter()
+        .map(|(path, counts)| (AnomalyScope::File(path.to_path_buf()), counts))
+        .chain(
+            component_series
+                .into_iter()
+                .map(|(name, counts)| (AnomalyScope::Component(name.to_string()), counts)),
+        );
+    for (scope, counts) in series {
+        for (metric, kind) in [
+            (0, AnomalyKind::ErrorSpike),
+            (1, AnomalyKind::WarningSpike),
+            (2, AnomalyKind::VolumeSpike),
+        ] {
+            find_spikes(&scope, &counts, metric, kind, bucket_secs, &mut findings);
+        }
+    }
+    for (path, times) in &mut file_times {
+        times.sort_unstable();
+        if let Some(finding) = find_silence(path, times, last) {
+            findings.push(finding);
+        }
+    }
+
+    findings.sort_by(|a, b| b.score.total_cmp(&a.score));
+    findings.truncate(MAX_ANOMALY_FINDINGS);
+    findings.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.scope.cmp(&b.scope)));
+    report.findings = findings;
+    report
+}
+
+fn median(sorted: &[f64]) -> f64 {
+    let n = sorted.len();
+    if n == 0 {
+        0.0
+    } else if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    }
+}
+
+/// Flag runs of buckets in one series/metric whose count deviates sharply
+/// from the series median.
+fn find_spikes(
+    scope: &AnomalyScope,
+    counts: &SeriesCounts,
+    metric: usize,
+    kind: AnomalyKind,
+    bucket_secs: i64,
+    findings: &mut Vec<AnomalyFinding>,
+) {
+    let (Some(&lo), Some(&hi)) = (counts.keys().next(), counts.keys().next_back()) else {
+        return;
+    };
+    let span = (hi - lo + 1) as usize;
+    if span < ANOMALY_MIN_SERIES_BUCKETS {
+        return;
+    }
+    let values: Vec<f64> = (lo..=hi)
+        .map(|b| counts.get(&b).map_or(0, |c| c[metric]) as f64)
+        .collect();
+
+    let mut sorted = values.clone();
+    sorted.sort_by(f64::total_cmp);
+    let med = median(&sorted);
+    let mut deviations: Vec<f64> = values.iter().map(|v| (v - med).abs()).collect();
+    deviations.sort_by(f64::total_cmp);
+    let scale = (1.4826 * median(&deviations)).max(med.sqrt()).max(1.0);
+
+    let z = |v: f64| (v - med) / scale;
+    let is_spike =
+        |v: f64| v >= ANOMALY_MIN_SPIKE_COUNT as f64 && z(v) >= ANOMALY_SPIKE_Z_THRESHOLD;
+
+    let mut i = 0;
+    while i < values.len() {
+        if !is_spike(values[i]) {
+            i += 1;
+            continue;
+        }
+        let run_start = i;
+        let mut peak = values[i];
+        while i < values.len() && is_spike(values[i]) {
+            peak = peak.max(values[i]);
+            i += 1;
+        }
+        let start_bucket = lo + run_start as i64;
+        let end_bucket = lo + i as i64; // exclusive
+        let (Some(start), Some(end)) = (
+            DateTime::from_timestamp(start_bucket * bucket_secs, 0),
+            DateTime::from_timestamp(end_bucket * bucket_secs - 1, 0),
+        ) else {
+            continue;
+        };
+        findings.push(AnomalyFinding {
+            kind,
+            scope: scope.clone(),
+            start,
+            end,
+            observed: peak as usize,
+            baseline: med,
+            score: z(peak),
+        });
+    }
+}
+
+/// Report the quiet period of a file that stands out from all its others.
+/// `times` must be sorted; `timeline_end` is the latest timestamp of the
+/// merged timeline.
+fn find_silence(
+    path: &Path,
+    times: &[DateTime<Utc>],
+    timeline_end: DateTime<Utc>,
+) -> Option<AnomalyFinding> {
+    if times.len() < ANOMALY_MIN_SILENCE_ENTRIES {
+        return None;
+    }
+    let mut gaps: Vec<f64> = times
+        .windows(2)
+        .map(|w| (w[1] - w[0]).num_seconds() as f64)
+        .collect();
+    gaps.sort_by(f64::total_cmp);
+    let median_gap = median(&gaps).max(1.0);
+    let threshold = (ANOMALY_SILENCE_FACTOR * median_gap).max(ANOMALY_MIN_SILENCE_SECS as f64);
+
+    // Quiet periods: between consecutive entries, then after the last entry.
+    let last = *times.last()?;
+    let quiet_periods = times
+        .windows(2)
+        .map(|w| (w[0], w[1]))
+        .chain(std::iter::once((last, timeline_end)));
+    let mut longest: Option<(DateTime<Utc>, DateTime<Utc>)> = None;
+    let mut longest_secs = 0i64;
+    let mut runner_up_secs = 0i64;
+    for (from, to) in quiet_periods {
+        let secs = (to - from).num_seconds();
+        if secs > longest_secs {
+            runner_up_secs = longest_secs;
+            longest_secs = secs;
+            longest = Some((from, to));
+        } else if secs > runner_up_secs {
+            runner_up_secs = secs;
+        }
+    }
+
+    let (from, to) = longest?;
+    if (longest_secs as f64) < threshold || longest_secs < 2 * runner_up_secs {
+        return None;
+    }
+    Some(AnomalyFinding {
+        kind: AnomalyKind::Silence,
+        scope: AnomalyScope::File(path.to_path_buf()),
+        start: from + Duration::seconds(1),
+        end: to,
+        observed: 0,
+        baseline: median_gap,
+        score: longest_secs as f64 / median_gap,
+    })
+}
+
+// =============================================================================
+// Unit tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn base() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()
+    }
+
+    fn entry(id: u64, file: &str, severity: Severity, offset_secs: i64) -> LogEntry {
+        LogEntry {
+            id,
+            timestamp: Some(base() + Duration::seconds(offset_secs)),
+            severity,
+            source_file: PathBuf::from(file).into(),
+            line_number: id + 1,
+            thread: None,
+            component: None,
+            message: "msg".to_string().into(),
+            raw_text: "msg".to_string(),
+            profile_id: "test".to_string().into(),
+            file_modified: None,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Steady traffic over four hours: one Info entry per minute in two files.
+    fn steady() -> Vec<LogEntry> {
+        let mut entries = Vec::new();
+        for minute in 0..240 {
+            for file in ["a.log", "b.log"] {
+                let id = entries.len() as u64;
+                entries.push(entry(id, file, Severity::Info, minute * 60));
+            }
+        }
+        entries
+    }
+
+    #[test]
+    fn test_bucket_width_and_format_duration() {
+        assert_eq!(bucket_width(0), ANOMALY_MIN_BUCKET_SECS);
+        assert_eq!(bucket_width(4 * 3_600), 120);
+        assert_eq!(bucket_width(24 * 3_600), 720);
+        assert_eq!(format_duration(45), "45s");
+        assert_eq!(format_duration(12 * 60 + 5), "12m");
+        assert_eq!(format_duration(2 * 3_600 + 5 * 60), "2h 5m");
+        assert_eq!(format_duration(3 * 86_400 + 4 * 3_600), "3d 4h");
+    }
+
+    /// Steady traffic produces no findings.
+    #[test]
+    fn test_detect_anomalies_steady_traffic_is_quiet() {
+        let report = detect_anomalies(&steady());
+        assert!(report.findings.is_empty(), "got {:?}", report.findings);
+        assert_eq!(report.bucket_secs, 120);
+    }
+
+    /// A burst of errors in one file is reported as an error spike covering
+    /// the burst's bucket, in that file and in its component.
+    #[test]
+    fn test_detect_anomalies_error_burst() {
+        let mut entries = steady();
+        for e in entries
+            .iter_mut()
+            .filter(|e| *e.source_file == *Path::new("a.log"))
+        {
+            e.component = Some("Agent".to_string().into());
+        }
+        for i in 0..30 {
+            let mut e = entry(10_000 + i, "a.log", Severity::Error, 100 * 60 + i as i64);
+            e.component = Some("Agent".to_string().into());
+            entries.push(e);
+        }
+        let report = detect_anomalies(&entries);
+        let spike = report
+            .findings
+            .iter()
+            .find(|f| {
+                f.kind == AnomalyKind::ErrorSpike
+                    && f.scope == AnomalyScope::File(PathBuf::from("a.log"))
+            })
+            .expect("error spike in a.log");
+        assert_eq!(spike.observed, 30);
+        assert!(spike.start <= base() + Duration::minutes(100));
+        assert!(spike.end >= base() + Duration::minutes(100));
+        assert!((spike.end - spike.start).num_seconds() < 2 * report.bucket_secs);
+        assert!(report
+            .findings
+            .iter()
+            .any(|f| f.kind == AnomalyKind::ErrorSpike
+                && f.scope == AnomalyScope::Component("Agent".to_string())));
+        assert!(
+            !report
+                .findings
+                .iter()
+                .any(|f| f.scope == AnomalyScope::File(PathBuf::from("b.log"))),
+            "b.log is unaffected"
+        );
+    }
+
+    /// A file that stops logging while the others carry on is reported as a
+    /// silence from its last entry to the end of the timeline.
+    #[test]
+    fn test_detect_anomalies_file_goes_silent() {
+        let entries: Vec<LogEntry> = steady()
+            .into_iter()
+            .filter(|e| !(*e.source_file == *Path::new("b.log") && e.line_number > 300))
+            .collect();
+        let report = detect_anomalies(&entries);
+        let silences: Vec<&AnomalyFinding> = report
+            .findings
+            .iter()
+            .filter(|f| f.kind == AnomalyKind::Silence)
+            .collect();
+        assert_eq!(silences.len(), 1, "got {silences:?}");
+        let s = silences[0];
+        assert_eq!(s.scope, AnomalyScope::File(PathBuf::from("b.log")));
+        assert_eq!(s.end, base() + Duration::minutes(239));
+        assert!(s
+            .describe(report.bucket_secs)
+            .starts_with("No entries for 1h"));
+    }
+
+    #[test]
+    fn test_detect_anomalies_no_timestamps() {
+        let mut e = entry(0, "a.log", Severity::Error, 0);
+        e.timestamp = None;
+        let report = detect_anomalies(&[e]);
+        assert!(report.findings.is_empty());
+        assert_eq!(report.entries_analysed, 1);
+    }
+}
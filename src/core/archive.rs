@@ -0,0 +1,695 @@
+// LogSleuth - core/archive.rs
+//
+// Transparent access to compressed and archived logs (.gz, .zip, .tar.gz).
+//
+// Archive members are exposed to the rest of the application as *virtual
+// paths*: the archive path, `ARCHIVE_MEMBER_SEPARATOR`, then the member name
+// inside the archive, e.g. `/cases/4711/bundle.zip!/logs/app.log`.  Because
+// the member name uses `/`, `Path::file_name()` on a virtual path still
+// yields the member's own file name (`app.log`), so profile auto-detection
+// and filename-based UI code keep working unchanged.
+//
+// Like `core::discovery`, this module touches the filesystem only through
+// thin std / crate abstractions: `list_members` reads archive directories
+// (metadata) for discovery, `open_member` streams a single member's
+// decompressed bytes to the scan pipeline, and `walk_tar_members` streams
+// every member of a tarball in one pass.  Decoding the bytes to text stays
+// in `app::scan`.
+//
+// Rule 11 compliance:
+//   - Members listed per archive are capped at MAX_ARCHIVE_MEMBERS.
+//   - Members are streamed, never buffered whole (except `read_member`,
+//     used for .evtx), and reading more than MAX_ARCHIVE_MEMBER_BYTES of a
+//     member fails with an error, so a decompression bomb cannot run on.
+
+use crate::util::constants::{
+    ARCHIVE_MEMBER_SEPARATOR, MAX_ARCHIVE_MEMBERS, MAX_ARCHIVE_MEMBER_BYTES,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+// =============================================================================
+// Archive kinds
+// =============================================================================
+
+/// Container formats that discovery looks inside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    /// A single gzip-compressed file (`syslog.2.gz`, `app.log.1.gz`).
+    Gzip,
+    /// A zip archive (support bundles, diagnostic exports).
+    Zip,
+    /// A gzip-compressed tarball (`.tar.gz` / `.tgz`).
+    TarGz,
+}
+
+impl ArchiveKind {
+    /// Classify a file by its name (case-insensitive extension match).
+    /// Returns `None` for anything that is not a supported archive.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let lower = file_name.to_ascii_lowercase();
+        if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if lower.ends_with(".gz") {
+            Some(Self::Gzip)
+        } else if lower.ends_with(".zip") {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+
+    /// Classify a path by its final component.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .and_then(Self::from_file_name)
+    }
+}
+
+/// A regular file inside an archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveMember {
+    /// Member name relative to the archive root, `/`-separated.
+    pub name: String,
+    /// Uncompressed size in bytes (for gzip: the ISIZE trailer, i.e. the
+    /// size modulo 4 GiB).
+    pub size: u64,
+    /// Member modification time, when the format records one.  Zip stores
+    /// local wall-clock time without a zone; it is taken as UTC.
+    pub modified: Option<DateTime<Utc>>,
+}
+
+/// Result of listing an archive.
+#[derive(Debug, Default)]
+pub struct ArchiveListing {
+    /// Regular-file members, in archive order.
+    pub members: Vec<ArchiveMember>,
+    /// True when the archive held more than `MAX_ARCHIVE_MEMBERS` files and
+    /// the listing was cut short.
+    pub truncated: bool,
+}
+
+// =============================================================================
+// Virtual paths
+// =============================================================================
+
+/// Build the virtual path of `member` inside `archive`.
+pub fn member_path(archive: &Path, member: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "{}{ARCHIVE_MEMBER_SEPARATOR}{member}",
+        archive.display()
+    ))
+}
+
+/// Split a virtual path into the archive path and the member name.
+///
+/// Returns `None` for ordinary paths.  Only a separator that directly follows
+/// a supported archive file name counts, so a real directory whose name
+/// happens to contain `!/` is not misread as an archive member.
+pub fn split_member_path(path: &Path) -> Option<(PathBuf, String)> {
+    let s = path.to_str()?;
+    s.match_indices(ARCHIVE_MEMBER_SEPARATOR)
+        .find_map(|(idx, _)| {
+            let archive = Path::new(&s[..idx]);
+            let member = &s[idx + ARCHIVE_MEMBER_SEPARATOR.len()..];
+            (ArchiveKind::from_path(archive).is_some() && !member.is_empty())
+                .then(|| (archive.to_path_buf(), member.to_string()))
+        })
+}
+
+/// True when `path` names a member inside an archive (see [`member_path`]).
+pub fn is_member_path(path: &Path) -> bool {
+    split_member_path(path).is_some()
+}
+
+/// Short label for a source file in the UI.
+///
+/// Ordinary files show their file name (`app.log`); archive members show the
+/// archive file name plus the member path (`bundle.zip!/logs/app.log`) so the
+/// origin of each entry stays visible in the timeline and detail pane.
+pub fn display_name(path: &Path) -> Cow<'_, str> {
+    if let Some((archive, member)) = split_member_path(path) {
+        let archive_name = archive
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        return Cow::Owned(format!("{archive_name}{ARCHIVE_MEMBER_SEPARATOR}{member}"));
+    }
+    path.file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or(Cow::Borrowed("?"))
+}
+
+/// The real on-disk file behind `path`: the archive for a virtual member
+/// path, otherwise `path` itself.  Used for "show in folder" and stat calls.
+pub fn physical_path(path: &Path) -> Cow<'_, Path> {
+    match split_member_path(path) {
+        Some((archive, _)) => Cow::Owned(archive),
+        None => Cow::Borrowed(path),
+    }
+}
+
+// =============================================================================
+// Listing
+// =============================================================================
+
+/// List the regular-file members of `archive`.
+///
+/// Directories, links and other special tar entries are skipped.  Returns an
+/// `InvalidInput` error when `archive` is not a supported archive name and
+/// `InvalidData` when the content is not a valid archive.
+pub fn list_members(archive: &Path) -> io::Result<ArchiveListing> {
+    match ArchiveKind::from_path(archive) {
+        Some(ArchiveKind::Gzip) => list_gzip(archive),
+        Some(ArchiveKind::Zip) => list_zip(archive),
+        Some(ArchiveKind::TarGz) => list_tar_gz(archive),
+        None => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("'{}' is not a supported archive", archive.display()),
+        )),
+    }
+}
+
+/// A gzip file holds exactly one member: the archive name minus `.gz`.
+/// The size comes from the 4-byte ISIZE trailer so no decompression is needed.
+fn list_gzip(archive: &Path) -> io::Result<ArchiveListing> {
+    let mut file = File::open(archive)?;
+    let mut magic = [0u8; 2];
+    file.read_exact(&mut magic)?;
+    if magic != [0x1f, 0x8b] {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a gzip file (bad magic bytes)",
+        ));
+    }
+    file.seek(SeekFrom::End(-4))?;
+    let mut isize_bytes = [0u8; 4];
+    file.read_exact(&mut isize_bytes)?;
+    let modified = file
+        .metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .map(DateTime::<Utc>::from);
+
+    let file_name = archive
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    // ".gz" is ASCII, so slicing 3 bytes off the end is on a char boundary.
+    let name = file_name[..file_name.len().saturating_sub(3)].to_string();
+
+    Ok(ArchiveListing {
+        members: vec![ArchiveMember {
+            name,
+            size: u64::from(u32::from_le_bytes(isize_bytes)),
+            modified,
+        }],
+        truncated: false,
+    })
+}
+
+fn list_zip(archive: &Path) -> io::Result<ArchiveListing> {
+    let mut zip = zip::ZipArchive::new(BufReader::new(File::open(archive)?)).map_err(zip_to_io)?;
+    let mut listing = ArchiveListing::default();
+    for i in 0..zip.len() {
+        // `by_index_raw` reads the central-directory record without
+        // decompressing the member data.
+        let member = zip.by_index_raw(i).map_err(zip_to_io)?;
+        if !member.is_file() {
+            continue;
+        }
+        if listing.members.len() >= MAX_ARCHIVE_MEMBERS {
+            listing.truncated = true;
+            break;
+        }
+        let modified = member.last_modified().and_then(|dt| {
+            NaiveDate::from_ymd_opt(
+                i32::from(dt.year()),
+                u32::from(dt.month()),
+                u32::from(dt.day()),
+            )?
+            .and_hms_opt(
+                u32::from(dt.hour()),
+                u32::from(dt.minute()),
+                u32::from(dt.second()),
+            )
+            .map(|ndt| ndt.and_utc())
+        });
+        listing.members.push(ArchiveMember {
+            name: member.name().to_string(),
+            size: member.size(),
+            modified,
+        });
+    }
+    Ok(listing)
+}
+
+/// Tarballs have no central directory, so listing walks (and decompresses)
+/// the whole stream; member data is skipped, not buffered.
+fn list_tar_gz(archive: &Path) -> io::Result<ArchiveListing> {
+    let decoder = flate2::read::MultiGzDecoder::new(BufReader::new(File::open(archive)?));
+    let mut tar = tar::Archive::new(decoder);
+    let mut listing = ArchiveListing::default();
+    for entry in tar.entries()? {
+        let entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        if listing.members.len() >= MAX_ARCHIVE_MEMBERS {
+            listing.truncated = true;
+            break;
+        }
+        listing.members.push(ArchiveMember {
+            name: normalise_tar_name(&entry.path_bytes()),
+            size: entry.size(),
+            modified: entry
+                .header()
+                .mtime()
+                .ok()
+                .and_then(|secs| DateTime::from_timestamp(i64::try_from(secs).ok()?, 0)),
+        });
+    }
+    Ok(listing)
+}
+
+/// Tar member names are byte strings, often prefixed with `./`.
+fn normalise_tar_name(raw: &[u8]) -> String {
+    let name = String::from_utf8_lossy(raw);
+    name.trim_start_matches("./").to_string()
+}
+
+// =============================================================================
+// Reading
+// =============================================================================
+
+/// Open the member named by the virtual path `path` as a stream of its
+/// decompressed bytes.  Nothing is buffered beyond the decoder's own state,
+/// so memory does not grow with the member's size.
+///
+/// Fails with `InvalidInput` for non-virtual paths and `NotFound` when the
+/// member is missing.  Reading past `MAX_ARCHIVE_MEMBER_BYTES` of output
+/// fails with `InvalidData`.
+///
+/// A tar member cannot outlive the `tar::Archive` that yields it, so the
+/// tarball is walked once to find the member's offset in the decompressed
+/// stream and then decompressed again from the start, skipping to it.  To
+/// read many members of one tarball, use [`walk_tar_members`] instead.
+pub fn open_member(path: &Path) -> io::Result<Box<dyn Read + Send>> {
+    let (archive, member) = split_member_path(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("'{}' is not an archive member path", path.display()),
+        )
+    })?;
+    let file = BufReader::new(File::open(&archive)?);
+    let reader: Box<dyn Read + Send> = match ArchiveKind::from_path(&archive) {
+        Some(ArchiveKind::Gzip) => Box::new(flate2::read::MultiGzDecoder::new(file)),
+        Some(ArchiveKind::Zip) => open_zip_member(file, &member)?,
+        Some(ArchiveKind::TarGz) => {
+            let (offset, size) = find_tar_member(file, &member)?.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("'{member}' not found in '{}'", archive.display()),
+                )
+            })?;
+            let mut decoder =
+                flate2::read::MultiGzDecoder::new(BufReader::new(File::open(&archive)?));
+            let skipped = io::copy(&mut (&mut decoder).take(offset), &mut io::sink())?;
+            if skipped < offset {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("'{}' ended before member '{member}'", archive.display()),
+                ));
+            }
+            Box::new(decoder.take(size))
+        }
+        // split_member_path only accepts supported archive names.
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("'{}' is not a supported archive", archive.display()),
+            ))
+        }
+    };
+    Ok(Box::new(Bounded {
+        inner: reader,
+        remaining: MAX_ARCHIVE_MEMBER_BYTES,
+    }))
+}
+
+/// Decompress the member named by the virtual path `path` into memory, for
+/// readers that need the whole member at once (`.evtx`).  Same errors as
+/// [`open_member`].
+pub fn read_member(path: &Path) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    open_member(path)?.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Stream one zip member.  A `zip::read::ZipFile` borrows its archive, so the
+/// member's data range is looked up in the central directory and read from
+/// the file directly: Stored members as-is, Deflated members through a raw
+/// deflate decoder.
+fn open_zip_member(file: BufReader<File>, member: &str) -> io::Result<Box<dyn Read + Send>> {
+    let mut zip = zip::ZipArchive::new(file).map_err(zip_to_io)?;
+    let index = zip
+        .index_for_name(member)
+        .ok_or_else(|| zip_to_io(zip::result::ZipError::FileNotFound))?;
+    let (start, compressed, method, encrypted) = {
+        let raw = zip.by_index_raw(index).map_err(zip_to_io)?;
+        (
+            raw.data_start(),
+            raw.compressed_size(),
+            raw.compression(),
+            raw.encrypted(),
+        )
+    };
+    if encrypted {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("zip member '{member}' is encrypted"),
+        ));
+    }
+    let mut file = zip.into_inner();
+    file.seek(SeekFrom::Start(start))?;
+    let data = file.take(compressed);
+    match method {
+        zip::CompressionMethod::Stored => Ok(Box::new(data)),
+        zip::CompressionMethod::Deflated => Ok(Box::new(flate2::read::DeflateDecoder::new(data))),
+        other => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("zip member '{member}' uses unsupported compression {other}"),
+        )),
+    }
+}
+
+/// Walk the tarball read from `source` once, handing each regular-file
+/// member to `visit` with its normalised name and a stream of its bytes
+/// (bounded like [`open_member`]).  Bytes `visit` leaves unread are
+/// skipped; returning `false` stops the walk.
+///
+/// Reading every member this way decompresses the archive once, where
+/// [`open_member`] per member starts again from the first byte each time.
+pub fn walk_tar_members<R, F>(source: R, mut visit: F) -> io::Result<()>
+where
+    R: Read,
+    F: FnMut(&str, &mut dyn Read) -> bool,
+{
+    let mut tar = tar::Archive::new(flate2::read::MultiGzDecoder::new(BufReader::new(source)));
+    for entry in tar.entries()? {
+        let entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = normalise_tar_name(&entry.path_bytes());
+        let mut member = Bounded {
+            inner: entry,
+            remaining: MAX_ARCHIVE_MEMBER_BYTES,
+        };
+        if !visit(&name, &mut member) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Offset of `member`'s data in the decompressed tar stream, and its size.
+fn find_tar_member(file: BufReader<File>, member: &str) -> io::Result<Option<(u64, u64)>> {
+    let mut tar = tar::Archive::new(flate2::read::MultiGzDecoder::new(file));
+    for entry in tar.entries()? {
+        let entry = entry?;
+        if entry.header().entry_type().is_file()
+            && normalise_tar_name(&entry.path_bytes()) == member
+        {
+            return Ok(Some((entry.raw_file_position(), entry.size())));
+        }
+    }
+    Ok(None)
+}
+
+/// Reader that fails once more than `remaining` bytes have been read, so a
+/// decompression bomb ends in an error rather than an endless stream.
+struct Bounded<R> {
+    inner: R,
+    remaining: u64,
+}
+
+impl<R: Read> Read for Bounded<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n as u64 > self.remaining {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "archive member exceeds the {} MiB decompression limit",
+                    MAX_ARCHIVE_MEMBER_BYTES / (1024 * 1024)
+                ),
+            ));
+        }
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+fn zip_to_io(e: zip::result::ZipError) -> io::Error {
+    match e {
+        zip::result::ZipError::Io(io) => io,
+        zip::result::ZipError::FileNotFound => {
+            io::Error::new(io::ErrorKind::NotFound, "member not found in zip archive")
+        }
+        other => io::Error::new(io::ErrorKind::InvalidData, other.to_string()),
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write_gz(path: &Path, content: &[u8]) {
+        let mut enc =
+            flate2::write::GzEncoder::new(File::create(path).unwrap(), Default::default());
+        enc.write_all(content).unwrap();
+        enc.finish().unwrap();
+    }
+
+    #[test]
+    fn test_archive_kind_from_file_name() {
+        assert_eq!(
+            ArchiveKind::from_file_name("syslog.2.gz"),
+            Some(ArchiveKind::Gzip)
+        );
+        assert_eq!(
+            ArchiveKind::from_file_name("Bundle.ZIP"),
+            Some(ArchiveKind::Zip)
+        );
+        assert_eq!(
+            ArchiveKind::from_file_name("logs.tar.gz"),
+            Some(ArchiveKind::TarGz)
+        );
+        assert_eq!(
+            ArchiveKind::from_file_name("logs.tgz"),
+            Some(ArchiveKind::TarGz)
+        );
+        assert_eq!(ArchiveKind::from_file_name("app.log"), None);
+    }
+
+    #[test]
+    fn test_member_path_round_trip_and_display() {
+        let archive = Path::new("/cases/4711/bundle.zip");
+        let virt = member_path(archive, "logs/app.log");
+        assert_eq!(virt.file_name().and_then(|n| n.to_str()), Some("app.log"));
+        assert_eq!(
+            split_member_path(&virt),
+            Some((archive.to_path_buf(), "logs/app.log".to_string()))
+        );
+        assert_eq!(display_name(&virt), "bundle.zip!/logs/app.log");
+        assert_eq!(physical_path(&virt), archive);
+
+        // A real directory containing "!/" after a non-archive name is not
+        // an archive member.
+        assert!(!is_member_path(Path::new("/weird!/app.log")));
+        assert_eq!(display_name(Path::new("/var/log/app.log")), "app.log");
+    }
+
+    #[test]
+    fn test_gzip_list_and_read() {
+        let dir = TempDir::new().unwrap();
+        let gz = dir.path().join("syslog.2.gz");
+        write_gz(&gz, b"line one\nline two\n");
+
+        let listing = list_members(&gz).unwrap();
+        assert_eq!(listing.members.len(), 1);
+        assert_eq!(listing.members[0].name, "syslog.2");
+        assert_eq!(listing.members[0].size, 18);
+
+        let bytes = read_member(&member_path(&gz, "syslog.2")).unwrap();
+        assert_eq!(bytes, b"line one\nline two\n");
+    }
+
+    #[test]
+    fn test_zip_list_and_read_skips_directories() {
+        let dir = TempDir::new().unwrap();
+        let zip_path = dir.path().join("bundle.zip");
+        {
+            let mut w = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+            let opts = zip::write::SimpleFileOptions::default();
+            w.add_directory("logs/", opts).unwrap();
+            w.start_file("logs/app.log", opts).unwrap();
+            w.write_all(b"hello from zip\n").unwrap();
+            w.finish().unwrap();
+        }
+
+        let listing = list_members(&zip_path).unwrap();
+        let names: Vec<&str> = listing.members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["logs/app.log"]);
+
+        let bytes = read_member(&member_path(&zip_path, "logs/app.log")).unwrap();
+        assert_eq!(bytes, b"hello from zip\n");
+        let missing = read_member(&member_path(&zip_path, "nope.log")).unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_tar_gz_list_and_read() {
+        let dir = TempDir::new().unwrap();
+        let tgz = dir.path().join("logs.tar.gz");
+        {
+            let enc =
+                flate2::write::GzEncoder::new(File::create(&tgz).unwrap(), Default::default());
+            let mut builder = tar::Builder::new(enc);
+            let data = b"tar line\n";
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(1_705_329_022);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, "./var/log/app.log", &data[..])
+                .unwrap();
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+
+        let listing = list_members(&tgz).unwrap();
+        assert_eq!(listing.members.len(), 1);
+        assert_eq!(listing.members[0].name, "var/log/app.log");
+        assert_eq!(
+            listing.members[0].modified.map(|t| t.timestamp()),
+            Some(1_705_329_022)
+        );
+
+        let bytes = read_member(&member_path(&tgz, "var/log/app.log")).unwrap();
+        assert_eq!(bytes, b"tar line\n");
+    }
+
+    #[test]
+    fn test_corrupt_gzip_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        let gz = dir.path().join("bad.gz");
+        std::fs::write(&gz, b"not gzip at all").unwrap();
+        assert_eq!(
+            list_members(&gz).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_open_member_streams_stored_zip_and_later_tar_members() {
+        let dir = TempDir::new().unwrap();
+        let zip_path = dir.path().join("stored.zip");
+        {
+            let mut w = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+            let opts = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Stored);
+            w.start_file("a.log", opts).unwrap();
+            w.write_all(b"first\n").unwrap();
+            w.start_file("b.log", opts).unwrap();
+            w.write_all(b"second\n").unwrap();
+            w.finish().unwrap();
+        }
+        let mut text = String::new();
+        open_member(&member_path(&zip_path, "b.log"))
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text, "second\n");
+
+        let tgz = dir.path().join("two.tar.gz");
+        {
+            let enc =
+                flate2::write::GzEncoder::new(File::create(&tgz).unwrap(), Default::default());
+            let mut builder = tar::Builder::new(enc);
+            for (name, data) in [("one.log", &b"one\n"[..]), ("two.log", &b"two\n"[..])] {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(data.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder.append_data(&mut header, name, data).unwrap();
+            }
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+        let mut text = String::new();
+        open_member(&member_path(&tgz, "two.log"))
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text, "two\n");
+        let missing = open_member(&member_path(&tgz, "three.log")).err().unwrap();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_walk_tar_members_reads_each_member_in_one_pass() {
+        let dir = TempDir::new().unwrap();
+        let tgz = dir.path().join("three.tar.gz");
+        {
+            let enc =
+                flate2::write::GzEncoder::new(File::create(&tgz).unwrap(), Default::default());
+            let mut builder = tar::Builder::new(enc);
+            for (name, data) in [
+                ("./a.log", &b"alpha\n"[..]),
+                ("b.log", &b"bravo\n"[..]),
+                ("c.log", &b"charlie\n"[..]),
+            ] {
+                let mut header = tar::Header::new_gnu();
+                header.set_size(data.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                builder.append_data(&mut header, name, data).unwrap();
+            }
+            builder.into_inner().unwrap().finish().unwrap();
+        }
+
+        let mut seen = Vec::new();
+        walk_tar_members(File::open(&tgz).unwrap(), |name, member| {
+            // b.log is left unread; the walk skips its bytes.
+            if name != "b.log" {
+                let mut text = String::new();
+                member.read_to_string(&mut text).unwrap();
+                seen.push(format!("{name}={text}"));
+            }
+            name != "c.log"
+        })
+        .unwrap();
+        assert_eq!(seen, vec!["a.log=alpha\n", "c.log=charlie\n"]);
+    }
+
+    #[test]
+    fn test_bounded_reader_errors_past_limit() {
+        let mut reader = Bounded {
+            inner: Box::new(io::Cursor::new(vec![0u8; 16])),
+            remaining: 8,
+        };
+        let err = io::copy(&mut reader, &mut io::sink()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,446 @@
+// LogSleuth - core/clock.rs
+//
+// Timezones and per-file / per-directory clock adjustment for cross-host
+// correlation.
+//
+// Logs merged from several hosts are only comparable if their timestamps
+// agree.  Two things can be wrong:
+// - the zone: many products write zone-less timestamps in server local
+//   time.  `AssumedZone` says which zone a naive timestamp is in; the parser
+//   converts with it (`FormatProfile::timezone`, overridable per file).
+//   Timestamps that carry their own offset are never re-zoned.
+// - the clock: the host clock ran fast or slow.  A `ClockRule` adds an
+//   offset to every timestamp of a file or directory.
+//
+// A `ClockRule` targets a file or a directory (every file under it); the most
+// specific rule (longest path) wins.  Offsets are applied to
+// `LogEntry::timestamp` at ingest and `retime` reverts the old rule and
+// applies the new one when rules change, so entries never need re-parsing.
+// A rule's zone overrides the profile's at parse time, so changing it means
+// re-parsing the files it covers.
+//
+// Core layer: pure logic, no I/O or UI dependencies.
+
+use crate::core::model::LogEntry;
+use crate::util::constants::MAX_CLOCK_OFFSET_SECS;
+use chrono::{DateTime, Duration, Local, LocalResult, NaiveDateTime, Offset, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Largest UTC offset of any real timezone, in minutes (UTC+14:00).
+const MAX_ZONE_OFFSET_MINS: i32 = 14 * 60;
+
+/// The timezone zone-less timestamps were written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AssumedZone {
+    /// Timestamps are UTC: no shift.
+    #[default]
+    Utc,
+    /// Timestamps are in this machine's local time, DST-aware.
+    Local,
+    /// Timestamps are at a fixed offset from UTC, in minutes east.
+    Fixed(i32),
+    /// Timestamps are in an IANA zone such as `Europe/Berlin`, DST-aware.
+    Named(chrono_tz::Tz),
+}
+
+impl AssumedZone {
+    /// Parse `UTC`, `Z`, `local`, an IANA name (`America/New_York`),
+    /// `+02:00`, `-0530`, `UTC+2` or `+2`.  An empty string is UTC.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let t = s.trim();
+        if t.is_empty() || t.eq_ignore_ascii_case("utc") || t.eq_ignore_ascii_case("z") {
+            return Ok(Self::Utc);
+        }
+        if t.eq_ignore_ascii_case("local") {
+            return Ok(Self::Local);
+        }
+        if let Ok(tz) = t.parse::<chrono_tz::Tz>() {
+            return Ok(Self::Named(tz));
+        }
+        let t = t
+            .strip_prefix("UTC")
+            .or_else(|| t.strip_prefix("utc"))
+            .unwrap_or(t)
+            .trim();
+        let invalid =
+            || format!("'{s}' is not a timezone (use UTC, Local, +HH:MM or an IANA name)");
+        let (sign, rest) = match t.as_bytes().first() {
+            Some(b'+') => (1, &t[1..]),
+            Some(b'-') => (-1, &t[1..]),
+            _ => return Err(invalid()),
+        };
+        let (hours, mins) = match rest.split_once(':') {
+            Some((h, m)) => (h, m),
+            None if rest.len() == 4 => rest.split_at(2),
+            None => (rest, "0"),
+        };
+        let hours: i32 = hours.parse().map_err(|_| invalid())?;
+        let mins: i32 = mins.parse().map_err(|_| invalid())?;
+        let total = hours * 60 + mins;
+        if mins >= 60 || total > MAX_ZONE_OFFSET_MINS {
+            return Err(invalid());
+        }
+        Ok(if total == 0 {
+            Self::Utc
+        } else {
+            Self::Fixed(sign * total)
+        })
+    }
+
+    /// `UTC`, `Local`, `UTC+02:00` or the IANA name.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Utc => "UTC".to_string(),
+            Self::Local => "Local".to_string(),
+            Self::Fixed(mins) => {
+                let sign = if *mins < 0 { '-' } else { '+' };
+                let abs = mins.unsigned_abs();
+                format!("UTC{sign}{:02}:{:02}", abs / 60, abs % 60)
+            }
+            Self::Named(tz) => tz.name().to_string(),
+        }
+    }
+
+    /// The UTC instant of wall-clock time `naive` in this zone.  An
+    /// ambiguous time (DST fall-back) resolves to the earlier instant; a time
+    /// inside a DST gap is read with the offset in force before the gap
+    /// (02:30 in a 02:00 spring-forward gap becomes 03:30).
+    pub fn to_utc(&self, naive: NaiveDateTime) -> DateTime<Utc> {
+        match self {
+            Self::Utc => naive.and_utc(),
+            Self::Fixed(mins) => {
+                let utc = naive.and_utc();
+                utc.checked_sub_signed(Duration::minutes(i64::from(*mins)))
+                    .unwrap_or(utc)
+            }
+            Self::Local => wall_to_utc(&Local, naive),
+            Self::Named(tz) => wall_to_utc(tz, naive),
+        }
+    }
+}
+
+/// `AssumedZone::to_utc` for a DST-aware zone.
+fn wall_to_utc<Tz: TimeZone>(zone: &Tz, naive: NaiveDateTime) -> DateTime<Utc> {
+    match zone.from_local_datetime(&naive) {
+        LocalResult::Single(dt) | LocalResult::Ambiguous(dt, _) => dt.with_timezone(&Utc),
+        LocalResult::None => {
+            let east = zone
+                .offset_from_utc_datetime(&naive)
+                .fix()
+                .local_minus_utc();
+            let utc = naive.and_utc();
+            utc.checked_sub_signed(Duration::seconds(i64::from(east)))
+                .unwrap_or(utc)
+        }
+    }
+}
+
+/// Clock adjustment for one file or directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockRule {
+    /// File, or directory whose files (recursively) the rule covers.
+    pub path: PathBuf,
+
+    /// Seconds added to every timestamp.  Positive when the host clock ran
+    /// slow.
+    pub offset_secs: i64,
+
+    /// Zone the host wrote its zone-less timestamps in, overriding the
+    /// profile's `timezone`.  `None` keeps the profile's.
+    #[serde(default)]
+    pub zone: Option<AssumedZone>,
+}
+
+impl ClockRule {
+    /// Whether the rule changes nothing (zero offset, profile timezone).
+    pub fn is_identity(&self) -> bool {
+        self.offset_secs == 0 && self.zone.is_none()
+    }
+
+    /// Whether the rule covers `file`.
+    pub fn covers(&self, file: &Path) -> bool {
+        file.starts_with(&self.path)
+    }
+
+    /// Corrected timestamp for a parsed `ts`.
+    pub fn apply(&self, ts: DateTime<Utc>) -> DateTime<Utc> {
+        ts.checked_add_signed(Duration::seconds(self.offset_secs))
+            .unwrap_or(ts)
+    }
+
+    /// Inverse of `apply`: the parsed timestamp for a corrected `ts`.
+    pub fn revert(&self, ts: DateTime<Utc>) -> DateTime<Utc> {
+        ts.checked_sub_signed(Duration::seconds(self.offset_secs))
+            .unwrap_or(ts)
+    }
+
+    /// One-line description for labels and export metadata, e.g.
+    /// `/logs/sql: +00:01:30, timestamps in Europe/Berlin`.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.offset_secs != 0 {
+            parts.push(format_offset(self.offset_secs));
+        }
+        if let Some(zone) = self.zone {
+            parts.push(format!("timestamps in {}", zone.label()));
+        }
+        if parts.is_empty() {
+            parts.push("no adjustment".to_string());
+        }
+        format!("{}: {}", self.path.display(), parts.join(", "))
+    }
+}
+
+/// The zone override for `file`: the zone of the most specific rule covering
+/// it that sets one.
+pub fn zone_for(rules: &[ClockRule], file: &Path) -> Option<AssumedZone> {
+    rules
+        .iter()
+        .filter(|r| r.zone.is_some() && r.covers(file))
+        .max_by_key(|r| r.path.components().count())
+        .and_then(|r| r.zone)
+}
+
+/// The most specific rule covering `file` (longest path), if any.
+pub fn rule_for<'a>(rules: &'a [ClockRule], file: &Path) -> Option<&'a ClockRule> {
+    rules
+        .iter()
+        .filter(|r| r.covers(file))
+        .max_by_key(|r| r.path.components().count())
+}
+
+/// Apply `rules` to freshly parsed entries.
+pub fn apply_rules(rules: &[ClockRule], entries: &mut [LogEntry]) {
+    if rules.is_empty() {
+        return;
+    }
+    for entry in entries {
+        if let (Some(ts), Some(rule)) = (entry.timestamp, rule_for(rules, &entry.source_file)) {
+            entry.timestamp = Some(rule.apply(ts));
+        }
+    }
+}
+
+/// Re-time entries that were adjusted with `old` rules so they carry the
+/// offsets of `new` rules instead.  Zone changes are not handled here: the
+/// caller re-parses those files.  The caller re-sorts afterwards.
+pub fn retime(old: &[ClockRule], new: &[ClockRule], entries: &mut [LogEntry]) {
+    for entry in entries {
+        let Some(ts) = entry.timestamp else {
+            continue;
+        };
+        let before = rule_for(old, &entry.source_file);
+        let after = rule_for(new, &entry.source_file);
+        if before.map(|r| r.offset_secs) == after.map(|r| r.offset_secs) {
+            continue;
+        }
+        let parsed = before.map_or(ts, |r| r.revert(ts));
+        entry.timestamp = Some(after.map_or(parsed, |r| r.apply(parsed)));
+    }
+}
+
+/// Parse an offset: seconds (`90`, `-15`), `90s`, `5m`, `2h`, or
+/// `[+-][HH:]MM:SS`.  Bounded to `MAX_CLOCK_OFFSET_SECS`.
+pub fn parse_offset(s: &str) -> Result<i64, String> {
+    let t = s.trim();
+    if t.is_empty() {
+        return Ok(0);
+    }
+    let invalid = || format!("'{s}' is not an offset (e.g. +90, -5m, +01:30:00)");
+    let (sign, body) = match t.as_bytes()[0] {
+        b'+' => (1, &t[1..]),
+        b'-' => (-1, &t[1..]),
+        _ => (1, t),
+    };
+    let secs: i64 = if body.contains(':') {
+        let parts: Vec<&str> = body.split(':').collect();
+        if parts.len() > 3 {
+            return Err(invalid());
+        }
+        parts.iter().try_fold(0i64, |acc, p| {
+            p.trim()
+                .parse::<i64>()
+                .ok()
+                .filter(|v| *v >= 0)
+                .map(|v| acc * 60 + v)
+                .ok_or_else(invalid)
+        })?
+    } else {
+        let (num, unit) = match body.char_indices().last() {
+            Some((i, 's')) => (&body[..i], 1),
+            Some((i, 'm')) => (&body[..i], 60),
+            Some((i, 'h')) => (&body[..i], 3_600),
+            _ => (body, 1),
+        };
+        num.trim().parse::<i64>().map_err(|_| invalid())? * unit
+    };
+    if secs > MAX_CLOCK_OFFSET_SECS {
+        return Err(format!(
+            "offset is larger than {} hours",
+            MAX_CLOCK_OFFSET_SECS / 3_600
+        ));
+    }
+    Ok(sign * secs)
+}
+
+/// `+HH:MM:SS` / `-HH:MM:SS`.
+pub fn format_offset(secs: i64) -> String {
+    let sign = if secs < 0 { '-' } else { '+' };
+    let abs = secs.unsigned_abs();
+    format!(
+        "{sign}{:02}:{:02}:{:02}",
+        abs / 3_600,
+        (abs % 3_600) / 60,
+        abs % 60
+    )
+}
+
+// =============================================================================
+// Unit tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::model::Severity;
+    use std::collections::BTreeMap;
+
+    fn ts(h: u32, m: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 5, h, m, s).unwrap()
+    }
+
+    fn entry(file: &str, t: Option<DateTime<Utc>>) -> LogEntry {
+        LogEntry {
+            id: 0,
+            timestamp: t,
+            severity: Severity::Info,
+            source_file: PathBuf::from(file).into(),
+            line_number: 1,
+            thread: None,
+            component: None,
+            message: String::new().into(),
+            raw_text: String::new(),
+            profile_id: "test".to_string().into(),
+            file_modified: None,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_offset_and_zone() {
+        assert_eq!(parse_offset("90"), Ok(90));
+        assert_eq!(parse_offset("-5m"), Ok(-300));
+        assert_eq!(parse_offset("+2h"), Ok(7_200));
+        assert_eq!(parse_offset("-01:30"), Ok(-90));
+        assert_eq!(parse_offset("+01:00:05"), Ok(3_605));
+        assert_eq!(parse_offset(""), Ok(0));
+        assert!(parse_offset("soon").is_err());
+        assert!(parse_offset("9999h").is_err());
+        assert_eq!(format_offset(-3_605), "-01:00:05");
+
+        assert_eq!(AssumedZone::parse("utc"), Ok(AssumedZone::Utc));
+        assert_eq!(AssumedZone::parse("Local"), Ok(AssumedZone::Local));
+        assert_eq!(AssumedZone::parse("+02:00"), Ok(AssumedZone::Fixed(120)));
+        assert_eq!(AssumedZone::parse("UTC-0530"), Ok(AssumedZone::Fixed(-330)));
+        assert_eq!(AssumedZone::parse("+2"), Ok(AssumedZone::Fixed(120)));
+        assert!(AssumedZone::parse("+15:00").is_err());
+        assert!(AssumedZone::parse("Mars").is_err());
+        assert_eq!(AssumedZone::Fixed(-330).label(), "UTC-05:30");
+        let berlin = AssumedZone::parse("Europe/Berlin").unwrap();
+        assert_eq!(berlin, AssumedZone::Named(chrono_tz::Europe::Berlin));
+        assert_eq!(berlin.label(), "Europe/Berlin");
+    }
+
+    /// Wall-clock times convert to UTC per zone, honouring DST.
+    #[test]
+    fn test_zone_to_utc() {
+        let wall = |mo, d, h, mi| {
+            chrono::NaiveDate::from_ymd_opt(2024, mo, d)
+                .unwrap()
+                .and_hms_opt(h, mi, 0)
+                .unwrap()
+        };
+        let at = |mo, d, h, mi| Utc.with_ymd_and_hms(2024, mo, d, h, mi, 0).unwrap();
+        assert_eq!(AssumedZone::Utc.to_utc(wall(1, 5, 10, 0)), at(1, 5, 10, 0));
+        assert_eq!(
+            AssumedZone::Fixed(-330).to_utc(wall(1, 5, 10, 0)),
+            at(1, 5, 15, 30)
+        );
+        let ny = AssumedZone::Named(chrono_tz::America::New_York);
+        assert_eq!(ny.to_utc(wall(1, 5, 10, 0)), at(1, 5, 15, 0), "EST");
+        assert_eq!(ny.to_utc(wall(7, 5, 10, 0)), at(7, 5, 14, 0), "EDT");
+        // 02:30 on 10 March 2024 does not exist in New York: read as EST,
+        // which is 03:30 EDT.
+        assert_eq!(ny.to_utc(wall(3, 10, 2, 30)), at(3, 10, 7, 30));
+        // 01:30 on 3 November 2024 happens twice: the earlier (EDT) one.
+        assert_eq!(ny.to_utc(wall(11, 3, 1, 30)), at(11, 3, 5, 30));
+    }
+
+    /// The most specific rule wins; apply and revert are inverses.  Zone
+    /// overrides are inherited from the closest rule that sets one.
+    #[test]
+    fn test_rules_apply_most_specific_and_revert() {
+        let rules = vec![
+            ClockRule {
+                path: PathBuf::from("/logs/sql"),
+                offset_secs: 90,
+                zone: Some(AssumedZone::Fixed(120)),
+            },
+            ClockRule {
+                path: PathBuf::from("/logs/sql/agent.log"),
+                offset_secs: -7_200,
+                zone: None,
+            },
+        ];
+        let mut entries = vec![
+            entry("/logs/sql/errorlog", Some(ts(10, 0, 0))),
+            entry("/logs/sql/agent.log", Some(ts(10, 0, 0))),
+            entry("/logs/client/app.log", Some(ts(10, 0, 0))),
+            entry("/logs/sql/errorlog", None),
+        ];
+        apply_rules(&rules, &mut entries);
+        assert_eq!(entries[0].timestamp, Some(ts(10, 1, 30)));
+        assert_eq!(entries[1].timestamp, Some(ts(8, 0, 0)));
+        assert_eq!(entries[2].timestamp, Some(ts(10, 0, 0)));
+        assert_eq!(entries[3].timestamp, None);
+
+        for rule in &rules {
+            assert_eq!(rule.revert(rule.apply(ts(10, 0, 0))), ts(10, 0, 0));
+        }
+        assert_eq!(
+            zone_for(&rules, Path::new("/logs/sql/agent.log")),
+            Some(AssumedZone::Fixed(120))
+        );
+        assert_eq!(zone_for(&rules, Path::new("/logs/client/app.log")), None);
+        assert!(rules[0]
+            .describe()
+            .ends_with("sql: +00:01:30, timestamps in UTC+02:00"));
+    }
+
+    /// Changing the rules re-times entries from their parsed values.
+    #[test]
+    fn test_retime_between_rule_sets() {
+        let old = vec![ClockRule {
+            path: PathBuf::from("/logs/sql"),
+            offset_secs: 90,
+            zone: None,
+        }];
+        let new = vec![ClockRule {
+            path: PathBuf::from("/logs/sql"),
+            offset_secs: -60,
+            zone: None,
+        }];
+        let mut entries = vec![
+            entry("/logs/sql/errorlog", Some(ts(10, 0, 0))),
+            entry("/logs/web/access.log", Some(ts(10, 0, 0))),
+        ];
+        apply_rules(&old, &mut entries);
+        retime(&old, &new, &mut entries);
+        assert_eq!(entries[0].timestamp, Some(ts(9, 59, 0)));
+        assert_eq!(entries[1].timestamp, Some(ts(10, 0, 0)));
+        retime(&new, &[], &mut entries);
+        assert_eq!(entries[0].timestamp, Some(ts(10, 0, 0)));
+        assert!(old[0].describe().ends_with("sql: +00:01:30"));
+    }
+}
@@ -0,0 +1,326 @@
+// LogSleuth - core/compare.rs
+//
+// Compare mode: diff two entry sets ("what's new in the failing run").
+//
+// Each side is an `EntrySelector` over the loaded entries: one file, every
+// file under a directory (a scan root), or a time range.  Messages are keyed
+// by the normalised first line (`filter::normalize_message`), so the same
+// message with different numbers, IPs or GUIDs counts as one.  The report
+// lists messages only in A, only in B, and messages whose share of their
+// set changed by at least `COMPARE_CHANGE_RATIO`.
+//
+// Core layer: pure logic, no I/O or UI dependencies.
+
+use crate::core::filter::normalize_message;
+use crate::core::model::{LogEntry, Severity};
+use crate::util::constants::{COMPARE_CHANGE_RATIO, COMPARE_MIN_CHANGE_COUNT};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Which entries make up one side of a comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntrySelector {
+    /// Entries of one source file.
+    File(PathBuf),
+    /// Entries of every source file under a directory.
+    Directory(PathBuf),
+    /// Entries whose parsed timestamp is within `start..=end`.  Entries
+    /// without a timestamp are never selected.
+    TimeRange {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+}
+
+impl EntrySelector {
+    /// Whether `entry` belongs to this side.
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        match self {
+            Self::File(path) => *entry.source_file == **path,
+            Self::Directory(dir) => entry.source_file.starts_with(dir),
+            Self::TimeRange { start, end } => {
+                entry.timestamp.is_some_and(|ts| ts >= *start && ts <= *end)
+            }
+        }
+    }
+
+    /// Short description for headers and filter labels.
+    pub fn label(&self) -> String {
+        match self {
+            Self::File(path) => crate::core::archive::display_name(path).into_owned(),
+            Self::Directory(dir) => format!("{}/", dir.display()),
+            Self::TimeRange { start, end } => format!(
+                "{} \u{2013} {}",
+                start.format("%Y-%m-%d %H:%M:%S"),
+                end.format("%Y-%m-%d %H:%M:%S")
+            ),
+        }
+    }
+}
+
+/// How a message differs between the two sides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CompareKind {
+    /// Present in A, absent from B.
+    OnlyA,
+    /// Present in B, absent from A.
+    OnlyB,
+    /// Present in both, with a much different share of each set.
+    Changed,
+}
+
+/// One normalised message and its occurrences on each side.
+#[derive(Debug, Clone)]
+pub struct CompareRow {
+    pub kind: CompareKind,
+
+    /// Normalised first line of the message.
+    pub template: String,
+
+    /// `LogEntry::id` of the occurrences in A, in entry order.
+    pub ids_a: Vec<u64>,
+
+    /// `LogEntry::id` of the occurrences in B, in entry order.
+    pub ids_b: Vec<u64>,
+
+    /// Most severe level among all occurrences.
+    pub severity: Severity,
+}
+
+/// Result of comparing two entry sets.
+#[derive(Debug, Clone, Default)]
+pub struct CompareReport {
+    /// Differing messages grouped by kind (only A, only B, changed).  Within
+    /// a kind, rows are sorted by count (only A / only B) or by the size of
+    /// the change.
+    pub rows: Vec<CompareRow>,
+
+    /// Number of entries selected by side A.
+    pub total_a: usize,
+
+    /// Number of entries selected by side B.
+    pub total_b: usize,
+
+    /// Human-readable description of side A.
+    pub label_a: String,
+
+    /// Human-readable description of side B.
+    pub label_b: String,
+}
+
+impl CompareRow {
+    /// B's share of its set divided by A's share of its set.  Infinite for
+    /// messages only in B, zero for messages only in A.
+    pub fn ratio(&self, total_a: usize, total_b: usize) -> f64 {
+        let share_a = self.ids_a.len() as f64 / total_a.max(1) as f64;
+        let share_b = self.ids_b.len() as f64 / total_b.max(1) as f64;
+        if share_a == 0.0 {
+            f64::INFINITY
+        } else {
+            share_b / share_a
+        }
+    }
+}
+
+/// Compare the entries selected by `a` with those selected by `b`.
+pub fn compare(entries: &[LogEntry], a: &EntrySelector, b: &EntrySelector) -> CompareReport {
+    // key -> (ids in A, ids in B, most severe level)
+    let mut groups: HashMap<String, (Vec<u64>, Vec<u64>, Severity)> = HashMap::new();
+    let mut total_a = 0;
+    let mut total_b = 0;
+
+    for entry in entries {
+        let in_a = a.matches(entry);
+        let in_b = b.matches(entry);
+        if !in_a && !in_b {
+            continue;
+        }
+        let first_line = entry.message().lines().next().unwrap_or("");
+        let group = groups
+            .entry(normalize_message(first_line))
+            .or_insert_with(|| (Vec::new(), Vec::new(), entry.severity));
+        group.2 = group.2.min(entry.severity);
+        if in_a {
+            group.0.push(entry.id);
+            total_a += 1;
+        }
+        if in_b {
+            group.1.push(entry.id);
+            total_b += 1;
+        }
+    }
+
+    let mut rows: Vec<CompareRow> = groups
+        .into_iter()
+        .filter_map(|(template, (ids_a, ids_b, severity))| {
+            let kind = if ids_b.is_empty() {
+                CompareKind::OnlyA
+            } else if ids_a.is_empty() {
+                CompareKind::OnlyB
+            } else {
+                CompareKind::Changed
+            };
+            let row = CompareRow {
+                kind,
+                template,
+                ids_a,
+                ids_b,
+                severity,
+            };
+            if kind == CompareKind::Changed {
+                let ratio = row.ratio(total_a, total_b);
+                let big_enough = row.ids_a.len().max(row.ids_b.len()) >= COMPARE_MIN_CHANGE_COUNT;
+                let changed = ratio >= COMPARE_CHANGE_RATIO || ratio <= 1.0 / COMPARE_CHANGE_RATIO;
+                if !(big_enough && changed) {
+                    return None;
+                }
+            }
+            Some(row)
+        })
+        .collect();
+
+    let change_size = |r: &CompareRow| r.ratio(total_a, total_b).ln().abs();
+    rows.sort_by(|x, y| {
+        x.kind
+            .cmp(&y.kind)
+            .then_with(|| match x.kind {
+                CompareKind::OnlyA => y.ids_a.len().cmp(&x.ids_a.len()),
+                CompareKind::OnlyB => y.ids_b.len().cmp(&x.ids_b.len()),
+                CompareKind::Changed => change_size(y).total_cmp(&change_size(x)),
+            })
+            .then_with(|| x.template.cmp(&y.template))
+    });
+
+    CompareReport {
+        rows,
+        total_a,
+        total_b,
+        label_a: a.label(),
+        label_b: b.label(),
+    }
+}
+
+// =============================================================================
+// Unit tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::collections::BTreeMap;
+
+    fn entry(id: u64, file: &str, hour: u32, severity: Severity, msg: &str) -> LogEntry {
+        LogEntry {
+            id,
+            timestamp: Some(Utc.with_ymd_and_hms(2024, 5, 1, hour, 0, 0).unwrap()),
+            severity,
+            source_file: PathBuf::from(file).into(),
+            line_number: id + 1,
+            thread: None,
+            component: None,
+            message: msg.to_string().into(),
+            raw_text: msg.to_string(),
+            profile_id: "test".to_string().into(),
+            file_modified: None,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Yesterday's successful run against today's failing run: the failure
+    /// is only in B, the success line only in A, and a retry message that
+    /// became much more frequent is reported as changed.
+    #[test]
+    fn test_compare_runs_only_and_changed() {
+        let mut entries = vec![
+            entry(0, "/logs/mon/job.log", 1, Severity::Info, "Job 17 started"),
+            entry(
+                1,
+                "/logs/mon/job.log",
+                1,
+                Severity::Info,
+                "Job 17 finished: Success",
+            ),
+            entry(2, "/logs/mon/job.log", 1, Severity::Warning, "Retry 1 of 5"),
+            entry(3, "/logs/tue/job.log", 2, Severity::Info, "Job 18 started"),
+            entry(
+                4,
+                "/logs/tue/job.log",
+                2,
+                Severity::Error,
+                "Job 18 failed: timeout",
+            ),
+        ];
+        for i in 0..5 {
+            for (id, file) in [(20 + i, "/logs/mon/job.log"), (30 + i, "/logs/tue/job.log")] {
+                entries.push(entry(
+                    id,
+                    file,
+                    1,
+                    Severity::Info,
+                    &format!("Copied block {i}"),
+                ));
+            }
+        }
+        for i in 0..6 {
+            entries.push(entry(
+                10 + i,
+                "/logs/tue/job.log",
+                2,
+                Severity::Warning,
+                &format!("Retry {i} of 5"),
+            ));
+        }
+
+        let report = compare(
+            &entries,
+            &EntrySelector::Directory(PathBuf::from("/logs/mon")),
+            &EntrySelector::Directory(PathBuf::from("/logs/tue")),
+        );
+        assert_eq!(report.total_a, 8);
+        assert_eq!(report.total_b, 13);
+
+        let kinds: Vec<(CompareKind, &str)> = report
+            .rows
+            .iter()
+            .map(|r| (r.kind, r.template.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (CompareKind::OnlyA, "Job <NUM> finished: Success"),
+                (CompareKind::OnlyB, "Job <NUM> failed: timeout"),
+                (CompareKind::Changed, "Retry <NUM> of <NUM>"),
+            ],
+            "the 'started' and 'Copied block' lines are omitted"
+        );
+        let failed = &report.rows[1];
+        assert_eq!(failed.ids_b, vec![4]);
+        assert_eq!(failed.severity, Severity::Error);
+    }
+
+    /// Time-range and file selectors; small count changes are ignored.
+    #[test]
+    fn test_compare_time_ranges_and_small_changes() {
+        let entries = vec![
+            entry(0, "a.log", 1, Severity::Info, "heartbeat"),
+            entry(1, "a.log", 1, Severity::Info, "heartbeat"),
+            entry(2, "a.log", 3, Severity::Info, "heartbeat"),
+            entry(3, "b.log", 3, Severity::Info, "other file"),
+        ];
+        let hour = |h| Utc.with_ymd_and_hms(2024, 5, 1, h, 0, 0).unwrap();
+        let report = compare(
+            &entries,
+            &EntrySelector::TimeRange {
+                start: hour(0),
+                end: hour(2),
+            },
+            &EntrySelector::File(PathBuf::from("a.log")),
+        );
+        assert_eq!(report.total_a, 2);
+        assert_eq!(report.total_b, 3);
+        assert!(report.rows.is_empty(), "got {:?}", report.rows);
+        assert!(report.label_a.contains("2024-05-01 00:00:00"));
+    }
+}
@@ -0,0 +1,307 @@
+// LogSleuth - core/correlation.rs
+//
+// Key-based correlation: link entries across files by a shared identifier
+// (GUID, trace / hex ID, `requestId=...` style value, thread) instead of by
+// time proximity.
+//
+// - `extract_keys` finds candidate keys in one entry by regex class.
+// - `CorrelationKey::matches` tests whether an entry carries the key: thread
+//   keys compare `LogEntry::thread` exactly; every other key is a
+//   case-insensitive whole-token match in the message or a structured field
+//   value (so `1234` does not match inside `512345`).
+// - `find_transaction` returns all matching entries ordered by time: the
+//   stitched transaction.
+//
+// Core layer: pure logic, no I/O or UI dependencies.
+
+use crate::core::model::LogEntry;
+use crate::util::constants::{MAX_CORRELATION_KEYS_PER_ENTRY, MIN_CORRELATION_KEY_LEN};
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// How a correlation key was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyClass {
+    /// 8-4-4-4-12 hex GUID.
+    Guid,
+    /// Long hex identifier such as a trace ID or `0x...` handle.
+    HexId,
+    /// Value of an ID-like name: `requestId=...`, `session_id: ...`, or a
+    /// structured field whose name ends in `id`.  Holds the name.
+    Named(String),
+    /// The entry's thread (e.g. CMTrace `thread="1234"`).  Matched against
+    /// `LogEntry::thread` only.
+    Thread,
+    /// Token typed or picked by the user.
+    Custom,
+}
+
+impl KeyClass {
+    /// Human-readable label for UI display.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Guid => "GUID".to_string(),
+            Self::HexId => "Hex ID".to_string(),
+            Self::Named(name) => name.clone(),
+            Self::Thread => "Thread".to_string(),
+            Self::Custom => "Token".to_string(),
+        }
+    }
+}
+
+/// An identifier to correlate entries by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationKey {
+    pub class: KeyClass,
+
+    /// The identifier text as it appears in the source entry.
+    pub token: String,
+}
+
+impl CorrelationKey {
+    /// A user-supplied token.  Returns `None` when the trimmed token is
+    /// shorter than `MIN_CORRELATION_KEY_LEN` characters.
+    pub fn custom(token: &str) -> Option<Self> {
+        let token = token.trim();
+        (token.chars().count() >= MIN_CORRELATION_KEY_LEN).then(|| Self {
+            class: KeyClass::Custom,
+            token: token.to_string(),
+        })
+    }
+
+    /// `class: token` for labels.
+    pub fn label(&self) -> String {
+        format!("{}: {}", self.class.label(), self.token)
+    }
+
+    /// Whether `entry` carries this key.
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if self.class == KeyClass::Thread {
+            return entry.thread.as_deref() == Some(self.token.as_str());
+        }
+        contains_token(entry.message(), &self.token)
+            || entry
+                .fields
+                .values()
+                .any(|v| contains_token(v, &self.token))
+    }
+}
+
+struct KeyRegexes {
+    guid: Regex,
+    hex: Regex,
+    named: Regex,
+}
+
+fn key_regexes() -> &'static KeyRegexes {
+    static INSTANCE: OnceLock<KeyRegexes> = OnceLock::new();
+    INSTANCE.get_or_init(|| KeyRegexes {
+        guid: Regex::new(
+            r"(?i)\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b",
+        )
+        .expect("GUID regex must compile"),
+        // 0x-prefixed handles, or bare runs of 12+ hex digits (trace IDs).
+        hex: Regex::new(r"(?i)\b(?:0x[0-9a-f]{6,}|[0-9a-f]{12,})\b")
+            .expect("hex ID regex must compile"),
+        // `fooId=value`, `session_id: value`, `job id "value"`.
+        named: Regex::new(
+            r#"(?i)\b([a-z][a-z0-9_.]*?[ _.-]?(?:id|key|token))\s*[=:]?\s*["']?([a-z0-9][a-z0-9_.:/-]*[a-z0-9])"#,
+        )
+        .expect("named ID regex must compile"),
+    })
+}
+
+/// Find candidate correlation keys in `entry`, most specific first: GUIDs,
+/// named IDs, hex IDs, ID-like structured fields, then the thread.  At most
+/// `MAX_CORRELATION_KEYS_PER_ENTRY`; duplicates and tokens shorter than
+/// `MIN_CORRELATION_KEY_LEN` are skipped.
+pub fn extract_keys(entry: &LogEntry) -> Vec<CorrelationKey> {
+    let re = key_regexes();
+    let mut keys: Vec<CorrelationKey> = Vec::new();
+    let mut push = |class: KeyClass, token: &str| {
+        if keys.len() >= MAX_CORRELATION_KEYS_PER_ENTRY
+            || token.chars().count() < MIN_CORRELATION_KEY_LEN
+            || keys.iter().any(|k| k.token.eq_ignore_ascii_case(token))
+        {
+            return;
+        }
+        keys.push(CorrelationKey {
+            class,
+            token: token.to_string(),
+        });
+    };
+
+    let msg = entry.message();
+    for m in re.guid.find_iter(msg) {
+        push(KeyClass::Guid, m.as_str());
+    }
+    for caps in re.named.captures_iter(msg) {
+        let value = &caps[2];
+        // A value that is a bare word (no digit) is usually prose:
+        // "the key is missing".
+        if value.chars().any(|c| c.is_ascii_digit()) && !re.guid.is_match(value) {
+            push(KeyClass::Named(caps[1].to_string()), value);
+        }
+    }
+    for m in re.hex.find_iter(msg) {
+        let token = m.as_str();
+        let inside_guid = re
+            .guid
+            .find_iter(msg)
+            .any(|g| g.start() <= m.start() && m.end() <= g.end());
+        if !inside_guid && token.chars().any(|c| c.is_ascii_digit()) {
+            push(KeyClass::HexId, token);
+        }
+    }
+    for (name, value) in &entry.fields {
+        let lower = name.to_ascii_lowercase();
+        if lower.ends_with("id") || lower.ends_with("_key") || lower.ends_with("token") {
+            push(KeyClass::Named(name.clone()), value.trim());
+        }
+    }
+    if let Some(ref thread) = entry.thread {
+        push(KeyClass::Thread, thread);
+    }
+    keys
+}
+
+/// Indices of every entry carrying `key`, ordered by timestamp (entries
+/// without one last), then by entry ID.
+pub fn find_transaction(entries: &[LogEntry], key: &CorrelationKey) -> Vec<usize> {
+    let mut indices: Vec<usize> = entries
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| key.matches(e))
+        .map(|(i, _)| i)
+        .collect();
+    indices.sort_by(|&a, &b| {
+        let (ea, eb) = (&entries[a], &entries[b]);
+        match (ea.timestamp, eb.timestamp) {
+            (Some(ta), Some(tb)) => ta.cmp(&tb),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+        .then_with(|| ea.id.cmp(&eb.id))
+    });
+    indices
+}
+
+/// ASCII-case-insensitive search for `token` in `haystack` as a whole token:
+/// where the token starts or ends with a letter or digit, the neighbouring
+/// character must not be one.
+fn contains_token(haystack: &str, token: &str) -> bool {
+    let h = haystack.as_bytes();
+    let t = token.as_bytes();
+    if t.is_empty() || h.len() < t.len() {
+        return false;
+    }
+    let word = |b: u8| b.is_ascii_alphanumeric();
+    let check_start = word(t[0]);
+    let check_end = word(t[t.len() - 1]);
+    (0..=h.len() - t.len()).any(|i| {
+        let end = i + t.len();
+        h[i..end].eq_ignore_ascii_case(t)
+            && !(check_start && i > 0 && word(h[i - 1]))
+            && !(check_end && end < h.len() && word(h[end]))
+    })
+}
+
+// =============================================================================
+// Unit tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::model::Severity;
+    use chrono::{TimeZone, Utc};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn entry(id: u64, file: &str, secs: Option<u32>, msg: &str) -> LogEntry {
+        LogEntry {
+            id,
+            timestamp: secs.map(|s| Utc.with_ymd_and_hms(2024, 6, 1, 10, 0, s).unwrap()),
+            severity: Severity::Info,
+            source_file: PathBuf::from(file).into(),
+            line_number: id + 1,
+            thread: None,
+            component: None,
+            message: msg.to_string().into(),
+            raw_text: msg.to_string(),
+            profile_id: "test".to_string().into(),
+            file_modified: None,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_extract_keys_by_class() {
+        let mut e = entry(
+            0,
+            "a.log",
+            Some(0),
+            "Request requestId=req-8812 for session 3f2504e0-4f89-11d3-9a0c-0305e82c3301 \
+             trace 4bf92f3577b34da6a3ce929d0e0e4736 took 1500 ms; the key is missing",
+        );
+        e.thread = Some("4412".to_string().into());
+        e.fields.insert("JobId".to_string(), "77231".to_string());
+
+        let keys = extract_keys(&e);
+        let got: Vec<(String, &str)> = keys
+            .iter()
+            .map(|k| (k.class.label(), k.token.as_str()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("GUID".to_string(), "3f2504e0-4f89-11d3-9a0c-0305e82c3301"),
+                ("requestId".to_string(), "req-8812"),
+                ("Hex ID".to_string(), "4bf92f3577b34da6a3ce929d0e0e4736"),
+                ("JobId".to_string(), "77231"),
+                ("Thread".to_string(), "4412"),
+            ]
+        );
+    }
+
+    /// Matching is case-insensitive and whole-token; thread keys compare the
+    /// thread field only.
+    #[test]
+    fn test_key_matches_whole_tokens() {
+        let key = CorrelationKey::custom("REQ-8812").unwrap();
+        assert!(key.matches(&entry(0, "a", None, "start req-8812 now")));
+        assert!(!key.matches(&entry(1, "a", None, "start req-88123 now")));
+        assert!(!key.matches(&entry(2, "a", None, "start xreq-8812 now")));
+        let mut with_field = entry(3, "a", None, "no id here");
+        with_field
+            .fields
+            .insert("rid".to_string(), "req-8812".to_string());
+        assert!(key.matches(&with_field));
+
+        assert!(CorrelationKey::custom(" ab ").is_none());
+
+        let thread = CorrelationKey {
+            class: KeyClass::Thread,
+            token: "12".to_string(),
+        };
+        let mut t = entry(4, "a", None, "thread 12 mentioned");
+        assert!(!thread.matches(&t));
+        t.thread = Some("12".to_string().into());
+        assert!(thread.matches(&t));
+    }
+
+    /// The transaction spans files and is ordered by time, untimed last.
+    #[test]
+    fn test_find_transaction_orders_by_time_across_files() {
+        let entries = vec![
+            entry(0, "web.log", Some(5), "GET /api id=abc123"),
+            entry(1, "db.log", Some(2), "query for abc123"),
+            entry(2, "web.log", Some(1), "unrelated"),
+            entry(3, "app.log", None, "abc123 done"),
+            entry(4, "app.log", Some(3), "handling abc123"),
+        ];
+        let key = CorrelationKey::custom("abc123").unwrap();
+        assert_eq!(find_transaction(&entries, &key), vec![1, 4, 0, 3]);
+    }
+}
@@ -8,18 +8,36 @@
 // by the app layer (app::scan), which passes sample lines here for profile
 // auto-detection.
 //
+// Archives (.gz, .zip, .tar.gz) are the one exception to the metadata-only
+// rule: their directory is read via `core::archive` so each member that
+// passes the include/exclude patterns is returned as a virtual
+// `DiscoveredFile` (`bundle.zip!/logs/app.log`) instead of the archive itself.
+//
+// Rotation families: `rotation_segment` maps `app.log.1`, `app.log.2.gz` and
+// `app-2024-01-15.log` to the logical stream `app.log` and their place in it,
+// and `group_streams` groups a file list into streams with segments oldest
+// first.  The UI shows each stream as one source (one colour, one checkbox)
+// and the scan keeps a stream's entries in segment order.  Naming only: no
+// file is opened to decide membership.
+//
 // Rule 11 compliance:
 //   - Per-file I/O errors are non-fatal and collected as warnings.
 //   - max_files is enforced with an explicit named-constant upper bound.
 //   - Exclude patterns short-circuit directory descent via filter_entry so
 //     excluded subtrees (e.g. node_modules/) are never traversed at all.
 
-use crate::core::model::DiscoveredFile;
+use crate::core::archive::{self, ArchiveKind};
+use crate::core::model::{DiscoveredFile, FileSummary};
+use crate::util::constants::MAX_ROTATION_NUMBER_DIGITS;
 use crate::util::error::DiscoveryError;
-use chrono::{DateTime, Utc};
-use std::path::Path;
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::Regex;
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 // =============================================================================
 // Configuration
@@ -293,12 +311,27 @@ where
             }
         };
 
-        // Apply exclude patterns to the filename itself (for *.gz, *.bak, etc.).
+        // Apply exclude patterns to the filename itself (for *.bak, *.tmp, and
+        // *.gz / *.zip when the user opts out of archive reading).
         if is_excluded_filename(file_name, &exclude_pats) {
             tracing::trace!(file = file_name, "Excluded by pattern");
             continue;
         }
 
+        // Archives: list the members instead of the archive itself.  Include
+        // patterns apply to member names, not to the archive name.
+        if ArchiveKind::from_file_name(file_name).is_some() {
+            let (members, member_warnings) =
+                archive_members(path, config, &include_pats, &exclude_pats);
+            warnings.extend(member_warnings);
+            for discovered in members {
+                let count = files.len() + 1;
+                on_file_found(&discovered, count);
+                files.push(discovered);
+            }
+            continue;
+        }
+
         // Apply include patterns to the filename.
         if !is_included(file_name, &include_pats) {
             tracing::trace!(file = file_name, "Not matched by include patterns");
@@ -401,6 +434,260 @@ where
     Ok((files, warnings, total_found))
 }
 
+// =============================================================================
+// Archive members
+// =============================================================================
+
+/// List the members of the archive at `path` as virtual `DiscoveredFile`s,
+/// applying `config`'s include / exclude patterns and date filter to each
+/// member.  Returns the accepted members and any non-fatal warnings.
+///
+/// Used by `discover_files` for archives found during the walk and by the app
+/// layer when the user adds an archive file directly.
+pub fn discover_archive_members(
+    path: &Path,
+    config: &DiscoveryConfig,
+) -> (Vec<DiscoveredFile>, Vec<String>) {
+    let include_pats = compile_patterns(&config.include_patterns, "include");
+    let exclude_pats = compile_patterns(&config.exclude_patterns, "exclude");
+    archive_members(path, config, &include_pats, &exclude_pats)
+}
+
+fn archive_members(
+    path: &Path,
+    config: &DiscoveryConfig,
+    include_pats: &[glob::Pattern],
+    exclude_pats: &[glob::Pattern],
+) -> (Vec<DiscoveredFile>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let listing = match archive::list_members(path) {
+        Ok(l) => l,
+        Err(e) => {
+            let msg = format!("Cannot read archive '{}': {e}", path.display());
+            tracing::debug!(warning = %msg, "Discovery warning");
+            warnings.push(msg);
+            return (Vec::new(), warnings);
+        }
+    };
+    if listing.truncated {
+        warnings.push(format!(
+            "Archive '{}' has more than {} files; only the first {} were listed.",
+            path.display(),
+            crate::util::constants::MAX_ARCHIVE_MEMBERS,
+            crate::util::constants::MAX_ARCHIVE_MEMBERS,
+        ));
+    }
+    let archive_mtime: Option<DateTime<Utc>> = std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(DateTime::<Utc>::from);
+
+    let mut files = Vec::new();
+    for member in listing.members {
+        let (dirs, member_name) = match member.name.rsplit_once('/') {
+            Some((dirs, name)) => (dirs, name),
+            None => ("", member.name.as_str()),
+        };
+        if member_name.is_empty()
+            || dirs
+                .split('/')
+                .any(|d| is_excluded_component(d, exclude_pats))
+            || is_excluded_filename(member_name, exclude_pats)
+            || !is_included(member_name, include_pats)
+        {
+            tracing::trace!(archive = %path.display(), member = %member.name, "Archive member filtered out");
+            continue;
+        }
+        let modified = member.modified.or(archive_mtime);
+        if let (Some(since), Some(mtime)) = (config.modified_since, modified) {
+            if mtime < since {
+                continue;
+            }
+        }
+        files.push(DiscoveredFile {
+            path: archive::member_path(path, &member.name),
+            size: member.size,
+            modified,
+            profile_id: None,
+            detection_confidence: 0.0,
+            is_large: member.size >= config.large_file_threshold,
+            parsing_skipped: false,
+        });
+    }
+    (files, warnings)
+}
+
+// =============================================================================
+// Rotation families
+// =============================================================================
+
+/// Place of a file within its rotation family.  Orders oldest first, so
+/// sorting a family's segments gives the order their lines were written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Segment {
+    /// Date-stamped segment (`app-2024-01-15.log`, `app.log-20240115`) and
+    /// its same-day sequence number (`app.log-20240115-2`; 0 when absent).
+    Dated(NaiveDate, u32),
+    /// Numbered segment (`app.log.3`): the higher the number, the older.
+    Numbered(Reverse<u32>),
+    /// The live file (`app.log`), or any file outside a rotation scheme.
+    Active,
+}
+
+/// One logical log stream: the segments of a rotation family, or a single
+/// file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogStream {
+    /// Path shared by every segment (`/var/log/app.log`).  It names the
+    /// family and need not exist on disk.
+    pub key: PathBuf,
+    /// Indices of the stream's files in the input, oldest segment first.
+    pub members: Vec<usize>,
+}
+
+/// The rotation family of `path` and its place in it.
+///
+/// Recognised schemes, on the file name with any `.gz` removed:
+///   - numeric suffix:  `app.log.1`, `app.log.2.gz`
+///   - date suffix:     `app.log-20240115`, `app.log.2024-01-15`
+///   - date in stem:    `app-2024-01-15.log`, `app_20240115.log`
+///
+/// A gzip archive member (`app.log.2.gz!/app.log.2`) belongs to the family of
+/// the archive's directory.  Any other file is the `Active` segment of a
+/// family keyed by its own path.
+pub fn rotation_segment(path: &Path) -> (PathBuf, Segment) {
+    let path = match archive::split_member_path(path) {
+        Some((archive, _)) if ArchiveKind::from_path(&archive) == Some(ArchiveKind::Gzip) => {
+            Cow::Owned(archive)
+        }
+        _ => Cow::Borrowed(path),
+    };
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return (path.into_owned(), Segment::Active);
+    };
+    let plain = strip_suffix_ignore_case(name, ".gz").unwrap_or(name);
+    match split_rotation_suffix(plain) {
+        Some((base, segment)) => (path.with_file_name(base), segment),
+        None if plain.len() < name.len() => (path.with_file_name(plain), Segment::Active),
+        None => (path.into_owned(), Segment::Active),
+    }
+}
+
+/// Group `paths` into logical streams, in order of each stream's first
+/// appearance.  Segments that sort equal keep their input order.
+pub fn group_streams<'a, I>(paths: I) -> Vec<LogStream>
+where
+    I: IntoIterator<Item = &'a Path>,
+{
+    let mut streams: Vec<LogStream> = Vec::new();
+    let mut segments: Vec<Vec<Segment>> = Vec::new();
+    let mut by_key: HashMap<PathBuf, usize> = HashMap::new();
+    for (idx, path) in paths.into_iter().enumerate() {
+        let (key, segment) = rotation_segment(path);
+        let slot = *by_key.entry(key.clone()).or_insert_with(|| {
+            streams.push(LogStream {
+                key,
+                members: Vec::new(),
+            });
+            segments.push(Vec::new());
+            streams.len() - 1
+        });
+        streams[slot].members.push(idx);
+        segments[slot].push(segment);
+    }
+    for (stream, segments) in streams.iter_mut().zip(segments) {
+        let mut order: Vec<(Segment, usize)> =
+            segments.into_iter().zip(stream.members.clone()).collect();
+        order.sort_by_key(|&(segment, _)| segment);
+        stream.members = order.into_iter().map(|(_, idx)| idx).collect();
+    }
+    streams
+}
+
+/// Combine the summaries of each rotation family into one summary per
+/// stream: summed counts, the combined time range, and the newest segment's
+/// profile.  Single files pass through unchanged.
+pub fn merge_stream_summaries(summaries: Vec<FileSummary>) -> Vec<FileSummary> {
+    let streams = group_streams(summaries.iter().map(|s| s.path.as_path()));
+    let mut slots: Vec<Option<FileSummary>> = summaries.into_iter().map(Some).collect();
+    streams
+        .into_iter()
+        .filter_map(|stream| {
+            let single = stream.members.len() == 1;
+            let mut parts = stream.members.iter().filter_map(|&idx| slots[idx].take());
+            let first = parts.next()?;
+            if single {
+                return Some(first);
+            }
+            Some(parts.fold(
+                FileSummary {
+                    path: stream.key,
+                    ..first
+                },
+                |merged, part| FileSummary {
+                    path: merged.path,
+                    profile_id: part.profile_id,
+                    entry_count: merged.entry_count + part.entry_count,
+                    error_count: merged.error_count + part.error_count,
+                    earliest: match (merged.earliest, part.earliest) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    },
+                    latest: merged.latest.max(part.latest),
+                    segments: merged.segments + part.segments,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Matches a date-stamped rotation segment: `<stem><sep><date>[<sep><seq>][<ext>]`.
+fn dated_segment_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"^(?P<stem>.+?)[-_.](?P<y>\d{4})[-_]?(?P<m>\d{2})[-_]?(?P<d>\d{2})(?:[-_.](?P<seq>\d{1,6}))?(?P<ext>\.[A-Za-z][A-Za-z0-9]{0,7})?$",
+        )
+        .unwrap()
+    })
+}
+
+/// Split a rotation suffix off `name`: `("app.log", Numbered(1))` for
+/// `app.log.1`.  `None` when the name carries no recognised suffix.
+fn split_rotation_suffix(name: &str) -> Option<(String, Segment)> {
+    if let Some(caps) = dated_segment_re().captures(name) {
+        let num = |group: &str| {
+            caps.name(group)
+                .and_then(|m| m.as_str().parse::<u32>().ok())
+        };
+        let date = NaiveDate::from_ymd_opt(num("y")? as i32, num("m")?, num("d")?);
+        if let Some(date) = date {
+            let ext = caps.name("ext").map_or("", |m| m.as_str());
+            let base = format!("{}{ext}", &caps["stem"]);
+            return Some((base, Segment::Dated(date, num("seq").unwrap_or(0))));
+        }
+    }
+    let (base, number) = name.rsplit_once('.')?;
+    if base.is_empty()
+        || number.is_empty()
+        || number.len() > MAX_ROTATION_NUMBER_DIGITS
+        || !number.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    Some((
+        base.to_string(),
+        Segment::Numbered(Reverse(number.parse().ok()?)),
+    ))
+}
+
+/// `name` without `suffix`, compared ASCII case-insensitively.
+fn strip_suffix_ignore_case<'a>(name: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = name.len().checked_sub(suffix.len())?;
+    (name.is_char_boundary(split) && name[split..].eq_ignore_ascii_case(suffix))
+        .then(|| &name[..split])
+}
+
 // =============================================================================
 // Glob helpers
 // =============================================================================
@@ -473,7 +760,7 @@ mod tests {
         fs::write(root.join("readme.txt"), "Just a readme\n").expect("write readme.txt");
 
         // Excluded file
-        fs::write(root.join("backup.log.gz"), "binary").expect("write .gz");
+        fs::write(root.join("backup.log.bak"), "binary").expect("write .bak");
 
         // Subdirectory
         let sub = root.join("subdir");
@@ -496,7 +783,7 @@ mod tests {
         let (files, warnings, _) = discover_files(dir.path(), &config, |_, _| {}).unwrap();
 
         // Should find app.log, service.log, readme.txt, sub/sub.log
-        // NOT backup.log.gz, NOT node_modules/module.log
+        // NOT backup.log.bak, NOT node_modules/module.log
         let paths: Vec<_> = files
             .iter()
             .map(|f| f.path.file_name().unwrap().to_str().unwrap().to_string())
@@ -508,8 +795,8 @@ mod tests {
         assert!(paths.contains(&"service.log".to_string()));
         assert!(paths.contains(&"sub.log".to_string()));
         assert!(
-            !paths.contains(&"backup.log.gz".to_string()),
-            "gz should be excluded"
+            !paths.contains(&"backup.log.bak".to_string()),
+            "bak should be excluded"
         );
         assert!(
             !paths.contains(&"module.log".to_string()),
@@ -652,4 +939,165 @@ mod tests {
             "profile_id is filled by app layer"
         );
     }
+
+    /// Archives are expanded into virtual `archive!/member` entries; members
+    /// are filtered by the same include/exclude rules as on-disk files.
+    #[test]
+    fn test_archive_members_discovered_as_virtual_files() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        {
+            let mut w = zip::ZipWriter::new(fs::File::create(root.join("bundle.zip")).unwrap());
+            let opts = zip::write::SimpleFileOptions::default();
+            w.start_file("logs/app.log", opts).unwrap();
+            w.write_all(b"[2024-01-01 12:00:00] Info zipped\n").unwrap();
+            w.start_file("logs/image.png", opts).unwrap();
+            w.write_all(b"not a log").unwrap();
+            w.start_file("node_modules/dep.log", opts).unwrap();
+            w.write_all(b"excluded").unwrap();
+            w.finish().unwrap();
+        }
+        {
+            let mut enc = flate2::write::GzEncoder::new(
+                fs::File::create(root.join("old.log.gz")).unwrap(),
+                Default::default(),
+            );
+            enc.write_all(b"[2024-01-01 11:00:00] Info rotated\n")
+                .unwrap();
+            enc.finish().unwrap();
+        }
+
+        let (files, warnings, _) =
+            discover_files(root, &DiscoveryConfig::default(), |_, _| {}).unwrap();
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+
+        let mut names: Vec<String> = files
+            .iter()
+            .map(|f| archive::display_name(&f.path).into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["bundle.zip!/logs/app.log", "old.log.gz!/old.log"]
+        );
+        assert!(files.iter().all(|f| archive::is_member_path(&f.path)));
+        let zipped = files
+            .iter()
+            .find(|f| f.path.ends_with("app.log"))
+            .expect("zip member");
+        assert_eq!(zipped.size, 34);
+    }
+
+    /// Numeric, date and gzip rotation names map to the live file's path;
+    /// names outside a rotation scheme are their own stream.
+    #[test]
+    fn test_rotation_segment_recognises_families() {
+        let dir = Path::new("/var/log");
+        let seg = |name: &str| rotation_segment(&dir.join(name));
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(seg("app.log"), (dir.join("app.log"), Segment::Active));
+        assert_eq!(
+            seg("app.log.1"),
+            (dir.join("app.log"), Segment::Numbered(Reverse(1)))
+        );
+        assert_eq!(
+            seg("app.log.2.gz"),
+            (dir.join("app.log"), Segment::Numbered(Reverse(2)))
+        );
+        assert_eq!(
+            seg("app.log.2.gz!/app.log.2"),
+            (dir.join("app.log"), Segment::Numbered(Reverse(2)))
+        );
+        assert_eq!(
+            seg("app-2024-01-15.log"),
+            (dir.join("app.log"), Segment::Dated(date(2024, 1, 15), 0))
+        );
+        assert_eq!(
+            seg("app.log-20240115-2.GZ"),
+            (dir.join("app.log"), Segment::Dated(date(2024, 1, 15), 2))
+        );
+        assert_eq!(
+            seg("access_log.2024_01_15.txt"),
+            (
+                dir.join("access_log.txt"),
+                Segment::Dated(date(2024, 1, 15), 0)
+            )
+        );
+        assert_eq!(seg("old.log.gz"), (dir.join("old.log"), Segment::Active));
+
+        // Not rotation suffixes: impossible dates, long numbers, bare numbers.
+        assert_eq!(seg("app-2024-13-45.log").0, dir.join("app-2024-13-45.log"));
+        assert_eq!(seg("build.123456").0, dir.join("build.123456"));
+        assert_eq!(seg(".1").0, dir.join(".1"));
+        // Zip members keep their own place inside the archive.
+        let member = Path::new("/b/bundle.zip!/logs/app.log.1");
+        assert_eq!(
+            rotation_segment(member).0,
+            Path::new("/b/bundle.zip!/logs/app.log")
+        );
+    }
+
+    /// Streams keep first-appearance order; segments sort oldest first.
+    #[test]
+    fn test_group_streams_orders_segments_oldest_first() {
+        let paths: Vec<PathBuf> = [
+            "app.log",
+            "other.log",
+            "app.log.1",
+            "app-2024-01-15.log",
+            "app.log.2.gz!/app.log.2",
+            "app-2024-01-14.log",
+        ]
+        .iter()
+        .map(|n| Path::new("/logs").join(n))
+        .collect();
+        let streams = group_streams(paths.iter().map(PathBuf::as_path));
+        assert_eq!(
+            streams,
+            vec![
+                LogStream {
+                    key: PathBuf::from("/logs/app.log"),
+                    members: vec![5, 3, 4, 2, 0],
+                },
+                LogStream {
+                    key: PathBuf::from("/logs/other.log"),
+                    members: vec![1],
+                },
+            ]
+        );
+    }
+
+    /// A family's summaries merge into one with the combined time range; a
+    /// lone file keeps its own path.
+    #[test]
+    fn test_merge_stream_summaries_combines_time_range() {
+        use chrono::TimeZone;
+        let at = |h| Some(Utc.with_ymd_and_hms(2024, 1, 15, h, 0, 0).unwrap());
+        let summary = |name: &str, entries, earliest, latest| FileSummary {
+            path: Path::new("/logs").join(name),
+            profile_id: "plain-text".to_string(),
+            entry_count: entries,
+            error_count: 1,
+            earliest,
+            latest,
+            segments: 1,
+        };
+        let merged = merge_stream_summaries(vec![
+            summary("app.log.1", 3, at(8), at(9)),
+            summary("other.log.1", 5, at(1), at(2)),
+            summary("app.log", 4, at(10), at(12)),
+            summary("app.log.2", 2, None, None),
+        ]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].path, Path::new("/logs/app.log"));
+        assert_eq!(merged[0].segments, 3);
+        assert_eq!(merged[0].entry_count, 9);
+        assert_eq!(merged[0].error_count, 3);
+        assert_eq!((merged[0].earliest, merged[0].latest), (at(8), at(12)));
+        assert_eq!(merged[1].path, Path::new("/logs/other.log.1"));
+        assert_eq!(merged[1].segments, 1);
+    }
 }
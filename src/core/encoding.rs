@@ -0,0 +1,350 @@
+// LogSleuth - core/encoding.rs
+//
+// Streaming text decoding for log files.
+//
+// - `Utf8Reader` wraps any `Read`, sniffs a byte-order mark and yields UTF-8:
+//   UTF-16 LE / BE input (CBS.log, WindowsUpdate.log) is transcoded one
+//   chunk at a time; UTF-8 and BOM-less input passes through unchanged.
+// - `LineReader` splits a `BufRead` into lines without holding more than one
+//   line in memory.  Lines longer than the cap are cut (the rest of the line
+//   is discarded) and invalid UTF-8 is replaced lossily, line by line.
+//
+// Together they let the parser walk a multi-GB file while holding roughly
+// one read chunk and one line, instead of the whole file as a `String`.
+//
+// Core layer: works on `Read` / `BufRead` trait objects only, never opens
+// files itself.
+
+use std::io::{self, BufRead, Read};
+
+/// Text encoding of a log file, detected from its byte-order mark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    /// UTF-8, with or without a BOM.  Also used for files with no BOM at
+    /// all; invalid bytes are replaced by `LineReader`.
+    Utf8,
+    /// UTF-16 little-endian (BOM `FF FE`).
+    Utf16Le,
+    /// UTF-16 big-endian (BOM `FE FF`).
+    Utf16Be,
+}
+
+impl TextEncoding {
+    /// Detect the encoding from the first bytes of a file.  Returns the
+    /// encoding and the length of the BOM to skip.
+    pub fn sniff(prefix: &[u8]) -> (Self, usize) {
+        match prefix {
+            [0xEF, 0xBB, 0xBF, ..] => (Self::Utf8, 3),
+            [0xFF, 0xFE, ..] => (Self::Utf16Le, 2),
+            [0xFE, 0xFF, ..] => (Self::Utf16Be, 2),
+            _ => (Self::Utf8, 0),
+        }
+    }
+
+    /// Human-readable label for log messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16 LE",
+            Self::Utf16Be => "UTF-16 BE",
+        }
+    }
+}
+
+/// `Read` adapter that yields UTF-8 for UTF-8 or UTF-16 input.
+///
+/// The BOM is sniffed on the first read.  UTF-16 input is transcoded in
+/// chunks of `chunk_size` bytes; a code unit or surrogate pair split across
+/// two chunks is carried over to the next one.  Unpaired surrogates become
+/// U+FFFD, and a trailing odd byte is dropped.
+pub struct Utf8Reader<R> {
+    inner: R,
+    chunk_size: usize,
+    /// `None` until the first read has sniffed the BOM.
+    encoding: Option<TextEncoding>,
+    /// Raw UTF-16 bytes not yet decoded (an odd byte or a high surrogate).
+    pending: Vec<u8>,
+    /// Decoded UTF-8 not yet handed to the caller, from `out_pos`.
+    out: Vec<u8>,
+    out_pos: usize,
+    eof: bool,
+}
+
+impl<R: Read> Utf8Reader<R> {
+    /// Wrap `inner`, reading it `chunk_size` bytes at a time.
+    pub fn new(inner: R, chunk_size: usize) -> Self {
+        Self {
+            inner,
+            chunk_size: chunk_size.max(4),
+            encoding: None,
+            pending: Vec::new(),
+            out: Vec::new(),
+            out_pos: 0,
+            eof: false,
+        }
+    }
+
+    /// The detected encoding, once the first read has happened.
+    pub fn encoding(&self) -> Option<TextEncoding> {
+        self.encoding
+    }
+
+    /// Read up to `chunk_size` bytes from the inner reader into `pending`.
+    /// Sets `eof` when the inner reader is exhausted.
+    fn read_chunk(&mut self) -> io::Result<()> {
+        let start = self.pending.len();
+        self.pending.resize(start + self.chunk_size, 0);
+        let n = loop {
+            match self.inner.read(&mut self.pending[start..]) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.pending.truncate(start);
+                    return Err(e);
+                }
+            }
+        };
+        self.pending.truncate(start + n);
+        self.eof = n == 0;
+        Ok(())
+    }
+
+    /// Sniff the BOM from the first bytes of the input.
+    fn sniff(&mut self) -> io::Result<TextEncoding> {
+        while self.pending.len() < 3 && !self.eof {
+            self.read_chunk()?;
+        }
+        let (encoding, bom_len) = TextEncoding::sniff(&self.pending);
+        self.pending.drain(..bom_len);
+        self.encoding = Some(encoding);
+        Ok(encoding)
+    }
+
+    /// Decode the complete UTF-16 code units in `pending` into `out`.
+    fn decode_utf16(&mut self, big_endian: bool) {
+        let mut units: Vec<u16> = self
+            .pending
+            .chunks_exact(2)
+            .map(|c| {
+                if big_endian {
+                    u16::from_be_bytes([c[0], c[1]])
+                } else {
+                    u16::from_le_bytes([c[0], c[1]])
+                }
+            })
+            .collect();
+        let mut keep = self.pending.len() % 2;
+        // A high surrogate at the end of the chunk waits for its partner.
+        if !self.eof && units.last().is_some_and(|u| (0xD800..0xDC00).contains(u)) {
+            units.pop();
+            keep += 2;
+        }
+        if self.eof {
+            keep = 0; // a trailing odd byte is dropped
+        }
+        let mut buf = [0u8; 4];
+        for c in char::decode_utf16(units) {
+            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+            self.out
+                .extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+        }
+        let consumed = self.pending.len() - keep;
+        self.pending.drain(..consumed);
+    }
+}
+
+impl<R: Read> Read for Utf8Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let encoding = match self.encoding {
+            Some(e) => e,
+            None => self.sniff()?,
+        };
+        while self.out_pos >= self.out.len() {
+            self.out.clear();
+            self.out_pos = 0;
+            if self.pending.is_empty() || encoding != TextEncoding::Utf8 {
+                if self.eof {
+                    if encoding != TextEncoding::Utf8 && !self.pending.is_empty() {
+                        self.decode_utf16(encoding == TextEncoding::Utf16Be);
+                        continue;
+                    }
+                    return Ok(0);
+                }
+                self.read_chunk()?;
+            }
+            match encoding {
+                TextEncoding::Utf8 => std::mem::swap(&mut self.out, &mut self.pending),
+                TextEncoding::Utf16Le => self.decode_utf16(false),
+                TextEncoding::Utf16Be => self.decode_utf16(true),
+            }
+        }
+        let n = buf.len().min(self.out.len() - self.out_pos);
+        buf[..n].copy_from_slice(&self.out[self.out_pos..self.out_pos + n]);
+        self.out_pos += n;
+        Ok(n)
+    }
+}
+
+/// Splits a `BufRead` into lines, like `str::lines`: lines end at `\n`, a
+/// `\r` before it is stripped, and a final line without a newline is still
+/// returned.
+///
+/// At most `max_line_len` bytes of a line are kept; the rest of an
+/// over-long line is read and discarded, so one pathological line cannot
+/// allocate without bound (Rule 11).
+pub struct LineReader<R> {
+    reader: R,
+    max_line_len: usize,
+    bytes: Vec<u8>,
+    line: String,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R, max_line_len: usize) -> Self {
+        Self {
+            reader,
+            max_line_len,
+            bytes: Vec::new(),
+            line: String::new(),
+        }
+    }
+
+    /// The next line, or `None` at end of input.  Invalid UTF-8 is replaced
+    /// with U+FFFD.
+    pub fn next_line(&mut self) -> io::Result<Option<&str>> {
+        self.bytes.clear();
+        let mut read_any = false;
+        let mut ended = false;
+        loop {
+            let available = match self.reader.fill_buf() {
+                Ok(b) => b,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            if available.is_empty() {
+                break;
+            }
+            read_any = true;
+            let (take, used) = match available.iter().position(|&b| b == b'\n') {
+                Some(i) => {
+                    ended = true;
+                    (i, i + 1)
+                }
+                None => (available.len(), available.len()),
+            };
+            let room = self.max_line_len.saturating_sub(self.bytes.len());
+            self.bytes.extend_from_slice(&available[..take.min(room)]);
+            self.reader.consume(used);
+            if ended {
+                break;
+            }
+        }
+        if !read_any {
+            return Ok(None);
+        }
+        if ended && self.bytes.last() == Some(&b'\r') {
+            self.bytes.pop();
+        }
+        self.line.clear();
+        match std::str::from_utf8(&self.bytes) {
+            Ok(s) => self.line.push_str(s),
+            Err(_) => self.line.push_str(&String::from_utf8_lossy(&self.bytes)),
+        }
+        Ok(Some(&self.line))
+    }
+
+    /// The underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+}
+
+// =============================================================================
+// Unit tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Read every line of `bytes` through a `Utf8Reader` with a tiny chunk
+    /// size, so code units and lines straddle chunk boundaries.
+    fn decode_lines(bytes: &[u8], chunk: usize, max_line: usize) -> Vec<String> {
+        let reader = std::io::BufReader::with_capacity(chunk, Utf8Reader::new(bytes, chunk));
+        let mut lines = LineReader::new(reader, max_line);
+        let mut out = Vec::new();
+        while let Some(line) = lines.next_line().unwrap() {
+            out.push(line.to_string());
+        }
+        out
+    }
+
+    fn utf16(text: &str, big_endian: bool) -> Vec<u8> {
+        let mut bytes = if big_endian {
+            vec![0xFE, 0xFF]
+        } else {
+            vec![0xFF, 0xFE]
+        };
+        for unit in text.encode_utf16() {
+            if big_endian {
+                bytes.extend_from_slice(&unit.to_be_bytes());
+            } else {
+                bytes.extend_from_slice(&unit.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// UTF-16 in both byte orders, including a surrogate pair, decodes the
+    /// same at every chunk size.
+    #[test]
+    fn test_utf16_transcodes_across_chunks() {
+        let text = "2026-04-04 12:00:00 start \u{1F600}\r\nnext \u{00E9}t\u{00E9}\nlast";
+        let expected = vec![
+            "2026-04-04 12:00:00 start \u{1F600}".to_string(),
+            "next \u{00E9}t\u{00E9}".to_string(),
+            "last".to_string(),
+        ];
+        for big_endian in [false, true] {
+            let bytes = utf16(text, big_endian);
+            for chunk in [4, 5, 7, 64] {
+                assert_eq!(
+                    decode_lines(&bytes, chunk, 1024),
+                    expected,
+                    "big_endian={big_endian} chunk={chunk}"
+                );
+            }
+        }
+    }
+
+    /// UTF-8 passes through with its BOM stripped; invalid bytes are
+    /// replaced per line and over-long lines are cut without losing the
+    /// next line.
+    #[test]
+    fn test_utf8_lines_are_lossy_and_capped() {
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(b"first\nbad \xFF byte\n");
+        bytes.extend_from_slice(&[b'x'; 40]);
+        bytes.extend_from_slice(b"\n\nend\r\n");
+        let lines = decode_lines(&bytes, 8, 10);
+        assert_eq!(
+            lines,
+            vec!["first", "bad \u{FFFD} byte", "xxxxxxxxxx", "", "end"]
+        );
+        assert!(decode_lines(b"", 8, 10).is_empty());
+    }
+
+    #[test]
+    fn test_sniff_encoding() {
+        assert_eq!(
+            TextEncoding::sniff(b"\xFF\xFEa"),
+            (TextEncoding::Utf16Le, 2)
+        );
+        assert_eq!(TextEncoding::sniff(b"\xFE\xFF"), (TextEncoding::Utf16Be, 2));
+        assert_eq!(
+            TextEncoding::sniff(b"\xEF\xBB\xBFx"),
+            (TextEncoding::Utf8, 3)
+        );
+        assert_eq!(TextEncoding::sniff(b"ab"), (TextEncoding::Utf8, 0));
+    }
+}
@@ -0,0 +1,693 @@
+// LogSleuth - core/entry_store.rs
+//
+// Out-of-core entry store: an append-only segment file of encoded
+// `LogEntry` records plus a compact in-memory index (offset, length and
+// time sort key per record, about 24 bytes per entry).
+//
+// Used once a session outgrows `max_total_entries`: the store then holds
+// every entry of the session.  The filters run over all of it in batches
+// (`for_each_batch`) and keep the matching positions in time order
+// (`sort_by_time`); the timeline reads the rows it shows from those
+// positions (`read`), and export streams them.  Reads are sorted by offset
+// so a page is read in one forward pass over the file.
+//
+// Unloading files only drops their index records (`remove_files`), and
+// re-timing rewrites just the affected records at the end of the file
+// (`rewrite_files`); the old bytes stay behind as dead space until the store
+// is dropped.
+//
+// The segment file lives in the data directory and is deleted when the store
+// is dropped.
+//
+// Record encoding (little-endian): u32 body length, then id, timestamp,
+// severity, file / profile indices into per-store tables, line number,
+// thread, component, message (a span of the raw text where possible), raw
+// text, fields and file mtime.  Paths and profile IDs are interned, so they
+// are stored once per store.
+
+use crate::core::model::{LogEntry, MessageText, Severity};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// File name prefix and extension of segment files.
+const SEGMENT_PREFIX: &str = "entries-";
+const SEGMENT_EXT: &str = "seg";
+
+/// Entries decoded per batch by `for_each_batch`.
+const READ_BATCH: usize = 10_000;
+
+/// Index record for one stored entry.
+#[derive(Debug, Clone, Copy)]
+struct StoredEntry {
+    offset: u64,
+    len: u32,
+    /// Index into `EntryStore::files`.
+    file: u32,
+    /// Timestamp in milliseconds; `i64::MAX` for untimed entries so they
+    /// sort last, like the timeline.
+    sort_key: i64,
+}
+
+/// Disk-backed, append-only store of log entries.
+#[derive(Debug)]
+pub struct EntryStore {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    reader: File,
+    /// Bytes written so far (the offset of the next record).
+    end: u64,
+    index: Vec<StoredEntry>,
+    /// Record positions in time order; rebuilt lazily after appends.
+    time_order: Vec<u32>,
+    order_dirty: bool,
+    files: Vec<Arc<Path>>,
+    file_ids: HashMap<Arc<Path>, u32>,
+    profiles: Vec<Arc<str>>,
+    profile_ids: HashMap<Arc<str>, u32>,
+    max_id: Option<u64>,
+    /// Scratch buffer for encoding.
+    buf: Vec<u8>,
+}
+
+impl EntryStore {
+    /// Create an empty store with a new segment file in `dir` (created if
+    /// missing).
+    pub fn create(dir: &Path) -> io::Result<Self> {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!(
+            "{SEGMENT_PREFIX}{}-{}.{SEGMENT_EXT}",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&path)?;
+        let reader = file.try_clone()?;
+        tracing::debug!(path = %path.display(), "Entry store created");
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            reader,
+            end: 0,
+            index: Vec::new(),
+            time_order: Vec::new(),
+            order_dirty: false,
+            files: Vec::new(),
+            file_ids: HashMap::new(),
+            profiles: Vec::new(),
+            profile_ids: HashMap::new(),
+            max_id: None,
+            buf: Vec::new(),
+        })
+    }
+
+    /// Number of stored entries.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Largest `LogEntry::id` stored.
+    pub fn max_id(&self) -> Option<u64> {
+        self.max_id
+    }
+
+    /// Path of the segment file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append `entries` to the end of the store.
+    pub fn append<'a>(
+        &mut self,
+        entries: impl IntoIterator<Item = &'a LogEntry>,
+    ) -> io::Result<()> {
+        for entry in entries {
+            let mut buf = std::mem::take(&mut self.buf);
+            buf.clear();
+            let stored = self.write_record(entry, &mut buf)?;
+            self.index.push(stored);
+            self.max_id = Some(self.max_id.map_or(entry.id, |m| m.max(entry.id)));
+            self.buf = buf;
+        }
+        self.order_dirty = true;
+        Ok(())
+    }
+
+    /// Encode `entry` into `buf` and write it at the end of the file.
+    fn write_record(&mut self, entry: &LogEntry, buf: &mut Vec<u8>) -> io::Result<StoredEntry> {
+        buf.clear();
+        let file = self.encode(entry, buf);
+        let len = u32::try_from(buf.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "entry too large"))?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(buf)?;
+        let stored = StoredEntry {
+            offset: self.end + 4,
+            len,
+            file,
+            sort_key: sort_key(entry.timestamp),
+        };
+        self.end += 4 + u64::from(len);
+        Ok(stored)
+    }
+
+    /// Drop every entry whose source file matches `remove`.  Only the index
+    /// changes (no file I/O); returns the number of entries removed.
+    pub fn remove_files(&mut self, remove: impl Fn(&Path) -> bool) -> usize {
+        let gone: Vec<bool> = self.files.iter().map(|f| remove(f)).collect();
+        let before = self.index.len();
+        self.index.retain(|s| !gone[s.file as usize]);
+        self.order_dirty = true;
+        before - self.index.len()
+    }
+
+    /// Pass the entries whose source file matches `affected` through `edit`
+    /// (in batches) and write them back.  The new records are appended and
+    /// the index repointed, so the cost is that of the affected entries, not
+    /// the whole store.  `edit` must not add or remove entries.  Returns the
+    /// number of entries rewritten.
+    pub fn rewrite_files(
+        &mut self,
+        affected: impl Fn(&Path) -> bool,
+        mut edit: impl FnMut(&mut [LogEntry]),
+    ) -> io::Result<usize> {
+        let hit: Vec<bool> = self.files.iter().map(|f| affected(f)).collect();
+        let positions: Vec<u32> = (0..self.index.len() as u32)
+            .filter(|&p| hit[self.index[p as usize].file as usize])
+            .collect();
+        let mut buf = std::mem::take(&mut self.buf);
+        for chunk in positions.chunks(READ_BATCH) {
+            let mut batch = self.read_positions(chunk.to_vec())?;
+            edit(&mut batch);
+            for (&p, entry) in chunk.iter().zip(&batch) {
+                self.index[p as usize] = self.write_record(entry, &mut buf)?;
+            }
+        }
+        self.buf = buf;
+        self.order_dirty = true;
+        Ok(positions.len())
+    }
+
+    /// Append every entry of `other`, in its append order.
+    pub fn append_store(&mut self, other: &mut EntryStore) -> io::Result<()> {
+        other.for_each_batch(|batch| self.append(&batch))
+    }
+
+    /// Call `f` with every stored entry, in append order, in batches of
+    /// `READ_BATCH`; the n-th entry passed is at position n.  Used to copy a
+    /// store and to run the filters over all of it.
+    pub fn for_each_batch(
+        &mut self,
+        mut f: impl FnMut(Vec<LogEntry>) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let mut start = 0;
+        while start < self.len() {
+            let end = (start + READ_BATCH).min(self.len());
+            f(self.read_positions((start..end).map(|p| p as u32).collect())?)?;
+            start = end;
+        }
+        Ok(())
+    }
+
+    /// Up to `count` entries starting at position `start` in time order
+    /// (untimed entries last, ties in append order).
+    pub fn window(&mut self, start: usize, count: usize) -> io::Result<Vec<LogEntry>> {
+        self.refresh_time_order();
+        let end = start.saturating_add(count).min(self.time_order.len());
+        let positions = self.time_order.get(start..end).unwrap_or(&[]).to_vec();
+        self.read_positions(positions)
+    }
+
+    /// The entries at `positions` (append order, as counted by
+    /// `for_each_batch`), in the order given.
+    pub fn read(&mut self, positions: &[u32]) -> io::Result<Vec<LogEntry>> {
+        self.read_positions(positions.to_vec())
+    }
+
+    /// Sort `positions` into time order, the order of `window`: untimed
+    /// entries last, ties in append order.
+    pub fn sort_by_time(&self, positions: &mut [u32]) {
+        positions.sort_by_key(|&p| (self.index[p as usize].sort_key, p));
+    }
+
+    /// Read the entries at `positions` (append order), returned in the order
+    /// given.  Reads are issued in file order.
+    fn read_positions(&mut self, positions: Vec<u32>) -> io::Result<Vec<LogEntry>> {
+        self.writer.flush()?;
+        let mut by_offset: Vec<(usize, u32)> = positions.iter().copied().enumerate().collect();
+        by_offset.sort_by_key(|&(_, p)| p);
+        let mut out: Vec<Option<LogEntry>> = vec![None; positions.len()];
+        let mut reader = BufReader::with_capacity(1 << 16, &self.reader);
+        // Current reader position; nearby records are reached with
+        // `seek_relative`, which keeps the read buffer.
+        let mut at: Option<u64> = None;
+        let mut body = Vec::new();
+        for (slot, p) in by_offset {
+            let stored = self.index[p as usize];
+            match at {
+                Some(pos) if pos <= stored.offset => {
+                    reader.seek_relative((stored.offset - pos) as i64)?
+                }
+                _ => {
+                    reader.seek(SeekFrom::Start(stored.offset))?;
+                }
+            }
+            body.resize(stored.len as usize, 0);
+            reader.read_exact(&mut body)?;
+            at = Some(stored.offset + u64::from(stored.len));
+            out[slot] = Some(self.decode(&body)?);
+        }
+        Ok(out.into_iter().flatten().collect())
+    }
+
+    fn refresh_time_order(&mut self) {
+        if !self.order_dirty && self.time_order.len() == self.index.len() {
+            return;
+        }
+        let index = &self.index;
+        self.time_order = (0..index.len() as u32).collect();
+        self.time_order
+            .sort_by_key(|&p| (index[p as usize].sort_key, p));
+        self.order_dirty = false;
+    }
+
+    // -------------------------------------------------------------------------
+    // Encoding
+    // -------------------------------------------------------------------------
+
+    /// Encode `e` into `buf`; returns its file index.
+    fn encode(&mut self, e: &LogEntry, buf: &mut Vec<u8>) -> u32 {
+        let file = intern(&mut self.files, &mut self.file_ids, &e.source_file);
+        let profile = intern(&mut self.profiles, &mut self.profile_ids, &e.profile_id);
+        buf.extend_from_slice(&e.id.to_le_bytes());
+        put_time(buf, e.timestamp);
+        buf.push(severity_code(e.severity));
+        buf.extend_from_slice(&file.to_le_bytes());
+        buf.extend_from_slice(&profile.to_le_bytes());
+        buf.extend_from_slice(&e.line_number.to_le_bytes());
+        put_opt_str(buf, e.thread.as_deref());
+        put_opt_str(buf, e.component.as_deref());
+        // A message that is a span of the raw text is stored as the span.
+        match &e.message {
+            MessageText::Span { start, end } => {
+                buf.push(0);
+                buf.extend_from_slice(&start.to_le_bytes());
+                buf.extend_from_slice(&end.to_le_bytes());
+            }
+            MessageText::Owned(message) => {
+                buf.push(1);
+                put_str(buf, message);
+            }
+        }
+        put_str(buf, &e.raw_text);
+        buf.extend_from_slice(&(e.fields.len() as u32).to_le_bytes());
+        for (k, v) in &e.fields {
+            put_str(buf, k);
+            put_str(buf, v);
+        }
+        put_time(buf, e.file_modified);
+        file
+    }
+
+    fn decode(&self, body: &[u8]) -> io::Result<LogEntry> {
+        let mut r = Decoder { body, pos: 0 };
+        let id = r.u64()?;
+        let timestamp = r.time()?;
+        let severity = severity_from_code(r.u8()?);
+        let file = r.u32()? as usize;
+        let profile = r.u32()? as usize;
+        let line_number = r.u64()?;
+        let thread = r.opt_str()?;
+        let component = r.opt_str()?;
+        let message = match r.u8()? {
+            0 => {
+                let (start, end) = (r.u32()?, r.u32()?);
+                MessageText::Span { start, end }
+            }
+            _ => MessageText::Owned(r.str()?),
+        };
+        let raw_text = r.str()?;
+        if let MessageText::Span { start, end } = message {
+            raw_text
+                .get(start as usize..end as usize)
+                .ok_or_else(corrupt)?;
+        }
+        let field_count = r.u32()?;
+        let mut fields = BTreeMap::new();
+        for _ in 0..field_count {
+            let k = r.str()?;
+            let v = r.str()?;
+            fields.insert(k, v);
+        }
+        let file_modified = r.time()?;
+        Ok(LogEntry {
+            id,
+            timestamp,
+            severity,
+            source_file: self.files.get(file).cloned().ok_or_else(corrupt)?,
+            line_number,
+            thread: thread.map(Arc::from),
+            component: component.map(Arc::from),
+            message,
+            raw_text,
+            profile_id: self.profiles.get(profile).cloned().ok_or_else(corrupt)?,
+            fields,
+            file_modified,
+        })
+    }
+}
+
+impl Drop for EntryStore {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            tracing::debug!(path = %self.path.display(), error = %e, "Could not remove entry store");
+        }
+    }
+}
+
+/// Delete segment files left in `dir` by sessions that did not exit cleanly:
+/// files of other processes not written for at least `max_age` (another
+/// running instance may still own a recent one).  Returns the number removed.
+pub fn remove_stale_segments(dir: &Path, max_age: std::time::Duration) -> usize {
+    let own = format!("{SEGMENT_PREFIX}{}-", std::process::id());
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    read_dir
+        .flatten()
+        .filter(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            name.starts_with(SEGMENT_PREFIX)
+                && name.ends_with(SEGMENT_EXT)
+                && !name.starts_with(&own)
+                && e.metadata()
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| t.elapsed().ok())
+                    .is_some_and(|age| age >= max_age)
+        })
+        .filter(|e| std::fs::remove_file(e.path()).is_ok())
+        .count()
+}
+
+fn sort_key(ts: Option<DateTime<Utc>>) -> i64 {
+    ts.map_or(i64::MAX, |t| t.timestamp_millis())
+}
+
+fn intern<K>(table: &mut Vec<K>, ids: &mut HashMap<K, u32>, key: &K) -> u32
+where
+    K: Clone + Eq + std::hash::Hash,
+{
+    if let Some(&id) = ids.get(key) {
+        return id;
+    }
+    let id = table.len() as u32;
+    table.push(key.clone());
+    ids.insert(key.clone(), id);
+    id
+}
+
+fn severity_code(s: Severity) -> u8 {
+    match s {
+        Severity::Critical => 0,
+        Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Info => 3,
+        Severity::Debug => 4,
+        Severity::Unknown => 5,
+    }
+}
+
+fn severity_from_code(code: u8) -> Severity {
+    match code {
+        0 => Severity::Critical,
+        1 => Severity::Error,
+        2 => Severity::Warning,
+        3 => Severity::Info,
+        4 => Severity::Debug,
+        _ => Severity::Unknown,
+    }
+}
+
+fn put_str(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn put_opt_str(buf: &mut Vec<u8>, s: Option<&str>) {
+    match s {
+        Some(s) => {
+            buf.push(1);
+            put_str(buf, s);
+        }
+        None => buf.push(0),
+    }
+}
+
+fn put_time(buf: &mut Vec<u8>, t: Option<DateTime<Utc>>) {
+    match t {
+        Some(t) => {
+            buf.push(1);
+            buf.extend_from_slice(&t.timestamp().to_le_bytes());
+            buf.extend_from_slice(&t.timestamp_subsec_nanos().to_le_bytes());
+        }
+        None => buf.push(0),
+    }
+}
+
+fn corrupt() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "corrupt entry store record")
+}
+
+struct Decoder<'a> {
+    body: &'a [u8],
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, n: usize) -> io::Result<&[u8]> {
+        let end = self.pos.checked_add(n).filter(|&e| e <= self.body.len());
+        let end = end.ok_or_else(corrupt)?;
+        let bytes = &self.body[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        let mut a = [0u8; 8];
+        a.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(a))
+    }
+
+    fn str(&mut self) -> io::Result<String> {
+        let len = self.u32()? as usize;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|_| corrupt())
+    }
+
+    fn opt_str(&mut self) -> io::Result<Option<String>> {
+        match self.u8()? {
+            0 => Ok(None),
+            _ => self.str().map(Some),
+        }
+    }
+
+    fn time(&mut self) -> io::Result<Option<DateTime<Utc>>> {
+        if self.u8()? == 0 {
+            return Ok(None);
+        }
+        let secs = self.u64()? as i64;
+        let nanos = self.u32()?;
+        DateTime::from_timestamp(secs, nanos)
+            .map(Some)
+            .ok_or_else(corrupt)
+    }
+}
+
+// =============================================================================
+// Unit tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(id: u64, file: &str, secs: Option<u32>, msg: &str) -> LogEntry {
+        LogEntry {
+            id,
+            timestamp: secs.map(|s| Utc.with_ymd_and_hms(2024, 6, 1, 10, 0, s).unwrap()),
+            severity: if msg.contains("fail") {
+                Severity::Error
+            } else {
+                Severity::Info
+            },
+            source_file: PathBuf::from(file).into(),
+            line_number: id + 1,
+            thread: (id % 2 == 0).then(|| format!("t{id}").into()),
+            component: None,
+            message: msg.to_string().into(),
+            raw_text: format!("raw {msg}"),
+            profile_id: "test".to_string().into(),
+            file_modified: Some(Utc.with_ymd_and_hms(2024, 6, 2, 0, 0, 0).unwrap()),
+            fields: BTreeMap::from([("k".to_string(), id.to_string())]),
+        }
+    }
+
+    /// Positions of the Error entries, in append order.
+    fn errors(store: &mut EntryStore) -> Vec<u32> {
+        let mut hits = Vec::new();
+        let mut pos = 0u32;
+        store
+            .for_each_batch(|batch| {
+                for e in &batch {
+                    if e.severity == Severity::Error {
+                        hits.push(pos);
+                    }
+                    pos += 1;
+                }
+                Ok(())
+            })
+            .unwrap();
+        hits
+    }
+
+    /// Entries round-trip exactly; windows come back in time order with
+    /// untimed entries last, across separate appends.
+    #[test]
+    fn test_append_and_window_in_time_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = EntryStore::create(dir.path()).unwrap();
+        let first = vec![
+            entry(0, "a.log", Some(30), "third"),
+            entry(1, "b.log", None, "untimed"),
+            entry(2, "a.log", Some(10), "first \u{e9}"),
+        ];
+        store.append(&first).unwrap();
+        store
+            .append(&[entry(3, "c.log", Some(20), "second")])
+            .unwrap();
+        assert_eq!(store.len(), 4);
+        assert_eq!(store.max_id(), Some(3));
+
+        let all = store.window(0, 10).unwrap();
+        let ids: Vec<u64> = all.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![2, 3, 0, 1]);
+        let e = &all[0];
+        let original = &first[2];
+        assert_eq!(
+            (e.message(), &e.raw_text, &e.source_file, e.timestamp),
+            (
+                original.message(),
+                &original.raw_text,
+                &original.source_file,
+                original.timestamp
+            )
+        );
+        assert_eq!(
+            (&e.thread, &e.fields, e.file_modified),
+            (&original.thread, &original.fields, original.file_modified)
+        );
+
+        let page: Vec<u64> = store.window(1, 2).unwrap().iter().map(|e| e.id).collect();
+        assert_eq!(page, vec![3, 0]);
+        assert!(store.window(9, 2).unwrap().is_empty());
+    }
+
+    /// Positions counted through `for_each_batch` read back the right
+    /// entries once sorted into time order; the segment file is removed on
+    /// drop.
+    #[test]
+    fn test_matching_positions_and_cleanup() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = EntryStore::create(dir.path()).unwrap();
+        let entries: Vec<LogEntry> = (0..50)
+            .map(|i| {
+                let msg = if i % 5 == 0 { "job fail" } else { "ok" };
+                entry(i, "a.log", Some(59 - i as u32), msg)
+            })
+            .collect();
+        store.append(&entries).unwrap();
+
+        let mut hits = errors(&mut store);
+        assert_eq!(hits.len(), 10);
+        store.sort_by_time(&mut hits);
+        let ids: Vec<u64> = store
+            .read(&hits[..3])
+            .unwrap()
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids, vec![45, 40, 35]);
+
+        let mut other = EntryStore::create(dir.path()).unwrap();
+        other.append_store(&mut store).unwrap();
+        assert_eq!(other.len(), 50);
+
+        let path = store.path().to_path_buf();
+        drop(store);
+        assert!(!path.exists());
+
+        // A segment of another (dead) process is stale; our own is kept.
+        std::fs::write(dir.path().join("entries-999999999-0.seg"), b"x").unwrap();
+        let stale = std::time::Duration::ZERO;
+        assert_eq!(remove_stale_segments(dir.path(), stale), 1);
+        assert!(other.path().exists());
+    }
+
+    /// Removing a file touches only the index; rewriting re-times just the
+    /// affected entries.  Windows and search see both, across the gaps.
+    #[test]
+    fn test_remove_and_rewrite_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = EntryStore::create(dir.path()).unwrap();
+        let entries: Vec<LogEntry> = (0..6)
+            .map(|i| {
+                let file = ["a.log", "b.log", "c.log"][i as usize % 3];
+                entry(i, file, Some(i as u32), "job fail")
+            })
+            .collect();
+        store.append(&entries).unwrap();
+
+        assert_eq!(store.remove_files(|f| f == Path::new("b.log")), 2);
+        let rewritten = store
+            .rewrite_files(
+                |f| f == Path::new("a.log"),
+                |batch| {
+                    for e in batch {
+                        e.timestamp = e.timestamp.map(|t| t + chrono::Duration::seconds(30));
+                    }
+                },
+            )
+            .unwrap();
+        assert_eq!(rewritten, 2);
+
+        let ids: Vec<u64> = store.window(0, 10).unwrap().iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![2, 5, 0, 3]);
+        let mut hits = errors(&mut store);
+        store.sort_by_time(&mut hits);
+        let ids: Vec<u64> = store.read(&hits).unwrap().iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![2, 5, 0, 3]);
+    }
+}
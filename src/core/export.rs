@@ -2,9 +2,15 @@
 //
 // CSV and JSON export of filtered log entries.
 // Core layer: writes to any Write trait object.
+//
+// `export_csv` / `export_json` take entries already in memory.  `write_csv` /
+// `write_json` take a stream of owned or borrowed entries that may fail to
+// load (the entry store is read in chunks as the export is written).
 
 use crate::core::model::LogEntry;
 use crate::util::error::ExportError;
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
 use std::io::Write;
 use std::path::Path;
 
@@ -14,17 +20,52 @@ pub struct ExportMetadata<'a> {
     pub scan_path: Option<&'a Path>,
     pub filter_description: &'a str,
     pub entry_count: usize,
+    /// Clock adjustments applied to entry timestamps (`ClockRule::describe`),
+    /// so the recipient knows the times are not as logged.
+    pub clock_adjustments: &'a [String],
 }
 
 /// Export filtered entries to CSV format.
 ///
 /// Writes a metadata comment block followed by: timestamp, severity,
-/// source_file, line_number, thread, component, message.
+/// source_file, line_number, thread, component, message, then one extra
+/// column per structured field key (`LogEntry::fields`) present in any
+/// exported entry, in sorted order.  A field key that collides with a fixed
+/// column name is written as `field.<key>`.  Entries lacking a field get an
+/// empty cell.
 ///
-/// Accepts an iterator of entry references so callers can stream entries
-/// without collecting them into a temporary `Vec`.
+/// Accepts a cloneable iterator of entry references so callers can stream
+/// entries without collecting them into a temporary `Vec`; the iterator is
+/// walked once up front to discover the field columns.
 pub fn export_csv<'a, W: Write>(
-    entries: impl Iterator<Item = &'a LogEntry>,
+    entries: impl Iterator<Item = &'a LogEntry> + Clone,
+    writer: W,
+    export_path: &Path,
+    metadata: &ExportMetadata<'_>,
+) -> Result<usize, ExportError> {
+    let field_keys = csv_field_keys(entries.clone());
+    write_csv(entries.map(Ok), &field_keys, writer, export_path, metadata)
+}
+
+/// Structured field keys present in any of `entries`: the extra CSV
+/// columns, in sorted order.
+pub fn csv_field_keys<'a>(entries: impl IntoIterator<Item = &'a LogEntry>) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    for entry in entries {
+        for key in entry.fields.keys() {
+            if !keys.contains(key) {
+                keys.insert(key.clone());
+            }
+        }
+    }
+    keys
+}
+
+/// Write `entries` as CSV (see [`export_csv`]) with one column per key in
+/// `field_keys`.  Stops at the first entry that failed to load.
+pub fn write_csv<E: Borrow<LogEntry>, W: Write>(
+    entries: impl IntoIterator<Item = Result<E, ExportError>>,
+    field_keys: &BTreeSet<String>,
     mut writer: W,
     export_path: &Path,
     metadata: &ExportMetadata<'_>,
@@ -41,8 +82,16 @@ pub fn export_csv<'a, W: Write>(
         format!("# Scan path: {scan}"),
         format!("# Filter: {}", metadata.filter_description),
         format!("# Entries: {}", metadata.entry_count),
-        String::new(),
-    ] {
+    ]
+    .into_iter()
+    .chain(
+        metadata
+            .clock_adjustments
+            .iter()
+            .map(|a| format!("# Clock adjustment: {a}")),
+    )
+    .chain([String::new()])
+    {
         writeln!(writer, "{line}").map_err(|e| ExportError::Io {
             path: export_path.to_path_buf(),
             source: e,
@@ -52,16 +101,16 @@ pub fn export_csv<'a, W: Write>(
     let mut csv_writer = csv::Writer::from_writer(writer);
 
     // Column header
+    let mut header: Vec<String> = FIXED_CSV_COLUMNS.iter().map(|c| c.to_string()).collect();
+    header.extend(field_keys.iter().map(|k| {
+        if FIXED_CSV_COLUMNS.contains(&k.as_str()) {
+            format!("field.{k}")
+        } else {
+            k.to_string()
+        }
+    }));
     csv_writer
-        .write_record([
-            "timestamp",
-            "severity",
-            "source_file",
-            "line",
-            "thread",
-            "component",
-            "message",
-        ])
+        .write_record(&header)
         .map_err(|e| ExportError::Csv {
             path: export_path.to_path_buf(),
             source: e,
@@ -69,18 +118,29 @@ pub fn export_csv<'a, W: Write>(
 
     let mut count = 0;
     for entry in entries {
+        let entry = entry?;
+        let entry = entry.borrow();
         let ts = entry.timestamp.map(|t| t.to_rfc3339()).unwrap_or_default();
+        let source = entry.source_file.display().to_string();
+        let line = entry.line_number.to_string();
+
+        let mut record: Vec<&str> = vec![
+            &ts,
+            entry.severity.label(),
+            &source,
+            &line,
+            entry.thread.as_deref().unwrap_or(""),
+            entry.component.as_deref().unwrap_or(""),
+            entry.message(),
+        ];
+        record.extend(
+            field_keys
+                .iter()
+                .map(|k| entry.fields.get(k).map(String::as_str).unwrap_or("")),
+        );
 
         csv_writer
-            .write_record([
-                &ts,
-                entry.severity.label(),
-                &entry.source_file.display().to_string(),
-                &entry.line_number.to_string(),
-                entry.thread.as_deref().unwrap_or(""),
-                entry.component.as_deref().unwrap_or(""),
-                &entry.message,
-            ])
+            .write_record(&record)
             .map_err(|e| ExportError::Csv {
                 path: export_path.to_path_buf(),
                 source: e,
@@ -96,6 +156,17 @@ pub fn export_csv<'a, W: Write>(
     Ok(count)
 }
 
+/// Fixed CSV columns written before any structured field columns.
+const FIXED_CSV_COLUMNS: &[&str] = &[
+    "timestamp",
+    "severity",
+    "source_file",
+    "line",
+    "thread",
+    "component",
+    "message",
+];
+
 /// Export filtered entries to JSON format.
 ///
 /// Produces a JSON object with `metadata` and `entries` fields.  The
@@ -103,6 +174,17 @@ pub fn export_csv<'a, W: Write>(
 /// and entry count per EXP-03.
 pub fn export_json<'a, W: Write>(
     entries: impl Iterator<Item = &'a LogEntry>,
+    writer: W,
+    export_path: &Path,
+    metadata: &ExportMetadata<'_>,
+) -> Result<usize, ExportError> {
+    write_json(entries.map(Ok), writer, export_path, metadata)
+}
+
+/// Write `entries` as JSON (see [`export_json`]).  Stops at the first entry
+/// that failed to load.
+pub fn write_json<E: Borrow<LogEntry>, W: Write>(
+    entries: impl IntoIterator<Item = Result<E, ExportError>>,
     mut writer: W,
     export_path: &Path,
     metadata: &ExportMetadata<'_>,
@@ -117,10 +199,11 @@ pub fn export_json<'a, W: Write>(
     // EXP-03: wrap entries in a metadata envelope
     write!(
         writer,
-        "{{\n  \"metadata\": {{\n    \"exported\": \"{now}\",\n    \"scan_path\": {},\n    \"filter\": {},\n    \"entry_count\": {}\n  }},\n  \"entries\": [\n",
+        "{{\n  \"metadata\": {{\n    \"exported\": \"{now}\",\n    \"scan_path\": {},\n    \"filter\": {},\n    \"entry_count\": {},\n    \"clock_adjustments\": {}\n  }},\n  \"entries\": [\n",
         serde_json::to_string(&scan).unwrap_or_else(|_| "null".to_string()),
         serde_json::to_string(metadata.filter_description).unwrap_or_else(|_| "null".to_string()),
         metadata.entry_count,
+        serde_json::to_string(metadata.clock_adjustments).unwrap_or_else(|_| "[]".to_string()),
     )
     .map_err(|e| ExportError::Io {
         path: export_path.to_path_buf(),
@@ -128,15 +211,18 @@ pub fn export_json<'a, W: Write>(
     })?;
     let mut count = 0;
     for entry in entries {
+        let entry = entry?;
         if count > 0 {
             writer.write_all(b",\n").map_err(|e| ExportError::Io {
                 path: export_path.to_path_buf(),
                 source: e,
             })?;
         }
-        serde_json::to_writer_pretty(&mut writer, entry).map_err(|e| ExportError::Json {
-            path: export_path.to_path_buf(),
-            source: e,
+        serde_json::to_writer_pretty(&mut writer, entry.borrow()).map_err(|e| {
+            ExportError::Json {
+                path: export_path.to_path_buf(),
+                source: e,
+            }
         })?;
         count += 1;
     }
@@ -160,25 +246,27 @@ mod tests {
             id,
             timestamp: None,
             severity: Severity::Error,
-            source_file: PathBuf::from("test.log"),
+            source_file: PathBuf::from("test.log").into(),
             line_number: id,
-            thread: Some("1".to_string()),
-            component: Some("test".to_string()),
-            message: message.to_string(),
+            thread: Some("1".to_string().into()),
+            component: Some("test".to_string().into()),
+            message: message.to_string().into(),
             raw_text: message.to_string(),
-            profile_id: "test".to_string(),
+            profile_id: "test".to_string().into(),
+            fields: Default::default(),
             file_modified: None,
         }
     }
 
     #[test]
     fn test_csv_export() {
-        let entries = vec![make_entry(1, "Error one"), make_entry(2, "Error two")];
+        let entries = [make_entry(1, "Error one"), make_entry(2, "Error two")];
         let mut buf = Vec::new();
         let meta = ExportMetadata {
             scan_path: Some(Path::new("/tmp/logs")),
             filter_description: "No filter (all entries)",
             entry_count: 2,
+            clock_adjustments: &["/tmp/logs/sql: +00:01:30".to_string()],
         };
         let count = export_csv(entries.iter(), &mut buf, &PathBuf::from("out.csv"), &meta).unwrap();
         assert_eq!(count, 2);
@@ -189,6 +277,7 @@ mod tests {
         assert!(output.contains("# Scan path: /tmp/logs"));
         assert!(output.contains("# Filter: No filter (all entries)"));
         assert!(output.contains("# Entries: 2"));
+        assert!(output.contains("# Clock adjustment: /tmp/logs/sql: +00:01:30\n"));
         // Data
         assert!(output.contains("timestamp,severity"));
         assert!(output.contains("Error one"));
@@ -197,12 +286,13 @@ mod tests {
 
     #[test]
     fn test_json_export() {
-        let entries = vec![make_entry(1, "Test message")];
+        let entries = [make_entry(1, "Test message")];
         let mut buf = Vec::new();
         let meta = ExportMetadata {
             scan_path: None,
             filter_description: "Severity: Error",
             entry_count: 1,
+            clock_adjustments: &[],
         };
         let count =
             export_json(entries.iter(), &mut buf, &PathBuf::from("out.json"), &meta).unwrap();
@@ -214,7 +304,36 @@ mod tests {
         assert!(output.contains("\"exported\""));
         assert!(output.contains("\"filter\""));
         assert!(output.contains("\"entry_count\": 1"));
+        assert!(output.contains("\"clock_adjustments\": []"));
         // Data
         assert!(output.contains("Test message"));
     }
+
+    /// Structured fields must become extra CSV columns (union of keys, sorted),
+    /// with empty cells for entries lacking a key and a `field.` prefix for
+    /// keys that collide with a fixed column.
+    #[test]
+    fn test_csv_export_adds_field_columns() {
+        let mut e1 = make_entry(1, "first");
+        let mut e2 = make_entry(2, "second");
+        e1.fields.insert("sc_status".to_string(), "500".to_string());
+        e1.fields.insert("message".to_string(), "dup".to_string());
+        e2.fields.insert("c_ip".to_string(), "10.0.0.1".to_string());
+        let entries = [e1, e2];
+        let mut buf = Vec::new();
+        let meta = ExportMetadata {
+            scan_path: None,
+            filter_description: "No filter (all entries)",
+            entry_count: 2,
+            clock_adjustments: &[],
+        };
+        export_csv(entries.iter(), &mut buf, &PathBuf::from("out.csv"), &meta).unwrap();
+
+        let output = String::from_utf8(buf).unwrap();
+        assert!(output.contains(
+            "timestamp,severity,source_file,line,thread,component,message,c_ip,field.message,sc_status\n"
+        ));
+        assert!(output.contains("first,,dup,500\n"));
+        assert!(output.contains("second,10.0.0.1,,\n"));
+    }
 }
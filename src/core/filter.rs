@@ -1,17 +1,19 @@
 // LogSleuth - core/filter.rs
 //
 // Composable filter engine for log entries.
-// All active filters are AND-combined.
+// All active filters are AND-combined.  The query language (`QueryExpr`)
+// adds AND / OR / NOT over entry fields as one more AND-ed filter.
 // Core layer: pure logic, no I/O or UI dependencies.
 
 use crate::core::model::{LogEntry, Severity};
 use crate::core::multi_search::MultiSearch;
+use crate::util::constants::{MAX_QUERY_DEPTH, MAX_QUERY_LENGTH};
 use crate::util::error::FilterError;
 use chrono::{DateTime, Utc};
 use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 // =============================================================================
@@ -122,6 +124,16 @@ pub fn normalize_message(msg: &str) -> String {
     normalized.into_owned()
 }
 
+/// The message key `entry` is grouped by for dedup: the raw message (Exact)
+/// or the normalised message (Normalized).  `None` when dedup is off.
+pub fn dedup_key(entry: &LogEntry, mode: DedupMode) -> Option<String> {
+    match mode {
+        DedupMode::Exact => Some(entry.message().to_string()),
+        DedupMode::Normalized => Some(normalize_message(entry.message())),
+        DedupMode::Off => None,
+    }
+}
+
 /// Apply deduplication to an already-filtered index set.
 ///
 /// Groups entries by `(source_file, message_key)` where `message_key` is either
@@ -144,19 +156,17 @@ pub fn apply_dedup(
 
     // Group indices by (source_file, message_key).
     // Use a BTreeMap-like approach but HashMap is fine for grouping.
-    let mut groups: HashMap<(&PathBuf, String), Vec<usize>> = HashMap::new();
+    let mut groups: HashMap<(&Path, String), Vec<usize>> = HashMap::new();
 
     for &idx in filtered_indices {
         let Some(entry) = entries.get(idx) else {
             continue;
         };
-        let key = match mode {
-            DedupMode::Exact => entry.message.clone(),
-            DedupMode::Normalized => normalize_message(&entry.message),
-            DedupMode::Off => unreachable!(),
+        let Some(key) = dedup_key(entry, mode) else {
+            continue;
         };
         groups
-            .entry((&entry.source_file, key))
+            .entry((&*entry.source_file, key))
             .or_default()
             .push(idx);
     }
@@ -304,6 +314,78 @@ pub struct FilterState {
     /// other filters.  Supports ANY/ALL modes, NOT terms, minimum match
     /// thresholds, and per-term highlighting.
     pub multi_search: MultiSearch,
+
+    /// Raw structured-field filter text typed by the user (e.g.
+    /// `sc_status=500, c_ip`).  Parsed into `field_filters` by
+    /// `set_field_filter()`; kept for the UI input buffer and the session.
+    pub field_filter_input: String,
+
+    /// Parsed structured-field terms.  Every term must match (AND) an entry's
+    /// `LogEntry::fields`.  Empty = no field filter.
+    pub field_filters: Vec<FieldFilter>,
+
+    /// Raw query-language text typed in the query bar (e.g.
+    /// `component:Agent AND severity>=warning`).  Parsed into `query` by
+    /// `set_query()`; kept for the UI input buffer and the session.
+    pub query_input: String,
+
+    /// Parsed query expression.  None = no query (or the last input was
+    /// invalid, see `query_error`).
+    pub query: Option<QueryExpr>,
+
+    /// Parse error for the current `query_input`, with its position.
+    pub query_error: Option<FilterError>,
+
+    /// Entry IDs of the template selected in the Patterns view (or of the
+    /// message picked in the Compare view).  None = no pattern filter.  Not
+    /// persisted: IDs are only valid for one scan.
+    pub pattern_ids: Option<HashSet<u64>>,
+
+    /// Template text of the selected pattern, for the filter indicator.
+    pub pattern_label: String,
+}
+
+/// A single `key=value` (or bare `key`) term of the structured-field filter.
+///
+/// The key is matched ASCII case-insensitively against `LogEntry::fields`
+/// keys.  An empty `value_lower` only requires the key to be present;
+/// otherwise the field value must contain `value_lower` (case-insensitive).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldFilter {
+    pub key: String,
+    pub value_lower: String,
+}
+
+impl FieldFilter {
+    /// Parse a comma- or newline-separated list of `key=value` / `key` terms.
+    /// Blank terms and terms with an empty key are ignored.
+    pub fn parse_list(input: &str) -> Vec<FieldFilter> {
+        input
+            .split([',', '\n'])
+            .filter_map(|term| {
+                let term = term.trim();
+                let (key, value) = term.split_once('=').unwrap_or((term, ""));
+                let key = key.trim();
+                if key.is_empty() {
+                    return None;
+                }
+                Some(FieldFilter {
+                    key: key.to_string(),
+                    value_lower: value.trim().to_lowercase(),
+                })
+            })
+            .collect()
+    }
+
+    /// True when `entry` carries this key and (if a value was given) its
+    /// value contains the needle.
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        entry
+            .fields
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(&self.key))
+            .is_some_and(|(_, v)| contains_ci(v, &self.value_lower))
+    }
 }
 
 impl FilterState {
@@ -338,6 +420,52 @@ impl FilterState {
             && !self.hide_no_timestamp
             && self.dedup_mode == DedupMode::Off
             && self.multi_search.is_empty()
+            && self.field_filters.is_empty()
+            && self.query.is_none()
+            && self.pattern_ids.is_none()
+    }
+
+    /// Restrict the severity filter to `min` and every more-severe level.
+    ///
+    /// `Severity` orders most-severe first, so `Warning` selects Critical,
+    /// Error and Warning.
+    pub fn set_min_severity(&mut self, min: Severity) {
+        self.severity_levels = Severity::all()
+            .iter()
+            .copied()
+            .filter(|s| *s <= min)
+            .collect();
+    }
+
+    /// Set the structured-field filter from raw user input, re-parsing it
+    /// into `field_filters`.  Always updates `field_filter_input`.
+    pub fn set_field_filter(&mut self, input: &str) {
+        self.field_filter_input = input.to_string();
+        self.field_filters = FieldFilter::parse_list(input);
+    }
+
+    /// Set the filter query from raw user input, parsing it into `query`.
+    /// Always updates `query_input`.  Blank input clears the query; on a
+    /// parse error `query` is cleared, the error is kept in `query_error`
+    /// and returned.
+    pub fn set_query(&mut self, input: &str) -> Result<(), FilterError> {
+        self.query_input = input.to_string();
+        self.query_error = None;
+        if input.trim().is_empty() {
+            self.query = None;
+            return Ok(());
+        }
+        match QueryExpr::parse(input) {
+            Ok(expr) => {
+                self.query = Some(expr);
+                Ok(())
+            }
+            Err(e) => {
+                self.query = None;
+                self.query_error = Some(e.clone());
+                Err(e)
+            }
+        }
     }
 
     /// Set the regex search pattern, compiling it.
@@ -433,7 +561,9 @@ pub fn apply_filters(entries: &[LogEntry], filter: &FilterState) -> Vec<usize> {
     let has_text_filter = !text_lower.is_empty()
         || filter.regex_search.is_some()
         || !excl_lower.is_empty()
-        || !filter.component_filter.is_empty();
+        || !filter.component_filter.is_empty()
+        || !filter.field_filters.is_empty()
+        || filter.query.is_some();
     let initial_capacity = if has_text_filter {
         entries.len() / 4
     } else {
@@ -449,6 +579,47 @@ pub fn apply_filters(entries: &[LogEntry], filter: &FilterState) -> Vec<usize> {
     result
 }
 
+/// Parse a user-supplied datetime string into a UTC bound for a time filter.
+///
+/// Accepted formats (most-specific first):
+///   `YYYY-MM-DD HH:MM:SS`  -- second precision
+///   `YYYY-MM-DD HH:MM`     -- minute precision
+///   `YYYY-MM-DD`           -- day precision (midnight in local time)
+///
+/// All formats are interpreted as **local** time and converted to UTC, matching
+/// the behaviour of `AppState::discovery_modified_since`.  An empty or
+/// non-parseable input returns `None`.
+///
+/// Used by `ui/panels/filters.rs` to commit absolute date range inputs directly
+/// to `FilterState::time_start` / `FilterState::time_end`, and by the headless
+/// `--since` option.
+pub fn parse_filter_datetime(s: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone as _};
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    // Interpret as LOCAL time, convert to UTC.  Falls back to treating the
+    // naive datetime as UTC on DST ambiguity (e.g. clock-back hour).
+    let local_to_utc = |ndt: NaiveDateTime| -> chrono::DateTime<chrono::Utc> {
+        Local
+            .from_local_datetime(&ndt)
+            .single()
+            .map(|dt| dt.to_utc())
+            .unwrap_or_else(|| ndt.and_utc())
+    };
+    if let Ok(ndt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M:%S") {
+        return Some(local_to_utc(ndt));
+    }
+    if let Ok(ndt) = NaiveDateTime::parse_from_str(trimmed, "%Y-%m-%d %H:%M") {
+        return Some(local_to_utc(ndt));
+    }
+    NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(local_to_utc)
+}
+
 /// Return true if every character of `query` appears in `text` in order
 /// (case-insensitive subsequence / fuzzy match).
 ///
@@ -539,7 +710,7 @@ fn matches_all(entry: &LogEntry, filter: &FilterState, text_lower: &str, excl_lo
     if filter.hide_all_sources {
         return false;
     }
-    if !filter.source_files.is_empty() && !filter.source_files.contains(&entry.source_file) {
+    if !filter.source_files.is_empty() && !filter.source_files.contains(&*entry.source_file) {
         return false;
     }
 
@@ -588,7 +759,7 @@ fn matches_all(entry: &LogEntry, filter: &FilterState, text_lower: &str, excl_lo
     // cheaper approach: only allocate when a non-ASCII byte is present.
     if !text_lower.is_empty() {
         let hit = if filter.fuzzy {
-            fuzzy_match(text_lower, &entry.message)
+            fuzzy_match(text_lower, entry.message())
                 || entry
                     .thread
                     .as_deref()
@@ -598,7 +769,7 @@ fn matches_all(entry: &LogEntry, filter: &FilterState, text_lower: &str, excl_lo
                     .as_deref()
                     .is_some_and(|c| fuzzy_match(text_lower, c))
         } else {
-            contains_ci(&entry.message, text_lower)
+            contains_ci(entry.message(), text_lower)
                 || entry
                     .thread
                     .as_deref()
@@ -615,7 +786,7 @@ fn matches_all(entry: &LogEntry, filter: &FilterState, text_lower: &str, excl_lo
 
     // Regex search: also matches thread and component metadata fields.
     if let Some(ref regex) = filter.regex_search {
-        let matches = regex.is_match(&entry.message)
+        let matches = regex.is_match(entry.message())
             || entry.thread.as_deref().is_some_and(|t| regex.is_match(t))
             || entry
                 .component
@@ -636,7 +807,7 @@ fn matches_all(entry: &LogEntry, filter: &FilterState, text_lower: &str, excl_lo
     // case-insensitive substring engine as `text_search` but inverts the gate.
     // `excl_lower` is pre-lowercased by the caller to avoid per-entry allocation.
     if !excl_lower.is_empty() {
-        let hit = contains_ci(&entry.message, excl_lower)
+        let hit = contains_ci(entry.message(), excl_lower)
             || entry
                 .thread
                 .as_deref()
@@ -659,11 +830,31 @@ fn matches_all(entry: &LogEntry, filter: &FilterState, text_lower: &str, excl_lo
         }
     }
 
+    // Pattern filter: membership in the template picked in the Patterns view.
+    if let Some(ref ids) = filter.pattern_ids {
+        if !ids.contains(&entry.id) {
+            return false;
+        }
+    }
+
+    // Structured-field filter: every `key=value` term must match.  Entries
+    // without the key are excluded while the filter is active.
+    if !filter.field_filters.iter().all(|f| f.matches(entry)) {
+        return false;
+    }
+
+    // Query language: the whole expression is one more AND-ed gate.
+    if let Some(ref query) = filter.query {
+        if !query.matches(entry) {
+            return false;
+        }
+    }
+
     // Multi-term search: delegates to the MultiSearch engine which uses
     // RegexSet for efficient single-pass multi-pattern matching.
     if filter.multi_search.is_active()
         && !filter.multi_search.matches_entry(
-            &entry.message,
+            entry.message(),
             entry.thread.as_deref(),
             entry.component.as_deref(),
         )
@@ -674,6 +865,493 @@ fn matches_all(entry: &LogEntry, filter: &FilterState, text_lower: &str, excl_lo
     true
 }
 
+// =============================================================================
+// Query language
+// =============================================================================
+//
+// Grammar (keywords are upper-case; adjacent terms are AND-ed):
+//
+//   query   := or
+//   or      := and ( "OR" and )*
+//   and     := unary ( [ "AND" ] unary )*
+//   unary   := "NOT" unary | primary
+//   primary := "(" or ")" | term
+//   term    := FIELD OP VALUE | VALUE
+//   OP      := ":" (contains) | "=" (equals) | "~" (regex)
+//            | ">=" | ">" | "<=" | "<"      (severity and line only)
+//   VALUE   := bare-word | "quoted string"   (\" and \\ escape inside quotes)
+//
+// A bare VALUE searches message, thread and component like the text search.
+// FIELD is one of msg/message, thread, component/comp, file/path, profile,
+// severity/level/sev, line; any other name looks up `LogEntry::fields`.
+
+/// Parsed filter query, evaluated per entry by `matches()`.
+#[derive(Debug, Clone)]
+pub enum QueryExpr {
+    /// Every child must match.
+    And(Vec<QueryExpr>),
+    /// At least one child must match.
+    Or(Vec<QueryExpr>),
+    /// The child must not match.
+    Not(Box<QueryExpr>),
+    /// Text comparison against one entry field (or message/thread/component
+    /// for `QueryField::Any`).
+    Text {
+        field: QueryField,
+        matcher: TextMatch,
+    },
+    /// Severity comparison.  "Greater" means more severe.
+    Severity { cmp: QueryCmp, level: Severity },
+    /// Line number comparison.
+    Line { cmp: QueryCmp, line: u64 },
+}
+
+/// Entry field targeted by a text term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryField {
+    /// Bare term: message, thread or component.
+    Any,
+    Message,
+    Thread,
+    Component,
+    /// Full source path (including `archive!/member` for archive members).
+    File,
+    Profile,
+    /// Structured field from `LogEntry::fields` (key matched ASCII
+    /// case-insensitively).
+    Field(String),
+}
+
+/// How a text term compares its value.  Needles are stored lowercased.
+#[derive(Debug, Clone)]
+pub enum TextMatch {
+    /// `field:value` -- case-insensitive substring.
+    Contains(String),
+    /// `field=value` -- case-insensitive whole-value equality.
+    Equals(String),
+    /// `field~"re"` -- case-insensitive regex.
+    Regex(Regex),
+}
+
+/// Comparison operator for severity and line terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryCmp {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl QueryCmp {
+    fn holds<T: Ord>(self, lhs: T, rhs: T) -> bool {
+        match self {
+            QueryCmp::Eq => lhs == rhs,
+            QueryCmp::Gt => lhs > rhs,
+            QueryCmp::Ge => lhs >= rhs,
+            QueryCmp::Lt => lhs < rhs,
+            QueryCmp::Le => lhs <= rhs,
+        }
+    }
+}
+
+impl TextMatch {
+    fn is_match(&self, value: &str) -> bool {
+        match self {
+            TextMatch::Contains(needle) => contains_ci(value, needle),
+            TextMatch::Equals(needle) => value
+                .chars()
+                .flat_map(char::to_lowercase)
+                .eq(needle.chars()),
+            TextMatch::Regex(re) => re.is_match(value),
+        }
+    }
+}
+
+impl QueryExpr {
+    /// Parse a query string.  Errors carry the character position of the
+    /// offending token.
+    pub fn parse(input: &str) -> Result<QueryExpr, FilterError> {
+        let mut parser = QueryParser {
+            src: input,
+            pos: 0,
+            depth: 0,
+        };
+        if input.len() > MAX_QUERY_LENGTH {
+            return Err(parser.error(
+                0,
+                format!("query is longer than {MAX_QUERY_LENGTH} characters"),
+            ));
+        }
+        let expr = parser.parse_or()?;
+        parser.skip_ws();
+        if parser.peek().is_some() {
+            // parse_or only stops early at an unmatched ')'.
+            return Err(parser.error(parser.pos, "unexpected ')' without a matching '('"));
+        }
+        Ok(expr)
+    }
+
+    /// True when `entry` satisfies the expression.
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        match self {
+            QueryExpr::And(children) => children.iter().all(|c| c.matches(entry)),
+            QueryExpr::Or(children) => children.iter().any(|c| c.matches(entry)),
+            QueryExpr::Not(child) => !child.matches(entry),
+            QueryExpr::Text { field, matcher } => match field {
+                QueryField::Any => {
+                    matcher.is_match(entry.message())
+                        || entry.thread.as_deref().is_some_and(|t| matcher.is_match(t))
+                        || entry
+                            .component
+                            .as_deref()
+                            .is_some_and(|c| matcher.is_match(c))
+                }
+                QueryField::Message => matcher.is_match(entry.message()),
+                QueryField::Thread => entry.thread.as_deref().is_some_and(|t| matcher.is_match(t)),
+                QueryField::Component => entry
+                    .component
+                    .as_deref()
+                    .is_some_and(|c| matcher.is_match(c)),
+                QueryField::File => matcher.is_match(&entry.source_file.to_string_lossy()),
+                QueryField::Profile => matcher.is_match(&entry.profile_id),
+                QueryField::Field(key) => entry
+                    .fields
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                    .is_some_and(|(_, v)| matcher.is_match(v)),
+            },
+            // `Severity` orders most-severe first, so "entry >= level" in
+            // query terms is "level >= entry" in `Ord` terms.
+            QueryExpr::Severity { cmp, level } => cmp.holds(*level, entry.severity),
+            QueryExpr::Line { cmp, line } => cmp.holds(entry.line_number, *line),
+        }
+    }
+}
+
+/// Operator between a field name and its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QueryOp {
+    Contains,
+    Equals,
+    Regex,
+    Cmp(QueryCmp),
+}
+
+impl QueryOp {
+    fn as_str(self) -> &'static str {
+        match self {
+            QueryOp::Contains => ":",
+            QueryOp::Equals => "=",
+            QueryOp::Regex => "~",
+            QueryOp::Cmp(QueryCmp::Eq) => "=",
+            QueryOp::Cmp(QueryCmp::Gt) => ">",
+            QueryOp::Cmp(QueryCmp::Ge) => ">=",
+            QueryOp::Cmp(QueryCmp::Lt) => "<",
+            QueryOp::Cmp(QueryCmp::Le) => "<=",
+        }
+    }
+}
+
+fn is_query_op_char(c: char) -> bool {
+    matches!(c, ':' | '=' | '~' | '<' | '>')
+}
+
+/// Characters that end a bare word regardless of context.
+fn is_query_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '(' | ')' | '"')
+}
+
+/// Recursive-descent parser over the query string.  `pos` is a byte offset;
+/// errors convert it to a character position.
+struct QueryParser<'a> {
+    src: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl QueryParser<'_> {
+    fn error(&self, at: usize, reason: impl Into<String>) -> FilterError {
+        FilterError::InvalidQuery {
+            position: self.src[..at].chars().count(),
+            reason: reason.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = &self.src[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Byte length of the field name / bare word at `pos`, stopping at
+    /// delimiters and operator characters.
+    fn name_len(&self) -> usize {
+        let rest = &self.src[self.pos..];
+        rest.find(|c| is_query_delimiter(c) || is_query_op_char(c))
+            .unwrap_or(rest.len())
+    }
+
+    /// True when the next word is exactly `keyword` (not a field name).
+    fn at_keyword(&self, keyword: &str) -> bool {
+        let rest = &self.src[self.pos..];
+        let n = self.name_len();
+        &rest[..n] == keyword && !rest[n..].starts_with(is_query_op_char)
+    }
+
+    fn enter_group(&mut self, at: usize) -> Result<(), FilterError> {
+        self.depth += 1;
+        if self.depth > MAX_QUERY_DEPTH {
+            return Err(self.error(at, format!("nested deeper than {MAX_QUERY_DEPTH} levels")));
+        }
+        Ok(())
+    }
+
+    fn parse_or(&mut self) -> Result<QueryExpr, FilterError> {
+        let mut children = vec![self.parse_and()?];
+        loop {
+            self.skip_ws();
+            if !self.at_keyword("OR") {
+                break;
+            }
+            self.pos += "OR".len();
+            children.push(self.parse_and()?);
+        }
+        Ok(if children.len() == 1 {
+            children.remove(0)
+        } else {
+            QueryExpr::Or(children)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<QueryExpr, FilterError> {
+        let mut children = vec![self.parse_unary()?];
+        loop {
+            self.skip_ws();
+            if matches!(self.peek(), None | Some(')')) || self.at_keyword("OR") {
+                break;
+            }
+            if self.at_keyword("AND") {
+                self.pos += "AND".len();
+            }
+            children.push(self.parse_unary()?);
+        }
+        Ok(if children.len() == 1 {
+            children.remove(0)
+        } else {
+            QueryExpr::And(children)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<QueryExpr, FilterError> {
+        self.skip_ws();
+        if self.at_keyword("NOT") {
+            let start = self.pos;
+            self.pos += "NOT".len();
+            self.enter_group(start)?;
+            let child = self.parse_unary()?;
+            self.depth -= 1;
+            return Ok(QueryExpr::Not(Box::new(child)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<QueryExpr, FilterError> {
+        self.skip_ws();
+        let start = self.pos;
+        match self.peek() {
+            None => Err(self.error(start, "expected a search term")),
+            Some(')') => Err(self.error(start, "expected a search term before ')'")),
+            Some('(') => {
+                self.enter_group(start)?;
+                self.pos += 1;
+                let inner = self.parse_or()?;
+                self.skip_ws();
+                if self.peek() != Some(')') {
+                    return Err(self.error(start, "'(' is never closed"));
+                }
+                self.pos += 1;
+                self.depth -= 1;
+                Ok(inner)
+            }
+            Some('"') => {
+                let text = self.parse_quoted()?;
+                Ok(QueryExpr::Text {
+                    field: QueryField::Any,
+                    matcher: TextMatch::Contains(text.to_lowercase()),
+                })
+            }
+            Some(_) if self.at_keyword("AND") || self.at_keyword("OR") => Err(self.error(
+                start,
+                format!(
+                    "expected a search term before '{}'",
+                    &self.src[start..start + self.name_len()]
+                ),
+            )),
+            Some(_) => self.parse_term(),
+        }
+    }
+
+    /// Parse a `"..."` string starting at the opening quote.
+    fn parse_quoted(&mut self) -> Result<String, FilterError> {
+        let start = self.pos;
+        let mut out = String::new();
+        let mut chars = self.src[start + 1..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos = start + 1 + i + 1;
+                    return Ok(out);
+                }
+                '\\' => match chars.clone().next() {
+                    Some((_, next @ ('"' | '\\'))) => {
+                        out.push(next);
+                        chars.next();
+                    }
+                    _ => out.push('\\'),
+                },
+                _ => out.push(c),
+            }
+        }
+        Err(self.error(start, "unterminated quoted string"))
+    }
+
+    fn parse_op(&mut self) -> Option<QueryOp> {
+        let rest = &self.src[self.pos..];
+        let (op, len) = if rest.starts_with(">=") {
+            (QueryOp::Cmp(QueryCmp::Ge), 2)
+        } else if rest.starts_with("<=") {
+            (QueryOp::Cmp(QueryCmp::Le), 2)
+        } else {
+            match rest.chars().next()? {
+                ':' => (QueryOp::Contains, 1),
+                '=' => (QueryOp::Equals, 1),
+                '~' => (QueryOp::Regex, 1),
+                '>' => (QueryOp::Cmp(QueryCmp::Gt), 1),
+                '<' => (QueryOp::Cmp(QueryCmp::Lt), 1),
+                _ => return None,
+            }
+        };
+        self.pos += len;
+        Some(op)
+    }
+
+    /// Parse a bare word or a `field OP value` term.
+    fn parse_term(&mut self) -> Result<QueryExpr, FilterError> {
+        let start = self.pos;
+        let name = &self.src[start..start + self.name_len()];
+        self.pos += name.len();
+        let op_start = self.pos;
+        let Some(op) = self.parse_op() else {
+            return Ok(QueryExpr::Text {
+                field: QueryField::Any,
+                matcher: TextMatch::Contains(name.to_lowercase()),
+            });
+        };
+        if name.is_empty() {
+            return Err(self.error(
+                start,
+                format!("expected a field name before '{}'", op.as_str()),
+            ));
+        }
+
+        let value_start = self.pos;
+        let value = if self.peek() == Some('"') {
+            self.parse_quoted()?
+        } else {
+            let rest = &self.src[self.pos..];
+            let len = rest.find(is_query_delimiter).unwrap_or(rest.len());
+            self.pos += len;
+            rest[..len].to_string()
+        };
+        if value.is_empty() {
+            return Err(self.error(
+                value_start,
+                format!("expected a value after '{name}{}'", op.as_str()),
+            ));
+        }
+
+        let cmp = match op {
+            QueryOp::Cmp(cmp) => Some(cmp),
+            QueryOp::Contains | QueryOp::Equals => Some(QueryCmp::Eq),
+            QueryOp::Regex => None,
+        };
+        match name.to_ascii_lowercase().as_str() {
+            "severity" | "level" | "sev" => {
+                let Some(cmp) = cmp else {
+                    return Err(self.error(op_start, "'~' cannot be used with severity"));
+                };
+                let level = Severity::from_label(&value)
+                    .or_else(|| {
+                        Severity::all()
+                            .iter()
+                            .copied()
+                            .find(|s| s.short_label().eq_ignore_ascii_case(&value))
+                    })
+                    .ok_or_else(|| {
+                        self.error(
+                            value_start,
+                            format!(
+                                "unknown severity '{value}' \
+                                 (expected critical, error, warning, info, debug or unknown)"
+                            ),
+                        )
+                    })?;
+                Ok(QueryExpr::Severity { cmp, level })
+            }
+            "line" => {
+                let Some(cmp) = cmp else {
+                    return Err(self.error(op_start, "'~' cannot be used with line"));
+                };
+                let line = value.parse::<u64>().map_err(|_| {
+                    self.error(
+                        value_start,
+                        format!("expected a line number, got '{value}'"),
+                    )
+                })?;
+                Ok(QueryExpr::Line { cmp, line })
+            }
+            other => {
+                let field = match other {
+                    "msg" | "message" => QueryField::Message,
+                    "thread" => QueryField::Thread,
+                    "component" | "comp" => QueryField::Component,
+                    "file" | "path" => QueryField::File,
+                    "profile" => QueryField::Profile,
+                    _ => QueryField::Field(name.to_string()),
+                };
+                let matcher = match op {
+                    QueryOp::Contains => TextMatch::Contains(value.to_lowercase()),
+                    QueryOp::Equals => TextMatch::Equals(value.to_lowercase()),
+                    QueryOp::Regex => TextMatch::Regex(
+                        RegexBuilder::new(&value)
+                            .case_insensitive(true)
+                            .build()
+                            .map_err(|e| {
+                                // regex syntax errors are multi-line (pattern,
+                                // caret, reason); keep only the reason.
+                                let text = e.to_string();
+                                let reason = text.lines().last().unwrap_or_default();
+                                let reason = reason.trim_start_matches("error: ");
+                                self.error(value_start, format!("invalid regex: {reason}"))
+                            })?,
+                    ),
+                    QueryOp::Cmp(_) => {
+                        return Err(self.error(
+                            op_start,
+                            format!("'{}' only applies to severity and line", op.as_str()),
+                        ))
+                    }
+                };
+                Ok(QueryExpr::Text { field, matcher })
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -685,13 +1363,14 @@ mod tests {
             id,
             timestamp: None,
             severity,
-            source_file: PathBuf::from("test.log"),
+            source_file: PathBuf::from("test.log").into(),
             line_number: id,
             thread: None,
             component: None,
-            message: message.to_string(),
+            message: message.to_string().into(),
             raw_text: message.to_string(),
-            profile_id: "test".to_string(),
+            profile_id: "test".to_string().into(),
+            fields: Default::default(),
             file_modified: None,
         }
     }
@@ -882,8 +1561,8 @@ mod tests {
     fn test_source_file_filter() {
         let mut entry_a = make_entry(1, Severity::Info, "file a entry");
         let mut entry_b = make_entry(2, Severity::Info, "file b entry");
-        entry_a.source_file = PathBuf::from("a.log");
-        entry_b.source_file = PathBuf::from("b.log");
+        entry_a.source_file = PathBuf::from("a.log").into();
+        entry_b.source_file = PathBuf::from("b.log").into();
         let entries = vec![entry_a, entry_b];
 
         let mut source_files = HashSet::new();
@@ -1016,8 +1695,8 @@ mod tests {
         let mut e1 = make_entry(1, Severity::Error, "auth failed");
         let mut e2 = make_entry(2, Severity::Error, "db query failed");
         let mut e3 = make_entry(3, Severity::Info, "health ok");
-        e1.component = Some("auth".to_string());
-        e2.component = Some("db".to_string());
+        e1.component = Some("auth".to_string().into());
+        e2.component = Some("db".to_string().into());
         e3.component = None;
         let entries = vec![e1, e2, e3];
 
@@ -1046,9 +1725,9 @@ mod tests {
         let mut e1 = make_entry(1, Severity::Error, "msg1");
         let mut e2 = make_entry(2, Severity::Error, "msg2");
         let mut e3 = make_entry(3, Severity::Error, "msg3");
-        e1.component = Some("auth".to_string());
-        e2.component = Some("db".to_string());
-        e3.component = Some("auth".to_string());
+        e1.component = Some("auth".to_string().into());
+        e2.component = Some("db".to_string().into());
+        e3.component = Some("auth".to_string().into());
         let entries = vec![e1, e2, e3];
 
         let mut component_filter = HashSet::new();
@@ -1062,6 +1741,79 @@ mod tests {
         assert_eq!(result, vec![0, 2]);
     }
 
+    // -------------------------------------------------------------------------
+    // Structured-field filter tests
+    // -------------------------------------------------------------------------
+
+    /// The Patterns view filter keeps only the selected template's members
+    /// and combines with the other filters.
+    #[test]
+    fn test_pattern_ids_filter() {
+        let entries = vec![
+            make_entry(10, Severity::Error, "a"),
+            make_entry(11, Severity::Info, "b"),
+            make_entry(12, Severity::Error, "c"),
+        ];
+        let mut filter = FilterState {
+            pattern_ids: Some([10, 11].into_iter().collect()),
+            ..Default::default()
+        };
+        assert!(!filter.is_empty());
+        assert_eq!(apply_filters(&entries, &filter), vec![0, 1]);
+
+        filter.severity_levels.insert(Severity::Error);
+        assert_eq!(apply_filters(&entries, &filter), vec![0]);
+    }
+
+    #[test]
+    fn test_field_filter_parse_list() {
+        let terms = FieldFilter::parse_list(" sc_status = 500 ,c_ip\n, =orphan,");
+        assert_eq!(
+            terms,
+            vec![
+                FieldFilter {
+                    key: "sc_status".to_string(),
+                    value_lower: "500".to_string(),
+                },
+                FieldFilter {
+                    key: "c_ip".to_string(),
+                    value_lower: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_field_filter_matches_key_and_value() {
+        let mut e1 = make_entry(1, Severity::Error, "GET /a");
+        let mut e2 = make_entry(2, Severity::Error, "GET /b");
+        let e3 = make_entry(3, Severity::Info, "no fields");
+        e1.fields.insert("sc_status".to_string(), "500".to_string());
+        e1.fields.insert("c_ip".to_string(), "10.0.0.1".to_string());
+        e2.fields.insert("SC_Status".to_string(), "404".to_string());
+        let entries = vec![e1, e2, e3];
+
+        let mut filter = FilterState::default();
+        filter.set_field_filter("sc_status");
+        // Key-only term: presence check, case-insensitive key match.
+        assert_eq!(apply_filters(&entries, &filter), vec![0, 1]);
+
+        filter.set_field_filter("sc_status=50");
+        assert_eq!(apply_filters(&entries, &filter), vec![0]);
+
+        filter.set_field_filter("sc_status=4, c_ip");
+        assert!(apply_filters(&entries, &filter).is_empty());
+    }
+
+    #[test]
+    fn test_field_filter_tracked_in_is_empty() {
+        let mut filter = FilterState::default();
+        filter.set_field_filter("user=alice");
+        assert!(!filter.is_empty());
+        filter.set_field_filter("  ");
+        assert!(filter.is_empty());
+    }
+
     /// Regression: `errors_and_warnings_from` must include Critical, Error,
     /// and Warning but exclude Info and Debug.  This is the canonical quick-filter
     /// factory used by the Filters panel "Errors + Warnings" preset button.
@@ -1212,13 +1964,14 @@ mod tests {
             id,
             timestamp: ts,
             severity,
-            source_file: PathBuf::from(file),
+            source_file: PathBuf::from(file).into(),
             line_number: id,
             thread: None,
             component: None,
-            message: message.to_string(),
+            message: message.to_string().into(),
             raw_text: message.to_string(),
-            profile_id: "test".to_string(),
+            profile_id: "test".to_string().into(),
+            fields: Default::default(),
             file_modified: None,
         }
     }
@@ -1467,4 +2220,142 @@ mod tests {
         // "alpha" at idx 1 (latest), "beta" at idx 2, "gamma" at idx 3
         assert_eq!(result, vec![1, 2, 3]);
     }
+
+    // -------------------------------------------------------------------------
+    // Query language
+    // -------------------------------------------------------------------------
+
+    fn query_entry(id: u64, severity: Severity, component: &str, message: &str) -> LogEntry {
+        let mut e = make_entry(id, severity, message);
+        e.component = Some(component.to_string().into());
+        e
+    }
+
+    fn query_ids(entries: &[LogEntry], query: &str) -> Vec<u64> {
+        let expr = QueryExpr::parse(query).expect("query must parse");
+        entries
+            .iter()
+            .filter(|e| expr.matches(e))
+            .map(|e| e.id)
+            .collect()
+    }
+
+    fn query_error_position(query: &str) -> usize {
+        match QueryExpr::parse(query) {
+            Err(FilterError::InvalidQuery { position, .. }) => position,
+            other => panic!("expected InvalidQuery for {query:?}, got {other:?}"),
+        }
+    }
+
+    /// The motivating example: OR inside a group, AND with a severity bound
+    /// and a negated message regex.
+    #[test]
+    fn test_query_grouped_or_with_severity_and_negated_regex() {
+        let entries = vec![
+            query_entry(1, Severity::Error, "Dispatcher", "queue stalled"),
+            query_entry(2, Severity::Warning, "Agent", "retry 3 of 5"),
+            query_entry(3, Severity::Info, "Agent", "heartbeat"),
+            query_entry(4, Severity::Critical, "Store", "disk full"),
+            query_entry(5, Severity::Warning, "agent", "lost connection"),
+        ];
+        let ids = query_ids(
+            &entries,
+            r#"(component:Dispatcher OR component:Agent) AND severity>=warning AND NOT msg~"retry \d+""#,
+        );
+        assert_eq!(ids, vec![1, 5]);
+    }
+
+    /// Adjacent terms are AND-ed; AND binds tighter than OR.
+    #[test]
+    fn test_query_implicit_and_and_precedence() {
+        let entries = vec![
+            make_entry(1, Severity::Info, "connection refused"),
+            make_entry(2, Severity::Info, "connection reset"),
+            make_entry(3, Severity::Info, "timeout"),
+        ];
+        assert_eq!(query_ids(&entries, "connection refused"), vec![1]);
+        assert_eq!(
+            query_ids(&entries, "timeout OR connection reset"),
+            vec![2, 3]
+        );
+        assert_eq!(query_ids(&entries, "\"connection re\" NOT reset"), vec![1]);
+        // Lower-case keywords are ordinary search words.
+        assert!(query_ids(&entries, "timeout or reset").is_empty());
+    }
+
+    #[test]
+    fn test_query_severity_comparisons() {
+        let entries: Vec<LogEntry> = Severity::all()
+            .iter()
+            .enumerate()
+            .map(|(i, s)| make_entry(i as u64, *s, "m"))
+            .collect();
+        // 0=Critical 1=Error 2=Warning 3=Info 4=Debug 5=Unknown
+        assert_eq!(query_ids(&entries, "severity>=warning"), vec![0, 1, 2]);
+        assert_eq!(query_ids(&entries, "level>err"), vec![0]);
+        assert_eq!(query_ids(&entries, "sev<info"), vec![4, 5]);
+        assert_eq!(query_ids(&entries, "severity:debug"), vec![4]);
+    }
+
+    #[test]
+    fn test_query_structured_fields_line_and_equality() {
+        let mut a = make_entry(1, Severity::Info, "GET /index");
+        a.fields.insert("sc_status".to_string(), "500".to_string());
+        let mut b = make_entry(20, Severity::Info, "GET /health");
+        b.fields.insert("sc_status".to_string(), "200".to_string());
+        let entries = vec![a, b];
+        assert_eq!(query_ids(&entries, "SC_STATUS=500"), vec![1]);
+        assert_eq!(query_ids(&entries, "sc_status:0"), vec![1, 20]);
+        assert_eq!(query_ids(&entries, "msg=\"get /health\""), vec![20]);
+        assert_eq!(query_ids(&entries, "line>=10"), vec![20]);
+        // A missing field never matches, so NOT of it always does.
+        assert_eq!(query_ids(&entries, "NOT c_ip:x"), vec![1, 20]);
+    }
+
+    /// Parse errors report the character position of the offending token.
+    #[test]
+    fn test_query_error_positions() {
+        assert_eq!(query_error_position("(a OR b"), 0);
+        assert_eq!(query_error_position("a AND (b OR c"), 6);
+        assert_eq!(query_error_position("a b)"), 3);
+        assert_eq!(query_error_position("severity>=loud"), 10);
+        assert_eq!(query_error_position("msg~\"(unclosed\""), 4);
+        assert_eq!(query_error_position("msg:\"open"), 4);
+        assert_eq!(query_error_position("a OR"), 4);
+        assert_eq!(query_error_position("AND b"), 0);
+        assert_eq!(query_error_position("component>=x"), 9);
+        assert_eq!(query_error_position("msg:"), 4);
+        // Positions count characters, not bytes.
+        assert_eq!(query_error_position("\u{e9}\u{e9} )"), 3);
+        let deep = "(".repeat(MAX_QUERY_DEPTH + 1) + "a" + &")".repeat(MAX_QUERY_DEPTH + 1);
+        assert_eq!(query_error_position(&deep), MAX_QUERY_DEPTH);
+    }
+
+    #[test]
+    fn test_set_query_tracked_in_is_empty_and_keeps_error() {
+        let mut f = FilterState::default();
+        assert!(f.set_query("severity>=error").is_ok());
+        assert!(!f.is_empty());
+        assert!(f.set_query("severity>=").is_err());
+        assert!(f.query.is_none(), "invalid query must not stay active");
+        assert_eq!(f.query_input, "severity>=");
+        assert!(matches!(
+            f.query_error,
+            Some(FilterError::InvalidQuery { position: 10, .. })
+        ));
+        assert!(f.set_query("   ").is_ok());
+        assert!(f.query_error.is_none());
+        assert!(f.is_empty());
+    }
+
+    #[test]
+    fn test_query_applied_by_apply_filters() {
+        let entries = vec![
+            make_entry(0, Severity::Error, "disk full"),
+            make_entry(1, Severity::Info, "disk ok"),
+        ];
+        let mut f = FilterState::default();
+        f.set_query("disk AND NOT ok").unwrap();
+        assert_eq!(apply_filters(&entries, &f), vec![0]);
+    }
 }
@@ -0,0 +1,77 @@
+// LogSleuth - core/intern.rs
+//
+// String interning for the repeated text of `LogEntry`: thread and component
+// names (and anything else with few distinct values across many entries)
+// are stored once and shared as `Arc<str>` handles.
+//
+// One `Interner` lives for one parse (a `StreamParser`, an EVTX file, an
+// entry-store decode).  It is bounded: once `MAX_INTERNED_STRINGS` distinct
+// values are held, further new values are allocated on their own, so a
+// column of unique values (request IDs in a thread slot) cannot grow the
+// table without limit (Rule 11).
+//
+// Core layer: pure logic, no I/O.
+
+use crate::util::constants::MAX_INTERNED_STRINGS;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Bounded set of shared strings.
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: HashSet<Arc<str>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A shared handle for `s`: the existing one if `s` was seen before.
+    pub fn intern(&mut self, s: &str) -> Arc<str> {
+        if let Some(existing) = self.strings.get(s) {
+            return Arc::clone(existing);
+        }
+        let handle: Arc<str> = Arc::from(s);
+        if self.strings.len() < MAX_INTERNED_STRINGS {
+            self.strings.insert(Arc::clone(&handle));
+        }
+        handle
+    }
+
+    /// Number of distinct strings held.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+// =============================================================================
+// Unit tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Repeated values share one allocation; the table stops growing at the
+    /// cap but interning still returns the right text.
+    #[test]
+    fn test_intern_shares_and_is_bounded() {
+        let mut interner = Interner::new();
+        let a = interner.intern("worker-1");
+        let b = interner.intern("worker-1");
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(interner.len(), 1);
+
+        for i in 0..MAX_INTERNED_STRINGS + 10 {
+            interner.intern(&format!("req-{i}"));
+        }
+        assert_eq!(interner.len(), MAX_INTERNED_STRINGS);
+        assert_eq!(&*interner.intern("late-value"), "late-value");
+        assert_eq!(interner.len(), MAX_INTERNED_STRINGS);
+    }
+}
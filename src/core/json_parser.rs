@@ -0,0 +1,271 @@
+// LogSleuth - core/json_parser.rs
+//
+// JSON-aware field extraction for JSON Lines / NDJSON profiles.
+//
+// A profile opts in by declaring a `[parsing.json]` section.  Each line that
+// matches the profile's `line_pattern` is decoded as a JSON object, flattened
+// into dotted key paths (`{"log":{"level":"warn"}}` -> `log.level`), and the
+// configured key lists pick out the timestamp, level, message, component and
+// thread.  Every key not consumed by a list is kept as a structured field so
+// the detail pane can show it.
+//
+// Pure logic: no I/O.  `parser::parse_content` owns severity mapping,
+// timestamp parsing and error accounting; this module only extracts values.
+
+use crate::core::model::JsonFieldMap;
+use crate::util::constants::{MAX_ENTRY_FIELDS, MAX_JSON_FIELD_DEPTH};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Raw timestamp value found under one of the `timestamp_keys`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JsonTimestamp {
+    /// A string value, parsed with the profile's `timestamp_format` and the
+    /// usual fallbacks.
+    Text(String),
+    /// A numeric Unix epoch value.  The unit (s / ms / us / ns) is inferred
+    /// from the magnitude by [`epoch_to_utc`].
+    Epoch(f64),
+}
+
+/// Values extracted from a single JSON record.
+#[derive(Debug, Default)]
+pub(crate) struct JsonRecord {
+    pub timestamp: Option<JsonTimestamp>,
+    pub level: Option<String>,
+    pub message: Option<String>,
+    pub component: Option<String>,
+    pub thread: Option<String>,
+    /// Keys not consumed by any key list, flattened to dotted paths.
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Decode `line` as a JSON object and map it through `map`.
+///
+/// Returns `None` when the line is not a JSON object (invalid JSON, an array,
+/// a bare scalar).  The caller then falls back to capture-group parsing so a
+/// malformed record is still shown rather than silently dropped.
+pub(crate) fn parse_json_line(line: &str, map: &JsonFieldMap) -> Option<JsonRecord> {
+    let trimmed = line.trim();
+    if !trimmed.starts_with('{') {
+        return None;
+    }
+    let value: Value = serde_json::from_str(trimmed).ok()?;
+    let Value::Object(obj) = value else {
+        return None;
+    };
+
+    let mut flat: Vec<(String, Value)> = Vec::new();
+    flatten_into(&mut flat, String::new(), obj, 0);
+
+    let mut record = JsonRecord {
+        timestamp: take_first(&mut flat, &map.timestamp_keys).map(|v| match v {
+            Value::Number(n) => n
+                .as_f64()
+                .map(JsonTimestamp::Epoch)
+                .unwrap_or_else(|| JsonTimestamp::Text(n.to_string())),
+            other => JsonTimestamp::Text(value_to_string(other)),
+        }),
+        level: take_first(&mut flat, &map.level_keys).map(value_to_string),
+        message: take_first(&mut flat, &map.message_keys).map(value_to_string),
+        component: take_first(&mut flat, &map.component_keys).map(value_to_string),
+        thread: take_first(&mut flat, &map.thread_keys).map(value_to_string),
+        fields: BTreeMap::new(),
+    };
+
+    // Keep whatever is left for the detail pane, bounded per entry (Rule 11).
+    for (key, value) in flat.into_iter().take(MAX_ENTRY_FIELDS) {
+        record.fields.insert(key, value_to_string(value));
+    }
+
+    Some(record)
+}
+
+/// Convert a numeric Unix epoch into a UTC timestamp.
+///
+/// The unit is inferred from the magnitude, which is unambiguous for any date
+/// between 1973 and 5138: seconds < 1e11, milliseconds < 1e14,
+/// microseconds < 1e17, nanoseconds otherwise.  Fractional seconds are kept.
+/// Returns `None` for negative, non-finite, or out-of-range values.
+pub(crate) fn epoch_to_utc(value: f64) -> Option<DateTime<Utc>> {
+    if !value.is_finite() || value < 0.0 {
+        return None;
+    }
+    let nanos_f = if value < 1e11 {
+        value * 1e9
+    } else if value < 1e14 {
+        value * 1e6
+    } else if value < 1e17 {
+        value * 1e3
+    } else {
+        value
+    };
+    if nanos_f > i64::MAX as f64 {
+        return None;
+    }
+    Some(DateTime::<Utc>::from_timestamp_nanos(nanos_f as i64))
+}
+
+/// Recursively flatten `obj` into `out` as `(dotted.path, leaf)` pairs.
+///
+/// Nulls are dropped (they carry no information for display or mapping).
+/// Arrays are kept as leaves and rendered as compact JSON.  Objects nested
+/// deeper than `MAX_JSON_FIELD_DEPTH` are also kept as compact JSON leaves.
+fn flatten_into(
+    out: &mut Vec<(String, Value)>,
+    prefix: String,
+    obj: serde_json::Map<String, Value>,
+    depth: usize,
+) {
+    for (key, value) in obj {
+        let path = if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            Value::Null => {}
+            Value::Object(inner) if depth < MAX_JSON_FIELD_DEPTH && !inner.is_empty() => {
+                flatten_into(out, path, inner, depth + 1);
+            }
+            other => out.push((path, other)),
+        }
+    }
+}
+
+/// Remove and return the value of the first key in `keys` present in `flat`.
+///
+/// Matching is ASCII case-insensitive so `Level`, `level` and `LEVEL` all
+/// satisfy a `level` key.
+fn take_first(flat: &mut Vec<(String, Value)>, keys: &[String]) -> Option<Value> {
+    for key in keys {
+        if let Some(pos) = flat.iter().position(|(k, _)| k.eq_ignore_ascii_case(key)) {
+            return Some(flat.remove(pos).1);
+        }
+    }
+    None
+}
+
+/// Render a leaf value for display: strings verbatim (no quotes), everything
+/// else as compact JSON.
+fn value_to_string(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn default_map() -> JsonFieldMap {
+        let to_vec = |keys: &[&str]| keys.iter().map(|k| k.to_string()).collect();
+        use crate::util::constants as c;
+        JsonFieldMap {
+            timestamp_keys: to_vec(c::JSON_DEFAULT_TIMESTAMP_KEYS),
+            level_keys: to_vec(c::JSON_DEFAULT_LEVEL_KEYS),
+            message_keys: to_vec(c::JSON_DEFAULT_MESSAGE_KEYS),
+            component_keys: to_vec(c::JSON_DEFAULT_COMPONENT_KEYS),
+            thread_keys: to_vec(c::JSON_DEFAULT_THREAD_KEYS),
+        }
+    }
+
+    #[test]
+    fn test_flat_record_maps_known_keys_and_keeps_the_rest() {
+        let line = r#"{"ts":"2024-01-15T14:30:22Z","level":"error","msg":"boom","logger":"db","thread":"main","user":"alice","attempt":3}"#;
+        let rec = parse_json_line(line, &default_map()).expect("object should decode");
+        assert_eq!(
+            rec.timestamp,
+            Some(JsonTimestamp::Text("2024-01-15T14:30:22Z".into()))
+        );
+        assert_eq!(rec.level.as_deref(), Some("error"));
+        assert_eq!(rec.message.as_deref(), Some("boom"));
+        assert_eq!(rec.component.as_deref(), Some("db"));
+        assert_eq!(rec.thread.as_deref(), Some("main"));
+        assert_eq!(rec.fields.len(), 2);
+        assert_eq!(rec.fields.get("user").map(String::as_str), Some("alice"));
+        assert_eq!(rec.fields.get("attempt").map(String::as_str), Some("3"));
+    }
+
+    /// ECS-style nested keys (`log.level`) must resolve through nested
+    /// objects, and sibling keys of a consumed leaf must survive as fields.
+    #[test]
+    fn test_nested_keys_resolve_via_dotted_path() {
+        let line = r#"{"@timestamp":"2024-01-15T14:30:22.123Z","log":{"level":"WARN","logger":"api"},"message":"slow","http":{"status":503}}"#;
+        let rec = parse_json_line(line, &default_map()).expect("object should decode");
+        assert_eq!(rec.level.as_deref(), Some("WARN"));
+        assert_eq!(rec.component.as_deref(), Some("api"));
+        assert_eq!(
+            rec.fields.get("http.status").map(String::as_str),
+            Some("503")
+        );
+        assert!(!rec.fields.contains_key("log.level"));
+    }
+
+    #[test]
+    fn test_literal_dotted_key_matches() {
+        let line = r#"{"log.level":"info","message":"x"}"#;
+        let rec = parse_json_line(line, &default_map()).expect("object should decode");
+        assert_eq!(rec.level.as_deref(), Some("info"));
+    }
+
+    #[test]
+    fn test_numeric_timestamp_is_epoch() {
+        let line = r#"{"time":1705329022000,"msg":"x"}"#;
+        let rec = parse_json_line(line, &default_map()).expect("object should decode");
+        let Some(JsonTimestamp::Epoch(v)) = rec.timestamp else {
+            panic!("expected epoch timestamp");
+        };
+        assert_eq!(
+            epoch_to_utc(v),
+            Some(Utc.with_ymd_and_hms(2024, 1, 15, 14, 30, 22).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_epoch_unit_inference() {
+        let expected = Utc.with_ymd_and_hms(2024, 1, 15, 14, 30, 22).unwrap();
+        assert_eq!(epoch_to_utc(1_705_329_022.0), Some(expected));
+        assert_eq!(epoch_to_utc(1_705_329_022_000.0), Some(expected));
+        assert_eq!(epoch_to_utc(1_705_329_022_000_000.0), Some(expected));
+        assert_eq!(epoch_to_utc(1.705_329_022e18), Some(expected));
+        assert_eq!(epoch_to_utc(-1.0), None);
+        assert_eq!(epoch_to_utc(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_non_object_lines_return_none() {
+        let map = default_map();
+        assert!(parse_json_line("[1,2,3]", &map).is_none());
+        assert!(parse_json_line("{not json}", &map).is_none());
+        assert!(parse_json_line("plain text", &map).is_none());
+    }
+
+    #[test]
+    fn test_key_match_is_case_insensitive_and_nulls_dropped() {
+        let line = r#"{"Level":"debug","Message":"hi","extra":null}"#;
+        let rec = parse_json_line(line, &default_map()).expect("object should decode");
+        assert_eq!(rec.level.as_deref(), Some("debug"));
+        assert_eq!(rec.message.as_deref(), Some("hi"));
+        assert!(rec.fields.is_empty());
+    }
+
+    /// Unmapped fields must be capped at MAX_ENTRY_FIELDS per entry.
+    #[test]
+    fn test_field_count_is_bounded() {
+        let mut line = String::from("{");
+        for i in 0..(MAX_ENTRY_FIELDS + 20) {
+            line.push_str(&format!("\"k{i:03}\":{i},"));
+        }
+        line.push_str("\"msg\":\"x\"}");
+        let rec = parse_json_line(&line, &default_map()).expect("object should decode");
+        assert_eq!(rec.fields.len(), MAX_ENTRY_FIELDS);
+    }
+}
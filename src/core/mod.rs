@@ -4,12 +4,22 @@
 // Dependencies: standard library only.
 // Must NOT depend on: ui, platform, app, or any I/O crate directly.
 
+pub mod alert;
+pub mod anomaly;
+pub mod archive;
+pub mod clock;
+pub mod compare;
+pub mod correlation;
 pub mod discovery;
-#[cfg(target_os = "windows")]
+pub mod encoding;
+pub mod entry_store;
 pub mod evtx_parser;
 pub mod export;
 pub mod filter;
+pub mod intern;
+pub mod json_parser;
 pub mod model;
 pub mod multi_search;
 pub mod parser;
+pub mod patterns;
 pub mod profile;
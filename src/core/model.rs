@@ -6,9 +6,11 @@
 // These types are the shared vocabulary across all layers.
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 // =============================================================================
 // Log Entry (normalised output of parsing)
@@ -19,7 +21,12 @@ use std::path::PathBuf;
 /// This is the core data unit that flows through filtering, display,
 /// and export. Every format profile produces these regardless of the
 /// source log's native structure.
-#[derive(Debug, Clone, Serialize)]
+///
+/// Memory: repeated text is shared rather than copied per entry.  The source
+/// path and profile ID are one `Arc` per parsed file; thread and component
+/// names are interned per parse (`core::intern`); the message is usually a
+/// range of `raw_text` (`MessageText::Span`).
+#[derive(Debug, Clone)]
 pub struct LogEntry {
     /// Monotonically increasing unique ID within the scan session.
     pub id: u64,
@@ -31,26 +38,37 @@ pub struct LogEntry {
     /// Normalised severity level.
     pub severity: Severity,
 
-    /// Path to the source log file.
-    pub source_file: PathBuf,
+    /// Path to the source log file (shared by every entry of the file).
+    pub source_file: Arc<Path>,
 
     /// Line number in the source file where this entry begins.
     pub line_number: u64,
 
     /// Thread or process ID extracted from the log line (format-dependent).
-    pub thread: Option<String>,
+    pub thread: Option<Arc<str>>,
 
     /// Source component or module name (format-dependent).
-    pub component: Option<String>,
+    pub component: Option<Arc<str>>,
 
     /// Full message text, including any continuation/multi-line content.
-    pub message: String,
+    /// Read it with `message()`.
+    pub message: MessageText,
 
     /// Original unparsed text from the source file.
     pub raw_text: String,
 
     /// ID of the format profile used to parse this entry.
-    pub profile_id: String,
+    pub profile_id: Arc<str>,
+
+    /// Additional structured key/value fields that do not map onto one of the
+    /// fixed slots above: extra named capture groups in the profile's
+    /// `line_pattern`, or unmapped keys of a JSON Lines record (flattened to
+    /// dotted names such as `http.status`).  Shown in the detail pane, usable
+    /// as a filter, and exported as extra CSV columns.
+    ///
+    /// Empty for most formats; an empty `BTreeMap` does not allocate.  Bounded
+    /// to `MAX_ENTRY_FIELDS` keys per entry.
+    pub fields: BTreeMap<String, String>,
 
     /// Last-modified time of the **source file** as recorded by the OS at scan
     /// time (or at the tail-tick time for live-tail entries).
@@ -63,10 +81,140 @@ pub struct LogEntry {
     ///   - Live-tail entries written right now whose log timestamps may lag
     ///
     /// Not serialised to CSV/JSON export (internal bookkeeping only).
-    #[serde(skip)]
     pub file_modified: Option<DateTime<Utc>>,
 }
 
+/// Storage of a `LogEntry` message.
+///
+/// Most messages are a slice of the entry's own `raw_text` (the `message`
+/// capture group, or the whole line), so they are kept as a byte range into
+/// it instead of a second copy.  Messages that are not (JSON records,
+/// truncated text) are owned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageText {
+    /// `raw_text[start..end]`.
+    Span {
+        start: u32,
+        end: u32,
+    },
+    Owned(String),
+}
+
+impl MessageText {
+    /// `raw[range]` as a span, or owned if the range does not fit in `u32`.
+    pub fn slice_of(raw: &str, range: std::ops::Range<usize>) -> Self {
+        match (u32::try_from(range.start), u32::try_from(range.end)) {
+            (Ok(start), Ok(end)) => Self::Span { start, end },
+            _ => Self::Owned(raw[range].to_string()),
+        }
+    }
+}
+
+impl From<String> for MessageText {
+    fn from(s: String) -> Self {
+        Self::Owned(s)
+    }
+}
+
+impl From<&str> for MessageText {
+    fn from(s: &str) -> Self {
+        Self::Owned(s.to_string())
+    }
+}
+
+impl LogEntry {
+    /// The message text.
+    pub fn message(&self) -> &str {
+        match &self.message {
+            MessageText::Span { start, end } => self
+                .raw_text
+                .get(*start as usize..*end as usize)
+                .unwrap_or(""),
+            MessageText::Owned(s) => s,
+        }
+    }
+
+    /// Replace the message with owned text.
+    pub fn set_message(&mut self, message: String) {
+        self.message = MessageText::Owned(message);
+    }
+
+    /// Append a continuation `line` to the message and raw text, each only
+    /// while it is at most `max_size` bytes.  A span message that ends where
+    /// `raw_text` ends simply grows with it.
+    pub fn append_line(&mut self, line: &str, max_size: usize) {
+        let raw_end = self.raw_text.len();
+        let grows_with_raw =
+            matches!(self.message, MessageText::Span { end, .. } if end as usize == raw_end);
+        if grows_with_raw && raw_end <= max_size {
+            self.raw_text.push('\n');
+            self.raw_text.push_str(line);
+            self.message =
+                MessageText::slice_of(&self.raw_text, self.message_start()..self.raw_text.len());
+            return;
+        }
+        if self.message().len() <= max_size {
+            let mut message = self.take_message();
+            message.push('\n');
+            message.push_str(line);
+            self.message = MessageText::Owned(message);
+        }
+        if self.raw_text.len() <= max_size {
+            self.own_message();
+            self.raw_text.push('\n');
+            self.raw_text.push_str(line);
+        }
+    }
+
+    /// Make the message owned, so `raw_text` can change without altering it.
+    pub fn own_message(&mut self) {
+        if let MessageText::Span { .. } = self.message {
+            self.message = MessageText::Owned(self.message().to_string());
+        }
+    }
+
+    fn take_message(&mut self) -> String {
+        match std::mem::replace(&mut self.message, MessageText::Owned(String::new())) {
+            MessageText::Owned(s) => s,
+            MessageText::Span { start, end } => self
+                .raw_text
+                .get(start as usize..end as usize)
+                .unwrap_or("")
+                .to_string(),
+        }
+    }
+
+    fn message_start(&self) -> usize {
+        match self.message {
+            MessageText::Span { start, .. } => start as usize,
+            MessageText::Owned(_) => 0,
+        }
+    }
+}
+
+/// Field order and skip rules of the JSON export: `message` is written as
+/// text, `fields` only when non-empty, `file_modified` never.
+impl Serialize for LogEntry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = if self.fields.is_empty() { 10 } else { 11 };
+        let mut s = serializer.serialize_struct("LogEntry", len)?;
+        s.serialize_field("id", &self.id)?;
+        s.serialize_field("timestamp", &self.timestamp)?;
+        s.serialize_field("severity", &self.severity)?;
+        s.serialize_field("source_file", &*self.source_file)?;
+        s.serialize_field("line_number", &self.line_number)?;
+        s.serialize_field("thread", &self.thread.as_deref())?;
+        s.serialize_field("component", &self.component.as_deref())?;
+        s.serialize_field("message", self.message())?;
+        s.serialize_field("raw_text", &self.raw_text)?;
+        s.serialize_field("profile_id", &*self.profile_id)?;
+        if !self.fields.is_empty() {
+            s.serialize_field("fields", &self.fields)?;
+        }
+        s.end()
+    }
+}
+
 // =============================================================================
 // Severity
 // =============================================================================
@@ -113,6 +261,16 @@ impl Severity {
         }
     }
 
+    /// Parse a severity from its display label, ignoring ASCII case
+    /// (`"warning"`, `"Error"`).  Used by the `--filter-level` and
+    /// `scan --severity` CLI options.
+    pub fn from_label(s: &str) -> Option<Severity> {
+        Severity::all()
+            .iter()
+            .copied()
+            .find(|sev| sev.label().eq_ignore_ascii_case(s.trim()))
+    }
+
     /// Short label for compact display (e.g. table columns).
     pub fn short_label(&self) -> &'static str {
         match self {
@@ -153,6 +311,104 @@ pub enum MultilineMode {
     Raw,
 }
 
+// =============================================================================
+// Multi-group timestamps
+// =============================================================================
+
+/// How the optional `tz_offset` capture group is interpreted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TzOffsetStyle {
+    /// ISO 8601 style UTC offset: `Z`, `+05:30`, `+0530` or `+05`.
+    /// The captured time is local time, so UTC = local - offset.
+    #[default]
+    Iso,
+
+    /// Windows time-zone bias in minutes, as written by CMTrace in the
+    /// `time="14:30:22.123+480"` suffix.  UTC = local + bias, so `+480` is
+    /// US Pacific Standard Time and `-60` is Central European Time.
+    BiasMinutes,
+}
+
+impl TzOffsetStyle {
+    /// TOML spelling of the style, used in parse error messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Iso => "iso",
+            Self::BiasMinutes => "bias_minutes",
+        }
+    }
+}
+
+/// One piece of a compiled timestamp template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplatePart {
+    /// Literal text copied verbatim (e.g. the space in `{date} {time}`).
+    Literal(String),
+    /// Text of the named capture group.  A group that did not participate in
+    /// the match contributes nothing.
+    Group(String),
+}
+
+/// Recipe for building the raw timestamp string from several capture groups,
+/// e.g. `{date} {time}` for formats whose date and time are separate fields
+/// (CMTrace writes `time="..."` before `date="..."`).
+///
+/// Compiled from `[parsing] timestamp_template`, or derived automatically when
+/// the `line_pattern` declares `date` and/or `time` groups.  The assembled
+/// string is then parsed with the profile's `timestamp_format`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampTemplate {
+    pub parts: Vec<TemplatePart>,
+}
+
+impl TimestampTemplate {
+    /// Parse a template string of literals and `{group}` placeholders.
+    ///
+    /// Returns an error for unbalanced braces, empty placeholders, or a
+    /// template with no placeholder at all.
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+        while let Some(open) = rest.find(['{', '}']) {
+            if rest[open..].starts_with('}') {
+                return Err(format!(
+                    "unmatched '}}' at byte {}",
+                    template.len() - rest.len() + open
+                ));
+            }
+            if open > 0 {
+                parts.push(TemplatePart::Literal(rest[..open].to_string()));
+            }
+            let after = &rest[open + 1..];
+            let close = after
+                .find('}')
+                .ok_or_else(|| "unclosed '{' placeholder".to_string())?;
+            let name = after[..close].trim();
+            if name.is_empty() || name.contains('{') {
+                return Err("empty or nested '{}' placeholder".to_string());
+            }
+            parts.push(TemplatePart::Group(name.to_string()));
+            rest = &after[close + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Literal(rest.to_string()));
+        }
+        if !parts.iter().any(|p| matches!(p, TemplatePart::Group(_))) {
+            return Err("template has no {group} placeholder".to_string());
+        }
+        Ok(Self { parts })
+    }
+
+    /// Names of the capture groups referenced by the template, in order.
+    pub fn group_names(&self) -> impl Iterator<Item = &str> {
+        self.parts.iter().filter_map(|p| match p {
+            TemplatePart::Group(name) => Some(name.as_str()),
+            TemplatePart::Literal(_) => None,
+        })
+    }
+}
+
 // =============================================================================
 // Format Profile (runtime representation)
 // =============================================================================
@@ -194,12 +450,50 @@ pub struct FormatProfile {
     /// Named capture groups: timestamp, level, thread, component, message.
     pub line_pattern: regex::Regex,
 
-    /// chrono format string for parsing the timestamp capture group.
+    /// Names of any additional named capture groups in `line_pattern` (e.g.
+    /// `c_ip`, `sc_status`, `pid`).  Their matched text is stored in
+    /// `LogEntry::fields` under the group name.  Computed once at profile load
+    /// so the parser does not re-filter `capture_names()` on every line.
+    pub field_groups: Vec<String>,
+
+    /// chrono format string for parsing the timestamp capture group (or the
+    /// string assembled by `timestamp_template`).
     pub timestamp_format: String,
 
+    /// Multi-group timestamp recipe.  When `Some`, the raw timestamp is built
+    /// from the referenced capture groups instead of the single `timestamp`
+    /// group.  `None` for single-group profiles.
+    pub timestamp_template: Option<TimestampTemplate>,
+
+    /// Interpretation of the optional `tz_offset` capture group.  When that
+    /// group matches, the parsed timestamp is shifted from local time to UTC.
+    pub tz_offset_style: TzOffsetStyle,
+
+    /// Zone that naive timestamps (no offset in the text or a `tz_offset`
+    /// group) are written in, from `[parsing] timezone`.  Defaults to UTC.
+    /// A per-file `ClockRule` zone overrides it.
+    pub timezone: crate::core::clock::AssumedZone,
+
     /// How to handle lines that do not match line_pattern.
     pub multiline_mode: MultilineMode,
 
+    /// Optional record framing from `[parsing] entry_start`: only a line
+    /// matching this starts a new entry; every other line continues the
+    /// current one, even if it matches `line_pattern`.
+    pub entry_start: Option<regex::Regex>,
+
+    /// Optional record framing from `[parsing] entry_end`: a line matching
+    /// this is the last line of its entry; the next line starts a new one.
+    pub entry_end: Option<regex::Regex>,
+
+    /// JSON key lists from the optional `[parsing.json]` TOML section.
+    ///
+    /// When `Some`, every line matching `line_pattern` is first decoded as a
+    /// JSON object and the entry fields are taken from the configured keys;
+    /// lines that are not valid JSON objects fall back to the capture-group
+    /// path.  `None` for all regex-only profiles.
+    pub json_fields: Option<JsonFieldMap>,
+
     /// Maps normalised Severity variants to lists of format-specific strings.
     /// Matching is case-insensitive.
     pub severity_mapping: HashMap<Severity, Vec<String>>,
@@ -222,6 +516,21 @@ pub struct FormatProfile {
     pub log_locations: Vec<String>,
 }
 
+/// Key lists used to map a JSON Lines record onto `LogEntry` fields.
+///
+/// Each list is tried in order and the first key present in the record wins.
+/// Keys are matched case-insensitively against the flattened (dot-joined) key
+/// path, so `log.level` matches both nested and literally-dotted keys.  Keys
+/// that are not consumed by any list are kept in `LogEntry::fields`.
+#[derive(Debug, Clone, Default)]
+pub struct JsonFieldMap {
+    pub timestamp_keys: Vec<String>,
+    pub level_keys: Vec<String>,
+    pub message_keys: Vec<String>,
+    pub component_keys: Vec<String>,
+    pub thread_keys: Vec<String>,
+}
+
 impl FormatProfile {
     /// Determines the normalised severity for a raw level string.
     ///
@@ -348,6 +657,10 @@ pub struct ScanSummary {
     /// Per-file breakdown.
     pub file_summaries: Vec<FileSummary>,
 
+    /// True when entries beyond `max_total_entries` were dropped (scans
+    /// without a spill directory); the session is then incomplete.
+    pub entry_cap_reached: bool,
+
     /// Wall-clock scan duration.
     pub duration: std::time::Duration,
 }
@@ -372,6 +685,11 @@ pub struct FileSummary {
 
     /// Latest timestamp found (if any).
     pub latest: Option<DateTime<Utc>>,
+
+    /// Files summarised: 1 for a single file, the segment count once a
+    /// rotation family is merged into one stream (`path` is then the
+    /// family's shared path, e.g. `app.log` for `app.log.1`, `app.log.2.gz`).
+    pub segments: usize,
 }
 
 // =============================================================================
@@ -437,6 +755,13 @@ pub enum ScanProgress {
     /// while still allowing the UI to display partial results before the scan
     /// finishes.
     EntriesBatch { entries: Vec<LogEntry> },
+
+    /// Entries beyond `max_total_entries`, written to an on-disk store
+    /// instead of dropped.  Sent once, after the last `EntriesBatch` and
+    /// before `ParsingCompleted`; only when the scan had a spill directory.
+    EntriesSpilled {
+        store: std::sync::Arc<std::sync::Mutex<crate::core::entry_store::EntryStore>>,
+    },
 }
 
 // =============================================================================
@@ -483,11 +808,48 @@ pub enum TailProgress {
     Started { file_count: usize },
 
     /// One or more new entries were parsed from a watched file.
-    NewEntries { entries: Vec<LogEntry> },
+    NewEntries {
+        entries: Vec<LogEntry>,
+        /// The entries are history replayed by a `TailStart` replay (content
+        /// already in the file when the tail started), not new content.
+        replay: bool,
+    },
 
     /// Tail was stopped (either by user request or a fatal error).
     Stopped,
 
     /// A non-fatal per-file error occurred (file temporarily inaccessible etc).
     FileError { path: PathBuf, message: String },
+
+    /// A watched file was rotated.  Lines still unread in the rotated file
+    /// have been delivered as `NewEntries` before this message.
+    Rotated {
+        /// The watched path, now followed from the start of the new file.
+        path: PathBuf,
+        kind: RotationKind,
+        /// Where the rotated content now lives (`app.log.1`), when found.
+        predecessor: Option<PathBuf>,
+        /// Synthetic entry that records the rotation in the timeline.
+        marker: LogEntry,
+    },
+}
+
+/// How a tailed file was rotated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationKind {
+    /// Renamed away (`app.log` -> `app.log.1`) and a new file created at
+    /// its path.
+    Renamed,
+    /// Copied aside and truncated in place (logrotate `copytruncate`).
+    Truncated,
+}
+
+impl RotationKind {
+    /// Lower-case name, used in the rotation marker entry.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Renamed => "renamed",
+            Self::Truncated => "truncated",
+        }
+    }
 }
@@ -3,12 +3,22 @@
 // Stream-oriented log file parsing using format profiles.
 // Core layer: accepts Read trait objects, never touches filesystem directly.
 
-use crate::core::model::{FormatProfile, LogEntry};
+use crate::core::clock::{self, AssumedZone, ClockRule};
+use crate::core::encoding::LineReader;
+use crate::core::intern::Interner;
+use crate::core::json_parser::{self, JsonTimestamp};
+use crate::core::model::{
+    FormatProfile, LogEntry, MessageText, Severity, TemplatePart, TimestampTemplate, TzOffsetStyle,
+};
+use crate::util::constants::{MAX_ENTRY_FIELDS, MAX_TZ_OFFSET_MINUTES};
 use crate::util::error::ParseError;
 use chrono::{DateTime, Datelike, NaiveDateTime, Utc};
 use regex::Regex;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
 use std::path::Path;
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 /// Configuration for parsing operations.
 #[derive(Debug, Clone)]
@@ -16,6 +26,10 @@ pub struct ParseConfig {
     pub chunk_size: usize,
     pub max_entry_size: usize,
     pub max_parse_errors_per_file: usize,
+    /// Clock rules whose zones override the profile's `timezone` for the
+    /// files they cover (`core::clock::zone_for`).  Offsets are applied
+    /// after parsing, not here.
+    pub clock_rules: Vec<ClockRule>,
 }
 
 impl Default for ParseConfig {
@@ -25,10 +39,19 @@ impl Default for ParseConfig {
             chunk_size: constants::DEFAULT_CHUNK_SIZE,
             max_entry_size: constants::DEFAULT_MAX_ENTRY_SIZE,
             max_parse_errors_per_file: constants::MAX_PARSE_ERRORS_PER_FILE,
+            clock_rules: Vec::new(),
         }
     }
 }
 
+impl ParseConfig {
+    /// Zone that naive timestamps in `file` are written in: a clock-rule
+    /// override, else the profile's `timezone`.
+    pub fn zone_for(&self, file: &Path, profile: &FormatProfile) -> AssumedZone {
+        clock::zone_for(&self.clock_rules, file).unwrap_or(profile.timezone)
+    }
+}
+
 /// Result of parsing a single log file.
 #[derive(Debug)]
 pub struct ParseResult {
@@ -61,170 +84,286 @@ fn truncate_to_char_boundary(s: &mut String, max_len: usize) {
     s.truncate(boundary);
 }
 
-/// Parse a log file using the given format profile.
-///
-/// Reads the file content and applies the profile's line_pattern to extract
-/// structured fields from each line. Multi-line entries are handled according
-/// to the profile's multiline_mode setting.
+/// Cap `entry`'s message and raw text at `max_entry_size` bytes, marking
+/// either as truncated.
+fn cap_entry_size(entry: &mut LogEntry, max_entry_size: usize) {
+    if entry.message().len() > max_entry_size {
+        let mut message = entry.message().to_string();
+        truncate_to_char_boundary(&mut message, max_entry_size);
+        message.push_str("... [truncated]");
+        entry.set_message(message);
+    }
+    if entry.raw_text.len() > max_entry_size {
+        entry.own_message();
+        truncate_to_char_boundary(&mut entry.raw_text, max_entry_size);
+        entry.raw_text.push_str("... [truncated]");
+    }
+}
+
+/// An entry for a line with no parsed fields (Raw mode, or a framed record
+/// whose first line does not match `line_pattern`).
+fn unparsed_entry(
+    id: u64,
+    line: &str,
+    source_file: &Arc<Path>,
+    line_number: u64,
+    profile_id: &Arc<str>,
+) -> LogEntry {
+    // The message is the whole line: a span of raw_text, not a copy.
+    LogEntry {
+        id,
+        timestamp: None,
+        severity: Severity::Unknown,
+        source_file: Arc::clone(source_file),
+        line_number,
+        thread: None,
+        component: None,
+        message: MessageText::slice_of(line, 0..line.len()),
+        raw_text: line.to_string(),
+        profile_id: Arc::clone(profile_id),
+        fields: BTreeMap::new(),
+        file_modified: None, // set by app layer after parsing
+    }
+}
+
+/// Incremental parser for one file: feed it lines in order with
+/// `push_line`, then call `finish` for the entries.
 ///
-/// # Arguments
-/// * `content` - File content as a string (the app layer handles reading)
-/// * `file_path` - Path to the source file (for LogEntry metadata)
-/// * `profile` - The format profile to use for parsing
-/// * `config` - Parsing configuration (limits)
-/// * `id_start` - Starting ID for entries (for global uniqueness across files)
-pub fn parse_content(
-    content: &str,
-    file_path: &Path,
-    profile: &FormatProfile,
-    config: &ParseConfig,
-    id_start: u64,
-) -> ParseResult {
-    tracing::debug!(
-        file = %file_path.display(),
-        profile = %profile.id,
-        "Parsing started"
-    );
-
-    // Hoist allocations that are identical for every entry out of the loop.
-    // On a 100 k-line file this eliminates 100 k PathBuf clones and 100 k
-    // String clones for profile_id.
-    let source_file = file_path.to_path_buf();
-    let profile_id = profile.id.clone();
-
-    let mut entries = Vec::new();
-    let mut errors = Vec::new();
-    let mut current_id = id_start;
-    let mut lines_processed: u64 = 0;
-
-    for (line_idx, line) in content.lines().enumerate() {
-        lines_processed += 1;
-        let line_number = (line_idx as u64) + 1;
+/// Holds the parsed entries and the multi-line / framing state, never the
+/// file content, so callers can stream a file of any size through it
+/// (`parse_reader`, `app::scan`).
+pub struct StreamParser<'a> {
+    profile: &'a FormatProfile,
+    config: &'a ParseConfig,
+    // Hoisted out of the per-line path: identical for every entry, and
+    // shared by every entry rather than copied into each.
+    source_file: Arc<Path>,
+    profile_id: Arc<str>,
+    // Thread and component names, shared across entries.
+    strings: Interner,
+    zone: AssumedZone,
+    entries: Vec<LogEntry>,
+    errors: Vec<ParseError>,
+    current_id: u64,
+    lines_processed: u64,
+    // Record framing (`entry_start` / `entry_end`): whether the current
+    // record still takes continuation lines, and whether it produced an entry
+    // (a Skip-mode record whose first line does not match `line_pattern` is
+    // dropped whole rather than appended to the previous entry).
+    framed: bool,
+    record_open: bool,
+    record_kept: bool,
+}
+
+impl<'a> StreamParser<'a> {
+    /// Start parsing `file_path` with `profile`; entry IDs count up from
+    /// `id_start` (for global uniqueness across files).
+    pub fn new(
+        file_path: &Path,
+        profile: &'a FormatProfile,
+        config: &'a ParseConfig,
+        id_start: u64,
+    ) -> Self {
+        tracing::debug!(
+            file = %file_path.display(),
+            profile = %profile.id,
+            "Parsing started"
+        );
+        Self {
+            profile,
+            config,
+            source_file: Arc::from(file_path),
+            profile_id: Arc::from(profile.id.as_str()),
+            strings: Interner::new(),
+            zone: config.zone_for(file_path, profile),
+            entries: Vec::new(),
+            errors: Vec::new(),
+            current_id: id_start,
+            lines_processed: 0,
+            framed: profile.entry_start.is_some() || profile.entry_end.is_some(),
+            record_open: false,
+            record_kept: false,
+        }
+    }
+
+    /// Parse the next line of the file (without its line terminator).
+    pub fn push_line(&mut self, line: &str) {
+        self.lines_processed += 1;
+        let line_number = self.lines_processed;
 
         // Skip empty lines
         if line.trim().is_empty() {
-            continue;
+            return;
         }
 
-        // Attempt to match the line against the profile's line_pattern
-        if let Some(caps) = profile.line_pattern.captures(line) {
-            let message = caps
-                .name("message")
-                .map(|m| m.as_str().to_string())
-                .unwrap_or_else(|| line.to_string());
-
-            // Extract severity:
-            //   1. If the profile captured a `level` field, map it via
-            //      severity_mapping (case-insensitive exact match).
-            //   2. If step 1 returns Unknown (unrecognised level string),
-            //      fall back to regex override patterns on the message.
-            //   3. If there is no `level` capture group, try regex override
-            //      patterns first, then plain-keyword message inference.
-            // This layered approach means: structured profiles get precise
-            // level-based classification; plain-text and fallback profiles can
-            // classify entries via [WARN]-style embedded markers.
-            let severity = if let Some(level_match) = caps.name("level") {
-                let mapped = profile.map_severity(level_match.as_str());
-                if mapped == crate::core::model::Severity::Unknown {
-                    // Level field present but value not in severity_mapping --
-                    // try regex override on the message as a second chance.
-                    profile
-                        .apply_severity_override(&message)
-                        .unwrap_or(crate::core::model::Severity::Unknown)
-                } else {
-                    mapped
+        if self.framed {
+            let starts = self
+                .profile
+                .entry_start
+                .as_ref()
+                .is_some_and(|re| re.is_match(line));
+            let ends = self
+                .profile
+                .entry_end
+                .as_ref()
+                .is_some_and(|re| re.is_match(line));
+            if self.record_open && !starts {
+                // Inside a record: append whatever the line looks like.
+                if self.record_kept {
+                    if let Some(last) = self.entries.last_mut() {
+                        last.append_line(line, self.config.max_entry_size);
+                    }
                 }
-            } else {
-                // No level capture -- regex override takes priority over
-                // keyword substring matching so patterns like \[WARN\] win
-                // before the generic substring fallback fires.
-                profile
-                    .apply_severity_override(&message)
-                    .unwrap_or_else(|| profile.infer_severity_from_message(&message))
-            };
+                self.record_open = !ends;
+                return;
+            }
+            // This line starts a record (it may also end it).
+            self.record_open = !ends;
+            self.record_kept = true;
+        }
 
-            // Parse timestamp using the profile's format string.
-            // On failure: record a non-fatal parse error and keep timestamp as None
-            // so the entry is still visible in the timeline (sorted to the end).
-            let timestamp: Option<DateTime<Utc>> =
-                if let Some(raw_ts) = caps.name("timestamp").map(|m| m.as_str()) {
-                    match parse_timestamp(raw_ts, &profile.timestamp_format) {
-                        Ok(ts) => Some(ts),
-                        Err(_msg) => {
-                            if errors.len() < config.max_parse_errors_per_file {
-                                errors.push(ParseError::TimestampParse {
-                                    file: source_file.clone(),
-                                    line_number,
-                                    raw_timestamp: raw_ts.to_string(),
-                                    format: profile.timestamp_format.clone(),
-                                });
-                            }
-                            None
-                        }
-                    }
-                } else {
-                    None
+        // Attempt to match the line against the profile's line_pattern
+        if let Some(caps) = self.profile.line_pattern.captures(line) {
+            // JSON-aware path: profiles with a `[parsing.json]` section take
+            // their fields from the decoded record.  A line that matches the
+            // pattern but is not a valid JSON object falls through to the
+            // capture-group path below so it is still shown.
+            if let Some(record) = self
+                .profile
+                .json_fields
+                .as_ref()
+                .and_then(|map| json_parser::parse_json_line(line, map))
+            {
+                let message = match record.message {
+                    Some(m) => MessageText::Owned(m),
+                    None => MessageText::slice_of(line, 0..line.len()),
+                };
+                let severity = resolve_severity(
+                    self.profile,
+                    record.level.as_deref(),
+                    match &message {
+                        MessageText::Owned(m) => m,
+                        MessageText::Span { .. } => line,
+                    },
+                );
+                let timestamp = match record.timestamp {
+                    Some(JsonTimestamp::Epoch(secs)) => json_parser::epoch_to_utc(secs),
+                    Some(JsonTimestamp::Text(raw_ts)) => timestamp_or_error(
+                        &raw_ts,
+                        self.profile,
+                        self.zone,
+                        &self.source_file,
+                        line_number,
+                        self.config,
+                        &mut self.errors,
+                    ),
+                    None => None,
+                };
+                self.entries.push(LogEntry {
+                    id: self.current_id,
+                    timestamp,
+                    severity,
+                    source_file: Arc::clone(&self.source_file),
+                    line_number,
+                    thread: record.thread.map(|t| self.strings.intern(&t)),
+                    component: record.component.map(|c| self.strings.intern(&c)),
+                    message,
+                    raw_text: line.to_string(),
+                    profile_id: Arc::clone(&self.profile_id),
+                    fields: record.fields,
+                    file_modified: None, // set by app layer after parsing
+                });
+                self.current_id += 1;
+            } else {
+                // The message is a span of the line (kept as raw_text), not
+                // a copy.
+                let message_range = caps.name("message").map_or(0..line.len(), |m| m.range());
+
+                let severity = resolve_severity(
+                    self.profile,
+                    caps.name("level").map(|m| m.as_str()),
+                    &line[message_range.clone()],
+                );
+
+                // Parse timestamp using the profile's format string.
+                // On failure: record a non-fatal parse error and keep timestamp as None
+                // so the entry is still visible in the timeline (sorted to the end).
+                let timestamp = capture_timestamp(
+                    self.profile,
+                    self.zone,
+                    &caps,
+                    &self.source_file,
+                    line_number,
+                    self.config,
+                    &mut self.errors,
+                );
+
+                let entry = LogEntry {
+                    id: self.current_id,
+                    timestamp,
+                    severity,
+                    source_file: Arc::clone(&self.source_file),
+                    line_number,
+                    thread: caps.name("thread").map(|m| self.strings.intern(m.as_str())),
+                    component: caps
+                        .name("component")
+                        .map(|m| self.strings.intern(m.as_str())),
+                    message: MessageText::slice_of(line, message_range),
+                    raw_text: line.to_string(),
+                    profile_id: Arc::clone(&self.profile_id),
+                    fields: capture_fields(self.profile, &caps),
+                    file_modified: None, // set by app layer after parsing
                 };
 
-            let entry = LogEntry {
-                id: current_id,
-                timestamp,
-                severity,
-                source_file: source_file.clone(),
-                line_number,
-                thread: caps.name("thread").map(|m| m.as_str().to_string()),
-                component: caps.name("component").map(|m| m.as_str().to_string()),
-                message,
-                raw_text: line.to_string(),
-                profile_id: profile_id.clone(),
-                file_modified: None, // set by app layer after parsing
-            };
-
-            entries.push(entry);
-            current_id += 1;
+                self.entries.push(entry);
+                self.current_id += 1;
+            }
+        } else if self.framed {
+            // The first line of a framed record does not match line_pattern.
+            // Skip drops the whole record; otherwise it is kept unparsed and
+            // its continuation lines attach to it.
+            if self.profile.multiline_mode == crate::core::model::MultilineMode::Skip {
+                self.record_kept = false;
+            } else {
+                self.entries.push(unparsed_entry(
+                    self.current_id,
+                    line,
+                    &self.source_file,
+                    line_number,
+                    &self.profile_id,
+                ));
+                self.current_id += 1;
+            }
+            if self.profile.multiline_mode != crate::core::model::MultilineMode::Raw
+                && self.errors.len() < self.config.max_parse_errors_per_file
+            {
+                self.errors.push(ParseError::LineParse {
+                    file: self.source_file.to_path_buf(),
+                    line_number,
+                    reason: "Entry start does not match profile pattern".to_string(),
+                });
+            }
         } else {
             // Line does not match the pattern
-            match profile.multiline_mode {
+            match self.profile.multiline_mode {
                 crate::core::model::MultilineMode::Continuation => {
                     // Append to previous entry if one exists.
-                    // Skip the append when the entry has already been truncated
-                    // to avoid repeated grow-then-truncate cycles that waste CPU
-                    // and temporarily spike memory for pathological files.
-                    if let Some(last) = entries.last_mut() {
-                        if last.message.len() <= config.max_entry_size {
-                            last.message.push('\n');
-                            last.message.push_str(line);
-                        }
-                        if last.raw_text.len() <= config.max_entry_size {
-                            last.raw_text.push('\n');
-                            last.raw_text.push_str(line);
-                        }
+                    if let Some(last) = self.entries.last_mut() {
+                        last.append_line(line, self.config.max_entry_size);
                     }
                 }
                 crate::core::model::MultilineMode::Skip => {
                     // Ignore the line
                 }
                 crate::core::model::MultilineMode::Raw => {
-                    // Create an unparsed entry.  Reuse the message String as
-                    // raw_text by cloning it once rather than calling
-                    // line.to_string() twice (two separate heap allocations for
-                    // identical content).
-                    let message = line.to_string();
-                    let raw_text = message.clone();
-                    entries.push(LogEntry {
-                        id: current_id,
-                        timestamp: None,
-                        severity: crate::core::model::Severity::Unknown,
-                        source_file: source_file.clone(),
+                    self.entries.push(unparsed_entry(
+                        self.current_id,
+                        line,
+                        &self.source_file,
                         line_number,
-                        thread: None,
-                        component: None,
-                        message,
-                        raw_text,
-                        profile_id: profile_id.clone(),
-                        file_modified: None, // set by app layer after parsing
-                    });
-                    current_id += 1;
+                        &self.profile_id,
+                    ));
+                    self.current_id += 1;
                 }
             }
 
@@ -235,15 +374,15 @@ pub fn parse_content(
             // - Raw mode: an unparsed entry was successfully created above, so the
             //   line is handled; do NOT record an error or the scan summary will
             //   show inflated error counts for every Raw-mode entry.
-            if errors.len() < config.max_parse_errors_per_file {
-                let is_error = match profile.multiline_mode {
-                    crate::core::model::MultilineMode::Continuation => entries.is_empty(),
+            if self.errors.len() < self.config.max_parse_errors_per_file {
+                let is_error = match self.profile.multiline_mode {
+                    crate::core::model::MultilineMode::Continuation => self.entries.is_empty(),
                     crate::core::model::MultilineMode::Skip => true,
                     crate::core::model::MultilineMode::Raw => false,
                 };
                 if is_error {
-                    errors.push(ParseError::LineParse {
-                        file: source_file.clone(),
+                    self.errors.push(ParseError::LineParse {
+                        file: self.source_file.to_path_buf(),
                         line_number,
                         reason: "Line does not match profile pattern".to_string(),
                     });
@@ -255,61 +394,378 @@ pub fn parse_content(
         // Both `message` and `raw_text` are capped so that a pathological
         // file with millions of continuation lines cannot grow an entry
         // without bound (memory safety — Rule 11 resource bounds).
-        if let Some(last) = entries.last_mut() {
-            if last.message.len() > config.max_entry_size {
-                truncate_to_char_boundary(&mut last.message, config.max_entry_size);
-                last.message.push_str("... [truncated]");
-            }
-            if last.raw_text.len() > config.max_entry_size {
-                truncate_to_char_boundary(&mut last.raw_text, config.max_entry_size);
-                last.raw_text.push_str("... [truncated]");
+        if let Some(last) = self.entries.last_mut() {
+            cap_entry_size(last, self.config.max_entry_size);
+        }
+    }
+
+    /// Lines pushed so far.
+    pub fn lines_processed(&self) -> u64 {
+        self.lines_processed
+    }
+
+    /// Finish the file: cap entry sizes, sniff missing timestamps and
+    /// return the result.
+    pub fn finish(self) -> ParseResult {
+        let StreamParser {
+            config,
+            zone,
+            source_file,
+            mut entries,
+            errors,
+            lines_processed,
+            ..
+        } = self;
+
+        // -------------------------------------------------------------------------
+        // Post-loop pass: enforce entry size caps + timestamp sniff fallback.
+        //
+        // These two concerns are combined into a single O(n) traversal to avoid
+        // iterating `entries` twice (previously two separate loops).
+        //
+        // Truncation guard: the in-loop check only fires on continuation appends.
+        // A single matching line longer than max_entry_size (e.g. a 100 KB JSON
+        // blob) would escape it entirely.  The post-loop pass guarantees every
+        // entry is bounded regardless of origin.
+        //
+        // Timestamp sniff: any entry that still has timestamp: None after the
+        // primary parse gets one more chance via pattern-based scanning.
+        // -------------------------------------------------------------------------
+        let mut sniff_tier_hint: Option<usize> = None;
+        for entry in &mut entries {
+            // --- size cap ---
+            cap_entry_size(entry, config.max_entry_size);
+            // --- timestamp sniff ---
+            if entry.timestamp.is_none() {
+                entry.timestamp =
+                    sniff_timestamp_hinted(&entry.raw_text, zone, &mut sniff_tier_hint);
             }
         }
+
+        tracing::debug!(
+            file = %source_file.display(),
+            entries = entries.len(),
+            errors = errors.len(),
+            lines = lines_processed,
+            "Parsing complete"
+        );
+
+        ParseResult {
+            entries,
+            errors,
+            lines_processed,
+        }
     }
+}
 
-    // -------------------------------------------------------------------------
-    // Post-loop pass: enforce entry size caps + timestamp sniff fallback.
-    //
-    // These two concerns are combined into a single O(n) traversal to avoid
-    // iterating `entries` twice (previously two separate loops).
-    //
-    // Truncation guard: the in-loop check only fires on continuation appends.
-    // A single matching line longer than max_entry_size (e.g. a 100 KB JSON
-    // blob) would escape it entirely.  The post-loop pass guarantees every
-    // entry is bounded regardless of origin.
-    //
-    // Timestamp sniff: any entry that still has timestamp: None after the
-    // primary parse gets one more chance via pattern-based scanning.
-    // -------------------------------------------------------------------------
-    let mut sniff_tier_hint: Option<usize> = None;
-    for entry in &mut entries {
-        // --- size cap ---
-        if entry.message.len() > config.max_entry_size {
-            truncate_to_char_boundary(&mut entry.message, config.max_entry_size);
-            entry.message.push_str("... [truncated]");
+/// Parse a log file using the given format profile.
+///
+/// Applies the profile's line_pattern to extract structured fields from each
+/// line. Multi-line entries are handled according to the profile's
+/// multiline_mode setting.
+///
+/// # Arguments
+/// * `content` - File content as a string (the app layer handles reading)
+/// * `file_path` - Path to the source file (for LogEntry metadata)
+/// * `profile` - The format profile to use for parsing
+/// * `config` - Parsing configuration (limits)
+/// * `id_start` - Starting ID for entries (for global uniqueness across files)
+pub fn parse_content(
+    content: &str,
+    file_path: &Path,
+    profile: &FormatProfile,
+    config: &ParseConfig,
+    id_start: u64,
+) -> ParseResult {
+    let mut parser = StreamParser::new(file_path, profile, config, id_start);
+    for line in content.lines() {
+        parser.push_line(line);
+    }
+    parser.finish()
+}
+
+/// Parse a log file from a buffered reader of UTF-8 text, one line at a time.
+///
+/// Same result as `parse_content` on the whole text, but only one line is
+/// held at a time, so memory does not grow with the file.  Wrap the source
+/// in `encoding::Utf8Reader` first for UTF-16 files.  Lines longer than
+/// `max_entry_size` are cut (they would be truncated anyway).
+///
+/// Returns the I/O error if reading fails part-way.
+pub fn parse_reader<R: BufRead>(
+    reader: R,
+    file_path: &Path,
+    profile: &FormatProfile,
+    config: &ParseConfig,
+    id_start: u64,
+) -> io::Result<ParseResult> {
+    let mut lines = LineReader::new(reader, max_line_len(config));
+    let mut parser = StreamParser::new(file_path, profile, config, id_start);
+    while let Some(line) = lines.next_line()? {
+        parser.push_line(line);
+    }
+    Ok(parser.finish())
+}
+
+/// Whether a file can be split before `line` for parallel parsing: a record
+/// starts here whatever came before, so parsing from this line gives the same
+/// entries as reaching it sequentially.  `prev` is the previous non-blank
+/// line (blank lines never change the parse state).
+///
+/// - Framed profiles: `line` matches `entry_start`, or, with only
+///   `entry_end`, `prev` closed a record.
+/// - Continuation mode: `line` matches `line_pattern` (anything else would
+///   be appended to the previous entry).
+/// - Skip and Raw modes: any non-blank line.
+pub fn starts_record(profile: &FormatProfile, prev: &str, line: &str) -> bool {
+    if line.trim().is_empty() {
+        return false;
+    }
+    if let Some(ref re) = profile.entry_start {
+        return re.is_match(line);
+    }
+    if let Some(ref re) = profile.entry_end {
+        return re.is_match(prev);
+    }
+    match profile.multiline_mode {
+        crate::core::model::MultilineMode::Continuation => profile.line_pattern.is_match(line),
+        crate::core::model::MultilineMode::Skip | crate::core::model::MultilineMode::Raw => true,
+    }
+}
+
+/// Join the results of consecutive segments of one file, each parsed from
+/// its own first line with IDs from 0 (`starts_record`), into the result of
+/// the whole file: line numbers continue across segments, entry IDs count up
+/// from `id_start`, and errors stay capped at `max_errors`.
+pub fn stitch_segments(
+    segments: Vec<ParseResult>,
+    id_start: u64,
+    max_errors: usize,
+) -> ParseResult {
+    let mut whole = ParseResult {
+        entries: Vec::with_capacity(segments.iter().map(|s| s.entries.len()).sum()),
+        errors: Vec::new(),
+        lines_processed: 0,
+    };
+    for segment in segments {
+        let line_offset = whole.lines_processed;
+        for mut entry in segment.entries {
+            entry.id = id_start + whole.entries.len() as u64;
+            entry.line_number += line_offset;
+            whole.entries.push(entry);
         }
-        if entry.raw_text.len() > config.max_entry_size {
-            truncate_to_char_boundary(&mut entry.raw_text, config.max_entry_size);
-            entry.raw_text.push_str("... [truncated]");
+        for mut error in segment.errors {
+            if whole.errors.len() >= max_errors {
+                break;
+            }
+            if let ParseError::LineParse { line_number, .. }
+            | ParseError::TimestampParse { line_number, .. } = &mut error
+            {
+                *line_number += line_offset;
+            }
+            whole.errors.push(error);
         }
-        // --- timestamp sniff ---
-        if entry.timestamp.is_none() {
-            entry.timestamp = sniff_timestamp_hinted(&entry.raw_text, &mut sniff_tier_hint);
+        whole.lines_processed += segment.lines_processed;
+    }
+    whole
+}
+
+/// Longest line `LineReader` should keep for `config`: one byte over
+/// `max_entry_size`, so a cut line is still marked as truncated.
+pub fn max_line_len(config: &ParseConfig) -> usize {
+    config.max_entry_size.saturating_add(1)
+}
+
+/// Determine the severity of an entry from its level string and message.
+///
+///   1. If the record carries a level value, map it via severity_mapping
+///      (case-insensitive exact match).
+///   2. If step 1 returns Unknown (unrecognised level string), fall back to
+///      regex override patterns on the message.
+///   3. If there is no level value, try regex override patterns first, then
+///      plain-keyword message inference.
+///
+/// This layered approach means: structured profiles get precise level-based
+/// classification; plain-text and fallback profiles can classify entries via
+/// [WARN]-style embedded markers.
+fn resolve_severity(profile: &FormatProfile, level: Option<&str>, message: &str) -> Severity {
+    if let Some(level) = level {
+        let mapped = profile.map_severity(level);
+        if mapped == Severity::Unknown {
+            // Level field present but value not in severity_mapping --
+            // try regex override on the message as a second chance.
+            profile
+                .apply_severity_override(message)
+                .unwrap_or(Severity::Unknown)
+        } else {
+            mapped
         }
+    } else {
+        // No level value -- regex override takes priority over keyword
+        // substring matching so patterns like \[WARN\] win before the
+        // generic substring fallback fires.
+        profile
+            .apply_severity_override(message)
+            .unwrap_or_else(|| profile.infer_severity_from_message(message))
     }
+}
 
-    tracing::debug!(
-        file = %file_path.display(),
-        entries = entries.len(),
-        errors = errors.len(),
-        lines = lines_processed,
-        "Parsing complete"
-    );
+/// Collect the profile's extra named capture groups into a field map.
+///
+/// Groups that did not participate in the match, or matched an empty string,
+/// are omitted.  Bounded by `MAX_ENTRY_FIELDS` (Rule 11).
+fn capture_fields(profile: &FormatProfile, caps: &regex::Captures<'_>) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    for name in profile.field_groups.iter().take(MAX_ENTRY_FIELDS) {
+        if let Some(m) = caps.name(name).filter(|m| !m.as_str().is_empty()) {
+            fields.insert(name.clone(), m.as_str().to_string());
+        }
+    }
+    fields
+}
 
-    ParseResult {
-        entries,
+/// Build and parse an entry timestamp from the capture groups of one line.
+///
+/// The raw string comes from the profile's `timestamp_template` when present
+/// (e.g. CMTrace's separate `time="..."` and `date="..."` attributes joined
+/// as `{date} {time}`), otherwise from the single `timestamp` group.  When a
+/// `tz_offset` group matched, the parsed local time is shifted to UTC
+/// according to the profile's `tz_offset_style`; otherwise, or when the offset
+/// is malformed (recorded as a parse error), the time is read in `zone`.
+fn capture_timestamp(
+    profile: &FormatProfile,
+    zone: AssumedZone,
+    caps: &regex::Captures<'_>,
+    source_file: &Path,
+    line_number: u64,
+    config: &ParseConfig,
+    errors: &mut Vec<ParseError>,
+) -> Option<DateTime<Utc>> {
+    let raw_ts: Cow<'_, str> = match &profile.timestamp_template {
+        Some(template) => Cow::Owned(assemble_timestamp(template, caps)),
+        None => Cow::Borrowed(caps.name("timestamp")?.as_str()),
+    };
+    if raw_ts.trim().is_empty() {
+        return None;
+    }
+    let offset = caps
+        .name("tz_offset")
+        .map(|m| m.as_str().trim())
+        .filter(|s| !s.is_empty());
+    // A malformed offset is reported but does not cost the entry its time:
+    // the wall-clock value is then read in `zone` like a naive timestamp.
+    let east = offset.and_then(|raw| {
+        let east = utc_offset_seconds(raw, profile.tz_offset_style);
+        if east.is_none() && errors.len() < config.max_parse_errors_per_file {
+            errors.push(ParseError::TimestampParse {
+                file: source_file.to_path_buf(),
+                line_number,
+                raw_timestamp: raw.to_string(),
+                format: format!("tz_offset ({})", profile.tz_offset_style.label()),
+            });
+        }
+        east
+    });
+    // A captured offset says which zone the wall-clock time is in.
+    let wall_zone = if east.is_some() {
+        AssumedZone::Utc
+    } else {
+        zone
+    };
+    let local = timestamp_or_error(
+        &raw_ts,
+        profile,
+        wall_zone,
+        source_file,
+        line_number,
+        config,
         errors,
-        lines_processed,
+    )?;
+    Some(match east {
+        Some(east) => local - chrono::Duration::seconds(east),
+        None => local,
+    })
+}
+
+/// Concatenate the template's literals and capture-group values.  Groups that
+/// did not participate in the match contribute nothing.
+fn assemble_timestamp(template: &TimestampTemplate, caps: &regex::Captures<'_>) -> String {
+    let mut out = String::new();
+    for part in &template.parts {
+        match part {
+            TemplatePart::Literal(text) => out.push_str(text),
+            TemplatePart::Group(name) => {
+                if let Some(m) = caps.name(name) {
+                    out.push_str(m.as_str());
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Convert a captured UTC offset into seconds east of UTC.
+///
+/// `Iso` accepts `Z`, `+HH:MM`, `+HHMM` and `+HH`.  `BiasMinutes` accepts a
+/// signed minute count where UTC = local + bias, so the result is negated.
+/// Returns `None` for malformed values or magnitudes beyond
+/// `MAX_TZ_OFFSET_MINUTES`.
+fn utc_offset_seconds(raw: &str, style: TzOffsetStyle) -> Option<i64> {
+    let minutes_east = match style {
+        TzOffsetStyle::BiasMinutes => -raw.parse::<i64>().ok()?,
+        TzOffsetStyle::Iso => {
+            if raw.eq_ignore_ascii_case("z") {
+                return Some(0);
+            }
+            let (sign, rest) = match raw.as_bytes().first()? {
+                b'+' => (1, &raw[1..]),
+                b'-' => (-1, &raw[1..]),
+                _ => return None,
+            };
+            let digits: String = rest.chars().filter(|&c| c != ':').collect();
+            if !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let (hours, mins): (i64, i64) = match digits.len() {
+                2 => (digits.parse().ok()?, 0),
+                4 => (digits[..2].parse().ok()?, digits[2..].parse().ok()?),
+                _ => return None,
+            };
+            if mins >= 60 {
+                return None;
+            }
+            sign * (hours * 60 + mins)
+        }
+    };
+    (minutes_east.abs() <= MAX_TZ_OFFSET_MINUTES).then_some(minutes_east * 60)
+}
+
+/// Parse `raw_ts` with the profile's timestamp format, reading naive values
+/// in `zone`.  Records a non-fatal `ParseError::TimestampParse` (bounded by
+/// `max_parse_errors_per_file`) and returns `None` on failure so the entry is
+/// still kept.
+fn timestamp_or_error(
+    raw_ts: &str,
+    profile: &FormatProfile,
+    zone: AssumedZone,
+    source_file: &Path,
+    line_number: u64,
+    config: &ParseConfig,
+    errors: &mut Vec<ParseError>,
+) -> Option<DateTime<Utc>> {
+    match parse_timestamp(raw_ts, &profile.timestamp_format, zone) {
+        Ok(ts) => Some(ts),
+        Err(_msg) => {
+            if errors.len() < config.max_parse_errors_per_file {
+                errors.push(ParseError::TimestampParse {
+                    file: source_file.to_path_buf(),
+                    line_number,
+                    raw_timestamp: raw_ts.to_string(),
+                    format: profile.timestamp_format.clone(),
+                });
+            }
+            None
+        }
     }
 }
 
@@ -334,7 +790,7 @@ pub fn parse_content(
 /// single line at a time.
 #[allow(dead_code)] // Public API used by tests; parse_content uses the hinted variant.
 pub(crate) fn sniff_timestamp(raw_line: &str) -> Option<DateTime<Utc>> {
-    sniff_timestamp_hinted(raw_line, &mut None)
+    sniff_timestamp_hinted(raw_line, AssumedZone::Utc, &mut None)
 }
 
 /// Inner implementation of [`sniff_timestamp`] that accepts a mutable tier
@@ -342,17 +798,34 @@ pub(crate) fn sniff_timestamp(raw_line: &str) -> Option<DateTime<Utc>> {
 /// successful match, that tier is tried first before falling back to the
 /// full sequential scan.  For homogeneous log files this reduces per-entry
 /// cost from 15 regex attempts to 1.
+///
+/// Zone-less matches are read as wall-clock time in `zone`.
 fn sniff_timestamp_hinted(
     raw_line: &str,
+    zone: AssumedZone,
     last_successful_tier: &mut Option<usize>,
 ) -> Option<DateTime<Utc>> {
     /// A sniff candidate: a regex that finds a timestamp substring, plus a
     /// parsing closure that converts the matched text to `DateTime<Utc>`.
     struct Sniffer {
         re: Regex,
+        /// The match carries its own offset or is an epoch value, so it is
+        /// never re-read in the file's zone.
+        absolute: bool,
         parse: fn(&str) -> Option<DateTime<Utc>>,
     }
 
+    impl Sniffer {
+        fn sniff(&self, raw_line: &str, zone: AssumedZone) -> Option<DateTime<Utc>> {
+            let dt = (self.parse)(self.re.find(raw_line)?.as_str())?;
+            Some(if self.absolute {
+                dt
+            } else {
+                zone.to_utc(dt.naive_utc())
+            })
+        }
+    }
+
     static SNIFFERS: OnceLock<Vec<Sniffer>> = OnceLock::new();
 
     let sniffers = SNIFFERS.get_or_init(|| {
@@ -387,6 +860,7 @@ fn sniff_timestamp_hinted(
             try_re(r"\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(?:[.,]\d+)?(?:Z|[+-]\d{2}:?\d{2})").map(
                 |r| Sniffer {
                     re: r,
+                    absolute: true,
                     parse: |s| {
                         // Normalise `+0530` -> `+05:30` so parse_from_rfc3339 accepts it.
                         let fixed = if s.len() > 20 {
@@ -421,6 +895,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2},\d+").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     // Replace comma with dot so chrono's %.f specifier accepts it.
                     let canonical = s.replace(',', ".").replace('T', " ");
@@ -439,6 +914,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}(?:\.\d+)?").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     let canonical = s.replace('T', " ");
                     // Try with fractional seconds first, then without.
@@ -454,6 +930,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"\d{4}/\d{2}/\d{2}[ T]\d{2}:\d{2}:\d{2}(?:\.\d+)?").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     let canonical = s.replace('/', "-").replace('T', " ");
                     NaiveDateTime::parse_from_str(&canonical, "%Y-%m-%d %H:%M:%S%.f")
@@ -468,6 +945,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"\d{2}\.\d{2}\.\d{4} \d{2}:\d{2}:\d{2}(?:\.\d+)?").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     NaiveDateTime::parse_from_str(s, "%d.%m.%Y %H:%M:%S%.f")
                         .or_else(|_| NaiveDateTime::parse_from_str(s, "%d.%m.%Y %H:%M:%S"))
@@ -481,6 +959,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"\d{2}/[A-Za-z]{3}/\d{4}:\d{2}:\d{2}:\d{2} [+-]\d{4}").map(|r| Sniffer {
                 re: r,
+                absolute: true,
                 parse: |s| {
                     DateTime::parse_from_str(s, "%d/%b/%Y:%H:%M:%S %z")
                         .ok()
@@ -504,6 +983,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"\d{2}/\d{2}/\d{4} \d{2}:\d{2}:\d{2}").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     // Extract the two leading numeric fields.
                     let mut parts = s.splitn(3, '/');
@@ -543,6 +1023,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"\d{2}/\d{2}/\d{2},\d{2}:\d{2}:\d{2}").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     let mut parts = s.splitn(3, '/');
                     let (first, second) = match (
@@ -579,6 +1060,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"[A-Z][a-z]{2,8} \d{1,2},? \d{4} \d{2}:\d{2}:\d{2}").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     // Normalise: remove optional comma, collapse multiple spaces.
                     let cleaned = s.replace(',', " ");
@@ -595,6 +1077,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"[A-Z][a-z]{2} [ \d]\d \d{2}:\d{2}:\d{2}").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     let year = Utc::now().year();
                     let with_year = format!("{year} {s}");
@@ -609,6 +1092,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"\d{8}[T ]\d{6}").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     let compact = s.replace(' ', "T");
                     NaiveDateTime::parse_from_str(&compact, "%Y%m%dT%H%M%S")
@@ -623,6 +1107,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"^\d{10}(?:\.\d+)?").map(|r| Sniffer {
                 re: r,
+                absolute: true,
                 parse: |s| {
                     let (secs_str, _) = s.split_once('.').unwrap_or((s, ""));
                     secs_str
@@ -638,6 +1123,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"^\d{13}(?:\.\d+)?").map(|r| Sniffer {
                 re: r,
+                absolute: true,
                 parse: |s| {
                     let (ms_str, _) = s.split_once('.').unwrap_or((s, ""));
                     ms_str.parse::<i64>().ok().and_then(|ms| {
@@ -654,6 +1140,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"\b\d{1,2}\.\d{1,2}\.\d{4}\b").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     // Try DD.MM.YYYY then MM.DD.YYYY as fallback.
                     chrono::NaiveDate::parse_from_str(s, "%d.%m.%Y")
@@ -672,6 +1159,7 @@ fn sniff_timestamp_hinted(
             // ------------------------------------------------------------------
             try_re(r"\b\d{2}:\d{2}:\d{2}(?:[.,]\d+)?\b").map(|r| Sniffer {
                 re: r,
+                absolute: false,
                 parse: |s| {
                     let today = Utc::now().date_naive();
                     // Normalise comma-millis to dot-millis.
@@ -690,21 +1178,15 @@ fn sniff_timestamp_hinted(
     // try that tier first. For files where every line uses the same format,
     // this turns 15 sequential regex attempts into 1.
     if let Some(hint) = *last_successful_tier {
-        if let Some(sniffer) = sniffers.get(hint) {
-            if let Some(m) = sniffer.re.find(raw_line) {
-                if let Some(dt) = (sniffer.parse)(m.as_str()) {
-                    return Some(dt);
-                }
-            }
+        if let Some(dt) = sniffers.get(hint).and_then(|s| s.sniff(raw_line, zone)) {
+            return Some(dt);
         }
     }
 
     for (idx, sniffer) in sniffers.iter().enumerate() {
-        if let Some(m) = sniffer.re.find(raw_line) {
-            if let Some(dt) = (sniffer.parse)(m.as_str()) {
-                *last_successful_tier = Some(idx);
-                return Some(dt);
-            }
+        if let Some(dt) = sniffer.sniff(raw_line, zone) {
+            *last_successful_tier = Some(idx);
+            return Some(dt);
         }
     }
     None
@@ -732,8 +1214,11 @@ fn sniff_timestamp_hinted(
 ///      spanning a year boundary will show incorrect dates for entries from
 ///      the previous year.
 ///
+/// Naive results (every strategy except an offset in the text) are read as
+/// wall-clock time in `zone`.
+///
 /// Returns `Ok(DateTime<Utc>)` on success, or `Err(description)` on failure.
-fn parse_timestamp(raw: &str, format: &str) -> Result<DateTime<Utc>, String> {
+fn parse_timestamp(raw: &str, format: &str, zone: AssumedZone) -> Result<DateTime<Utc>, String> {
     let trimmed = raw.trim();
 
     // Offset-aware formats: when the format string contains a UTC-offset
@@ -749,14 +1234,14 @@ fn parse_timestamp(raw: &str, format: &str) -> Result<DateTime<Utc>, String> {
 
     // First try: parse as a full NaiveDateTime with the format string.
     if let Ok(ndt) = NaiveDateTime::parse_from_str(trimmed, format) {
-        return Ok(ndt.and_utc());
+        return Ok(zone.to_utc(ndt));
     }
 
     // Second try: parse as NaiveDate only (for date-only formats like "%Y-%m-%d").
-    // Treat as midnight UTC.
+    // Treat as midnight in the file's zone.
     if let Ok(nd) = chrono::NaiveDate::parse_from_str(trimmed, format) {
         if let Some(ndt) = nd.and_hms_opt(0, 0, 0) {
-            return Ok(ndt.and_utc());
+            return Ok(zone.to_utc(ndt));
         }
     }
 
@@ -773,11 +1258,11 @@ fn parse_timestamp(raw: &str, format: &str) -> Result<DateTime<Utc>, String> {
     let normalised = trimmed.replace('/', "-").replace('T', " ");
     if normalised != trimmed {
         if let Ok(ndt) = NaiveDateTime::parse_from_str(&normalised, format) {
-            return Ok(ndt.and_utc());
+            return Ok(zone.to_utc(ndt));
         }
         if let Ok(nd) = chrono::NaiveDate::parse_from_str(&normalised, format) {
             if let Some(ndt) = nd.and_hms_opt(0, 0, 0) {
-                return Ok(ndt.and_utc());
+                return Ok(zone.to_utc(ndt));
             }
         }
     }
@@ -791,7 +1276,7 @@ fn parse_timestamp(raw: &str, format: &str) -> Result<DateTime<Utc>, String> {
         let with_year = format!("{year} {trimmed}");
         let year_format = format!("%Y {format}");
         if let Ok(ndt) = NaiveDateTime::parse_from_str(&with_year, &year_format) {
-            return Ok(ndt.and_utc());
+            return Ok(zone.to_utc(ndt));
         }
     }
 
@@ -886,7 +1371,7 @@ info = ["Info"]
 
         assert_eq!(result.entries.len(), 2);
         assert_eq!(result.entries[0].severity, Severity::Error);
-        assert_eq!(result.entries[0].message, "Something failed");
+        assert_eq!(result.entries[0].message(), "Something failed");
         assert_eq!(result.entries[1].severity, Severity::Info);
     }
 
@@ -907,8 +1392,82 @@ info = ["Info"]
         );
 
         assert_eq!(result.entries.len(), 2);
-        assert!(result.entries[0].message.contains("Client.java:42"));
-        assert!(result.entries[0].message.contains("Main.java:10"));
+        assert!(result.entries[0].message().contains("Client.java:42"));
+        assert!(result.entries[0].message().contains("Main.java:10"));
+    }
+
+    /// The message is kept as a span of raw_text, continuation lines grow
+    /// the span, and the file and profile are shared by every entry.
+    #[test]
+    fn test_parse_message_is_span_of_raw_text() {
+        let profile = make_test_profile();
+        let content = "[2024-01-15 14:30:22] Error Connection failed\n\
+                        at Client.java:42\n\
+                        [2024-01-15 14:30:23] Info Retry succeeded\n";
+
+        let result = parse_content(
+            content,
+            &PathBuf::from("test.log"),
+            &profile,
+            &ParseConfig::default(),
+            0,
+        );
+
+        assert_eq!(result.entries.len(), 2);
+        let first = &result.entries[0];
+        assert!(matches!(first.message, MessageText::Span { .. }));
+        assert_eq!(first.message(), "Connection failed\nat Client.java:42");
+        assert!(first.raw_text.ends_with(first.message()));
+        assert!(Arc::ptr_eq(
+            &first.source_file,
+            &result.entries[1].source_file
+        ));
+        assert!(Arc::ptr_eq(
+            &first.profile_id,
+            &result.entries[1].profile_id
+        ));
+    }
+
+    /// Streaming a UTF-16 file through `Utf8Reader` in small chunks gives
+    /// the same entries as `parse_content` on the decoded text, and an
+    /// over-long line is cut and marked as truncated.
+    #[test]
+    fn test_parse_reader_matches_parse_content() {
+        use crate::core::encoding::Utf8Reader;
+
+        let profile = make_test_profile();
+        let config = ParseConfig {
+            max_entry_size: 64,
+            ..ParseConfig::default()
+        };
+        let content = format!(
+            "[2024-01-15 14:30:22] Error Connection failed\r\n\
+             \tat com.example.Client.connect(Client.java:42)\r\n\
+             \r\n\
+             [2024-01-15 14:30:23] Info {}\r\n\
+             [2024-01-15 14:30:24] Warning Caf\u{e9} closed",
+            "y".repeat(500)
+        );
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in content.encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+
+        let path = PathBuf::from("test.log");
+        let reader = std::io::BufReader::with_capacity(16, Utf8Reader::new(&bytes[..], 16));
+        let streamed = parse_reader(reader, &path, &profile, &config, 7).unwrap();
+        let whole = parse_content(&content, &path, &profile, &config, 7);
+
+        assert_eq!(streamed.lines_processed, 5);
+        assert_eq!(streamed.entries.len(), 3);
+        for (s, w) in streamed.entries.iter().zip(&whole.entries) {
+            assert_eq!((s.id, s.line_number), (w.id, w.line_number));
+            assert_eq!(s.timestamp, w.timestamp);
+            assert_eq!(s.raw_text, w.raw_text);
+        }
+        assert!(streamed.entries[0].message().contains("Client.java:42"));
+        assert!(streamed.entries[1].raw_text.ends_with("... [truncated]"));
+        assert_eq!(streamed.entries[2].message(), "Caf\u{e9} closed");
     }
 
     #[test]
@@ -939,8 +1498,8 @@ info = ["Info"]
         let result = parse_content(&content, &PathBuf::from("big.log"), &profile, &config, 0);
 
         assert_eq!(result.entries.len(), 1);
-        assert!(result.entries[0].message.len() < 1100); // truncated + suffix
-        assert!(result.entries[0].message.ends_with("... [truncated]"));
+        assert!(result.entries[0].message().len() < 1100); // truncated + suffix
+        assert!(result.entries[0].message().ends_with("... [truncated]"));
     }
 
     /// Regression: truncation at a byte offset that lands inside a multi-byte
@@ -966,7 +1525,7 @@ info = ["Info"]
         let result = parse_content(&content, &PathBuf::from("utf8.log"), &profile, &config, 0);
 
         assert_eq!(result.entries.len(), 1);
-        assert!(result.entries[0].message.ends_with("... [truncated]"));
+        assert!(result.entries[0].message().ends_with("... [truncated]"));
         // The truncated portion must be valid UTF-8 (no partial sequences).
         // This is implicitly verified because `message` is a String — Rust
         // guarantees String contents are valid UTF-8.
@@ -1022,7 +1581,8 @@ info = ["Info"]
 
     #[test]
     fn test_parse_timestamp_naive_datetime() {
-        let ts = parse_timestamp("2024-01-15 14:30:22", "%Y-%m-%d %H:%M:%S").unwrap();
+        let ts =
+            parse_timestamp("2024-01-15 14:30:22", "%Y-%m-%d %H:%M:%S", AssumedZone::Utc).unwrap();
         assert_eq!(
             ts.format("%Y-%m-%d %H:%M:%S").to_string(),
             "2024-01-15 14:30:22"
@@ -1031,7 +1591,12 @@ info = ["Info"]
 
     #[test]
     fn test_parse_timestamp_with_milliseconds() {
-        let ts = parse_timestamp("2024-01-15 14:30:22.123", "%Y-%m-%d %H:%M:%S%.f").unwrap();
+        let ts = parse_timestamp(
+            "2024-01-15 14:30:22.123",
+            "%Y-%m-%d %H:%M:%S%.f",
+            AssumedZone::Utc,
+        )
+        .unwrap();
         assert_eq!(
             ts.format("%Y-%m-%d %H:%M:%S").to_string(),
             "2024-01-15 14:30:22"
@@ -1040,7 +1605,12 @@ info = ["Info"]
 
     #[test]
     fn test_parse_timestamp_rfc3339() {
-        let ts = parse_timestamp("2024-01-15T14:30:22+00:00", "%Y-%m-%dT%H:%M:%S%z").unwrap();
+        let ts = parse_timestamp(
+            "2024-01-15T14:30:22+00:00",
+            "%Y-%m-%dT%H:%M:%S%z",
+            AssumedZone::Utc,
+        )
+        .unwrap();
         assert_eq!(
             ts.format("%Y-%m-%d %H:%M:%S").to_string(),
             "2024-01-15 14:30:22"
@@ -1049,7 +1619,7 @@ info = ["Info"]
 
     #[test]
     fn test_parse_timestamp_invalid_returns_error() {
-        let result = parse_timestamp("not-a-date", "%Y-%m-%d %H:%M:%S");
+        let result = parse_timestamp("not-a-date", "%Y-%m-%d %H:%M:%S", AssumedZone::Utc);
         assert!(result.is_err(), "invalid timestamp should return Err");
     }
 
@@ -1059,14 +1629,24 @@ info = ["Info"]
     /// instead of the correct 09:00:22 UTC.
     #[test]
     fn test_parse_timestamp_applies_utc_offset_with_z_specifier() {
-        let ts = parse_timestamp("15/Jan/2024:14:30:22 +0530", "%d/%b/%Y:%H:%M:%S %z").unwrap();
+        let ts = parse_timestamp(
+            "15/Jan/2024:14:30:22 +0530",
+            "%d/%b/%Y:%H:%M:%S %z",
+            AssumedZone::Utc,
+        )
+        .unwrap();
         assert_eq!(
             ts.format("%Y-%m-%d %H:%M:%S").to_string(),
             "2024-01-15 09:00:22",
             "+05:30 offset must be applied when converting to UTC"
         );
 
-        let ts2 = parse_timestamp("2024-01-15 14:30:22 -0800", "%Y-%m-%d %H:%M:%S %z").unwrap();
+        let ts2 = parse_timestamp(
+            "2024-01-15 14:30:22 -0800",
+            "%Y-%m-%d %H:%M:%S %z",
+            AssumedZone::Utc,
+        )
+        .unwrap();
         assert_eq!(
             ts2.format("%Y-%m-%d %H:%M:%S").to_string(),
             "2024-01-15 22:30:22",
@@ -1074,7 +1654,12 @@ info = ["Info"]
         );
 
         // Colon variant %:z.
-        let ts3 = parse_timestamp("2024-01-15T14:30:22+05:30", "%Y-%m-%dT%H:%M:%S%:z").unwrap();
+        let ts3 = parse_timestamp(
+            "2024-01-15T14:30:22+05:30",
+            "%Y-%m-%dT%H:%M:%S%:z",
+            AssumedZone::Utc,
+        )
+        .unwrap();
         assert_eq!(ts3.format("%H:%M:%S").to_string(), "09:00:22");
     }
 
@@ -1101,7 +1686,8 @@ info = ["Info"]
     /// "-" separators but the actual log line uses "/".
     #[test]
     fn test_parse_timestamp_slash_separated_date() {
-        let ts = parse_timestamp("2024/01/15 14:30:22", "%Y-%m-%d %H:%M:%S").unwrap();
+        let ts =
+            parse_timestamp("2024/01/15 14:30:22", "%Y-%m-%d %H:%M:%S", AssumedZone::Utc).unwrap();
         assert_eq!(
             ts.format("%Y-%m-%d %H:%M:%S").to_string(),
             "2024-01-15 14:30:22"
@@ -1112,7 +1698,8 @@ info = ["Info"]
     /// was failing because "%Y-%m-%d %H:%M:%S" expects a space before the hour.
     #[test]
     fn test_parse_timestamp_t_separator() {
-        let ts = parse_timestamp("2024-01-15T14:30:22", "%Y-%m-%d %H:%M:%S").unwrap();
+        let ts =
+            parse_timestamp("2024-01-15T14:30:22", "%Y-%m-%d %H:%M:%S", AssumedZone::Utc).unwrap();
         assert_eq!(
             ts.format("%Y-%m-%d %H:%M:%S").to_string(),
             "2024-01-15 14:30:22"
@@ -1127,7 +1714,7 @@ info = ["Info"]
     /// Previously always produced None; now injects the current year.
     #[test]
     fn test_parse_timestamp_syslog_yearless() {
-        let ts = parse_timestamp("Jan 15 14:30:22", "%b %d %H:%M:%S")
+        let ts = parse_timestamp("Jan 15 14:30:22", "%b %d %H:%M:%S", AssumedZone::Utc)
             .expect("syslog year-less timestamp should succeed with year injection");
         // The year will be the current UTC year — just verify it is reasonable.
         let year = ts.format("%Y").to_string().parse::<i32>().unwrap();
@@ -1138,15 +1725,241 @@ info = ["Info"]
         assert_eq!(ts.format("%m-%d %H:%M:%S").to_string(), "01-15 14:30:22");
     }
 
+    fn framed_profile(parsing: &str) -> FormatProfile {
+        let toml = format!(
+            "[profile]\nid = \"framed\"\nname = \"Framed\"\n\n\
+             [detection]\ncontent_match = '.'\n\n[parsing]\n{parsing}"
+        );
+        let path = PathBuf::from("test.toml");
+        profile::validate_and_compile(
+            profile::parse_profile_toml(&toml, &path).unwrap(),
+            &path,
+            false,
+        )
+        .unwrap()
+    }
+
+    /// With `entry_start`, a continuation line that happens to match
+    /// `line_pattern` (a timestamp inside a stack trace) stays in its entry.
+    #[test]
+    fn test_entry_start_frames_records() {
+        let profile = framed_profile(
+            r#"line_pattern = '^(?P<timestamp>\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}) (?:(?P<level>[A-Z]+) )?(?P<message>.*)$'
+timestamp_format = "%Y-%m-%d %H:%M:%S"
+entry_start = '^\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2} (?:INFO|WARN|ERROR) '
+"#,
+        );
+        let content = "2024-01-15 10:00:00 ERROR Request failed\n\
+                       2024-01-15 10:00:01 retry scheduled by watchdog\n\
+                       \x20   at Foo.bar(Foo.java:10)\n\
+                       2024-01-15 10:00:02 INFO Recovered\n";
+        let result = parse_content(
+            content,
+            Path::new("app.log"),
+            &profile,
+            &ParseConfig::default(),
+            0,
+        );
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(
+            result.entries[0].message(),
+            "Request failed\n2024-01-15 10:00:01 retry scheduled by watchdog\n    at Foo.bar(Foo.java:10)"
+        );
+        assert_eq!(result.entries[1].line_number, 4);
+        assert!(result.errors.is_empty());
+    }
+
+    /// With `entry_end`, a record runs to its closing line.  In Skip mode a
+    /// record whose first line does not match `line_pattern` is dropped whole
+    /// instead of being appended to the previous entry.
+    #[test]
+    fn test_entry_end_frames_records_and_skip_drops_bad_records() {
+        let profile = framed_profile(
+            r#"line_pattern = '^<Event id="(?P<thread>\d+)">$'
+timestamp_format = "%Y-%m-%d %H:%M:%S"
+entry_end = '^</Event>'
+multiline_mode = "skip"
+"#,
+        );
+        let content = "<Event id=\"1\">\n\
+                       \x20 <Time>2024-01-15 10:00:00</Time>\n\
+                       </Event>\n\
+                       garbage header\n\
+                       \x20 <Time>2024-01-15 11:00:00</Time>\n\
+                       </Event>\n\
+                       <Event id=\"2\">\n\
+                       </Event>\n";
+        let result = parse_content(
+            content,
+            Path::new("events.xml"),
+            &profile,
+            &ParseConfig::default(),
+            0,
+        );
+        assert_eq!(result.entries.len(), 2);
+        assert_eq!(result.entries[0].thread.as_deref(), Some("1"));
+        assert_eq!(result.entries[0].raw_text.lines().count(), 3);
+        assert_eq!(
+            result.entries[0].timestamp.map(|t| t.to_rfc3339()),
+            Some("2024-01-15T10:00:00+00:00".to_string()),
+            "sniffed from the record body"
+        );
+        assert_eq!(result.entries[1].thread.as_deref(), Some("2"));
+        assert_eq!(result.errors.len(), 1, "the dropped record");
+    }
+
+    /// Parse-error reasons name the profile pattern in plain words, for
+    /// framed records and for single lines.
+    #[test]
+    fn test_parse_error_reasons() {
+        let reasons = |result: &ParseResult| -> Vec<String> {
+            result
+                .errors
+                .iter()
+                .filter_map(|e| match e {
+                    ParseError::LineParse { reason, .. } => Some(reason.clone()),
+                    _ => None,
+                })
+                .collect()
+        };
+        let framed = framed_profile(
+            r#"line_pattern = '^<Event id="(?P<thread>\d+)">$'
+timestamp_format = "%Y-%m-%d %H:%M:%S"
+entry_end = '^</Event>'
+multiline_mode = "skip"
+"#,
+        );
+        let result = parse_content(
+            "garbage\n</Event>\n",
+            Path::new("events.xml"),
+            &framed,
+            &ParseConfig::default(),
+            0,
+        );
+        assert_eq!(
+            reasons(&result),
+            vec!["Entry start does not match profile pattern"]
+        );
+
+        let result = parse_content(
+            "no bracket prefix\n",
+            Path::new("app.log"),
+            &make_test_profile(),
+            &ParseConfig::default(),
+            0,
+        );
+        assert_eq!(
+            reasons(&result),
+            vec!["Line does not match profile pattern"]
+        );
+    }
+
+    /// Split points: record starts per framing / multiline mode.
+    #[test]
+    fn test_starts_record() {
+        let cont = make_test_profile();
+        assert!(starts_record(&cont, "", "[2024-01-15 14:30:22] Info ok"));
+        assert!(!starts_record(&cont, "", "\tat com.example.Main"));
+        assert!(!starts_record(&cont, "", "   "));
+
+        let start =
+            framed_profile(
+            "line_pattern = '^BEGIN (?P<message>.*)$'\ntimestamp_format = \"%s\"\nentry_start = '^BEGIN'",
+        );
+        assert!(starts_record(&start, "x", "BEGIN job"));
+        assert!(!starts_record(&start, "x", "detail"));
+
+        let end = framed_profile(
+            "line_pattern = '^(?P<message>.*)$'\ntimestamp_format = \"%s\"\nentry_end = '^END$'",
+        );
+        assert!(starts_record(&end, "END", "next"));
+        assert!(!starts_record(&end, "detail", "next"));
+    }
+
+    /// Segments parsed separately and stitched give the whole-file result:
+    /// line numbers, IDs and error line numbers continue across the seam.
+    #[test]
+    fn test_stitch_segments_matches_whole_parse() {
+        let profile = make_test_profile();
+        let config = ParseConfig::default();
+        let path = PathBuf::from("test.log");
+        let first = "[2024-01-15 14:30:22] Error Connection failed\n\
+                     \tat com.example.Client.connect(Client.java:42)\n\
+                     \n";
+        let second = "[2024-01-15 14:30:23] Info Retry\n\
+                      [bad timestamp] Info Unparsed time\n";
+
+        let whole = parse_content(&format!("{first}{second}"), &path, &profile, &config, 10);
+        let stitched = stitch_segments(
+            vec![
+                parse_content(first, &path, &profile, &config, 0),
+                parse_content(second, &path, &profile, &config, 0),
+            ],
+            10,
+            config.max_parse_errors_per_file,
+        );
+
+        assert_eq!(stitched.lines_processed, whole.lines_processed);
+        let key = |r: &ParseResult| {
+            r.entries
+                .iter()
+                .map(|e| (e.id, e.line_number, e.message().to_string()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(key(&stitched), key(&whole));
+        assert_eq!(stitched.entries[2].id, 12);
+        assert_eq!(stitched.entries[2].line_number, 5);
+        let errors = |r: &ParseResult| r.errors.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert_eq!(errors(&stitched), errors(&whole));
+        assert!(!stitched.errors.is_empty());
+    }
+
+    /// Naive timestamps are read in the profile's timezone unless a clock
+    /// rule overrides it for the file; explicit offsets are never re-zoned,
+    /// and sniffed timestamps follow the same zone.
+    #[test]
+    fn test_parse_content_reads_naive_timestamps_in_zone() {
+        use chrono::TimeZone;
+        let mut profile = make_test_profile();
+        profile.timezone = AssumedZone::Named(chrono_tz::America::New_York);
+        let content = "[2024-01-15 09:00:00] Info local morning\n\
+                       [2024-01-15T09:00:00Z] Info explicit UTC\n";
+        let utc = |h| Utc.with_ymd_and_hms(2024, 1, 15, h, 0, 0).unwrap();
+
+        let file = Path::new("/hosts/iis/u_ex240115.log");
+        let mut config = ParseConfig::default();
+        let result = parse_content(content, file, &profile, &config, 0);
+        assert_eq!(result.entries[0].timestamp, Some(utc(14)), "EST is UTC-5");
+        assert_eq!(result.entries[1].timestamp, Some(utc(9)));
+
+        config.clock_rules.push(ClockRule {
+            path: PathBuf::from("/hosts/iis"),
+            offset_secs: 0,
+            zone: Some(AssumedZone::Fixed(60)),
+        });
+        let result = parse_content(content, file, &profile, &config, 0);
+        assert_eq!(result.entries[0].timestamp, Some(utc(8)), "override wins");
+        assert_eq!(result.entries[1].timestamp, Some(utc(9)));
+
+        let ny = AssumedZone::Named(chrono_tz::America::New_York);
+        assert_eq!(
+            sniff_timestamp_hinted("saved at 2024-01-15 09:00:00 ok", ny, &mut None),
+            Some(utc(14))
+        );
+        assert_eq!(
+            sniff_timestamp_hinted("1705329022 epoch", ny, &mut None),
+            sniff_timestamp("1705329022 epoch"),
+            "epoch values are absolute"
+        );
+    }
+
     // =========================================================================
     // sniff_timestamp tests
     // =========================================================================
 
     fn sniff(s: &str) -> String {
         sniff_timestamp(s)
-            .expect(&format!(
-                "sniff_timestamp should find a timestamp in: {s:?}"
-            ))
+            .unwrap_or_else(|| panic!("sniff_timestamp should find a timestamp in: {s:?}"))
             .format("%Y-%m-%d %H:%M:%S")
             .to_string()
     }
@@ -1589,12 +2402,12 @@ multiline_mode = "raw"
 
         assert_eq!(result.entries.len(), 1, "should parse exactly one entry");
         assert!(
-            result.entries[0].message.len() < 300,
+            result.entries[0].message().len() < 300,
             "message must be truncated (got {} bytes)",
-            result.entries[0].message.len()
+            result.entries[0].message().len()
         );
         assert!(
-            result.entries[0].message.ends_with("... [truncated]"),
+            result.entries[0].message().ends_with("... [truncated]"),
             "truncated message must have the truncation suffix"
         );
         assert!(
@@ -1608,6 +2421,253 @@ multiline_mode = "raw"
         );
     }
 
+    /// Regression: CMTrace profiles only captured the `date=` attribute, so
+    /// every SCCM / Intune entry landed at midnight.  The date, time and
+    /// `+bias` groups must now combine into the exact UTC instant.
+    #[test]
+    fn test_cmtrace_timestamp_assembled_from_date_time_and_bias() {
+        let profiles = profile::load_builtin_profiles();
+        let content = concat!(
+            r#"<![LOG[Pacific]LOG]!><time="13:55:36.123+480" date="1-15-2024" component="C" context="" type="1" thread="1" file="a.cpp">"#,
+            "\n",
+            r#"<![LOG[CET]LOG]!><time="09:05:00.000-60" date="01-16-2024" component="C" context="" type="1" thread="1" file="a.cpp">"#,
+            "\n",
+            r#"<![LOG[No bias]LOG]!><time="23:59:59.5" date="1-16-2024" component="C" context="" type="1" thread="1" file="a.cpp">"#,
+            "\n",
+        );
+        for id in ["intune-ime", "sccm-cmtrace"] {
+            let p = profiles
+                .iter()
+                .find(|p| p.id == id)
+                .unwrap_or_else(|| panic!("{id} profile not found"));
+            let result = parse_content(
+                content,
+                &PathBuf::from("CcmExec.log"),
+                p,
+                &ParseConfig::default(),
+                0,
+            );
+            assert!(result.errors.is_empty(), "{id}: {:?}", result.errors);
+            let ts: Vec<String> = result
+                .entries
+                .iter()
+                .map(|e| e.timestamp.map(|t| t.to_rfc3339()).unwrap_or_default())
+                .collect();
+            assert_eq!(
+                ts,
+                vec![
+                    "2024-01-15T21:55:36.123+00:00",
+                    "2024-01-16T08:05:00+00:00",
+                    "2024-01-16T23:59:59.500+00:00",
+                ],
+                "{id}"
+            );
+        }
+    }
+
+    /// IIS and Exchange rows in the default column layout keep their columns
+    /// as fields; rows in any other layout still parse as a plain message.
+    #[test]
+    fn test_iis_and_exchange_columns_become_fields() {
+        let profiles = profile::load_builtin_profiles();
+        let parse = |id: &str, content: &str| {
+            let p = profiles.iter().find(|p| p.id == id).unwrap();
+            parse_content(
+                content,
+                &PathBuf::from("x.log"),
+                p,
+                &ParseConfig::default(),
+                0,
+            )
+            .entries
+        };
+
+        let iis = parse(
+            "iis-w3c",
+            "2024-01-15 14:30:23 10.0.0.1 POST /api/login - 443 admin 192.168.1.101 Mozilla/5.0 - 500 0 64 1523\n\
+             2024-01-15 14:30:24 GET /custom/layout 200\n",
+        );
+        assert_eq!(iis.len(), 2);
+        let f = &iis[0].fields;
+        assert_eq!(f.get("cs_uri_stem").map(String::as_str), Some("/api/login"));
+        assert_eq!(f.get("s_ip").map(String::as_str), Some("10.0.0.1"));
+        assert_eq!(f.get("sc_status").map(String::as_str), Some("500"));
+        assert_eq!(f.get("time_taken").map(String::as_str), Some("1523"));
+        assert_eq!(iis[0].severity, Severity::Error);
+        assert!(iis[1].fields.is_empty());
+        assert_eq!(iis[1].message(), "GET /custom/layout 200");
+
+        let exchange = parse(
+            "exchange-tracking",
+            "2024-01-15T09:50:46.234Z,10.0.0.5,CLIENT1,10.0.0.9,EX01,\"08D1,ctx\",EX01\\Default,SMTP,FAIL,123,<a@b>,abc,user@contoso.com,550 5.1.1,2048,1,,,\"Hi, there\",sender@contoso.com,Outgoing\n\
+             2024-01-15T09:50:47.000Z,short,row\n",
+        );
+        assert_eq!(exchange.len(), 2);
+        let f = &exchange[0].fields;
+        assert_eq!(f.get("event_id").map(String::as_str), Some("FAIL"));
+        assert_eq!(
+            f.get("recipient_address").map(String::as_str),
+            Some("user@contoso.com")
+        );
+        assert_eq!(
+            f.get("sender_address").map(String::as_str),
+            Some("sender@contoso.com")
+        );
+        assert_eq!(f.get("server_hostname").map(String::as_str), Some("EX01"));
+        assert_eq!(exchange[0].severity, Severity::Error);
+        assert!(exchange[1].fields.is_empty());
+    }
+
+    /// A malformed offset is recorded as a parse warning but the entry keeps
+    /// its wall-clock time, read like a naive timestamp.
+    #[test]
+    fn test_malformed_tz_offset_falls_back_to_naive_time() {
+        let profile = profile::load_builtin_profiles()
+            .into_iter()
+            .find(|p| p.id == "sccm-cmtrace")
+            .expect("sccm-cmtrace profile not found");
+        let content = concat!(
+            r#"<![LOG[Bad bias]LOG]!><time="13:55:36.000+9999" date="1-15-2024" component="C" context="" type="1" thread="1" file="a.cpp">"#,
+            "\n",
+        );
+        let result = parse_content(
+            content,
+            &PathBuf::from("CcmExec.log"),
+            &profile,
+            &ParseConfig::default(),
+            0,
+        );
+        assert_eq!(
+            result.entries[0].timestamp.map(|t| t.to_rfc3339()),
+            Some("2024-01-15T13:55:36+00:00".to_string())
+        );
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            &result.errors[0],
+            ParseError::TimestampParse { raw_timestamp, .. } if raw_timestamp == "+9999"
+        ));
+    }
+
+    #[test]
+    fn test_utc_offset_seconds_styles() {
+        use TzOffsetStyle::{BiasMinutes, Iso};
+        assert_eq!(utc_offset_seconds("+05:30", Iso), Some(19_800));
+        assert_eq!(utc_offset_seconds("-0800", Iso), Some(-28_800));
+        assert_eq!(utc_offset_seconds("+01", Iso), Some(3_600));
+        assert_eq!(utc_offset_seconds("Z", Iso), Some(0));
+        assert_eq!(utc_offset_seconds("0530", Iso), None);
+        assert_eq!(utc_offset_seconds("+5:3", Iso), None);
+        assert_eq!(utc_offset_seconds("+480", BiasMinutes), Some(-28_800));
+        assert_eq!(utc_offset_seconds("-60", BiasMinutes), Some(3_600));
+        assert_eq!(utc_offset_seconds("+9999", BiasMinutes), None);
+    }
+
+    /// Named capture groups beyond the reserved set must be kept as
+    /// structured fields (klog's pid and source-file columns).
+    #[test]
+    fn test_extra_capture_groups_become_fields() {
+        let profile = profile::load_builtin_profiles()
+            .into_iter()
+            .find(|p| p.id == "kubernetes-klog")
+            .expect("kubernetes-klog built-in profile must load");
+        assert_eq!(profile.field_groups, vec!["pid", "source"]);
+        let content = "E0115 14:31:05.123456    1001 runtime.go:78] observe failed\n";
+        let result = parse_content(
+            content,
+            &PathBuf::from("kubelet.log"),
+            &profile,
+            &ParseConfig::default(),
+            0,
+        );
+        assert_eq!(result.entries.len(), 1);
+        let e = &result.entries[0];
+        assert_eq!(e.message(), "observe failed");
+        assert_eq!(e.fields.get("pid").map(String::as_str), Some("1001"));
+        assert_eq!(
+            e.fields.get("source").map(String::as_str),
+            Some("runtime.go:78")
+        );
+    }
+
+    fn json_lines_profile() -> FormatProfile {
+        profile::load_builtin_profiles()
+            .into_iter()
+            .find(|p| p.id == "json-lines")
+            .expect("json-lines built-in profile must load")
+    }
+
+    /// Regression: the json-lines profile used to capture the whole line as
+    /// `message`, leaving every NDJSON entry with Unknown severity and no
+    /// parsed timestamp.  Known keys must now populate the entry fields and
+    /// unmapped keys must be kept in `fields`.
+    #[test]
+    fn test_json_lines_extracts_structured_fields() {
+        let profile = json_lines_profile();
+        let content = concat!(
+            r#"{"timestamp":"2024-01-15T14:30:22Z","level":"error","message":"Connection refused","service":"api"}"#,
+            "\n",
+            r#"{"@timestamp":"2024-01-15T14:30:23.500Z","log":{"level":"WARN","logger":"db"},"msg":"slow query","thread":"w-1"}"#,
+            "\n",
+            r#"{"time":1705329024,"level":30,"msg":"pino style"}"#,
+            "\n",
+        );
+        let result = parse_content(
+            content,
+            &PathBuf::from("app.jsonl"),
+            &profile,
+            &ParseConfig::default(),
+            0,
+        );
+        assert_eq!(result.entries.len(), 3);
+        assert!(result.errors.is_empty(), "errors: {:?}", result.errors);
+
+        let e0 = &result.entries[0];
+        assert_eq!(e0.severity, Severity::Error);
+        assert_eq!(e0.message(), "Connection refused");
+        assert_eq!(
+            e0.timestamp.map(|t| t.to_rfc3339()),
+            Some("2024-01-15T14:30:22+00:00".to_string())
+        );
+        assert_eq!(e0.fields.get("service").map(String::as_str), Some("api"));
+        assert!(e0.raw_text.starts_with('{'), "raw_text keeps the full line");
+
+        let e1 = &result.entries[1];
+        assert_eq!(e1.severity, Severity::Warning);
+        assert_eq!(e1.component.as_deref(), Some("db"));
+        assert_eq!(e1.thread.as_deref(), Some("w-1"));
+        assert_eq!(e1.message(), "slow query");
+        assert!(
+            e1.fields.is_empty(),
+            "all keys were mapped: {:?}",
+            e1.fields
+        );
+
+        let e2 = &result.entries[2];
+        assert_eq!(e2.severity, Severity::Info, "numeric Pino level 30 = info");
+        assert_eq!(
+            e2.timestamp.map(|t| t.to_rfc3339()),
+            Some("2024-01-15T14:30:24+00:00".to_string())
+        );
+    }
+
+    /// A line that matches the JSON profile's pattern but is not valid JSON
+    /// must still produce an entry via the capture-group path.
+    #[test]
+    fn test_json_lines_malformed_record_falls_back_to_pattern() {
+        let profile = json_lines_profile();
+        let content = "{this is not json}\n";
+        let result = parse_content(
+            content,
+            &PathBuf::from("bad.jsonl"),
+            &profile,
+            &ParseConfig::default(),
+            0,
+        );
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].message(), "{this is not json}");
+        assert!(result.entries[0].fields.is_empty());
+    }
+
     #[test]
     #[ignore = "performance benchmark"]
     fn bench_parse_content_large_dataset_throughput() {
@@ -1634,6 +2694,7 @@ multiline_mode = "raw"
             0,
         );
         assert_eq!(warm.entries.len(), LINES);
+        let bytes_per_entry = estimated_heap_bytes(&warm.entries) / LINES;
 
         let mut best = std::time::Duration::MAX;
         for _ in 0..RUNS {
@@ -1652,10 +2713,36 @@ multiline_mode = "raw"
 
         let lines_per_sec = (LINES as f64 / best.as_secs_f64()).round();
         println!(
-            "PARSER_BENCH lines={} best_ms={} lines_per_sec={}",
+            "PARSER_BENCH lines={} best_ms={} lines_per_sec={} bytes_per_entry={}",
             LINES,
             best.as_millis(),
-            lines_per_sec
+            lines_per_sec,
+            bytes_per_entry
         );
     }
+
+    /// Approximate heap held by `entries`: the entries themselves, their
+    /// text buffers, and each shared string counted once.
+    fn estimated_heap_bytes(entries: &[LogEntry]) -> usize {
+        let mut shared: std::collections::HashSet<*const u8> = std::collections::HashSet::new();
+        let mut total = std::mem::size_of_val(entries);
+        for e in entries {
+            total += e.raw_text.capacity();
+            if let MessageText::Owned(m) = &e.message {
+                total += m.capacity();
+            }
+            let arcs = [
+                Some(e.source_file.as_os_str().as_encoded_bytes()).map(|p| (p.len(), p.as_ptr())),
+                Some((e.profile_id.len(), e.profile_id.as_ptr())),
+                e.thread.as_ref().map(|t| (t.len(), t.as_ptr())),
+                e.component.as_ref().map(|c| (c.len(), c.as_ptr())),
+            ];
+            for (len, ptr) in arcs.into_iter().flatten() {
+                if shared.insert(ptr) {
+                    total += len;
+                }
+            }
+        }
+        total
+    }
 }
@@ -0,0 +1,384 @@
+// LogSleuth - core/patterns.rs
+//
+// Log template mining: clusters entries into message templates with
+// wildcards, Drain-style.
+//
+// Algorithm (a flat variant of Drain):
+// 1. The first line of each message is normalised with
+//    `filter::normalize_message` (IPs, GUIDs, hex and numbers become tokens)
+//    and split on whitespace.
+// 2. Entries are bucketed by (token count, first token).  A leading token
+//    containing a digit is treated as a wildcard for bucketing so e.g.
+//    `job42 started` and `job43 started` land in the same bucket.
+// 3. Within a bucket the entry joins the most similar template: similarity
+//    is the share of positions where the template token equals the entry
+//    token.  At or above `PATTERN_SIMILARITY_THRESHOLD` the entry joins and
+//    differing positions become `<*>`; otherwise a new template is started.
+//
+// Core layer: pure logic, no I/O or UI dependencies.
+
+use crate::core::filter::normalize_message;
+use crate::core::model::{LogEntry, Severity};
+use crate::util::constants::{
+    MAX_PATTERN_CLUSTERS, MAX_PATTERN_TOKENS, PATTERN_SIMILARITY_THRESHOLD,
+};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Wildcard token used in templates for positions that vary between members.
+pub const WILDCARD: &str = "<*>";
+
+/// Token appended to a template when the message exceeded
+/// `MAX_PATTERN_TOKENS` tokens.
+const TRUNCATED: &str = "\u{2026}";
+
+/// One mined message template and the entries it covers.
+#[derive(Debug, Clone)]
+pub struct LogPattern {
+    /// Template text: normalised tokens joined by a single space, with `<*>`
+    /// for positions that differ between members.
+    pub template: String,
+
+    /// `LogEntry::id` of every member, in entry order.
+    pub member_ids: Vec<u64>,
+
+    /// Number of members per severity.
+    pub severity_counts: BTreeMap<Severity, usize>,
+
+    /// Earliest parsed timestamp among the members.
+    pub first_seen: Option<DateTime<Utc>>,
+
+    /// Latest parsed timestamp among the members.
+    pub last_seen: Option<DateTime<Utc>>,
+
+    /// Distinct source files contributing members, sorted.
+    pub files: Vec<PathBuf>,
+}
+
+impl LogPattern {
+    /// Number of entries matching this template.
+    pub fn count(&self) -> usize {
+        self.member_ids.len()
+    }
+
+    /// Most severe level present among the members.
+    pub fn worst_severity(&self) -> Option<Severity> {
+        self.severity_counts.keys().next().copied()
+    }
+}
+
+/// Result of a mining run.
+#[derive(Debug, Clone, Default)]
+pub struct PatternSummary {
+    /// Templates sorted by member count (descending), then template text.
+    pub patterns: Vec<LogPattern>,
+
+    /// Entries that matched no template after `MAX_PATTERN_CLUSTERS`
+    /// templates had been created.
+    pub unclustered: usize,
+
+    /// Number of entries the summary was mined from.
+    pub entries_analysed: usize,
+}
+
+/// Working state for one template during mining.
+struct Cluster {
+    tokens: Vec<String>,
+    members: Vec<usize>,
+}
+
+impl Cluster {
+    /// Share of positions where the template token equals `tokens`.
+    /// Wildcard positions do not count as matches.
+    fn similarity(&self, tokens: &[String]) -> f64 {
+        if tokens.is_empty() {
+            return 1.0;
+        }
+        let same = self
+            .tokens
+            .iter()
+            .zip(tokens)
+            .filter(|(t, e)| t.as_str() != WILDCARD && t == e)
+            .count();
+        same as f64 / tokens.len() as f64
+    }
+
+    fn wildcards(&self) -> usize {
+        self.tokens
+            .iter()
+            .filter(|t| t.as_str() == WILDCARD)
+            .count()
+    }
+
+    /// Replace every position that differs from `tokens` with a wildcard.
+    fn absorb(&mut self, tokens: &[String]) {
+        for (t, e) in self.tokens.iter_mut().zip(tokens) {
+            if t != e && t.as_str() != WILDCARD {
+                *t = WILDCARD.to_string();
+            }
+        }
+    }
+}
+
+/// Normalise and tokenise the first line of a message.
+fn tokenize(message: &str) -> Vec<String> {
+    let first_line = message.lines().next().unwrap_or("");
+    let normalized = normalize_message(first_line);
+    let mut tokens: Vec<String> = normalized
+        .split_whitespace()
+        .take(MAX_PATTERN_TOKENS + 1)
+        .map(str::to_string)
+        .collect();
+    if tokens.len() > MAX_PATTERN_TOKENS {
+        tokens.truncate(MAX_PATTERN_TOKENS);
+        tokens.push(TRUNCATED.to_string());
+    }
+    tokens
+}
+
+/// Bucketing form of the leading token: a variable-looking token collapses
+/// to the wildcard so it does not split otherwise identical templates.
+fn bucket_token(token: Option<&String>) -> String {
+    match token {
+        Some(t) if t.chars().any(|c| c.is_ascii_digit()) => WILDCARD.to_string(),
+        Some(t) => t.clone(),
+        None => String::new(),
+    }
+}
+
+/// Cluster `entries` into message templates.
+pub fn mine_patterns(entries: &[LogEntry]) -> PatternSummary {
+    let mut clusters: Vec<Cluster> = Vec::new();
+    let mut buckets: HashMap<(usize, String), Vec<usize>> = HashMap::new();
+    let mut unclustered = 0usize;
+
+    for (idx, entry) in entries.iter().enumerate() {
+        let tokens = tokenize(entry.message());
+        let key = (tokens.len(), bucket_token(tokens.first()));
+        let bucket = buckets.entry(key).or_default();
+
+        // Best match: highest similarity, ties broken towards the more
+        // general template (more wildcards).
+        let best = bucket
+            .iter()
+            .map(|&ci| (ci, clusters[ci].similarity(&tokens)))
+            .filter(|&(_, sim)| sim >= PATTERN_SIMILARITY_THRESHOLD)
+            .max_by(|&(a, sa), &(b, sb)| {
+                sa.total_cmp(&sb)
+                    .then_with(|| clusters[a].wildcards().cmp(&clusters[b].wildcards()))
+            })
+            .map(|(ci, _)| ci);
+
+        match best {
+            Some(ci) => {
+                let cluster = &mut clusters[ci];
+                cluster.absorb(&tokens);
+                cluster.members.push(idx);
+            }
+            None if clusters.len() < MAX_PATTERN_CLUSTERS => {
+                bucket.push(clusters.len());
+                clusters.push(Cluster {
+                    tokens,
+                    members: vec![idx],
+                });
+            }
+            None => unclustered += 1,
+        }
+    }
+
+    let mut patterns: Vec<LogPattern> = clusters
+        .into_iter()
+        .map(|c| build_pattern(entries, c))
+        .collect();
+    patterns.sort_by(|a, b| {
+        b.count()
+            .cmp(&a.count())
+            .then_with(|| a.template.cmp(&b.template))
+    });
+
+    PatternSummary {
+        patterns,
+        unclustered,
+        entries_analysed: entries.len(),
+    }
+}
+
+fn build_pattern(entries: &[LogEntry], cluster: Cluster) -> LogPattern {
+    let mut severity_counts: BTreeMap<Severity, usize> = BTreeMap::new();
+    let mut files: BTreeSet<&Path> = BTreeSet::new();
+    let mut first_seen: Option<DateTime<Utc>> = None;
+    let mut last_seen: Option<DateTime<Utc>> = None;
+    let mut member_ids = Vec::with_capacity(cluster.members.len());
+
+    for &idx in &cluster.members {
+        let entry = &entries[idx];
+        member_ids.push(entry.id);
+        *severity_counts.entry(entry.severity).or_insert(0) += 1;
+        files.insert(&*entry.source_file);
+        if let Some(ts) = entry.timestamp {
+            first_seen = Some(first_seen.map_or(ts, |f| f.min(ts)));
+            last_seen = Some(last_seen.map_or(ts, |l| l.max(ts)));
+        }
+    }
+
+    LogPattern {
+        template: cluster.tokens.join(" "),
+        member_ids,
+        severity_counts,
+        first_seen,
+        last_seen,
+        files: files.into_iter().map(Path::to_path_buf).collect(),
+    }
+}
+
+// =============================================================================
+// Unit tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(id: u64, file: &str, severity: Severity, secs: Option<i64>, msg: &str) -> LogEntry {
+        LogEntry {
+            id,
+            timestamp: secs.map(|s| Utc.timestamp_opt(1_700_000_000 + s, 0).unwrap()),
+            severity,
+            source_file: PathBuf::from(file).into(),
+            line_number: id + 1,
+            thread: None,
+            component: None,
+            message: msg.to_string().into(),
+            raw_text: msg.to_string(),
+            profile_id: "test".to_string().into(),
+            file_modified: None,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Messages differing only in variable words collapse into one template
+    /// with wildcards; numbers and IPs are already normalised tokens.
+    #[test]
+    fn test_mine_patterns_merges_variable_words() {
+        let entries = vec![
+            entry(
+                0,
+                "a.log",
+                Severity::Info,
+                Some(10),
+                "User alice logged in from 10.0.0.1",
+            ),
+            entry(
+                1,
+                "b.log",
+                Severity::Warning,
+                Some(5),
+                "User bob logged in from 10.0.0.2",
+            ),
+            entry(
+                2,
+                "a.log",
+                Severity::Info,
+                None,
+                "User carol logged in from 192.168.1.7",
+            ),
+            entry(
+                3,
+                "a.log",
+                Severity::Error,
+                Some(20),
+                "Disk full on volume 3",
+            ),
+        ];
+        let summary = mine_patterns(&entries);
+        assert_eq!(summary.entries_analysed, 4);
+        assert_eq!(summary.unclustered, 0);
+        assert_eq!(summary.patterns.len(), 2);
+
+        let login = &summary.patterns[0];
+        assert_eq!(login.template, "User <*> logged in from <IP>");
+        assert_eq!(login.member_ids, vec![0, 1, 2]);
+        assert_eq!(login.severity_counts.get(&Severity::Info), Some(&2));
+        assert_eq!(login.severity_counts.get(&Severity::Warning), Some(&1));
+        assert_eq!(login.worst_severity(), Some(Severity::Warning));
+        assert_eq!(login.first_seen, entries[1].timestamp);
+        assert_eq!(login.last_seen, entries[0].timestamp);
+        assert_eq!(
+            login.files,
+            vec![PathBuf::from("a.log"), PathBuf::from("b.log")]
+        );
+
+        assert_eq!(summary.patterns[1].template, "Disk full on volume <NUM>");
+    }
+
+    /// Messages with different token counts or leading words never merge,
+    /// and dissimilar messages in the same bucket start a new template.
+    #[test]
+    fn test_mine_patterns_keeps_distinct_templates_apart() {
+        let entries = vec![
+            entry(0, "a.log", Severity::Info, None, "Service started"),
+            entry(
+                1,
+                "a.log",
+                Severity::Info,
+                None,
+                "Service stopped unexpectedly",
+            ),
+            entry(2, "a.log", Severity::Info, None, "Connection reset by peer"),
+            entry(
+                3,
+                "a.log",
+                Severity::Info,
+                None,
+                "Connection refused to remote",
+            ),
+        ];
+        let summary = mine_patterns(&entries);
+        let templates: Vec<&str> = summary
+            .patterns
+            .iter()
+            .map(|p| p.template.as_str())
+            .collect();
+        assert_eq!(templates.len(), 4, "got {templates:?}");
+    }
+
+    /// Only the first line of a multi-line message is used, and very long
+    /// messages are truncated to `MAX_PATTERN_TOKENS` plus an ellipsis.
+    #[test]
+    fn test_mine_patterns_first_line_and_truncation() {
+        let long = vec!["word"; MAX_PATTERN_TOKENS + 10].join(" ");
+        let entries = vec![
+            entry(
+                0,
+                "a.log",
+                Severity::Error,
+                None,
+                "Unhandled exception\n   at Foo.Bar()",
+            ),
+            entry(
+                1,
+                "a.log",
+                Severity::Error,
+                None,
+                "Unhandled exception\n   at Baz.Qux()",
+            ),
+            entry(2, "a.log", Severity::Info, None, &long),
+        ];
+        let summary = mine_patterns(&entries);
+        assert_eq!(summary.patterns.len(), 2);
+        assert_eq!(summary.patterns[0].template, "Unhandled exception");
+        assert_eq!(summary.patterns[0].count(), 2);
+        let tokens: Vec<&str> = summary.patterns[1].template.split(' ').collect();
+        assert_eq!(tokens.len(), MAX_PATTERN_TOKENS + 1);
+        assert_eq!(tokens.last(), Some(&TRUNCATED));
+    }
+
+    #[test]
+    fn test_mine_patterns_empty_input() {
+        let summary = mine_patterns(&[]);
+        assert!(summary.patterns.is_empty());
+        assert_eq!(summary.entries_analysed, 0);
+    }
+}
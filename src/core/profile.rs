@@ -4,7 +4,10 @@
 // Core layer: accepts TOML strings and file content, never touches the filesystem.
 // I/O is handled by the app::profile_mgr which feeds content here.
 
-use crate::core::model::{FormatProfile, MultilineMode, Severity};
+use crate::core::clock::AssumedZone;
+use crate::core::model::{
+    FormatProfile, JsonFieldMap, MultilineMode, Severity, TimestampTemplate, TzOffsetStyle,
+};
 use crate::util::constants;
 use crate::util::error::ProfileError;
 use regex::Regex;
@@ -59,6 +62,75 @@ pub struct ParsingDef {
     pub timestamp_format: String,
     #[serde(default)]
     pub multiline_mode: MultilineMode,
+    /// Optional template assembling the raw timestamp from several capture
+    /// groups, e.g. `"{date} {time}"`.  When omitted and the `line_pattern`
+    /// has `date` and/or `time` groups, `"{date} {time}"` is implied.
+    #[serde(default)]
+    pub timestamp_template: Option<String>,
+    /// How a `tz_offset` capture group is read: `"iso"` (default) or
+    /// `"bias_minutes"` (CMTrace `+480` suffix).
+    #[serde(default)]
+    pub tz_offset_style: TzOffsetStyle,
+    /// Zone of naive timestamps: `"utc"` (default), `"local"`, an IANA name
+    /// such as `"Europe/London"`, or a fixed offset such as `"+02:00"`.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Optional regex a line must match to start a new entry.  With it, lines
+    /// that do not match continue the current entry even if they match
+    /// `line_pattern` (timestamps inside Java stack frames).
+    #[serde(default)]
+    pub entry_start: Option<String>,
+    /// Optional regex matching the last line of an entry (`</Event>`,
+    /// Python's final `SomeError: ...` line).
+    #[serde(default)]
+    pub entry_end: Option<String>,
+    /// Optional `[parsing.json]` sub-table.  Its presence switches the profile
+    /// to JSON-aware field extraction (see `core::json_parser`).
+    #[serde(default)]
+    pub json: Option<JsonFieldsDef>,
+}
+
+/// Raw TOML representation of the optional `[parsing.json]` section.
+///
+/// Every list is optional; an omitted list falls back to the matching
+/// `JSON_DEFAULT_*_KEYS` constant so a bare `[parsing.json]` header is enough
+/// to enable JSON parsing with sensible defaults.  An explicitly empty list
+/// disables that mapping.
+#[derive(Debug, Deserialize, Default)]
+pub struct JsonFieldsDef {
+    #[serde(default)]
+    pub timestamp_keys: Option<Vec<String>>,
+    #[serde(default)]
+    pub level_keys: Option<Vec<String>>,
+    #[serde(default)]
+    pub message_keys: Option<Vec<String>>,
+    #[serde(default)]
+    pub component_keys: Option<Vec<String>>,
+    #[serde(default)]
+    pub thread_keys: Option<Vec<String>>,
+}
+
+impl JsonFieldsDef {
+    /// Resolve omitted lists to their defaults and drop blank keys.
+    fn compile(self) -> JsonFieldMap {
+        fn keys(list: Option<Vec<String>>, defaults: &[&str]) -> Vec<String> {
+            match list {
+                Some(v) => v
+                    .into_iter()
+                    .map(|k| k.trim().to_string())
+                    .filter(|k| !k.is_empty())
+                    .collect(),
+                None => defaults.iter().map(|k| k.to_string()).collect(),
+            }
+        }
+        JsonFieldMap {
+            timestamp_keys: keys(self.timestamp_keys, constants::JSON_DEFAULT_TIMESTAMP_KEYS),
+            level_keys: keys(self.level_keys, constants::JSON_DEFAULT_LEVEL_KEYS),
+            message_keys: keys(self.message_keys, constants::JSON_DEFAULT_MESSAGE_KEYS),
+            component_keys: keys(self.component_keys, constants::JSON_DEFAULT_COMPONENT_KEYS),
+            thread_keys: keys(self.thread_keys, constants::JSON_DEFAULT_THREAD_KEYS),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -101,6 +173,20 @@ pub struct SeverityOverrideDef {
 // Profile validation and compilation
 // =============================================================================
 
+/// Named capture groups that map onto fixed `LogEntry` slots or feed the
+/// timestamp (`date`, `time`, `tz_offset`).  Any other named group in a
+/// `line_pattern` is stored in `LogEntry::fields`.
+pub const RESERVED_CAPTURE_GROUPS: &[&str] = &[
+    "timestamp",
+    "date",
+    "time",
+    "tz_offset",
+    "level",
+    "thread",
+    "component",
+    "message",
+];
+
 /// Parse a TOML string into a `ProfileDefinition`.
 ///
 /// `source_path` is used for error messages only (not for I/O).
@@ -167,9 +253,54 @@ pub fn validate_and_compile(
     // Validate and compile line_pattern regex
     let line_pattern = compile_regex(id, "parsing.line_pattern", &def.parsing.line_pattern)?;
 
+    // Optional record framing regexes.
+    let entry_start = def
+        .parsing
+        .entry_start
+        .as_deref()
+        .map(|p| compile_regex(id, "parsing.entry_start", p))
+        .transpose()?;
+    let entry_end = def
+        .parsing
+        .entry_end
+        .as_deref()
+        .map(|p| compile_regex(id, "parsing.entry_end", p))
+        .transpose()?;
+
     // Validate line_pattern has at least a 'message' capture group
     let capture_names: Vec<&str> = line_pattern.capture_names().flatten().collect();
 
+    let timestamp_template = compile_timestamp_template(
+        id,
+        def.parsing.timestamp_template.as_deref(),
+        &def.parsing.timestamp_format,
+        &capture_names,
+    )?;
+
+    let timezone = match def.parsing.timezone.as_deref() {
+        Some(value) => {
+            AssumedZone::parse(value).map_err(|reason| ProfileError::InvalidTimezone {
+                profile_id: id.clone(),
+                value: value.to_string(),
+                reason,
+            })?
+        }
+        None => AssumedZone::Utc,
+    };
+
+    // Every named group that is not one of the fixed LogEntry slots (or used
+    // by the timestamp template) becomes a structured field.
+    let field_groups: Vec<String> = capture_names
+        .iter()
+        .filter(|name| !RESERVED_CAPTURE_GROUPS.contains(name))
+        .filter(|name| {
+            !timestamp_template
+                .as_ref()
+                .is_some_and(|t| t.group_names().any(|g| g == **name))
+        })
+        .map(|name| name.to_string())
+        .collect();
+
     if !capture_names.contains(&"message") {
         tracing::warn!(
             profile_id = id,
@@ -238,14 +369,65 @@ pub fn validate_and_compile(
             .collect(),
         content_match,
         line_pattern,
+        field_groups,
         timestamp_format: def.parsing.timestamp_format,
+        timestamp_template,
+        tz_offset_style: def.parsing.tz_offset_style,
+        timezone,
         multiline_mode: def.parsing.multiline_mode,
+        entry_start,
+        entry_end,
+        json_fields: def.parsing.json.map(JsonFieldsDef::compile),
         severity_mapping,
         severity_override,
         is_builtin,
     })
 }
 
+/// Resolve the profile's multi-group timestamp recipe.
+///
+/// An explicit `timestamp_template` must parse and may only reference named
+/// groups that exist in the `line_pattern`.  Without one, a pattern that has
+/// `date` and/or `time` groups gets the implied `{date} {time}` template.
+/// Returns `None` for ordinary single-`timestamp`-group profiles.
+fn compile_timestamp_template(
+    profile_id: &str,
+    template: Option<&str>,
+    timestamp_format: &str,
+    capture_names: &[&str],
+) -> Result<Option<TimestampTemplate>, ProfileError> {
+    let invalid = |reason: String| ProfileError::InvalidTimestampFormat {
+        profile_id: profile_id.to_string(),
+        format: timestamp_format.to_string(),
+        reason,
+    };
+    let source = match template {
+        Some(t) => t.to_string(),
+        None => {
+            let groups: Vec<&str> = ["date", "time"]
+                .into_iter()
+                .filter(|g| capture_names.contains(g))
+                .collect();
+            if groups.is_empty() {
+                return Ok(None);
+            }
+            groups
+                .iter()
+                .map(|g| format!("{{{g}}}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    };
+    let compiled = TimestampTemplate::parse(&source)
+        .map_err(|e| invalid(format!("timestamp_template '{source}': {e}")))?;
+    if let Some(missing) = compiled.group_names().find(|g| !capture_names.contains(g)) {
+        return Err(invalid(format!(
+            "timestamp_template references '{{{missing}}}' but line_pattern has no such named group"
+        )));
+    }
+    Ok(Some(compiled))
+}
+
 /// Compile a regex pattern with length validation to prevent ReDoS.
 fn compile_regex(
     profile_id: &str,
@@ -500,7 +682,7 @@ pub fn load_builtin_profiles() -> Vec<FormatProfile> {
 }
 
 // =============================================================================
-// Windows Event Log (.evtx) profile (Windows-only)
+// Windows Event Log (.evtx) profile
 // =============================================================================
 
 /// Create the built-in profile for Windows Event Log (.evtx) binary files.
@@ -511,7 +693,6 @@ pub fn load_builtin_profiles() -> Vec<FormatProfile> {
 /// values that satisfy the `FormatProfile` struct but are never used; the scan
 /// pipeline detects `.evtx` files by extension and routes them to
 /// `core::evtx_parser::parse_evtx_file()` instead.
-#[cfg(target_os = "windows")]
 pub fn create_evtx_profile() -> FormatProfile {
     FormatProfile {
         id: crate::util::constants::EVTX_PROFILE_ID.to_string(),
@@ -528,8 +709,15 @@ pub fn create_evtx_profile() -> FormatProfile {
         content_match: Regex::new("^ElfFile").expect("valid regex"),
         // Dummy line pattern: never used for binary parsing.
         line_pattern: Regex::new("(?P<message>.+)").expect("valid regex"),
+        field_groups: Vec::new(),
+        timestamp_template: None,
+        tz_offset_style: TzOffsetStyle::default(),
+        timezone: AssumedZone::Utc,
         timestamp_format: String::new(),
         multiline_mode: MultilineMode::default(),
+        entry_start: None,
+        entry_end: None,
+        json_fields: None,
         severity_mapping: HashMap::new(),
         severity_override: HashMap::new(),
         is_builtin: true,
@@ -726,7 +914,7 @@ timestamp_format = "%Y"
         let profile = validate_and_compile(def, &path, false).unwrap();
 
         // "test-something.log" matches the "test*.log" file_pattern.
-        let result = auto_detect("test-something.log", &[], &[profile.clone()]);
+        let result = auto_detect("test-something.log", &[], std::slice::from_ref(&profile));
         assert!(
             result.is_some(),
             "empty samples + filename match should produce a detection result"
@@ -813,10 +1001,21 @@ timestamp_format = "%Y"
                 "{id}: message capture"
             );
             assert_eq!(
-                caps.name("timestamp").map(|m| m.as_str()),
+                caps.name("date").map(|m| m.as_str()),
                 Some("1-15-2024"),
-                "{id}: timestamp capture"
+                "{id}: date capture"
+            );
+            assert_eq!(
+                caps.name("time").map(|m| m.as_str()),
+                Some("13:55:36.123"),
+                "{id}: time capture"
+            );
+            assert_eq!(
+                caps.name("tz_offset").map(|m| m.as_str()),
+                Some("+420"),
+                "{id}: tz_offset capture"
             );
+            assert_eq!(p.tz_offset_style, TzOffsetStyle::BiasMinutes);
             assert_eq!(
                 caps.name("component").map(|m| m.as_str()),
                 Some("IntuneManagementExtension"),
@@ -894,4 +1093,126 @@ debug   = ['\[DEBUG\]']
         assert_eq!(profile.apply_severity_override("[ERROR] something"), None);
         assert_eq!(profile.apply_severity_override("[WARN] disk low"), None);
     }
+
+    /// A bare `[parsing.json]` header enables JSON extraction with the
+    /// built-in key lists; an explicit empty list disables that mapping and
+    /// profiles without the section keep `json_fields == None`.
+    #[test]
+    fn test_parsing_json_section_defaults_and_overrides() {
+        let path = PathBuf::from("test.toml");
+        let def = parse_profile_toml(VALID_PROFILE_TOML, &path).unwrap();
+        let profile = validate_and_compile(def, &path, false).unwrap();
+        assert!(profile.json_fields.is_none());
+
+        let toml = r#"
+[profile]
+id = "json-test"
+name = "JSON Test"
+
+[detection]
+content_match = '^\{'
+
+[parsing]
+line_pattern = '^(?P<message>\{.+\})$'
+timestamp_format = "%Y-%m-%dT%H:%M:%S"
+
+[parsing.json]
+level_keys = ["lvl"]
+thread_keys = []
+"#;
+        let def = parse_profile_toml(toml, &path).unwrap();
+        let profile = validate_and_compile(def, &path, false).unwrap();
+        let map = profile.json_fields.expect("json section must compile");
+        assert_eq!(map.level_keys, vec!["lvl".to_string()]);
+        assert!(map.thread_keys.is_empty());
+        assert_eq!(
+            map.timestamp_keys.len(),
+            constants::JSON_DEFAULT_TIMESTAMP_KEYS.len(),
+            "omitted lists fall back to the defaults"
+        );
+    }
+
+    /// `[parsing] timezone` accepts UTC, local, IANA names and offsets and
+    /// defaults to UTC; anything else fails at load time.
+    #[test]
+    fn test_parsing_timezone_key() {
+        let path = PathBuf::from("test.toml");
+        let def = parse_profile_toml(VALID_PROFILE_TOML, &path).unwrap();
+        let profile = validate_and_compile(def, &path, false).unwrap();
+        assert_eq!(profile.timezone, AssumedZone::Utc);
+
+        let with_zone = |zone: &str| {
+            let toml = format!(
+                "[profile]\nid = \"tz\"\nname = \"Zoned\"\n\n\
+                 [detection]\ncontent_match = '.'\n\n\
+                 [parsing]\nline_pattern = '^(?P<message>.*)$'\n\
+                 timestamp_format = \"%Y-%m-%d %H:%M:%S\"\ntimezone = \"{zone}\"\n"
+            );
+            validate_and_compile(parse_profile_toml(&toml, &path)?, &path, false)
+        };
+        assert_eq!(with_zone("local").unwrap().timezone, AssumedZone::Local);
+        assert_eq!(
+            with_zone("Europe/London").unwrap().timezone,
+            AssumedZone::Named(chrono_tz::Europe::London)
+        );
+        assert_eq!(
+            with_zone("+05:30").unwrap().timezone,
+            AssumedZone::Fixed(330)
+        );
+        assert!(matches!(
+            with_zone("Mars/Olympus"),
+            Err(ProfileError::InvalidTimezone { .. })
+        ));
+    }
+
+    fn template_profile(
+        line_pattern: &str,
+        template: Option<&str>,
+    ) -> Result<FormatProfile, ProfileError> {
+        let template_line = template
+            .map(|t| format!("timestamp_template = \"{t}\"\n"))
+            .unwrap_or_default();
+        let toml = format!(
+            "[profile]\nid = \"tpl\"\nname = \"Template\"\n\n\
+             [detection]\ncontent_match = '.'\n\n\
+             [parsing]\nline_pattern = '{line_pattern}'\n{template_line}\
+             timestamp_format = \"%Y-%m-%d %H:%M:%S\"\n"
+        );
+        let path = PathBuf::from("test.toml");
+        validate_and_compile(parse_profile_toml(&toml, &path)?, &path, false)
+    }
+
+    /// `date` / `time` groups imply a `{date} {time}` template; an explicit
+    /// template may reference any named group, and those groups are not
+    /// duplicated into the structured fields.
+    #[test]
+    fn test_timestamp_template_implied_and_explicit() {
+        let p = template_profile(r"^(?P<time>\S+) (?P<date>\S+) (?P<message>.*)$", None).unwrap();
+        assert_eq!(
+            p.timestamp_template,
+            Some(TimestampTemplate::parse("{date} {time}").unwrap())
+        );
+        assert!(p.field_groups.is_empty());
+
+        let p = template_profile(r"^(?P<message>.*)$", None).unwrap();
+        assert!(p.timestamp_template.is_none());
+
+        let p = template_profile(
+            r"^(?P<d>\S+) (?P<t>\S+) (?P<pid>\d+) (?P<message>.*)$",
+            Some("{d}T{t}"),
+        )
+        .unwrap();
+        assert_eq!(p.field_groups, vec!["pid"]);
+    }
+
+    /// A template referencing a group missing from the pattern (or a
+    /// malformed template) must fail at load time, not silently per line.
+    #[test]
+    fn test_timestamp_template_rejects_unknown_group() {
+        let err = template_profile(r"^(?P<date>\S+) (?P<message>.*)$", Some("{date} {clock}"))
+            .unwrap_err();
+        assert!(err.to_string().contains("clock"), "{err}");
+        assert!(template_profile(r"^(?P<date>\S+) (?P<message>.*)$", Some("{date")).is_err());
+        assert!(template_profile(r"^(?P<date>\S+) (?P<message>.*)$", Some("no groups")).is_err());
+    }
 }
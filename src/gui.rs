@@ -4,6 +4,7 @@
 // Wires together all UI panels and manages the scan lifecycle.
 
 use crate::app::dir_watcher::{DirWatchConfig, DirWatcher};
+use crate::app::fs_watch::is_network_path;
 use crate::app::scan::ScanManager;
 use crate::app::state::AppState;
 use crate::app::tail::TailManager;
@@ -105,6 +106,11 @@ impl LogSleuthApp {
 
 impl eframe::App for LogSleuthApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Scans and the tail parse with the current clock rules' timezone
+        // overrides (a cheap comparison when the rules have not changed).
+        self.scan_manager.set_clock_rules(&self.state.clock_rules);
+        self.tail_manager.set_clock_rules(&self.state.clock_rules);
+
         // Apply the user's chosen theme every frame (cheap; egui diffs internally).
         if self.state.dark_mode {
             ctx.set_visuals(egui::Visuals::dark());
@@ -205,30 +211,20 @@ impl eframe::App for LogSleuthApp {
                     // Troubleshoot mode: drop non-Critical/Error entries before
                     // they consume memory or the entry cap.
                     self.state.filter_entries_for_ingest(&mut entries);
+                    // Per-file clock offsets / timezone assumptions.
+                    self.state.apply_clock_rules(&mut entries);
 
-                    // Cap total entries at the configured limit on the UI thread as
-                    // well as on the background thread.  This matters for append
-                    // scans where the UI already holds entries from a previous scan
-                    // and the background thread cannot know the prior count.
-                    let cap = self.state.max_total_entries;
-                    let current = self.state.entries.len();
-                    let remaining = cap.saturating_sub(current);
-                    if remaining > 0 {
-                        // Track max entry ID incrementally (Bug fix: O(1) instead
-                        // of O(n) full scan in next_entry_id).
-                        self.state.track_max_entry_id(&entries);
-                        // Track entries without parsed timestamps so the mtime-update
-                        // path in FileMtimeUpdates can be skipped when unneeded.
-                        if entries.len() <= remaining {
-                            self.state.track_notimestamp_entries(&entries);
-                            self.state.entries.extend(entries);
-                        } else {
-                            let to_add = &entries[..remaining];
-                            self.state.track_notimestamp_entries(to_add);
-                            self.state
-                                .entries
-                                .extend(entries.into_iter().take(remaining));
-                        }
+                    // Capped at max_total_entries; the overflow goes to the
+                    // on-disk entry store when spilling is enabled.
+                    self.state.ingest_entries(entries);
+                }
+                crate::core::model::ScanProgress::EntriesSpilled { store } => {
+                    // Sent once per scan; the pipeline holds no other reference.
+                    match std::sync::Arc::try_unwrap(store) {
+                        Ok(store) => self.state.absorb_entry_store(
+                            store.into_inner().unwrap_or_else(|p| p.into_inner()),
+                        ),
+                        Err(_) => tracing::warn!("Spilled entry store still shared; ignored"),
                     }
                 }
                 crate::core::model::ScanProgress::ParsingCompleted { summary } => {
@@ -328,6 +324,16 @@ impl eframe::App for LogSleuthApp {
                                 self.state.status_message =
                                     format!("Loaded {n} {word} (no entries found).");
                             }
+                        } else if self.state.request_start_tail_after_scan {
+                            // Directory opened with `--tail`: nothing is parsed
+                            // up front, but every file is watched so new lines
+                            // appear as they are written.
+                            self.state.filter_state.hide_all_sources = false;
+                            self.state.filter_state.source_files.clear();
+                            let n = self.state.discovered_files.len();
+                            let word = if n == 1 { "file" } else { "files" };
+                            self.state.status_message =
+                                format!("{n} {word} discovered \u{2014} starting Live Tail.");
                         } else {
                             // Directory scan: keep the opt-in model to avoid
                             // loading thousands of files automatically.
@@ -349,7 +355,12 @@ impl eframe::App for LogSleuthApp {
                     // are pre-sorted among themselves but must be interleaved with the
                     // existing sorted entries — sort_entries_chronologically handles both
                     // cases correctly and calls apply_filters() when done.
-                    self.state.sort_entries_chronologically();
+                    // A session in the entry store re-runs the filters over it instead.
+                    if self.state.store_window_stale {
+                        self.state.apply_filters();
+                    } else {
+                        self.state.sort_entries_chronologically();
+                    }
                     // Persist the session so the next launch can restore this state.
                     self.state.save_session();
 
@@ -396,8 +407,9 @@ impl eframe::App for LogSleuthApp {
                         self.state.request_start_tail = true;
                     }
 
-                    // Troubleshoot mode: auto-start Live Tail after the initial
-                    // scan completes so new log lines are captured immediately.
+                    // Troubleshoot mode / CLI --tail: auto-start Live Tail after
+                    // the initial scan completes so new log lines are captured
+                    // immediately.
                     if self.state.request_start_tail_after_scan {
                         self.state.request_start_tail_after_scan = false;
                         if !self.state.tail_active {
@@ -542,19 +554,62 @@ impl eframe::App for LogSleuthApp {
         let tail_messages = self.tail_manager.poll_progress(MAX_TAIL_MESSAGES_PER_FRAME);
         let had_tail = !tail_messages.is_empty();
         for msg in tail_messages {
+            // A rotation is recorded in the timeline by its marker entry,
+            // which is ingested like any other tail entry.
+            let msg = match msg {
+                crate::core::model::TailProgress::Rotated {
+                    path, kind, marker, ..
+                } => {
+                    tracing::info!(file = %path.display(), kind = kind.label(), "Live tail: log rotated");
+                    self.state.status_message = format!(
+                        "Live tail: {} {} by rotation; now following the new file.",
+                        crate::core::archive::display_name(&path),
+                        kind.label()
+                    );
+                    crate::core::model::TailProgress::NewEntries {
+                        entries: vec![marker],
+                        replay: false,
+                    }
+                }
+                msg => msg,
+            };
             match msg {
                 crate::core::model::TailProgress::Started { file_count } => {
                     tracing::info!(files = file_count, "Live tail active");
                 }
-                crate::core::model::TailProgress::NewEntries { mut entries } => {
+                crate::core::model::TailProgress::NewEntries {
+                    mut entries,
+                    replay,
+                } => {
                     // Troubleshoot mode: drop non-Critical/Error entries before
                     // they consume memory or the ring-buffer cap.
                     self.state.filter_entries_for_ingest(&mut entries);
+                    // Per-file clock offsets / timezone assumptions; applied
+                    // before the fast-append ordering check below.
+                    self.state.apply_clock_rules(&mut entries);
 
                     if entries.is_empty() {
                         continue;
                     }
 
+                    // Live alert rules see each batch of new content as it
+                    // arrives; a rule that fires pins its entry, which is
+                    // added just below.  Replayed history never fires them.
+                    if !replay {
+                        self.state.evaluate_alerts(&entries);
+                    }
+
+                    // A session in the entry store keeps every tail entry
+                    // on disk; there is no ring buffer to trim.
+                    if self.state.entry_store.is_some() {
+                        self.state.append_tail_to_store(entries);
+                        entries_changed = true;
+                        if self.state.sort_descending && self.state.tail_auto_scroll {
+                            self.state.scroll_top_requested = true;
+                        }
+                        continue;
+                    }
+
                     // ---------------------------------------------------------
                     // Ring-buffer eviction (Fix A — RAM runaway prevention)
                     //
@@ -642,6 +697,8 @@ impl eframe::App for LogSleuthApp {
                         self.state.warnings.push(msg);
                     }
                 }
+                // Rewritten to NewEntries above.
+                crate::core::model::TailProgress::Rotated { .. } => {}
                 crate::core::model::TailProgress::Stopped => {
                     self.state.tail_active = false;
                     self.state.status_message = "Live tail stopped.".to_string();
@@ -782,13 +839,13 @@ impl eframe::App for LogSleuthApp {
                     // iterate discovered_files and entries once each, reducing
                     // O(updates * files + entries) to O(updates + files + entries).
                     let mtime_map: std::collections::HashMap<
-                        &std::path::PathBuf,
+                        &std::path::Path,
                         chrono::DateTime<chrono::Utc>,
-                    > = updates.iter().map(|(p, t)| (p, *t)).collect();
+                    > = updates.iter().map(|(p, t)| (p.as_path(), *t)).collect();
                     // Update discovered_files using the HashMap (O(files) instead
                     // of O(updates * files) from the previous nested-find loop).
                     for f in self.state.discovered_files.iter_mut() {
-                        if let Some(&mtime) = mtime_map.get(&f.path) {
+                        if let Some(&mtime) = mtime_map.get(f.path.as_path()) {
                             f.modified = Some(mtime);
                         }
                     }
@@ -804,7 +861,7 @@ impl eframe::App for LogSleuthApp {
                     if self.state.notimestamp_entry_count > 0 && time_filter_active {
                         for entry in self.state.entries.iter_mut() {
                             if entry.timestamp.is_none() {
-                                if let Some(&mtime) = mtime_map.get(&entry.source_file) {
+                                if let Some(&mtime) = mtime_map.get(&*entry.source_file) {
                                     entry.file_modified = Some(mtime);
                                 }
                             }
@@ -866,7 +923,21 @@ impl eframe::App for LogSleuthApp {
             // Capture the date filter BEFORE clear() — clear() does not reset
             // discovery_date_input intentionally (user preference, not scan state).
             let modified_since = self.state.discovery_modified_since();
+            // Startup requests queued alongside this scan (troubleshoot or
+            // CLI --tail, files passed next to a directory on the command
+            // line) must survive clear().  Queued files are only carried
+            // when nothing is loaded yet, so they cannot belong to a
+            // previous session.
+            let tail_after_scan = self.state.request_start_tail_after_scan;
+            let startup_queue =
+                if self.state.discovered_files.is_empty() && !self.state.scan_in_progress {
+                    std::mem::take(&mut self.state.queued_parse_files)
+                } else {
+                    Vec::new()
+                };
             self.state.clear();
+            self.state.request_start_tail_after_scan = tail_after_scan;
+            self.state.queued_parse_files = startup_queue;
             self.state.scan_in_progress = true;
             self.state.fresh_scan_in_progress = true;
             self.state.scan_path = Some(path.clone());
@@ -982,7 +1053,9 @@ impl eframe::App for LogSleuthApp {
             if self.state.tail_active {
                 self.tail_manager.stop_tail();
             }
+            let tail_after_scan = self.state.request_start_tail_after_scan;
             self.state.clear();
+            self.state.request_start_tail_after_scan = tail_after_scan;
             // scan_path must be None for file-only sessions so the dir watcher is
             // not started in the ParsingCompleted handler.
             self.state.scan_path = None;
@@ -1126,6 +1199,8 @@ impl eframe::App for LogSleuthApp {
             let hide_all = self.state.filter_state.hide_all_sources;
             let source_filter = self.state.filter_state.source_files.clone();
             let mut skipped_evtx = 0usize;
+            let start = self.state.tail_replay.start_at(chrono::Utc::now());
+            let replaying = start != crate::app::tail::TailStart::End;
             let mut files: Vec<crate::app::tail::TailFileInfo> = Vec::new();
             for f in &self.state.discovered_files {
                 if hide_all {
@@ -1143,6 +1218,10 @@ impl eframe::App for LogSleuthApp {
                     skipped_evtx += 1;
                     continue;
                 }
+                // Archive members are static snapshots; there is nothing to tail.
+                if crate::core::archive::is_member_path(&f.path) {
+                    continue;
+                }
 
                 let Some(profile_id) = f.profile_id.as_ref() else {
                     continue;
@@ -1170,7 +1249,11 @@ impl eframe::App for LogSleuthApp {
                     // most a single scan duration.  All live stat work is
                     // confined to the background thread so it cannot freeze
                     // the UI (Rule 16).
-                    initial_offset: Some(f.size),
+                    //
+                    // A file the scan did not parse (directory opened with
+                    // `--tail`) has nothing loaded: with a replay start mode
+                    // it is left to the tail thread to find where to start.
+                    initial_offset: (!(f.parsing_skipped && replaying)).then_some(f.size),
                 });
             }
             // Safety cap: limit simultaneously-watched files to prevent
@@ -1218,8 +1301,14 @@ impl eframe::App for LogSleuthApp {
                 // that entries from the initial scan are never subject to tail
                 // eviction (Rule 11 — resource bounds).
                 self.state.set_tail_base();
-                self.tail_manager
-                    .start_tail(files, start_id, self.state.tail_poll_interval_ms);
+                self.tail_manager.start_tail(
+                    files,
+                    start,
+                    start_id,
+                    self.state.tail_poll_interval_ms,
+                );
+                // Alert rule counts start from zero with each tail session.
+                self.state.rebuild_alert_engine();
                 self.state.tail_active = true;
                 self.state.status_message = if files_total > MAX_TAIL_WATCH_FILES {
                     let mut msg = format!(
@@ -1477,7 +1566,9 @@ impl eframe::App for LogSleuthApp {
                     ui.separator();
                     // Export sub-menu -- enabled only when there are filtered entries
                     let has_entries = !self.state.filtered_indices.is_empty();
-                    let entry_count = self.state.filtered_indices.len();
+                    // With an entry store this counts every matching stored
+                    // entry, all of which are exported.
+                    let entry_count = self.state.filtered_count();
                     ui.add_enabled_ui(has_entries, |ui| {
                         ui.menu_button("Export", |ui| {
                             // EXP-05: warn user before large exports
@@ -1501,26 +1592,19 @@ impl eframe::App for LogSleuthApp {
                                     .save_file()
                                 {
                                     let filter_desc = self.state.filter_description();
+                                    let clock_adjustments = self.state.clock_rule_descriptions();
+                                    let scan_path = self.state.scan_path.clone();
                                     let metadata = crate::core::export::ExportMetadata {
-                                        scan_path: self.state.scan_path.as_deref(),
+                                        scan_path: scan_path.as_deref(),
                                         filter_description: &filter_desc,
                                         entry_count,
+                                        clock_adjustments: &clock_adjustments,
                                     };
                                     // SEC-04: atomic write via temp file + rename
                                     let tmp = dest.with_extension("csv.tmp");
                                     match std::fs::File::create(&tmp) {
                                         Ok(f) => {
-                                            let filtered_entries = self
-                                                .state
-                                                .filtered_indices
-                                                .iter()
-                                                .filter_map(|&i| self.state.entries.get(i));
-                                            match crate::core::export::export_csv(
-                                                filtered_entries,
-                                                f,
-                                                &dest,
-                                                &metadata,
-                                            ) {
+                                            match self.state.export_csv(f, &dest, &metadata) {
                                                 Ok(n) => {
                                                     if let Err(e) = std::fs::rename(&tmp, &dest) {
                                                         let _ = std::fs::remove_file(&tmp);
@@ -1565,26 +1649,19 @@ impl eframe::App for LogSleuthApp {
                                     .save_file()
                                 {
                                     let filter_desc = self.state.filter_description();
+                                    let clock_adjustments = self.state.clock_rule_descriptions();
+                                    let scan_path = self.state.scan_path.clone();
                                     let metadata = crate::core::export::ExportMetadata {
-                                        scan_path: self.state.scan_path.as_deref(),
+                                        scan_path: scan_path.as_deref(),
                                         filter_description: &filter_desc,
                                         entry_count,
+                                        clock_adjustments: &clock_adjustments,
                                     };
                                     // SEC-04: atomic write via temp file + rename
                                     let tmp = dest.with_extension("json.tmp");
                                     match std::fs::File::create(&tmp) {
                                         Ok(f) => {
-                                            let filtered_entries = self
-                                                .state
-                                                .filtered_indices
-                                                .iter()
-                                                .filter_map(|&i| self.state.entries.get(i));
-                                            match crate::core::export::export_json(
-                                                filtered_entries,
-                                                f,
-                                                &dest,
-                                                &metadata,
-                                            ) {
+                                            match self.state.export_json(f, &dest, &metadata) {
                                                 Ok(n) => {
                                                     if let Err(e) = std::fs::rename(&tmp, &dest) {
                                                         let _ = std::fs::remove_file(&tmp);
@@ -1650,6 +1727,36 @@ impl eframe::App for LogSleuthApp {
                             ui.close_menu();
                         }
                     });
+                    ui.add_enabled_ui(!self.state.entries.is_empty(), |ui| {
+                        if ui.button("Patterns")
+                            .on_hover_text("Group all entries into message templates and filter to one")
+                            .clicked()
+                        {
+                            self.state.show_patterns = true;
+                            ui.close_menu();
+                        }
+                        if ui.button("Anomalies")
+                            .on_hover_text("Find error-rate spikes and files that went silent, and jump to their time windows")
+                            .clicked()
+                        {
+                            self.state.show_anomalies = true;
+                            ui.close_menu();
+                        }
+                        if ui.button("Compare")
+                            .on_hover_text("Diff two files, scan roots or time ranges by normalised message")
+                            .clicked()
+                        {
+                            self.state.show_compare = true;
+                            ui.close_menu();
+                        }
+                    });
+                    if ui.button("Alert Rules\u{2026}")
+                        .on_hover_text("Rules that watch Live Tail for matching entries and raise an alert")
+                        .clicked()
+                    {
+                        self.state.show_alert_rules = true;
+                        ui.close_menu();
+                    }
                     ui.separator();
                     let has_bookmarks = self.state.bookmark_count() > 0;
                     ui.add_enabled_ui(has_bookmarks, |ui| {
@@ -1798,6 +1905,36 @@ impl eframe::App for LogSleuthApp {
                     }
                     ui.separator();
                 }
+                // ALERT badge -- flashes for ALERT_FLASH_SECS after a live
+                // alert rule fires.  Clicking opens the Alert Rules window.
+                if let Some((message, fired_at)) = &self.state.alert_flash {
+                    let elapsed = fired_at.elapsed();
+                    if elapsed.as_secs() < crate::util::constants::ALERT_FLASH_SECS {
+                        let bright = elapsed.as_millis() / 500 % 2 == 0;
+                        let alpha = if bright { 80 } else { 20 };
+                        if ui
+                            .add(
+                                egui::Button::new(
+                                    egui::RichText::new(" \u{1f514} ALERT ")
+                                        .strong()
+                                        .color(egui::Color32::from_rgb(239, 68, 68)) // Red 500
+                                        .background_color(
+                                            egui::Color32::from_rgba_unmultiplied(
+                                                239, 68, 68, alpha,
+                                            ),
+                                        ),
+                                )
+                                .frame(false),
+                            )
+                            .on_hover_text(format!("{message}\n\nClick to open Alert Rules."))
+                            .clicked()
+                        {
+                            self.state.show_alert_rules = true;
+                        }
+                        ui.separator();
+                        ctx.request_repaint_after(std::time::Duration::from_millis(250));
+                    }
+                }
                 ui.label(&self.state.status_message);
                 // Cancel button visible only while a scan is running
                 if self.state.scan_in_progress && ui.small_button("Cancel")
@@ -1808,11 +1945,11 @@ impl eframe::App for LogSleuthApp {
                 }
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     let total = self.state.entries.len();
-                    let filtered = self.state.filtered_indices.len();
                     let cap = self.state.max_total_entries;
 
                     // Entry-cap warning — shown when loaded entries are near or at the limit.
-                    if total > 0 && cap > 0 {
+                    // Entries beyond the cap of a session in the entry store are kept on disk.
+                    if total > 0 && cap > 0 && self.state.entry_store.is_none() {
                         let pct = (total as f64 / cap as f64 * 100.0).min(100.0);
                         if total >= cap {
                             // Hard cap reached — bright amber with exclamation.
@@ -1848,6 +1985,8 @@ impl eframe::App for LogSleuthApp {
                         }
                     }
 
+                    let total = self.state.session_entry_count();
+                    let filtered = self.state.filtered_count();
                     if total > 0 {
                         ui.label(format!("{filtered}/{total} entries"))
                             .on_hover_text(format!(
@@ -1939,7 +2078,9 @@ impl eframe::App for LogSleuthApp {
             .resizable(true)
             .default_height(ui::theme::DETAIL_PANE_HEIGHT)
             .show(ctx, |ui| {
-                ui::panels::detail::render(ui, &self.state);
+                if let Some(key) = ui::panels::detail::render(ui, &self.state) {
+                    self.state.set_correlation_key(Some(key));
+                }
             });
 
         // Left sidebar — tab-based, resizable.
@@ -2007,6 +2148,11 @@ impl eframe::App for LogSleuthApp {
         // Summary dialogs (modal-ish)
         ui::panels::summary::render(ctx, &mut self.state);
         ui::panels::log_summary::render(ctx, &mut self.state);
+        ui::panels::patterns::render(ctx, &mut self.state);
+        ui::panels::anomalies::render(ctx, &mut self.state);
+        ui::panels::alerts::render(ctx, &mut self.state);
+        ui::panels::compare::render(ctx, &mut self.state);
+        ui::panels::transaction::render(ctx, &mut self.state);
         ui::panels::about::render(ctx, &mut self.state);
         ui::panels::options::render(ctx, &mut self.state);
 
@@ -2027,21 +2173,6 @@ impl eframe::App for LogSleuthApp {
     }
 }
 
-fn is_network_path(path: &std::path::Path) -> bool {
-    #[cfg(windows)]
-    {
-        use std::path::{Component, Prefix};
-        if let Some(Component::Prefix(p)) = path.components().next() {
-            return matches!(
-                p.kind(),
-                Prefix::UNC(_, _) | Prefix::VerbatimUNC(_, _) | Prefix::DeviceNS(_)
-            );
-        }
-    }
-    let _ = path;
-    false
-}
-
 fn merge_additional_discovered_files(
     discovered_files: &mut Vec<crate::core::model::DiscoveredFile>,
     files: Vec<crate::core::model::DiscoveredFile>,
@@ -2176,7 +2307,7 @@ mod tests {
         assert_eq!(discovered_files.len(), 2);
         let b_file = discovered_files
             .iter()
-            .find(|f| f.path == std::path::PathBuf::from("b.log"))
+            .find(|f| f.path == std::path::Path::new("b.log"))
             .expect("b.log should exist after merge");
         assert_eq!(b_file.profile_id.as_deref(), Some("json-lines"));
         assert!(!b_file.parsing_skipped);
@@ -4,10 +4,12 @@
 // and potential future programmatic use.
 //
 // The GUI-specific `gui` module lives in `main.rs` and is not part of the
-// library surface.
+// library surface.  `ui` and `app::state` need egui and are only built with
+// the default `gui` feature.
 
 pub mod app;
 pub mod core;
 pub mod platform;
+#[cfg(feature = "gui")]
 pub mod ui;
 pub mod util;
@@ -4,10 +4,19 @@
 // 1. CLI argument parsing
 // 2. Logging initialisation (debug mode support)
 // 3. Format profile loading (built-in + user-defined)
-// 4. eframe GUI launch
+// 4. eframe GUI launch (default `gui` feature), or the headless `scan` mode
+//
+// Release GUI builds use the Windows GUI subsystem (no console window when
+// started from Explorer) and attach to the parent console at startup so
+// `logsleuth scan` output still reaches cmd.exe / PowerShell.  Builds with
+// `--no-default-features` are plain console programs without the GUI.
 
-#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+#![cfg_attr(
+    all(not(debug_assertions), feature = "gui"),
+    windows_subsystem = "windows"
+)]
 
+#[cfg(feature = "gui")]
 mod gui;
 
 // Re-export modules from the library crate so that `gui.rs` and other
@@ -16,18 +25,21 @@ pub use logsleuth::app;
 
 pub use logsleuth::core;
 pub use logsleuth::platform;
+#[cfg(feature = "gui")]
 pub use logsleuth::ui;
 pub use logsleuth::util;
 
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 use std::path::PathBuf;
 
 /// Compile-time-embedded icon PNG bytes (512x512 RGBA).
 ///
 /// Using `include_bytes!` ensures the asset is baked into the binary so the
 /// icon is always available regardless of the working directory at runtime.
+#[cfg(feature = "gui")]
 static ICON_PNG: &[u8] = include_bytes!("../assets/icon.png");
 
+#[cfg(feature = "gui")]
 /// Decode the embedded PNG and return an `eframe`-compatible `IconData`.
 ///
 /// Falls back to a transparent 1x1 placeholder if decoding fails so the
@@ -61,6 +73,7 @@ fn load_icon() -> egui::IconData {
     }
 }
 
+#[cfg(feature = "gui")]
 /// 1x1 transparent RGBA icon used when the real icon cannot be loaded.
 fn placeholder_icon() -> egui::IconData {
     egui::IconData {
@@ -70,6 +83,7 @@ fn placeholder_icon() -> egui::IconData {
     }
 }
 
+#[cfg(feature = "gui")]
 /// Pre-load system font definitions **before** `eframe::run_native` is called.
 ///
 /// Doing all file I/O here (rather than inside the creator closure) satisfies
@@ -176,12 +190,20 @@ fn build_font_definitions() -> egui::FontDefinitions {
 /// from multiple products in a unified, filterable timeline.
 #[derive(Parser, Debug)]
 #[command(name = "LogSleuth", version, about)]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
 struct Cli {
-    /// Directory to scan (opens file dialog if omitted).
-    path: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Directory and/or log files to open at startup.
+    paths: Vec<PathBuf>,
+
+    /// Start Live Tail as soon as the initial scan completes.
+    #[arg(short = 't', long = "tail")]
+    tail: bool,
 
     /// Additional directory containing user-defined format profiles.
-    #[arg(short = 'p', long = "profile-dir")]
+    #[arg(short = 'p', long = "profile-dir", global = true)]
     profile_dir: Option<PathBuf>,
 
     /// Initial severity filter level.
@@ -189,11 +211,51 @@ struct Cli {
     filter_level: Option<String>,
 
     /// Enable debug logging (equivalent to RUST_LOG=debug).
-    #[arg(short = 'd', long = "debug")]
+    #[arg(short = 'd', long = "debug", global = true)]
     debug: bool,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Scan, filter and export without opening the GUI.
+    ///
+    /// Matching entries are written to the --export file, or to stdout (one
+    /// line per entry) when no export is given.
+    Scan(ScanArgs),
+}
+
+#[derive(Args, Debug)]
+struct ScanArgs {
+    /// Directory (or single log file / archive) to scan.
+    path: PathBuf,
+
+    /// Minimum severity: critical, error, warning, info, debug.
+    #[arg(short = 's', long = "severity")]
+    severity: Option<String>,
+
+    /// Only entries since a relative window (30s, 15m, 2h, 7d) or an
+    /// absolute local time (YYYY-MM-DD[ HH:MM[:SS]]).
+    #[arg(long = "since")]
+    since: Option<String>,
+
+    /// Case-insensitive regex applied to the message text.
+    #[arg(short = 'r', long = "regex")]
+    regex: Option<String>,
+
+    /// Export matches to a .csv or .json file instead of stdout.
+    #[arg(short = 'o', long = "export")]
+    export: Option<PathBuf>,
+
+    /// Maximum entries to load (10000-1000000, default 1000000).  Reaching
+    /// it makes the output incomplete and the exit code 3.
+    #[arg(long = "max-entries")]
+    max_entries: Option<usize>,
+}
+
 fn main() {
+    #[cfg(all(windows, feature = "gui", not(debug_assertions)))]
+    attach_parent_console();
+
     let cli = Cli::parse();
 
     // Resolve platform paths first so config.toml can be loaded before
@@ -206,9 +268,14 @@ fn main() {
     let (app_config, config_warnings) = platform::config::load_config(&platform_paths.config_dir);
 
     // Initialise logging subsystem with priority: RUST_LOG > CLI > config > default.
+    // Headless scans default to warnings only so stderr stays readable in scripts.
+    let headless = matches!(cli.command, Some(Command::Scan(_)));
     util::logging::init(
         cli.debug,
-        app_config.log_level.as_deref(),
+        app_config
+            .log_level
+            .as_deref()
+            .or(headless.then_some("warn")),
         app_config.log_file.as_deref(),
     );
 
@@ -238,6 +305,51 @@ fn main() {
         }
     }
 
+    if let Some(Command::Scan(args)) = cli.command {
+        std::process::exit(run_headless_scan(args, profiles, &app_config));
+    }
+
+    #[cfg(feature = "gui")]
+    run_gui(
+        &cli,
+        profiles,
+        &app_config,
+        &platform_paths,
+        user_profile_dir,
+    );
+
+    #[cfg(not(feature = "gui"))]
+    {
+        eprintln!("Error: this build of LogSleuth has no GUI; use `logsleuth scan <PATH>`.");
+        std::process::exit(2);
+    }
+}
+
+/// Attach to the console of the process that started LogSleuth, if any.
+///
+/// Without this a GUI-subsystem binary has no stdout / stderr, so `scan`,
+/// `--help` and `--version` would print nothing.  Fails harmlessly when
+/// started from Explorer; handles redirected to files are left alone.
+#[cfg(all(windows, feature = "gui", not(debug_assertions)))]
+fn attach_parent_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    // SAFETY: AttachConsole takes no pointers; a zero return only means
+    // there is no parent console.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Build the application state from config, session and CLI options, then
+/// run the eframe window until it closes.
+#[cfg(feature = "gui")]
+fn run_gui(
+    cli: &Cli,
+    profiles: Vec<core::model::FormatProfile>,
+    app_config: &platform::config::AppConfig,
+    platform_paths: &platform::config::PlatformPaths,
+    user_profile_dir: &std::path::Path,
+) {
     tracing::info!(profiles = profiles.len(), "Ready to launch GUI");
 
     // Create application state
@@ -250,6 +362,8 @@ fn main() {
     state.correlation_window_secs = app_config.correlation_window_secs;
     state.correlation_window_input = app_config.correlation_window_secs.to_string();
     state.ui_font_size = app_config.font_size;
+    // Handed to the scan manager once the app is created.
+    let chunk_size = app_config.chunk_size;
 
     // Expose the external profiles directory to the UI so Options can show it
     // and trigger reloads without a restart.
@@ -265,10 +379,34 @@ fn main() {
         );
     }
 
+    // Load saved filter presets (stored next to config.toml).
+    state.presets_path = Some(app::presets::presets_path(&platform_paths.config_dir));
+    state.load_filter_presets();
+
+    // Load live alert rules and locate the alert log (both next to config.toml).
+    state.alert_rules_path = Some(app::alerts::rules_path(&platform_paths.config_dir));
+    state.alert_log_path = Some(app::alerts::log_path(&platform_paths.config_dir));
+    state.load_alert_rules();
+
     // Set the persistent session file path so save/restore can locate it.
     let session_file = app::session::session_path(&platform_paths.data_dir);
     state.session_path = Some(session_file.clone());
 
+    // On-disk entry store for sessions beyond the entry cap.  Segments left
+    // behind by a crash are removed once they are old enough not to belong
+    // to another running instance.
+    let spill_dir = platform_paths
+        .data_dir
+        .join(util::constants::ENTRY_STORE_DIR_NAME);
+    let stale = core::entry_store::remove_stale_segments(
+        &spill_dir,
+        std::time::Duration::from_secs(util::constants::STALE_ENTRY_STORE_SECS),
+    );
+    if stale > 0 {
+        tracing::info!(removed = stale, "Removed stale entry store segments");
+    }
+    state.spill_dir = Some(spill_dir);
+
     // Try restoring the previous session.  All errors are silently ignored:
     // a missing or corrupt file simply starts the app in a clean state.
     if let Some(session) = app::session::load(&session_file) {
@@ -295,30 +433,19 @@ fn main() {
         }
     }
 
-    // A path supplied on the CLI always overrides the session scan path.
-    if let Some(ref path) = cli.path {
-        state.scan_path = Some(path.clone());
-        state.initial_scan = Some(path.clone());
+    // Paths supplied on the CLI always override the session scan path.
+    if !cli.paths.is_empty() {
+        state.open_startup_paths(&cli.paths, cli.tail);
+    } else if cli.tail {
+        tracing::warn!("--tail ignored: no paths given on the command line");
     }
 
     // A severity level supplied on the CLI overrides the session-restored filter.
     // `--filter-level warning` shows Warning + Error + Critical (i.e. the given
     // level and all more-severe levels).  Matching is case-insensitive.
     if let Some(ref level_str) = cli.filter_level {
-        use crate::core::model::Severity;
-        let level_lower = level_str.to_lowercase();
-        let matched = Severity::all()
-            .iter()
-            .copied()
-            .find(|s| s.label().to_lowercase() == level_lower);
-        if let Some(sev) = matched {
-            // Collect all variants that are at least as severe as the requested level.
-            // Severity derives Ord; smaller discriminant == more severe (Critical < Error < …).
-            state.filter_state.severity_levels = Severity::all()
-                .iter()
-                .copied()
-                .filter(|s| *s <= sev)
-                .collect();
+        if let Some(sev) = core::model::Severity::from_label(level_str) {
+            state.filter_state.set_min_severity(sev);
             tracing::info!(level = %level_str, "Applied CLI --filter-level");
         } else {
             tracing::warn!(
@@ -427,7 +554,10 @@ fn main() {
         Box::new(move |cc| {
             // Apply pre-loaded font definitions (file I/O completed before run_native).
             cc.egui_ctx.set_fonts(font_defs);
-            Ok(Box::new(gui::LogSleuthApp::new(state)))
+            let mut app = gui::LogSleuthApp::new(state);
+            app.scan_manager.set_chunk_size(chunk_size);
+            app.scan_manager.set_spill_dir(app.state.spill_dir.clone());
+            Ok(Box::new(app))
         }),
     );
 
@@ -437,3 +567,117 @@ fn main() {
         std::process::exit(1);
     }
 }
+
+/// Run `logsleuth scan` and return the process exit code.
+///
+/// Exit codes: 0 = success (even with zero matches), 1 = scan or export
+/// failure, 2 = invalid option value, 3 = the entry cap was reached and the
+/// output is incomplete.  Scan warnings are printed to stderr.
+fn run_headless_scan(
+    args: ScanArgs,
+    profiles: Vec<core::model::FormatProfile>,
+    app_config: &platform::config::AppConfig,
+) -> i32 {
+    use app::headless::{self, ExportFormat, HeadlessOptions};
+
+    let min_severity = match args.severity.as_deref() {
+        None => None,
+        Some(s) => match core::model::Severity::from_label(s) {
+            Some(sev) => Some(sev),
+            None => {
+                eprintln!(
+                    "Error: unknown --severity '{s}'. \
+                     Valid values: critical, error, warning, info, debug"
+                );
+                return 2;
+            }
+        },
+    };
+    let since = match args.since.as_deref() {
+        None => None,
+        Some(s) => match headless::parse_since(s, chrono::Utc::now()) {
+            Some(t) => Some(t),
+            None => {
+                eprintln!(
+                    "Error: cannot parse --since '{s}'. \
+                     Use e.g. 30s, 15m, 2h, 7d or 'YYYY-MM-DD HH:MM'."
+                );
+                return 2;
+            }
+        },
+    };
+    let export = match args.export {
+        None => None,
+        Some(path) => match ExportFormat::from_path(&path) {
+            Some(format) => Some((path, format)),
+            None => {
+                eprintln!(
+                    "Error: --export '{}' must end in .csv or .json",
+                    path.display()
+                );
+                return 2;
+            }
+        },
+    };
+
+    let max_entries = match args.max_entries {
+        None => util::constants::MAX_TOTAL_ENTRIES,
+        Some(n)
+            if (util::constants::MIN_MAX_TOTAL_ENTRIES
+                ..=util::constants::ABSOLUTE_MAX_TOTAL_ENTRIES)
+                .contains(&n) =>
+        {
+            n
+        }
+        Some(n) => {
+            eprintln!(
+                "Error: --max-entries {n} is out of range ({}-{})",
+                util::constants::MIN_MAX_TOTAL_ENTRIES,
+                util::constants::ABSOLUTE_MAX_TOTAL_ENTRIES
+            );
+            return 2;
+        }
+    };
+
+    let opts = HeadlessOptions {
+        path: args.path,
+        min_severity,
+        since,
+        regex: args.regex,
+        export,
+        max_files: app_config.max_files,
+        max_depth: app_config.max_depth,
+        chunk_size: app_config.chunk_size,
+        max_entries,
+    };
+
+    match headless::run(&opts, profiles) {
+        Ok(report) => {
+            for warning in &report.warnings {
+                eprintln!("Warning: {warning}");
+            }
+            let target = opts
+                .export
+                .as_ref()
+                .map(|(p, _)| format!(" -> {}", p.display()))
+                .unwrap_or_default();
+            eprintln!(
+                "{} file(s), {} entries, {} matched{target}",
+                report.files_scanned, report.total_entries, report.matched_entries
+            );
+            if report.truncated {
+                eprintln!(
+                    "Error: output is incomplete: the limit of {max_entries} entries was \
+                     reached. Narrow the scan with --since or raise --max-entries."
+                );
+                return 3;
+            }
+            0
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Headless scan failed");
+            eprintln!("Error: {e}");
+            1
+        }
+    }
+}
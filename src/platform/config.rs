@@ -178,6 +178,10 @@ pub struct AppConfig {
     /// Body font size in points.
     pub font_size: f32,
 
+    // -- Parsing --
+    /// Read chunk size in bytes for streaming file reads.
+    pub chunk_size: usize,
+
     // -- Logging --
     /// Logging level string (for init before tracing is available).
     pub log_level: Option<String>,
@@ -193,6 +197,7 @@ impl Default for AppConfig {
             dark_mode: true,
             correlation_window_secs: constants::DEFAULT_CORRELATION_WINDOW_SECS,
             font_size: constants::DEFAULT_FONT_SIZE,
+            chunk_size: constants::DEFAULT_CHUNK_SIZE,
             log_level: None,
             log_file: None,
         }
@@ -278,6 +283,20 @@ pub fn load_config(config_dir: &Path) -> (AppConfig, Vec<String>) {
         }
     }
 
+    // -- Parsing: chunk_size_bytes --
+    if let Some(size) = raw.parsing.chunk_size_bytes {
+        if (constants::MIN_CHUNK_SIZE..=constants::MAX_CHUNK_SIZE).contains(&size) {
+            config.chunk_size = size;
+        } else {
+            warnings.push(format!(
+                "[parsing] chunk_size_bytes = {size} is out of range ({}-{}). Using default ({}).",
+                constants::MIN_CHUNK_SIZE,
+                constants::MAX_CHUNK_SIZE,
+                constants::DEFAULT_CHUNK_SIZE,
+            ));
+        }
+    }
+
     // -- UI: theme --
     if let Some(ref theme) = raw.ui.theme {
         match theme.to_lowercase().as_str() {
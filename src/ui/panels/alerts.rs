@@ -0,0 +1,255 @@
+// LogSleuth - ui/panels/alerts.rs
+//
+// Alert Rules window: the live alert rules (`core::alert`) with an enable
+// checkbox, Edit and Delete per rule, and an editor for a new or edited
+// rule.  Rules are evaluated against each Live Tail batch; a rule that fires
+// pins its entry, flashes the status bar, appends to the alert log and runs
+// its command (`AppState::evaluate_alerts`).
+//
+// Opened via View -> Alert Rules or by clicking the status-bar ALERT badge.
+// Every change is saved to the rules file immediately.
+//
+// Rule 16: the close button is always enabled.
+
+use crate::app::state::AppState;
+use crate::core::alert::format_command;
+use crate::util::constants::{MAX_ALERT_RULE_NAME_LEN, MAX_ALERT_THRESHOLD, MAX_ALERT_WINDOW_SECS};
+
+/// Render the Alert Rules window (if `state.show_alert_rules` is true).
+pub fn render(ctx: &egui::Context, state: &mut AppState) {
+    if !state.show_alert_rules {
+        return;
+    }
+
+    // Same open / close_clicked split as the Log Summary window.
+    let mut open = state.show_alert_rules;
+    let mut close_clicked = false;
+    let mut toggled: Option<(usize, bool)> = None;
+    let mut edit: Option<Option<usize>> = None;
+    let mut delete: Option<usize> = None;
+    let mut save = false;
+
+    egui::Window::new("Alert Rules")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .min_width(520.0)
+        .min_height(200.0)
+        .default_width(720.0)
+        .default_pos([
+            ctx.screen_rect().width() * 0.5 - 360.0,
+            48.0, // sit just below the menu bar, never off the top edge
+        ])
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(
+                    "Rules are checked against each Live Tail batch. A rule fires when its query matches the threshold number of entries within the window; the entry is pinned as a bookmark, the status bar flashes and the command (if any) runs with the entry as JSON on stdin.",
+                )
+                .weak()
+                .small(),
+            );
+            ui.add_space(4.0);
+            ui.separator();
+
+            // ----------------------------------------------------------------
+            // Rule list: enabled | name | query | threshold | command | actions
+            // ----------------------------------------------------------------
+            if state.alert_rules.is_empty() {
+                ui.label(egui::RichText::new("No alert rules yet.").weak().italics());
+            } else {
+                egui::ScrollArea::vertical()
+                    .id_salt("alert_rules_scroll")
+                    .max_height(220.0)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        egui::Grid::new("alert_rules_grid")
+                            .num_columns(6)
+                            .spacing([12.0, 3.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong("On");
+                                ui.strong("Name");
+                                ui.strong("Query");
+                                ui.strong("Threshold");
+                                ui.strong("Command");
+                                ui.label("");
+                                ui.end_row();
+
+                                for (i, rule) in state.alert_rules.iter().enumerate() {
+                                    let mut enabled = rule.enabled;
+                                    if ui
+                                        .checkbox(&mut enabled, "")
+                                        .on_hover_text("Evaluate this rule during Live Tail")
+                                        .changed()
+                                    {
+                                        toggled = Some((i, enabled));
+                                    }
+                                    let name = egui::RichText::new(&rule.name);
+                                    ui.label(if state.alert_draft_index == Some(i) {
+                                        name.strong()
+                                    } else {
+                                        name
+                                    });
+                                    ui.label(egui::RichText::new(&rule.query).monospace());
+                                    ui.label(rule.describe_threshold());
+                                    if rule.command.is_empty() {
+                                        ui.label(egui::RichText::new("--").weak());
+                                    } else {
+                                        ui.label(
+                                            egui::RichText::new(format_command(&rule.command))
+                                                .monospace()
+                                                .small(),
+                                        );
+                                    }
+                                    ui.horizontal(|ui| {
+                                        if ui
+                                            .small_button("Edit")
+                                            .on_hover_text("Load this rule into the editor below")
+                                            .clicked()
+                                        {
+                                            edit = Some(Some(i));
+                                        }
+                                        if ui
+                                            .small_button("Delete")
+                                            .on_hover_text("Delete this rule")
+                                            .clicked()
+                                        {
+                                            delete = Some(i);
+                                        }
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+
+            ui.add_space(6.0);
+            ui.separator();
+
+            // ----------------------------------------------------------------
+            // Editor: new rule, or the rule loaded with Edit
+            // ----------------------------------------------------------------
+            ui.strong(match state.alert_draft_index {
+                Some(_) => "Edit rule",
+                None => "New rule",
+            });
+            egui::Grid::new("alert_rule_editor")
+                .num_columns(2)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    ui.label("Name:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.alert_draft.name)
+                            .char_limit(MAX_ALERT_RULE_NAME_LEN)
+                            .hint_text("e.g. Database timeouts")
+                            .desired_width(f32::INFINITY),
+                    );
+                    ui.end_row();
+
+                    ui.label("Query:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.alert_draft.query)
+                            .hint_text("e.g. severity>=error AND timeout")
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY),
+                    )
+                    .on_hover_text("Same syntax as the filter query box");
+                    ui.end_row();
+
+                    ui.label("Threshold:");
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::Slider::new(
+                                &mut state.alert_draft.threshold,
+                                1..=MAX_ALERT_THRESHOLD,
+                            )
+                            .suffix(" matches")
+                            .logarithmic(true),
+                        );
+                        ui.label("within");
+                        ui.add(
+                            egui::Slider::new(
+                                &mut state.alert_draft.window_secs,
+                                1..=MAX_ALERT_WINDOW_SECS,
+                            )
+                            .suffix(" s")
+                            .logarithmic(true),
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label("Command:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.alert_draft_command)
+                            .hint_text("optional, e.g. notify-send LogSleuth")
+                            .font(egui::TextStyle::Monospace)
+                            .desired_width(f32::INFINITY),
+                    )
+                    .on_hover_text(
+                        "Program and arguments separated by spaces; quote an argument that contains spaces (\"DB alert\"). Run directly, not through a shell.",
+                    );
+                    ui.end_row();
+                });
+
+            if let Some(err) = &state.alert_draft_error {
+                ui.label(
+                    egui::RichText::new(format!("Cannot save: {err}"))
+                        .small()
+                        .color(egui::Color32::from_rgb(239, 68, 68)), // Red 500
+                );
+            }
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Save")
+                    .on_hover_text("Save the rule and apply it to the running tail")
+                    .clicked()
+                {
+                    save = true;
+                }
+                if ui
+                    .button("New")
+                    .on_hover_text("Clear the editor to start a new rule")
+                    .clicked()
+                {
+                    edit = Some(None);
+                }
+            });
+
+            ui.add_space(6.0);
+            ui.separator();
+            if let Some(path) = &state.alert_rules_path {
+                ui.label(
+                    egui::RichText::new(format!("Rules file: {}", path.display()))
+                        .weak()
+                        .small(),
+                );
+            }
+            if let Some(path) = &state.alert_log_path {
+                ui.label(
+                    egui::RichText::new(format!("Alert log: {}", path.display()))
+                        .weak()
+                        .small(),
+                );
+            }
+            if ui
+                .button("Close")
+                .on_hover_text("Close the Alert Rules window")
+                .clicked()
+            {
+                close_clicked = true;
+            }
+        });
+
+    if save {
+        state.save_alert_draft();
+    } else if let Some(index) = edit {
+        state.edit_alert_rule(index);
+    } else if let Some(index) = delete {
+        state.delete_alert_rule(index);
+    } else if let Some((index, enabled)) = toggled {
+        state.set_alert_rule_enabled(index, enabled);
+    }
+
+    // Write back: honour both the title-bar × and the body Close button.
+    state.show_alert_rules = open && !close_clicked;
+}
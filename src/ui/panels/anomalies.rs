@@ -0,0 +1,184 @@
+// LogSleuth - ui/panels/anomalies.rs
+//
+// Anomalies window: error / warning / volume spikes per file and component,
+// and files that went silent (`core::anomaly`).  Each finding has a "Jump"
+// button that pins the absolute time filter to the finding's window.
+//
+// Opened via View -> Anomalies.  Findings are computed when the window opens
+// and on Refresh, not on every frame.
+//
+// Rule 16: the close button is always enabled.
+
+use crate::app::state::AppState;
+use crate::core::anomaly::format_duration;
+use crate::ui::theme;
+
+/// Render the Anomalies window (if `state.show_anomalies` is true).
+pub fn render(ctx: &egui::Context, state: &mut AppState) {
+    if !state.show_anomalies {
+        return;
+    }
+    if state.anomalies.is_none() {
+        state.refresh_anomalies();
+    }
+
+    // Same open / close_clicked split as the Log Summary window.
+    let mut open = state.show_anomalies;
+    let mut close_clicked = false;
+    let mut refresh = false;
+    let mut jump: Option<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>)> = None;
+
+    egui::Window::new("Anomalies")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .min_width(560.0)
+        .min_height(200.0)
+        .default_width(820.0)
+        .default_height(420.0)
+        .default_pos([
+            ctx.screen_rect().width() * 0.5 - 410.0,
+            48.0, // sit just below the menu bar, never off the top edge
+        ])
+        .show(ctx, |ui| {
+            let Some(report) = state.anomalies.as_ref() else {
+                return;
+            };
+
+            // ----------------------------------------------------------------
+            // Header: counts, bucket width, staleness and Refresh
+            // ----------------------------------------------------------------
+            ui.horizontal(|ui| {
+                ui.strong(format!(
+                    "{} findings from {} entries",
+                    report.findings.len(),
+                    report.entries_analysed
+                ));
+                if report.bucket_secs > 0 {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "({} buckets)",
+                            format_duration(report.bucket_secs)
+                        ))
+                        .weak()
+                        .small(),
+                    )
+                    .on_hover_text("Spikes are measured per time bucket of this width");
+                }
+                if ui
+                    .small_button("\u{21bb} Refresh")
+                    .on_hover_text("Analyse all loaded entries again")
+                    .clicked()
+                {
+                    refresh = true;
+                }
+            });
+            if report.entries_analysed != state.entries.len() {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Entries have changed since this analysis ({} now loaded) \u{2014} Refresh to update.",
+                        state.entries.len()
+                    ))
+                    .small()
+                    .color(egui::Color32::from_rgb(251, 191, 36)),
+                );
+            }
+            ui.label(
+                egui::RichText::new(
+                    "Spikes compare each file and component with its own median rate; a silence is a quiet period far longer than the file's usual gap. Times are UTC.",
+                )
+                .weak()
+                .small(),
+            );
+
+            ui.add_space(4.0);
+            ui.separator();
+
+            // ----------------------------------------------------------------
+            // Findings table: kind | scope | window | description | jump
+            // ----------------------------------------------------------------
+            let reserved_bottom: f32 = 44.0;
+            let scroll_max_height = (ui.available_height() - reserved_bottom).max(120.0);
+            egui::ScrollArea::vertical()
+                .id_salt("anomalies_scroll")
+                .max_height(scroll_max_height)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    if report.findings.is_empty() {
+                        ui.label("No spikes or silences found.");
+                        return;
+                    }
+                    egui::Grid::new("anomalies_grid")
+                        .num_columns(5)
+                        .spacing([12.0, 3.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Finding");
+                            ui.strong("Where");
+                            ui.strong("Window");
+                            ui.strong("Detail");
+                            ui.label("");
+                            ui.end_row();
+
+                            for finding in &report.findings {
+                                let colour =
+                                    theme::severity_colour(&finding.kind.severity(), state.dark_mode);
+                                ui.colored_label(colour, finding.kind.label());
+
+                                let full = match &finding.scope {
+                                    crate::core::anomaly::AnomalyScope::File(p) => {
+                                        p.display().to_string()
+                                    }
+                                    crate::core::anomaly::AnomalyScope::Component(c) => c.clone(),
+                                };
+                                ui.label(finding.scope.label()).on_hover_text(full);
+
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{} \u{2013} {}",
+                                        finding.start.format("%Y-%m-%d %H:%M:%S"),
+                                        finding.end.format("%H:%M:%S")
+                                    ))
+                                    .small()
+                                    .monospace(),
+                                );
+
+                                ui.label(
+                                    egui::RichText::new(finding.describe(report.bucket_secs))
+                                        .small(),
+                                );
+
+                                if ui
+                                    .small_button("Jump")
+                                    .on_hover_text(
+                                        "Set the absolute time filter to this window",
+                                    )
+                                    .clicked()
+                                {
+                                    jump = Some((finding.start, finding.end));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+            ui.add_space(6.0);
+            ui.separator();
+            if ui
+                .button("Close")
+                .on_hover_text("Close the Anomalies window")
+                .clicked()
+            {
+                close_clicked = true;
+            }
+        });
+
+    if refresh {
+        state.refresh_anomalies();
+    } else if let Some((start, end)) = jump {
+        state.jump_to_window(start, end);
+    }
+
+    // Write back: honour both the title-bar × and the body Close button.
+    state.show_anomalies = open && !close_clicked;
+}
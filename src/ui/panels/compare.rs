@@ -0,0 +1,279 @@
+// LogSleuth - ui/panels/compare.rs
+//
+// Compare window: diff two entry sets -- two files, two scan roots or two
+// time ranges -- by normalised message (`core::compare`).  Lists messages
+// only in A, only in B, and messages whose frequency changed a lot.
+// Clicking a row filters the timeline to that message's occurrences and
+// selects the first one.
+//
+// Opened via View -> Compare.
+//
+// Rule 16: the close button is always enabled.
+// Rule 11: no unbounded allocation — at most MAX_COMPARE_ROWS rows per section.
+
+use crate::app::state::{AppState, CompareMode, CompareSideInput};
+use crate::core::compare::CompareKind;
+use crate::ui::theme;
+use std::path::PathBuf;
+
+/// Maximum number of rows shown per result section.
+const MAX_COMPARE_ROWS: usize = 200;
+
+/// Render the Compare window (if `state.show_compare` is true).
+pub fn render(ctx: &egui::Context, state: &mut AppState) {
+    if !state.show_compare {
+        return;
+    }
+
+    // Choices for the File and Directory modes: every file with loaded
+    // entries, and their parent directories.
+    let mut files: Vec<PathBuf> = state.file_colours.keys().cloned().collect();
+    files.sort();
+    let mut dirs: Vec<PathBuf> = files
+        .iter()
+        .filter_map(|f| f.parent().map(std::path::Path::to_path_buf))
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+
+    // Same open / close_clicked split as the Log Summary window.
+    let mut open = state.show_compare;
+    let mut close_clicked = false;
+    let mut run = false;
+    let mut clicked: Option<usize> = None;
+
+    egui::Window::new("Compare")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .min_width(560.0)
+        .min_height(240.0)
+        .default_width(820.0)
+        .default_height(520.0)
+        .default_pos([
+            ctx.screen_rect().width() * 0.5 - 410.0,
+            48.0, // sit just below the menu bar, never off the top edge
+        ])
+        .show(ctx, |ui| {
+            // ----------------------------------------------------------------
+            // Side selection
+            // ----------------------------------------------------------------
+            egui::Grid::new("compare_sides")
+                .num_columns(3)
+                .spacing([8.0, 4.0])
+                .show(ui, |ui| {
+                    ui.strong("A").on_hover_text("The baseline, e.g. yesterday's successful run");
+                    side_editor(ui, "compare_a", &mut state.compare_a, &files, &dirs);
+                    ui.end_row();
+                    ui.strong("B").on_hover_text("The run to check, e.g. today's failing run");
+                    side_editor(ui, "compare_b", &mut state.compare_b, &files, &dirs);
+                    ui.end_row();
+                });
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Compare")
+                    .on_hover_text("Diff the two entry sets by normalised message")
+                    .clicked()
+                {
+                    run = true;
+                }
+                if let Some(ref err) = state.compare_error {
+                    ui.colored_label(egui::Color32::from_rgb(248, 113, 113), err);
+                }
+            });
+
+            ui.add_space(4.0);
+            ui.separator();
+
+            // ----------------------------------------------------------------
+            // Results
+            // ----------------------------------------------------------------
+            let Some(report) = state.compare.as_ref() else {
+                ui.label(
+                    egui::RichText::new(
+                        "Choose two files, directories or time ranges and click Compare. Messages are matched after numbers, IPs, GUIDs and hex values are normalised.",
+                    )
+                    .weak()
+                    .small(),
+                );
+                return;
+            };
+            ui.label(
+                egui::RichText::new(format!(
+                    "A: {} ({} entries)    B: {} ({} entries)",
+                    report.label_a, report.total_a, report.label_b, report.total_b
+                ))
+                .small(),
+            );
+
+            let reserved_bottom: f32 = 44.0;
+            let scroll_max_height = (ui.available_height() - reserved_bottom).max(120.0);
+            egui::ScrollArea::vertical()
+                .id_salt("compare_scroll")
+                .max_height(scroll_max_height)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    for (kind, title) in [
+                        (CompareKind::OnlyB, "Only in B"),
+                        (CompareKind::OnlyA, "Only in A"),
+                        (CompareKind::Changed, "Frequency changed"),
+                    ] {
+                        let rows: Vec<(usize, &crate::core::compare::CompareRow)> = report
+                            .rows
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, r)| r.kind == kind)
+                            .collect();
+                        egui::CollapsingHeader::new(format!("{title} ({})", rows.len()))
+                            .id_salt(("compare_section", title))
+                            .default_open(kind != CompareKind::OnlyA)
+                            .show(ui, |ui| {
+                                egui::Grid::new(("compare_grid", title))
+                                    .num_columns(3)
+                                    .spacing([12.0, 2.0])
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        for &(i, row) in rows.iter().take(MAX_COMPARE_ROWS) {
+                                            ui.label(
+                                                egui::RichText::new(format!(
+                                                    "A {}",
+                                                    row.ids_a.len()
+                                                ))
+                                                .small()
+                                                .monospace(),
+                                            );
+                                            ui.label(
+                                                egui::RichText::new(format!(
+                                                    "B {}",
+                                                    row.ids_b.len()
+                                                ))
+                                                .small()
+                                                .monospace(),
+                                            );
+                                            let colour =
+                                                theme::severity_colour(&row.severity, state.dark_mode);
+                                            let text = if row.template.is_empty() {
+                                                egui::RichText::new("(empty message)").italics()
+                                            } else {
+                                                egui::RichText::new(&row.template).monospace()
+                                            };
+                                            if ui
+                                                .add(
+                                                    egui::Button::new(text.color(colour))
+                                                        .frame(false)
+                                                        .truncate(),
+                                                )
+                                                .on_hover_text(
+                                                    "Show these entries in the timeline",
+                                                )
+                                                .clicked()
+                                            {
+                                                clicked = Some(i);
+                                            }
+                                            ui.end_row();
+                                        }
+                                    });
+                                if rows.len() > MAX_COMPARE_ROWS {
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "... and {} more",
+                                            rows.len() - MAX_COMPARE_ROWS
+                                        ))
+                                        .weak()
+                                        .small()
+                                        .italics(),
+                                    );
+                                }
+                            });
+                    }
+                });
+
+            ui.add_space(6.0);
+            ui.separator();
+            if ui
+                .button("Close")
+                .on_hover_text("Close the Compare window")
+                .clicked()
+            {
+                close_clicked = true;
+            }
+        });
+
+    if run {
+        state.run_compare();
+    } else if let Some(i) = clicked {
+        state.show_compare_row(i);
+    }
+
+    // Write back: honour both the title-bar × and the body Close button.
+    state.show_compare = open && !close_clicked;
+}
+
+/// Mode selector plus the matching input for one side.
+fn side_editor(
+    ui: &mut egui::Ui,
+    id: &str,
+    input: &mut CompareSideInput,
+    files: &[PathBuf],
+    dirs: &[PathBuf],
+) {
+    egui::ComboBox::from_id_salt((id, "mode"))
+        .selected_text(input.mode.label())
+        .show_ui(ui, |ui| {
+            for mode in CompareMode::all() {
+                ui.selectable_value(&mut input.mode, *mode, mode.label());
+            }
+        });
+
+    ui.horizontal(|ui| match input.mode {
+        CompareMode::File => {
+            let selected = input
+                .file
+                .as_deref()
+                .map(|p| crate::core::archive::display_name(p).into_owned())
+                .unwrap_or_else(|| "(choose a file)".to_string());
+            egui::ComboBox::from_id_salt((id, "file"))
+                .selected_text(selected)
+                .width(360.0)
+                .show_ui(ui, |ui| {
+                    for file in files {
+                        let name = crate::core::archive::display_name(file);
+                        ui.selectable_value(&mut input.file, Some(file.clone()), name)
+                            .on_hover_text(file.display().to_string());
+                    }
+                });
+        }
+        CompareMode::Directory => {
+            ui.add(
+                egui::TextEdit::singleline(&mut input.directory)
+                    .desired_width(340.0)
+                    .hint_text("scan root or folder"),
+            );
+            ui.menu_button("\u{25be}", |ui| {
+                for dir in dirs {
+                    if ui.button(dir.display().to_string()).clicked() {
+                        input.directory = dir.display().to_string();
+                        ui.close_menu();
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Pick a folder containing loaded files");
+        }
+        CompareMode::TimeRange => {
+            ui.label(egui::RichText::new("From:").small());
+            ui.add(
+                egui::TextEdit::singleline(&mut input.from)
+                    .desired_width(150.0)
+                    .hint_text("YYYY-MM-DD HH:MM"),
+            );
+            ui.label(egui::RichText::new("To:").small());
+            ui.add(
+                egui::TextEdit::singleline(&mut input.to)
+                    .desired_width(150.0)
+                    .hint_text("YYYY-MM-DD HH:MM"),
+            )
+            .on_hover_text("Local time, like the Filters tab From / To inputs");
+        }
+    });
+}
@@ -2,12 +2,20 @@
 //
 // Entry detail pane showing full message, metadata, and raw text.
 // Severity label is coloured to match the timeline.
+// Correlation keys (GUIDs, trace / request IDs, thread) found in the entry
+// are offered as buttons that correlate the timeline by that key.
 
 use crate::app::state::AppState;
+use crate::core::correlation::CorrelationKey;
 use crate::ui::theme;
 
 /// Render the detail pane (bottom panel).
-pub fn render(ui: &mut egui::Ui, state: &AppState) {
+///
+/// Returns the correlation key the user clicked, if any; the caller applies
+/// it with `AppState::set_correlation_key` (the pane only borrows state).
+pub fn render(ui: &mut egui::Ui, state: &AppState) -> Option<CorrelationKey> {
+    let mut picked_key: Option<CorrelationKey> = None;
+
     // Multi-select banner: when multiple entries are selected, show a summary
     // bar with a "Copy Selected" button instead of / above the detail view.
     let multi_count = state.selected_indices.len();
@@ -39,7 +47,7 @@ pub fn render(ui: &mut egui::Ui, state: &AppState) {
                 .color(egui::Color32::from_rgb(107, 114, 128)),
             );
         });
-        return;
+        return None;
     };
 
     // Resolve the global entry index for dedup info lookup.
@@ -57,14 +65,7 @@ pub fn render(ui: &mut egui::Ui, state: &AppState) {
         );
         ui.separator();
         ui.label(
-            egui::RichText::new(
-                entry
-                    .source_file
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("?"),
-            )
-            .strong(),
+            egui::RichText::new(crate::core::archive::display_name(&entry.source_file)).strong(),
         );
         if let Some(ts) = entry.timestamp {
             ui.label(egui::RichText::new(ts.format("  %Y-%m-%d %H:%M:%S UTC").to_string()).weak());
@@ -90,26 +91,74 @@ pub fn render(ui: &mut egui::Ui, state: &AppState) {
 
             ui.label("Profile:")
                 .on_hover_text("The format profile used to parse this log entry");
-            ui.label(&entry.profile_id);
+            ui.label(&*entry.profile_id);
             ui.end_row();
 
             if let Some(ref thread) = entry.thread {
                 ui.label("Thread:")
                     .on_hover_text("Thread ID or name extracted from the log entry");
-                ui.label(egui::RichText::new(thread).monospace());
+                ui.label(egui::RichText::new(&**thread).monospace());
                 ui.end_row();
             }
 
             if let Some(ref component) = entry.component {
                 ui.label("Component:")
                     .on_hover_text("Source component or module that emitted this log entry");
-                ui.label(component);
+                ui.label(&**component);
+                ui.end_row();
+            }
+
+            let keys = crate::core::correlation::extract_keys(entry);
+            if !keys.is_empty() {
+                ui.label("Keys:").on_hover_text(
+                    "Identifiers found in this entry. Click one to highlight every entry \
+                     carrying it, in every file, and list them as a transaction",
+                );
+                ui.horizontal_wrapped(|ui| {
+                    for key in keys {
+                        let active = state.correlation_key.as_ref() == Some(&key);
+                        if ui
+                            .selectable_label(
+                                active,
+                                egui::RichText::new(&key.token).small().monospace(),
+                            )
+                            .on_hover_text(key.class.label())
+                            .clicked()
+                        {
+                            picked_key = Some(key);
+                        }
+                    }
+                });
                 ui.end_row();
             }
         });
 
     ui.add_space(4.0);
 
+    // -------------------------------------------------------------------------
+    // Structured fields section (only when the entry carries extra fields,
+    // e.g. unmapped keys of a JSON Lines record)
+    // -------------------------------------------------------------------------
+    if !entry.fields.is_empty() {
+        egui::CollapsingHeader::new(format!("Fields ({})", entry.fields.len()))
+            .id_salt("detail_fields")
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("detail_fields_grid")
+                    .num_columns(2)
+                    .spacing([8.0, 2.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (key, value) in &entry.fields {
+                            ui.label(egui::RichText::new(key).small().strong());
+                            ui.label(egui::RichText::new(value).small().monospace());
+                            ui.end_row();
+                        }
+                    });
+            });
+        ui.add_space(4.0);
+    }
+
     // -------------------------------------------------------------------------
     // Dedup occurrences section (only when the entry is part of a dedup group)
     // -------------------------------------------------------------------------
@@ -148,11 +197,7 @@ pub fn render(ui: &mut egui::Ui, state: &AppState) {
                                     .timestamp
                                     .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
                                     .unwrap_or_else(|| "--".to_string());
-                                let fname = occ
-                                    .source_file
-                                    .file_name()
-                                    .and_then(|n| n.to_str())
-                                    .unwrap_or("?");
+                                let fname = crate::core::archive::display_name(&occ.source_file);
                                 ui.label(egui::RichText::new(ts_str).small().monospace());
                                 ui.label(egui::RichText::new(fname).small());
                                 ui.label(egui::RichText::new(occ.line_number.to_string()).small());
@@ -173,9 +218,10 @@ pub fn render(ui: &mut egui::Ui, state: &AppState) {
             .on_hover_text("Copy the full message text to the clipboard")
             .clicked()
         {
-            ui.ctx().copy_text(entry.message.clone());
+            ui.ctx().copy_text(entry.message().to_string());
         }
         // Open the containing folder in Windows Explorer / macOS Finder / Linux file manager.
+        // For archive members this reveals the archive itself.
         if ui
             .small_button("\u{1f4c2} Show in folder")
             .on_hover_text(
@@ -183,7 +229,9 @@ pub fn render(ui: &mut egui::Ui, state: &AppState) {
             )
             .clicked()
         {
-            crate::platform::fs::reveal_in_file_manager(&entry.source_file);
+            crate::platform::fs::reveal_in_file_manager(&crate::core::archive::physical_path(
+                &entry.source_file,
+            ));
         }
     });
     // Use most of the available panel height so multi-line messages are readable.
@@ -195,12 +243,12 @@ pub fn render(ui: &mut egui::Ui, state: &AppState) {
             let highlight_ranges = state
                 .filter_state
                 .multi_search
-                .highlight_matches(&entry.message);
+                .highlight_matches(entry.message());
             if highlight_ranges.is_empty() {
-                ui.label(egui::RichText::new(&entry.message).monospace());
+                ui.label(egui::RichText::new(entry.message()).monospace());
             } else {
                 let job = build_highlighted_layout(
-                    &entry.message,
+                    entry.message(),
                     &highlight_ranges,
                     ui,
                     state.dark_mode,
@@ -208,6 +256,8 @@ pub fn render(ui: &mut egui::Ui, state: &AppState) {
                 ui.label(job);
             }
         });
+
+    picked_key
 }
 
 /// Build an `egui::text::LayoutJob` that renders `text` in monospace with
@@ -8,13 +8,17 @@
 //      collapses once a scan has been run to give the file list more space.
 //   2. Unified discovered-file list with inline source-file filter checkboxes.
 //      Replaces the duplicate file list that was previously shown in the
-//      filters panel.
+//      filters panel.  Each row has a clock button that opens the clock
+//      offset / timezone editor for that file or its directory.  A rotation
+//      family (`app.log`, `app.log.1`, `app.log.2.gz`) is one row whose
+//      checkbox covers every segment (`core::discovery::group_streams`).
 //
 // This panel writes `state.pending_scan`, `state.pending_append_scan`,
 // `state.request_cancel`, and `state.filter_state` flag fields; gui.rs
 // consumes them each frame.
 // No direct I/O or ScanManager access (Rule 1 boundary).
 
+use crate::app::fs_watch::is_network_path;
 use crate::app::state::AppState;
 use chrono::{DateTime, Datelike, Duration, Local, Utc};
 
@@ -68,34 +72,43 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         let total_all = state.discovered_files.len();
         // total reflects the (possibly activity-filtered) file count shown in the list.
 
-        // Pre-collect (path, name, size, profile_text, profile_colour, mtime_text, parsing_skipped) once
-        // so we can borrow state mutably for checkbox updates below.
-        let file_entries: Vec<(
-            std::path::PathBuf,
-            String, // display name
-            String, // size text
-            String, // profile text
-            egui::Color32,
-            String, // mtime text (formatted for display)
-            bool,   // parsing_skipped
-        )> = sorted_file_idxs
-            .iter()
-            .map(|&idx| {
-                let f = &state.discovered_files[idx];
-                let name = f
-                    .path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("?")
-                    .to_string();
-                let size = format_size(f.size);
-                let (profile_text, profile_colour) = match &f.profile_id {
-                    Some(id) if id == "plain-text" && f.detection_confidence == 0.0 => (
+        // One row per logical stream: a rotation family (`app.log`,
+        // `app.log.1`, `app.log.2.gz`) is listed once and its checkbox covers
+        // every segment.  Pre-collected so we can borrow state mutably for
+        // checkbox updates below.
+        let streams = crate::core::discovery::group_streams(
+            sorted_file_idxs
+                .iter()
+                .map(|&idx| state.discovered_files[idx].path.as_path()),
+        );
+        let file_entries: Vec<FileRow> = streams
+            .into_iter()
+            .map(|stream| {
+                let files: Vec<&crate::core::model::DiscoveredFile> = stream
+                    .members
+                    .iter()
+                    .map(|&m| &state.discovered_files[sorted_file_idxs[m]])
+                    .collect();
+                // The list is newest first, so the stream's member that comes
+                // first in it is the newest segment.
+                let first = stream.members.iter().copied().min().unwrap_or_default();
+                let newest = &state.discovered_files[sorted_file_idxs[first]];
+                let name = if files.len() > 1 {
+                    format!(
+                        "{} ({} files)",
+                        crate::core::archive::display_name(&stream.key),
+                        files.len()
+                    )
+                } else {
+                    crate::core::archive::display_name(&newest.path).into_owned()
+                };
+                let (profile_text, profile_colour) = match &newest.profile_id {
+                    Some(id) if id == "plain-text" && newest.detection_confidence == 0.0 => (
                         "plain-text (fallback)".to_string(),
                         egui::Color32::from_rgb(156, 163, 175),
                     ),
                     Some(id) => (
-                        format!("{id} ({:.0}%)", f.detection_confidence * 100.0),
+                        format!("{id} ({:.0}%)", newest.detection_confidence * 100.0),
                         egui::Color32::from_rgb(74, 222, 128),
                     ),
                     None => (
@@ -103,20 +116,21 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                         egui::Color32::from_rgb(156, 163, 175),
                     ),
                 };
-                let mtime_text = format_mtime(f.modified);
-                (
-                    f.path.clone(),
+                FileRow {
+                    path: newest.path.clone(),
+                    members: files.iter().map(|f| f.path.clone()).collect(),
                     name,
-                    size,
+                    size_text: format_size(files.iter().map(|f| f.size).sum()),
                     profile_text,
                     profile_colour,
-                    mtime_text,
-                    f.parsing_skipped,
-                )
+                    mtime_text: format_mtime(newest.modified),
+                    parsing_skipped: files.iter().any(|f| f.parsing_skipped),
+                }
             })
-            .collect::<Vec<_>>();
+            .collect();
 
-        let total = file_entries.len();
+        // Counts are in files; a stream row counts each of its segments.
+        let total: usize = file_entries.iter().map(|row| row.members.len()).sum();
         let active_count = state.filter_state.source_files.len();
 
         // Header row: count / active-filter indicator + All / global-reset.
@@ -169,7 +183,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                     .filter(|f| f.parsing_skipped)
                     .map(|f| f.path.clone())
                     .collect();
-                queue_parse_requests(state, to_parse);
+                state.queue_parse_requests(to_parse);
                 state.apply_filters();
             }
         });
@@ -229,6 +243,9 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         // Activity window — shown near Live Tail so related controls are grouped.
         render_activity_window(ui, state);
 
+        // Clock offsets — only shown once a rule exists or the editor is open.
+        render_clock_rules(ui, state);
+
         ui.add_space(2.0);
 
         // Search box — always shown.  Supports comma-separated patterns, each
@@ -265,9 +282,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         let mut visible: Vec<usize> = file_entries
             .iter()
             .enumerate()
-            .filter(|(_, (_, name, _, _, _, _, _))| {
-                matches_file_search(&state.file_list_search, name)
-            })
+            .filter(|(_, row)| matches_file_search(&state.file_list_search, &row.name))
             .map(|(i, _)| i)
             .collect();
         // Sort checked files to the top so selected files are always visible
@@ -277,8 +292,12 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
             let hide_all = state.filter_state.hide_all_sources;
             let source_files = &state.filter_state.source_files;
             visible.sort_by_key(|&i| {
-                let path = &file_entries[i].0;
-                let checked = !hide_all && (source_files.is_empty() || source_files.contains(path));
+                let checked = !hide_all
+                    && (source_files.is_empty()
+                        || file_entries[i]
+                            .members
+                            .iter()
+                            .any(|p| source_files.contains(p)));
                 if checked {
                     0u8
                 } else {
@@ -305,9 +324,9 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 {
                     let prev_hide_all = state.filter_state.hide_all_sources;
                     let visible_paths: std::collections::HashSet<&std::path::PathBuf> =
-                        visible.iter().map(|&i| &file_entries[i].0).collect();
+                        visible.iter().flat_map(|&i| &file_entries[i].members).collect();
                     let mut new_selected: std::collections::HashSet<std::path::PathBuf> =
-                        visible.iter().map(|&i| file_entries[i].0.clone()).collect();
+                        visible_paths.iter().map(|&p| p.clone()).collect();
                     // Preserve selection state of ALL non-visible files, including
                     // files hidden by the activity window.  Bug fix: the previous
                     // loop only iterated file_entries (activity-filtered), so files
@@ -315,7 +334,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                     // dropped, causing them to disappear from the filter when the
                     // activity window was later disabled.
                     let file_entry_paths: std::collections::HashSet<&std::path::PathBuf> =
-                        file_entries.iter().map(|(p, _, _, _, _, _, _)| p).collect();
+                        file_entries.iter().flat_map(|row| &row.members).collect();
                     for f in &state.discovered_files {
                         if !visible_paths.contains(&f.path) && !file_entry_paths.contains(&f.path) {
                             let was_selected = !prev_hide_all
@@ -326,7 +345,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                             }
                         }
                     }
-                    for (path, _, _, _, _, _, _) in &file_entries {
+                    for &path in &file_entry_paths {
                         if !visible_paths.contains(path) {
                             let was_selected = !prev_hide_all
                                 && (state.filter_state.source_files.is_empty()
@@ -351,7 +370,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                         })
                         .map(|f| f.path.clone())
                         .collect();
-                    queue_parse_requests(state, to_parse);
+                    state.queue_parse_requests(to_parse);
                     state.apply_filters();
                 }
                 if ui
@@ -360,24 +379,25 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                     .clicked()
                 {
                     let visible_paths: std::collections::HashSet<&std::path::PathBuf> =
-                        visible.iter().map(|&i| &file_entries[i].0).collect();
+                        visible.iter().flat_map(|&i| &file_entries[i].members).collect();
                     let mut non_visible_selected: std::collections::HashSet<std::path::PathBuf> =
                         file_entries
                             .iter()
-                            .filter(|(p, _, _, _, _, _, _)| !visible_paths.contains(p))
-                            .filter(|(p, _, _, _, _, _, _)| {
+                            .flat_map(|row| &row.members)
+                            .filter(|p| !visible_paths.contains(p))
+                            .filter(|p| {
                                 !state.filter_state.hide_all_sources
                                     && (state.filter_state.source_files.is_empty()
-                                        || state.filter_state.source_files.contains(p))
+                                        || state.filter_state.source_files.contains(*p))
                             })
-                            .map(|(p, _, _, _, _, _, _)| p.clone())
+                            .cloned()
                             .collect();
                     // Bug fix: also preserve selection state of files outside the
                     // activity window.  The loop above only iterates file_entries
                     // (activity-filtered); without this, out-of-window files that
                     // were previously selected are lost when "None" is clicked.
                     let file_entry_paths: std::collections::HashSet<&std::path::PathBuf> =
-                        file_entries.iter().map(|(p, _, _, _, _, _, _)| p).collect();
+                        file_entries.iter().flat_map(|row| &row.members).collect();
                     for f in &state.discovered_files {
                         if !file_entry_paths.contains(&f.path) {
                             let was_selected = !state.filter_state.hide_all_sources
@@ -442,7 +462,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 }
                 if !state.file_list_search.trim().is_empty() {
                     ui.label(
-                        egui::RichText::new(format!("{visible_count}/{total}"))
+                        egui::RichText::new(format!("{visible_count}/{}", file_entries.len()))
                             .small()
                             .weak(),
                     );
@@ -464,12 +484,20 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 .show_rows(ui, row_height, visible.len(), |ui, row_range| {
                     for display_idx in row_range {
                         let entry_idx = visible[display_idx];
-                        let (path, name, size_text, profile_text, profile_colour, mtime_text, parsing_skipped) =
-                            &file_entries[entry_idx];
+                        let FileRow {
+                            path,
+                            members,
+                            name,
+                            size_text,
+                            profile_text,
+                            profile_colour,
+                            mtime_text,
+                            parsing_skipped,
+                        } = &file_entries[entry_idx];
 
                         let mut checked = !state.filter_state.hide_all_sources
                             && (state.filter_state.source_files.is_empty()
-                                || state.filter_state.source_files.contains(path));
+                                || members.iter().any(|p| state.filter_state.source_files.contains(p)));
 
                         ui.horizontal(|ui| {
                             // Coloured dot matching the file's timeline stripe colour.
@@ -489,6 +517,18 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                                     path.display()
                                 )
                             };
+                            let hover_detail = if members.len() > 1 {
+                                let segments: Vec<String> = members
+                                    .iter()
+                                    .map(|m| format!("  {}", crate::core::archive::display_name(m)))
+                                    .collect();
+                                format!(
+                                    "{hover_detail}\nRotated segments, oldest first:\n{}",
+                                    segments.join("\n")
+                                )
+                            } else {
+                                hover_detail
+                            };
                             let hover_detail = if *parsing_skipped {
                                 format!("{hover_detail}\n\u{26a0} Not parsed \u{2014} tick the checkbox to load entries from this file.")
                             } else {
@@ -532,18 +572,35 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                                             state.filter_state.source_files.insert(f.path.clone());
                                         }
                                     }
-                                    state.filter_state.source_files.remove(path);
+                                    for member in members {
+                                        state.filter_state.source_files.remove(member);
+                                    }
                                     if state.filter_state.source_files.is_empty() {
                                         state.filter_state.hide_all_sources = true;
                                     }
-                                    // Remove this file's entries from memory and mark it
-                                    // as unparsed so re-ticking triggers a fresh parse.
+                                    // Remove the stream's entries from memory and mark its
+                                    // files as unparsed so re-ticking triggers a fresh parse.
                                     // apply_filters() is called below, which is sufficient;
-                                    // remove_entries_for_file does NOT call it internally.
-                                    state.remove_entries_for_file(path);
+                                    // neither removal call runs it internally.
+                                    if members.len() == 1 {
+                                        state.remove_entries_for_file(path);
+                                    } else {
+                                        let removed = state.remove_entries_for_paths(
+                                            &members.iter().cloned().collect(),
+                                        );
+                                        if removed > 0 {
+                                            state.status_message = format!(
+                                                "Removed {removed} entr{} for \"{name}\".",
+                                                if removed == 1 { "y" } else { "ies" }
+                                            );
+                                        }
+                                    }
                                 } else {
                                     state.filter_state.hide_all_sources = false;
-                                    state.filter_state.source_files.insert((*path).clone());
+                                    state
+                                        .filter_state
+                                        .source_files
+                                        .extend(members.iter().cloned());
                                     // Do NOT collapse source_files to empty when
                                     // all files are ticked.  Keeping the set
                                     // explicitly enumerated ensures new dir-watcher
@@ -561,12 +618,47 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                                     // comment claiming "re-parse will fire next frame"
                                     // was incorrect.
                                     if *parsing_skipped {
-                                        queue_parse_requests(state, vec![path.clone()]);
+                                        let to_parse: Vec<std::path::PathBuf> = state
+                                            .discovered_files
+                                            .iter()
+                                            .filter(|f| f.parsing_skipped && members.contains(&f.path))
+                                            .map(|f| f.path.clone())
+                                            .collect();
+                                        state.queue_parse_requests(to_parse);
                                     }
                                 }
                                 state.apply_filters();
                             }
 
+                            // Clock button: cyan when a clock rule covers this file.
+                            let clock_rule =
+                                crate::core::clock::rule_for(&state.clock_rules, path);
+                            let clock_colour = if clock_rule.is_some() {
+                                egui::Color32::from_rgb(34, 211, 238)
+                            } else {
+                                egui::Color32::from_rgb(107, 114, 128)
+                            };
+                            let clock_hover = clock_rule.map_or_else(
+                                || "Set a clock offset or timezone for this file or its folder".to_string(),
+                                |r| format!("Clock adjustment \u{2014} {}\nClick to edit", r.describe()),
+                            );
+                            if ui
+                                .add(
+                                    egui::Button::new(
+                                        egui::RichText::new("\u{23f2}").small().color(clock_colour),
+                                    )
+                                    .small()
+                                    .frame(false),
+                                )
+                                .on_hover_text(clock_hover)
+                                .clicked()
+                            {
+                                state.clock_edit = Some(crate::app::state::ClockRuleInput::for_file(
+                                    path,
+                                    &state.clock_rules,
+                                ));
+                            }
+
                             // Reveal-in-file-manager button — opens Explorer/Finder
                             // with this file pre-selected so the user can inspect it.
                             if ui
@@ -979,6 +1071,24 @@ fn render_scan_controls(ui: &mut egui::Ui, state: &mut AppState) {
     }
 }
 
+/// One row of the file list: a logical stream -- a rotation family shown as
+/// one source -- or a single file.
+struct FileRow {
+    /// Newest file of the stream: the row's colour, clock rule and reveal
+    /// target.
+    path: std::path::PathBuf,
+    /// Every file of the stream, oldest segment first.  The checkbox applies
+    /// to all of them.
+    members: Vec<std::path::PathBuf>,
+    name: String,
+    size_text: String,
+    profile_text: String,
+    profile_colour: egui::Color32,
+    mtime_text: String,
+    /// At least one of the stream's files has not been parsed.
+    parsing_skipped: bool,
+}
+
 /// Format a UTC modification time for compact display in a file-list row.
 ///
 /// Returns:
@@ -1020,33 +1130,6 @@ fn format_size(bytes: u64) -> String {
     }
 }
 
-/// Returns `true` when `path` resolves to a network location that LogSleuth
-/// refuses to scan.  Network paths can cause multi-second hangs during
-/// directory enumeration (slow links, VPN, offline DFS targets) and are
-/// therefore blocked at the UI layer.
-///
-/// Blocked on Windows:
-///   - UNC paths             (`\\\\server\\share\\...`)
-///   - Verbatim UNC paths    (`\\\\?\\UNC\\server\\share\\...`)
-///   - Device-namespace paths (`\\\\.\\ ...`)
-///
-/// On non-Windows the function always returns `false` — network path
-/// conventions differ and are enforced at the OS level instead.
-fn is_network_path(path: &std::path::Path) -> bool {
-    #[cfg(windows)]
-    {
-        use std::path::{Component, Prefix};
-        if let Some(Component::Prefix(p)) = path.components().next() {
-            return matches!(
-                p.kind(),
-                Prefix::UNC(_, _) | Prefix::VerbatimUNC(_, _) | Prefix::DeviceNS(_)
-            );
-        }
-    }
-    let _ = path;
-    false
-}
-
 fn is_evtx_path(path: &std::path::Path) -> bool {
     path.extension()
         .and_then(|e| e.to_str())
@@ -1054,37 +1137,6 @@ fn is_evtx_path(path: &std::path::Path) -> bool {
         .unwrap_or(false)
 }
 
-fn queue_parse_requests(state: &mut AppState, mut paths: Vec<std::path::PathBuf>) {
-    if paths.is_empty() {
-        return;
-    }
-    paths.sort();
-    paths.dedup();
-
-    if state.scan_in_progress {
-        let mut existing: std::collections::HashSet<std::path::PathBuf> =
-            state.queued_parse_files.iter().cloned().collect();
-        for p in paths {
-            if existing.insert(p.clone()) {
-                state.queued_parse_files.push(p);
-            }
-        }
-        return;
-    }
-
-    if let Some(ref mut pending) = state.pending_single_files {
-        let mut existing: std::collections::HashSet<std::path::PathBuf> =
-            pending.iter().cloned().collect();
-        for p in paths {
-            if existing.insert(p.clone()) {
-                pending.push(p);
-            }
-        }
-    } else {
-        state.pending_single_files = Some(paths);
-    }
-}
-
 /// Returns `true` if `name` matches any of the comma-separated patterns in
 /// `query` (case-insensitive).
 ///
@@ -1353,3 +1405,139 @@ fn render_activity_window(ui: &mut egui::Ui, state: &mut crate::app::state::AppS
         );
     }
 }
+
+/// Render the clock-offset section: the editor opened from a file row's clock
+/// button, and the list of active rules with remove buttons.
+///
+/// A rule shifts every timestamp of a file (or of every file under a folder)
+/// so logs from hosts with skewed clocks line up in the merged timeline.
+fn render_clock_rules(ui: &mut egui::Ui, state: &mut crate::app::state::AppState) {
+    if state.clock_rules.is_empty() && state.clock_edit.is_none() {
+        return;
+    }
+    ui.add_space(4.0);
+    ui.separator();
+    let cyan = egui::Color32::from_rgb(34, 211, 238);
+    ui.label(
+        egui::RichText::new("\u{23f2} Clock offsets")
+            .small()
+            .strong()
+            .color(cyan),
+    )
+    .on_hover_text(
+        "Correct for hosts whose clocks disagree. The offset is added to every \
+         timestamp of the file or folder; the timezone is the zone the host wrote \
+         its zone-less timestamps in, overriding the profile's timezone.",
+    );
+
+    let mut remove: Option<std::path::PathBuf> = None;
+    for rule in &state.clock_rules {
+        ui.horizontal(|ui| {
+            if ui
+                .add(
+                    egui::Button::new(
+                        egui::RichText::new("\u{d7}")
+                            .small()
+                            .color(egui::Color32::from_rgb(156, 163, 175)),
+                    )
+                    .small()
+                    .frame(false),
+                )
+                .on_hover_text("Remove this adjustment")
+                .clicked()
+            {
+                remove = Some(rule.path.clone());
+            }
+            ui.label(egui::RichText::new(rule.describe()).small())
+                .on_hover_text(rule.path.display().to_string());
+        });
+    }
+    if let Some(path) = remove {
+        state.remove_clock_rule(&path);
+    }
+
+    let mut apply = false;
+    let mut cancel = false;
+    if let Some(ref mut edit) = state.clock_edit {
+        let file_name = crate::core::archive::display_name(&edit.file).into_owned();
+        ui.horizontal(|ui| {
+            ui.radio_value(
+                &mut edit.whole_directory,
+                false,
+                egui::RichText::new(file_name).small(),
+            )
+            .on_hover_text(edit.file.display().to_string());
+            if let Some(dir) = edit.file.parent() {
+                let dir_name = dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| dir.display().to_string());
+                ui.radio_value(
+                    &mut edit.whole_directory,
+                    true,
+                    egui::RichText::new(format!("folder {dir_name}/")).small(),
+                )
+                .on_hover_text(format!("Every file under {}", dir.display()));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Offset:").small());
+            ui.add(
+                egui::TextEdit::singleline(&mut edit.offset)
+                    .desired_width(64.0)
+                    .hint_text("+90, -5m"),
+            )
+            .on_hover_text(
+                "Added to every timestamp: seconds, 90s / 5m / 2h, or +HH:MM:SS. \
+                 Positive when the host clock ran slow.",
+            );
+            ui.label(egui::RichText::new("Zone:").small());
+            ui.add(
+                egui::TextEdit::singleline(&mut edit.zone)
+                    .desired_width(110.0)
+                    .hint_text("profile"),
+            )
+            .on_hover_text(
+                "UTC, Local, an IANA zone such as Europe/London, or a fixed offset \
+                 such as +02:00. Empty keeps the profile's timezone. Changing it \
+                 re-parses the files.",
+            );
+        });
+        ui.horizontal(|ui| {
+            if ui
+                .small_button("Apply")
+                .on_hover_text("Re-time loaded entries and apply to entries parsed later")
+                .clicked()
+            {
+                apply = true;
+            }
+            if ui.small_button("Cancel").clicked() {
+                cancel = true;
+            }
+            if let Some(ref err) = edit.error {
+                ui.colored_label(
+                    egui::Color32::from_rgb(248, 113, 113),
+                    egui::RichText::new(err).small(),
+                );
+            }
+        });
+    }
+    if cancel {
+        state.clock_edit = None;
+    } else if apply {
+        if let Some(edit) = state.clock_edit.take() {
+            match edit.rule() {
+                Ok(rule) => {
+                    state.status_message = format!("Clock adjustment set: {}", rule.describe());
+                    state.set_clock_rule(rule);
+                }
+                Err(e) => {
+                    state.clock_edit = Some(crate::app::state::ClockRuleInput {
+                        error: Some(e),
+                        ..edit
+                    });
+                }
+            }
+        }
+    }
+}
@@ -9,6 +9,26 @@ use crate::core::filter::DedupMode;
 use crate::core::model::Severity;
 use crate::core::multi_search::{MultiSearch, MultiSearchMode};
 use crate::ui::theme;
+use crate::util::error::FilterError;
+
+/// Hover help for the query bar.
+const QUERY_HELP: &str =
+    "Filter with a query over entry fields. Combined with the other filters.\n\n\
+     Terms:\n  \
+     word / \"some text\"     message, thread or component contains\n  \
+     field:value           field contains value\n  \
+     field=value           field equals value\n  \
+     field~\"regex\"         field matches regex\n  \
+     severity>=warning     also >, <, <=, = (and line>=100)\n\n\
+     Fields: msg, component, thread, file, profile, severity, line,\n\
+     or any structured field name (e.g. sc_status=500).\n\n\
+     Combine with AND, OR, NOT (upper case) and parentheses.\n\
+     Adjacent terms are AND-ed. Matching is case-insensitive.\n\n\
+     Example:\n  (component:Dispatcher OR component:Agent) AND severity>=warning\n  \
+     AND NOT msg~\"retry \\d+\"";
+
+/// Number of query characters shown either side of an error position.
+const QUERY_ERROR_CONTEXT_CHARS: usize = 24;
 
 /// Render the filter controls sidebar section.
 pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
@@ -124,6 +144,19 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
             }
         });
 
+        // Patterns view: message templates mined from all loaded entries.
+        ui.add_enabled_ui(!state.entries.is_empty(), |ui| {
+            if ui
+                .small_button("Patterns")
+                .on_hover_text(
+                    "Group all entries into message templates with wildcards; click one to filter to it",
+                )
+                .clicked()
+            {
+                state.show_patterns = true;
+            }
+        });
+
         // Bookmarks toggle: shows only bookmarked entries when active.
         let bm_count = state.bookmark_count();
         let bm_active = state.filter_state.bookmarks_only;
@@ -165,6 +198,37 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         }
     });
 
+    render_presets(ui, state);
+
+    // Active pattern filter (set from the Patterns window).
+    if state.filter_state.pattern_ids.is_some() {
+        let mut clear = false;
+        ui.horizontal(|ui| {
+            ui.label(
+                egui::RichText::new("Pattern:")
+                    .small()
+                    .color(egui::Color32::from_rgb(168, 85, 247)),
+            );
+            // Button before the label: a truncated label takes the remaining width.
+            clear = ui
+                .small_button("\u{d7}")
+                .on_hover_text("Remove the pattern filter")
+                .clicked();
+            ui.add(
+                egui::Label::new(
+                    egui::RichText::new(&state.filter_state.pattern_label)
+                        .small()
+                        .monospace(),
+                )
+                .truncate(),
+            )
+            .on_hover_text(&state.filter_state.pattern_label);
+        });
+        if clear {
+            state.clear_pattern_filter();
+        }
+    }
+
     ui.add_space(6.0);
     ui.separator();
 
@@ -205,6 +269,10 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     ui.add_space(6.0);
     ui.separator();
 
+    render_query_bar(ui, state);
+
+    ui.add_space(4.0);
+
     // Text search (substring or fuzzy depending on mode toggle)
     ui.label("Text search:")
         .on_hover_text("Filter entries whose message contains this text. Toggle the ~ button for fuzzy (non-contiguous) matching.");
@@ -344,6 +412,49 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
         .on_hover_text("Entries containing the exclusion term are hidden");
     }
 
+    // -------------------------------------------------------------------------
+    // Structured-field filter (key=value terms against LogEntry::fields)
+    // -------------------------------------------------------------------------
+    ui.add_space(4.0);
+    let known_keys = if state.unique_field_keys.is_empty() {
+        "No structured fields in the loaded entries.".to_string()
+    } else {
+        format!("Known fields: {}", state.unique_field_keys.join(", "))
+    };
+    ui.label("Fields:").on_hover_text(format!(
+        "Filter on structured fields (extra named capture groups, unmapped JSON keys).\n\
+         Comma-separated terms, all must match:\n  \
+         key=value  field contains value (case-insensitive)\n  \
+         key        field is present\n\n{known_keys}"
+    ));
+    ui.horizontal(|ui| {
+        if ui
+            .text_edit_singleline(&mut state.filter_state.field_filter_input)
+            .on_hover_text("e.g. sc_status=500, c_ip")
+            .changed()
+        {
+            let input = state.filter_state.field_filter_input.clone();
+            state.filter_state.set_field_filter(&input);
+            state.filter_dirty_at = Some(std::time::Instant::now());
+        }
+        if !state.filter_state.field_filter_input.is_empty()
+            && ui
+                .add(
+                    egui::Button::new(
+                        egui::RichText::new("\u{d7}")
+                            .small()
+                            .color(egui::Color32::from_rgb(156, 163, 175)),
+                    )
+                    .frame(false),
+                )
+                .on_hover_text("Clear field filter")
+                .clicked()
+        {
+            state.filter_state.set_field_filter("");
+            state.apply_filters();
+        }
+    });
+
     // -------------------------------------------------------------------------
     // Deduplication mode
     // -------------------------------------------------------------------------
@@ -577,7 +688,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 if input.trim().is_empty() {
                     state.filter_state.time_start = None;
                     state.apply_filters();
-                } else if let Some(dt) = crate::app::state::parse_filter_datetime(&input) {
+                } else if let Some(dt) = crate::core::filter::parse_filter_datetime(&input) {
                     state.filter_state.relative_time_secs = None;
                     state.filter_state.relative_time_input.clear();
                     state.filter_state.time_start = Some(dt);
@@ -592,7 +703,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
             }
             // Validity indicator (occupies column 3).
             if !state.filter_state.abs_time_start_input.is_empty() {
-                let valid = crate::app::state::parse_filter_datetime(
+                let valid = crate::core::filter::parse_filter_datetime(
                     &state.filter_state.abs_time_start_input,
                 )
                 .is_some();
@@ -631,7 +742,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 if input.trim().is_empty() {
                     state.filter_state.time_end = None;
                     state.apply_filters();
-                } else if let Some(dt) = crate::app::state::parse_filter_datetime(&input) {
+                } else if let Some(dt) = crate::core::filter::parse_filter_datetime(&input) {
                     state.filter_state.relative_time_secs = None;
                     state.filter_state.relative_time_input.clear();
                     state.filter_state.time_end = Some(dt);
@@ -645,7 +756,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 }
             }
             if !state.filter_state.abs_time_end_input.is_empty() {
-                let valid = crate::app::state::parse_filter_datetime(
+                let valid = crate::core::filter::parse_filter_datetime(
                     &state.filter_state.abs_time_end_input,
                 )
                 .is_some();
@@ -801,6 +912,66 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 }
             }
         });
+
+        // Key correlation: stitch entries sharing an identifier into one
+        // transaction.  Keys can also be picked from the detail pane.
+        let indigo = egui::Color32::from_rgb(99, 102, 241);
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new("Key:").small()).on_hover_text(
+                "Correlate by a trace, request or job ID instead of by time. \
+                 Or pick an ID from the Keys row of the detail pane.",
+            );
+            let input_resp = ui
+                .add(
+                    egui::TextEdit::singleline(&mut state.correlation_key_input)
+                        .desired_width(110.0)
+                        .hint_text("ID or token"),
+                )
+                .on_hover_text(format!(
+                    "Whole-token, case-insensitive match in messages and fields \
+                     (at least {} characters)",
+                    crate::util::constants::MIN_CORRELATION_KEY_LEN
+                ));
+            let submitted =
+                input_resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui
+                .small_button("\u{1f517} Link")
+                .on_hover_text("Highlight and list every entry containing this token")
+                .clicked()
+                || submitted
+            {
+                let key =
+                    crate::core::correlation::CorrelationKey::custom(&state.correlation_key_input);
+                if key.is_some() {
+                    state.set_correlation_key(key);
+                }
+            }
+        });
+        if let Some(ref key) = state.correlation_key {
+            let label = key.label();
+            let n = state.transaction.len();
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(format!("{label} \u{2014} {n} entries"))
+                        .small()
+                        .color(indigo),
+                );
+                if ui
+                    .small_button("List")
+                    .on_hover_text("Show the stitched transaction, ordered by time")
+                    .clicked()
+                {
+                    state.show_transaction = true;
+                }
+                if ui
+                    .small_button("\u{00d7}")
+                    .on_hover_text("Stop correlating by this key")
+                    .clicked()
+                {
+                    state.set_correlation_key(None);
+                }
+            });
+        }
     }
 
     // Entry-count summary and "Copy Filtered" action at the bottom of the filter section.
@@ -850,8 +1021,8 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
 
         ui.add_space(6.0);
         ui.separator();
-        let total = state.entries.len();
-        let filtered = state.filtered_indices.len();
+        let total = state.session_entry_count();
+        let filtered = state.filtered_count();
         ui.horizontal(|ui| {
             if filtered == total {
                 ui.label(format!("{total} entries"))
@@ -881,6 +1052,200 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     }
 }
 
+/// Render the saved filter presets: one button per preset (click to apply,
+/// right-click to export or delete) plus save / import / export controls.
+fn render_presets(ui: &mut egui::Ui, state: &mut AppState) {
+    let header = if state.filter_presets.is_empty() {
+        "Presets".to_string()
+    } else {
+        format!("Presets ({})", state.filter_presets.len())
+    };
+    egui::CollapsingHeader::new(header)
+        .id_salt("filter_presets")
+        .default_open(true)
+        .show(ui, |ui| {
+            // Actions are collected during iteration and applied afterwards
+            // because they mutate `filter_presets`.
+            let mut apply: Option<usize> = None;
+            let mut export: Option<usize> = None;
+            let mut delete: Option<usize> = None;
+
+            if state.filter_presets.is_empty() {
+                ui.label(
+                    egui::RichText::new(
+                        "No saved presets. Set up filters, type a name below and click Save.",
+                    )
+                    .small()
+                    .weak(),
+                );
+            }
+            ui.horizontal_wrapped(|ui| {
+                for (i, preset) in state.filter_presets.iter().enumerate() {
+                    let resp = ui.small_button(&preset.name).on_hover_text(format!(
+                        "{}\n\nClick to apply. Right-click to export or delete.",
+                        preset.describe()
+                    ));
+                    if resp.clicked() {
+                        apply = Some(i);
+                    }
+                    resp.context_menu(|ui| {
+                        if ui.button("Export\u{2026}").clicked() {
+                            export = Some(i);
+                            ui.close_menu();
+                        }
+                        if ui.button("Delete").clicked() {
+                            delete = Some(i);
+                            ui.close_menu();
+                        }
+                    });
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut state.preset_name_input)
+                        .hint_text("Preset name")
+                        .desired_width(140.0),
+                );
+                let name = crate::app::presets::normalize_name(&state.preset_name_input);
+                let exists = state
+                    .filter_presets
+                    .iter()
+                    .any(|p| p.name.eq_ignore_ascii_case(&name));
+                let label = if exists { "Update" } else { "Save" };
+                if ui
+                    .add_enabled(!name.is_empty(), egui::Button::new(label).small())
+                    .on_hover_text(if exists {
+                        "Overwrite the preset with this name with the current filters"
+                    } else {
+                        "Save the current filters as a preset with this name"
+                    })
+                    .clicked()
+                {
+                    state.save_filter_preset(&name);
+                    state.preset_name_input.clear();
+                }
+            });
+
+            ui.horizontal(|ui| {
+                if ui
+                    .small_button("Import\u{2026}")
+                    .on_hover_text(
+                        "Add presets from a TOML file. Presets with an existing name replace it.",
+                    )
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("TOML", &["toml"])
+                        .pick_file()
+                    {
+                        state.import_filter_presets(&path);
+                    }
+                }
+                if ui
+                    .add_enabled(
+                        !state.filter_presets.is_empty(),
+                        egui::Button::new("Export all\u{2026}").small(),
+                    )
+                    .on_hover_text("Save every preset to a TOML file to share with others")
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("TOML", &["toml"])
+                        .set_file_name(crate::util::constants::FILTER_PRESETS_FILE_NAME)
+                        .save_file()
+                    {
+                        state.export_filter_presets(&path, None);
+                    }
+                }
+            });
+
+            if let Some(i) = export {
+                let file_name: String = state.filter_presets[i]
+                    .name
+                    .chars()
+                    .map(|c| if c.is_alphanumeric() { c } else { '_' })
+                    .collect();
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("TOML", &["toml"])
+                    .set_file_name(format!("{file_name}.toml"))
+                    .save_file()
+                {
+                    state.export_filter_presets(&path, Some(i));
+                }
+            }
+            if let Some(i) = delete {
+                state.delete_filter_preset(i);
+            } else if let Some(i) = apply {
+                state.apply_filter_preset(i);
+            }
+        });
+}
+
+/// Render the query bar with a parse-error caret under the offending token.
+fn render_query_bar(ui: &mut egui::Ui, state: &mut AppState) {
+    ui.label("Query:").on_hover_text(QUERY_HELP);
+    ui.horizontal(|ui| {
+        let resp = ui
+            .add(
+                egui::TextEdit::singleline(&mut state.filter_state.query_input)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text("component:Agent AND severity>=warning"),
+            )
+            .on_hover_text(QUERY_HELP);
+        if resp.changed() {
+            // Parse on every keystroke so the error caret tracks the input;
+            // the O(n) filter pass is debounced like the other text inputs.
+            let input = state.filter_state.query_input.clone();
+            let _ = state.filter_state.set_query(&input);
+            state.filter_dirty_at = Some(std::time::Instant::now());
+        }
+        if !state.filter_state.query_input.is_empty()
+            && ui
+                .add(
+                    egui::Button::new(
+                        egui::RichText::new("\u{d7}")
+                            .small()
+                            .color(egui::Color32::from_rgb(156, 163, 175)),
+                    )
+                    .frame(false),
+                )
+                .on_hover_text("Clear query")
+                .clicked()
+        {
+            let _ = state.filter_state.set_query("");
+            state.apply_filters();
+        }
+    });
+
+    if let Some(FilterError::InvalidQuery { position, reason }) = &state.filter_state.query_error {
+        let red = egui::Color32::from_rgb(248, 113, 113);
+        // Show a window of the query around the error with a caret under it.
+        let chars: Vec<char> = state.filter_state.query_input.chars().collect();
+        let start = position.saturating_sub(QUERY_ERROR_CONTEXT_CHARS);
+        let end = (position + QUERY_ERROR_CONTEXT_CHARS).min(chars.len());
+        let prefix = if start > 0 { "\u{2026}" } else { "" };
+        let snippet: String = chars[start.min(end)..end].iter().collect();
+        let caret_col = position - start + prefix.chars().count();
+        ui.label(
+            egui::RichText::new(format!("{prefix}{snippet}\n{}^", " ".repeat(caret_col)))
+                .monospace()
+                .small()
+                .color(red),
+        );
+        ui.label(
+            egui::RichText::new(format!("\u{2717} column {}: {reason}", position + 1))
+                .small()
+                .color(red),
+        );
+    } else if state.filter_state.query.is_some() {
+        ui.colored_label(
+            egui::Color32::from_rgb(74, 222, 128),
+            egui::RichText::new("\u{2713} query active").small(),
+        );
+    }
+}
+
 /// Render the multi-term search section inside the filter panel.
 ///
 /// Provides a collapsible UI for entering multiple search terms (one per
@@ -66,7 +66,7 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) {
                 .map(|t| t.format("%H:%M:%S").to_string())
                 .unwrap_or_else(|| "--:--:--".to_string());
             let file = entry.source_file.display().to_string();
-            let msg = sanitise_preview(entry.message.lines().next().unwrap_or(&entry.message));
+            let msg = sanitise_preview(entry.message().lines().next().unwrap_or(entry.message()));
             rows.push((ts, file, msg));
         }
     }
@@ -215,10 +215,9 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) {
                                             );
                                             // Show just the filename; full path in tooltip.
                                             let display_name =
-                                                std::path::Path::new(file.as_str())
-                                                    .file_name()
-                                                    .and_then(|n| n.to_str())
-                                                    .unwrap_or(file.as_str());
+                                                crate::core::archive::display_name(
+                                                    std::path::Path::new(file.as_str()),
+                                                );
                                             ui.label(
                                                 egui::RichText::new(display_name)
                                                     .monospace()
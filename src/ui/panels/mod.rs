@@ -1,10 +1,15 @@
 // LogSleuth - ui/panels/mod.rs
 
 pub mod about;
+pub mod alerts;
+pub mod anomalies;
+pub mod compare;
 pub mod detail;
 pub mod discovery;
 pub mod filters;
 pub mod log_summary;
 pub mod options;
+pub mod patterns;
 pub mod summary;
 pub mod timeline;
+pub mod transaction;
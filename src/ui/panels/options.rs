@@ -5,7 +5,7 @@
 //
 // Sections:
 //   1. Ingest Limits  — max files per scan, max total entries, max scan depth
-//   2. Live Tail      — tail poll interval
+//   2. Live Tail      — tail poll interval, start mode, ring-buffer size
 //   3. Directory Watch — watch poll interval
 //
 // Settings in sections 2 and 3 take effect when the *next* tail or watch
@@ -14,13 +14,15 @@
 // prevent accidental misconfiguration (Rule 13 + Rule 11 input validation).
 
 use crate::app::state::AppState;
+use crate::app::tail::TailReplay;
 use crate::util::constants::{
     ABSOLUTE_MAX_DEPTH, ABSOLUTE_MAX_FILES, ABSOLUTE_MAX_TAIL_BUFFER_ENTRIES,
     ABSOLUTE_MAX_TOTAL_ENTRIES, DEFAULT_FONT_SIZE, DEFAULT_MAX_DEPTH, DEFAULT_MAX_FILES,
-    DEFAULT_MAX_TAIL_BUFFER_ENTRIES, DIR_WATCH_POLL_INTERVAL_MS, MAX_DIR_WATCH_POLL_INTERVAL_MS,
-    MAX_FONT_SIZE, MAX_TAIL_POLL_INTERVAL_MS, MAX_TOTAL_ENTRIES, MIN_DIR_WATCH_POLL_INTERVAL_MS,
-    MIN_FONT_SIZE, MIN_MAX_FILES, MIN_MAX_TOTAL_ENTRIES, MIN_TAIL_BUFFER_ENTRIES,
-    MIN_TAIL_POLL_INTERVAL_MS, TAIL_POLL_INTERVAL_MS,
+    DEFAULT_MAX_TAIL_BUFFER_ENTRIES, DEFAULT_TAIL_REPLAY_LINES, DEFAULT_TAIL_REPLAY_MINUTES,
+    DIR_WATCH_POLL_INTERVAL_MS, MAX_DIR_WATCH_POLL_INTERVAL_MS, MAX_FONT_SIZE,
+    MAX_TAIL_POLL_INTERVAL_MS, MAX_TAIL_REPLAY_LINES, MAX_TAIL_REPLAY_MINUTES, MAX_TOTAL_ENTRIES,
+    MIN_DIR_WATCH_POLL_INTERVAL_MS, MIN_FONT_SIZE, MIN_MAX_FILES, MIN_MAX_TOTAL_ENTRIES,
+    MIN_TAIL_BUFFER_ENTRIES, MIN_TAIL_POLL_INTERVAL_MS, TAIL_POLL_INTERVAL_MS,
 };
 
 /// Render the Options dialog (if `state.show_options` is true).
@@ -246,7 +248,7 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) {
 
             ui.horizontal(|ui| {
                 ui.label("Poll interval:")
-                    .on_hover_text("How often the background thread checks files for new content during Live Tail.");
+                    .on_hover_text("How often Live Tail checks files for new content when the OS cannot report changes (network paths). Local files are read as soon as they are written.");
                 let mut v = state.tail_poll_interval_ms as f64;
                 if ui
                     .add(
@@ -290,6 +292,107 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) {
 
             ui.add_space(8.0);
 
+            // Tail start mode.
+            ui.horizontal(|ui| {
+                ui.label("Start from:").on_hover_text(
+                    "Where Live Tail starts in files whose content is not loaded \
+                     (a directory opened with --tail). A replay starts at the beginning \
+                     of a log record, so a stack trace is never cut in half. Files \
+                     already loaded by the scan continue from where it stopped. \
+                     Applied when the next tail session is started.",
+                );
+                egui::ComboBox::from_id_salt("tail_replay")
+                    .selected_text(state.tail_replay.label())
+                    .show_ui(ui, |ui| {
+                        let default_since = chrono::Utc::now()
+                            - chrono::Duration::minutes(DEFAULT_TAIL_REPLAY_MINUTES as i64);
+                        for mode in [
+                            TailReplay::Off,
+                            TailReplay::Lines(DEFAULT_TAIL_REPLAY_LINES),
+                            TailReplay::Minutes(DEFAULT_TAIL_REPLAY_MINUTES),
+                            TailReplay::Since(default_since),
+                        ] {
+                            let selected = std::mem::discriminant(&state.tail_replay)
+                                == std::mem::discriminant(&mode);
+                            if ui.selectable_label(selected, mode.label()).clicked() && !selected
+                            {
+                                state.tail_replay = mode;
+                                state.tail_replay_since_input.clear();
+                            }
+                        }
+                    });
+                match &mut state.tail_replay {
+                    TailReplay::Off => {}
+                    TailReplay::Lines(n) => {
+                        let mut v = *n as f64;
+                        if ui
+                            .add(
+                                egui::Slider::new(&mut v, 1.0..=(MAX_TAIL_REPLAY_LINES as f64))
+                                    .integer()
+                                    .suffix(" lines")
+                                    .logarithmic(true),
+                            )
+                            .changed()
+                        {
+                            *n = (v as usize).clamp(1, MAX_TAIL_REPLAY_LINES);
+                        }
+                    }
+                    TailReplay::Minutes(m) => {
+                        let mut v = *m as f64;
+                        if ui
+                            .add(
+                                egui::Slider::new(&mut v, 1.0..=(MAX_TAIL_REPLAY_MINUTES as f64))
+                                    .integer()
+                                    .suffix(" min")
+                                    .logarithmic(true),
+                            )
+                            .changed()
+                        {
+                            *m = (v as u64).clamp(1, MAX_TAIL_REPLAY_MINUTES);
+                        }
+                    }
+                    TailReplay::Since(at) => {
+                        // Same local-time input as the From / To time filter,
+                        // committed when focus leaves the field.
+                        let shown = || {
+                            at.with_timezone(&chrono::Local)
+                                .format("%Y-%m-%d %H:%M:%S")
+                                .to_string()
+                        };
+                        if state.tail_replay_since_input.is_empty() {
+                            state.tail_replay_since_input = shown();
+                        }
+                        let resp = ui
+                            .add(
+                                egui::TextEdit::singleline(&mut state.tail_replay_since_input)
+                                    .desired_width(150.0)
+                                    .hint_text("YYYY-MM-DD HH:MM"),
+                            )
+                            .on_hover_text(
+                                "Replay the records stamped at or after this local time. \
+                                 Tab or click away to apply.",
+                            );
+                        let parsed = crate::core::filter::parse_filter_datetime(
+                            &state.tail_replay_since_input,
+                        );
+                        if resp.lost_focus() {
+                            match parsed {
+                                Some(dt) => *at = dt,
+                                None => state.tail_replay_since_input = shown(),
+                            }
+                        } else if parsed.is_none() {
+                            ui.label(
+                                egui::RichText::new("\u{2717}")
+                                    .small()
+                                    .color(egui::Color32::from_rgb(248, 113, 113)),
+                            );
+                        }
+                    }
+                }
+            });
+
+            ui.add_space(8.0);
+
             // Tail ring-buffer size.
             ui.horizontal(|ui| {
                 ui.label("Ring-buffer size:")
@@ -365,7 +468,7 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) {
 
             ui.horizontal(|ui| {
                 ui.label("Poll interval:")
-                    .on_hover_text("How often the directory watcher polls for newly created files.");
+                    .on_hover_text("How often the directory watcher walks the tree for newly created files when the OS cannot report changes (network paths). Local directories report new files as they appear.");
                 let mut v = state.dir_watch_poll_interval_ms as f64;
                 if ui
                     .add(
@@ -0,0 +1,237 @@
+// LogSleuth - ui/panels/patterns.rs
+//
+// Patterns window: message templates mined from all loaded entries
+// (`core::patterns`), one row per template with count, severity mix,
+// first/last seen and contributing files.  Clicking a template filters the
+// timeline to its members; clicking it again removes the filter.
+//
+// Opened via View -> Patterns or the "Patterns" button in the Filters
+// sidebar.  Templates are mined when the window opens and on Refresh, not on
+// every frame: mining normalises every message.
+//
+// Rule 16: the close button is always enabled.
+// Rule 11: no unbounded allocation — at most MAX_PATTERN_ROWS rows are shown.
+
+use crate::app::state::AppState;
+use crate::ui::theme;
+
+/// Maximum number of templates listed.  Templates are sorted by count, so
+/// the rarest are the ones left out.
+const MAX_PATTERN_ROWS: usize = 500;
+
+/// Render the Patterns window (if `state.show_patterns` is true).
+pub fn render(ctx: &egui::Context, state: &mut AppState) {
+    if !state.show_patterns {
+        return;
+    }
+    if state.patterns.is_none() {
+        state.refresh_patterns();
+    }
+
+    // Same open / close_clicked split as the Log Summary window.
+    let mut open = state.show_patterns;
+    let mut close_clicked = false;
+    let mut refresh = false;
+    let mut clicked: Option<usize> = None;
+
+    egui::Window::new("Patterns")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .min_width(560.0)
+        .min_height(200.0)
+        .default_width(820.0)
+        .default_height(460.0)
+        .default_pos([
+            ctx.screen_rect().width() * 0.5 - 410.0,
+            48.0, // sit just below the menu bar, never off the top edge
+        ])
+        .show(ctx, |ui| {
+            let Some(summary) = state.patterns.as_ref() else {
+                return;
+            };
+
+            // ----------------------------------------------------------------
+            // Header: counts, staleness and Refresh
+            // ----------------------------------------------------------------
+            ui.horizontal(|ui| {
+                ui.strong(format!(
+                    "{} templates from {} entries",
+                    summary.patterns.len(),
+                    summary.entries_analysed
+                ));
+                if summary.unclustered > 0 {
+                    ui.label(
+                        egui::RichText::new(format!("({} unclustered)", summary.unclustered))
+                            .weak()
+                            .small(),
+                    )
+                    .on_hover_text("Entries left over after the template limit was reached");
+                }
+                if ui
+                    .small_button("\u{21bb} Refresh")
+                    .on_hover_text("Mine templates again from all loaded entries")
+                    .clicked()
+                {
+                    refresh = true;
+                }
+            });
+            if summary.entries_analysed != state.entries.len() {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Entries have changed since these templates were mined ({} now loaded) \u{2014} Refresh to update.",
+                        state.entries.len()
+                    ))
+                    .small()
+                    .color(egui::Color32::from_rgb(251, 191, 36)),
+                );
+            }
+            ui.label(
+                egui::RichText::new(
+                    "<*> marks words that vary; <NUM>, <IP>, <GUID> and <HEX> are normalised values. Click a template to filter the timeline to it.",
+                )
+                .weak()
+                .small(),
+            );
+
+            ui.add_space(4.0);
+            ui.separator();
+
+            // ----------------------------------------------------------------
+            // Template table: count | severity mix | first | last | files | template
+            // ----------------------------------------------------------------
+            let active_label = state
+                .filter_state
+                .pattern_ids
+                .as_ref()
+                .map(|_| state.filter_state.pattern_label.as_str());
+            let reserved_bottom: f32 = 44.0;
+            let scroll_max_height = (ui.available_height() - reserved_bottom).max(120.0);
+            egui::ScrollArea::vertical()
+                .id_salt("patterns_scroll")
+                .max_height(scroll_max_height)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    egui::Grid::new("patterns_grid")
+                        .num_columns(6)
+                        .spacing([12.0, 3.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Count");
+                            ui.strong("Severity");
+                            ui.strong("First seen");
+                            ui.strong("Last seen");
+                            ui.strong("Files");
+                            ui.strong("Template");
+                            ui.end_row();
+
+                            for (i, pattern) in
+                                summary.patterns.iter().take(MAX_PATTERN_ROWS).enumerate()
+                            {
+                                ui.label(pattern.count().to_string());
+
+                                ui.horizontal(|ui| {
+                                    ui.spacing_mut().item_spacing.x = 4.0;
+                                    for (sev, n) in &pattern.severity_counts {
+                                        let colour = theme::severity_colour(sev, state.dark_mode);
+                                        let initial = &sev.label()[..1];
+                                        ui.label(
+                                            egui::RichText::new(format!("{initial}{n}"))
+                                                .small()
+                                                .color(colour),
+                                        )
+                                        .on_hover_text(format!("{}: {n}", sev.label()));
+                                    }
+                                });
+
+                                for ts in [pattern.first_seen, pattern.last_seen] {
+                                    let text = ts
+                                        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+                                        .unwrap_or_else(|| "--".to_string());
+                                    ui.label(egui::RichText::new(text).small().monospace().weak());
+                                }
+
+                                let file_list: Vec<String> = pattern
+                                    .files
+                                    .iter()
+                                    .map(|f| f.display().to_string())
+                                    .collect();
+                                ui.label(pattern.files.len().to_string())
+                                    .on_hover_text(file_list.join("\n"));
+
+                                let selected = active_label == Some(pattern.template.as_str());
+                                let colour = pattern
+                                    .worst_severity()
+                                    .map(|s| theme::severity_colour(&s, state.dark_mode))
+                                    .unwrap_or_else(|| ui.style().visuals.text_color());
+                                let text = if pattern.template.is_empty() {
+                                    egui::RichText::new("(empty message)").italics()
+                                } else {
+                                    egui::RichText::new(&pattern.template).monospace()
+                                };
+                                if ui
+                                    .add(
+                                        egui::Button::new(text.color(colour))
+                                            .selected(selected)
+                                            .frame(selected)
+                                            .truncate(),
+                                    )
+                                    .on_hover_text(if selected {
+                                        "Click to remove this pattern filter"
+                                    } else {
+                                        "Click to show only entries matching this template"
+                                    })
+                                    .clicked()
+                                {
+                                    clicked = Some(i);
+                                }
+                                ui.end_row();
+                            }
+                        });
+
+                    if summary.patterns.len() > MAX_PATTERN_ROWS {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "... and {} rarer templates not shown",
+                                summary.patterns.len() - MAX_PATTERN_ROWS
+                            ))
+                            .weak()
+                            .small()
+                            .italics(),
+                        );
+                    }
+                    if summary.patterns.is_empty() {
+                        ui.label("No entries loaded.");
+                    }
+                });
+
+            ui.add_space(6.0);
+            ui.separator();
+            if ui
+                .button("Close")
+                .on_hover_text("Close the Patterns window")
+                .clicked()
+            {
+                close_clicked = true;
+            }
+        });
+
+    if refresh {
+        state.refresh_patterns();
+    } else if let Some(i) = clicked {
+        let already_active = state.filter_state.pattern_ids.is_some()
+            && state
+                .patterns
+                .as_ref()
+                .and_then(|p| p.patterns.get(i))
+                .is_some_and(|p| p.template == state.filter_state.pattern_label);
+        if already_active {
+            state.clear_pattern_filter();
+        } else {
+            state.apply_pattern_filter(i);
+        }
+    }
+
+    // Write back: honour both the title-bar × and the body Close button.
+    state.show_patterns = open && !close_clicked;
+}
@@ -96,11 +96,13 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) {
                                     ui.end_row();
 
                                     for fs in &summary.file_summaries {
-                                        let name = fs
-                                            .path
-                                            .file_name()
-                                            .and_then(|n| n.to_str())
-                                            .unwrap_or("?");
+                                        let name = crate::core::archive::display_name(&fs.path);
+                                        // A rotation family is summarised as one stream.
+                                        let name = if fs.segments > 1 {
+                                            format!("{name} ({} files)", fs.segments)
+                                        } else {
+                                            name.into_owned()
+                                        };
                                         ui.label(egui::RichText::new(name).monospace());
                                         ui.label(&fs.profile_id);
                                         ui.label(fs.entry_count.to_string());
@@ -116,6 +118,14 @@ pub fn render(ctx: &egui::Context, state: &mut AppState) {
                                             (Some(e), Some(l)) if e == l => {
                                                 e.format("%Y-%m-%d %H:%M:%S").to_string()
                                             }
+                                            // Segments of a stream often span days.
+                                            (Some(e), Some(l)) if e.date_naive() != l.date_naive() => {
+                                                format!(
+                                                    "{} \u{2013} {}",
+                                                    e.format("%Y-%m-%d %H:%M"),
+                                                    l.format("%Y-%m-%d %H:%M")
+                                                )
+                                            }
                                             (Some(e), Some(l)) => {
                                                 format!(
                                                     "{} \u{2013} {}",
@@ -20,7 +20,18 @@ use egui::text::{LayoutJob, TextFormat};
 
 /// Render the timeline panel (central area).
 pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
-    let filtered = state.filtered_indices.len();
+    let store_mode = state.entry_store.is_some();
+    if store_mode {
+        render_store_bar(ui, state);
+    }
+    // Rows: every matching entry, in the store when there is one.  Only the
+    // page starting at row `page_start` is loaded into `filtered_indices`.
+    let filtered = state.filtered_count();
+    let page_start = if store_mode {
+        state.store_window_start
+    } else {
+        0
+    };
 
     if filtered == 0 {
         ui.centered_and_justified(|ui| {
@@ -125,6 +136,12 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                     if !f.multi_search.is_empty() {
                         active_filters.push("Multi-search".to_string());
                     }
+                    if f.query.is_some() {
+                        active_filters.push("Query".to_string());
+                    }
+                    if f.pattern_ids.is_some() {
+                        active_filters.push("Pattern".to_string());
+                    }
                     if let Some(secs) = state.activity_window_secs {
                         let label = if secs < 60 {
                             format!("Activity window: {}s", secs)
@@ -230,6 +247,8 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     // Deferred multi-select actions collected during show_rows and applied after.
     let mut click_action: Option<(usize, bool, bool)> = None; // (actual_idx, ctrl, shift)
     let mut context_menu_copy = false;
+    // Store row scrolled into view outside the loaded page.
+    let mut page_request: Option<usize> = None;
 
     // Build the scroll area; optionally snap to the top for descending mode.
     let snap_top = state.scroll_top_requested && state.sort_descending && state.tail_auto_scroll;
@@ -255,9 +274,22 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
             } else {
                 display_idx
             };
-            let Some(&entry_idx) = state.filtered_indices.get(actual_idx) else {
+            let Some(&entry_idx) = actual_idx
+                .checked_sub(page_start)
+                .and_then(|idx| state.filtered_indices.get(idx))
+            else {
+                if store_mode {
+                    // Blank row until the page around it is read from the store.
+                    ui.allocate_exact_size(
+                        egui::vec2(ui.available_width(), row_height),
+                        egui::Sense::hover(),
+                    );
+                    page_request.get_or_insert(actual_idx);
+                }
                 continue;
             };
+            // Position within the loaded page, as selection indices use.
+            let actual_idx = actual_idx - page_start;
             let Some(entry) = state.entries.get(entry_idx) else {
                 continue;
             };
@@ -269,6 +301,7 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
             let entry_id = entry.id;
             let is_bookmarked = state.is_bookmarked(entry_id);
             let is_correlated = state.correlated_ids.contains(&entry_id);
+            let is_key_correlated = state.key_correlated_ids.contains(&entry_id);
 
             // Build a LayoutJob so the severity badge ([CRIT], [ERR ], etc.)
             // keeps its severity-specific hue while the rest of the row
@@ -280,12 +313,8 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 .timestamp
                 .map(|t| t.format("%H:%M:%S").to_string())
                 .unwrap_or_else(|| "--:--:--".to_string());
-            let file_name = entry
-                .source_file
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("?");
-            let first_line = entry.message.lines().next().unwrap_or(&entry.message);
+            let file_name = crate::core::archive::display_name(&entry.source_file);
+            let first_line = entry.message().lines().next().unwrap_or(entry.message());
 
             let font = egui::FontId::monospace(font_size);
             let body_colour = theme::row_text_colour(state.dark_mode);
@@ -356,6 +385,20 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
                 );
             }
 
+            // Indigo tint on rows sharing the correlation key (stitched
+            // transaction); same layering as the teal time-window tint.
+            if is_key_correlated {
+                let tint_rect = egui::Rect::from_min_size(
+                    ui.cursor().min,
+                    egui::vec2(ui.available_width(), row_height),
+                );
+                ui.painter().rect_filled(
+                    tint_rect,
+                    0.0,
+                    egui::Color32::from_rgba_premultiplied(99, 102, 241, 30),
+                );
+            }
+
             // Subtle gold background tint on bookmarked rows.
             if is_bookmarked {
                 let tint_rect = egui::Rect::from_min_size(
@@ -565,4 +608,28 @@ pub fn render(ui: &mut egui::Ui, state: &mut AppState) {
     if correlation_update_needed {
         state.update_correlation();
     }
+
+    // Read the page centred on the first unloaded row in view.
+    if let Some(row) = page_request {
+        state.load_store_page(row.saturating_sub(state.max_total_entries / 2));
+        ui.ctx().request_repaint();
+    }
+}
+
+/// Bar above the timeline when the session lives in the on-disk entry store.
+fn render_store_bar(ui: &mut egui::Ui, state: &AppState) {
+    ui.label(
+        egui::RichText::new(format!(
+            "{} of {} entries match (on-disk store)",
+            state.store_rows.len(),
+            state.session_entry_count()
+        ))
+        .small(),
+    )
+    .on_hover_text(
+        "The session is larger than the in-memory entry limit.  Filters and \
+         export cover every stored entry; the timeline reads the rows it \
+         shows from disk as you scroll.",
+    );
+    ui.separator();
 }
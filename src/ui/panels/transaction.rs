@@ -0,0 +1,208 @@
+// LogSleuth - ui/panels/transaction.rs
+//
+// Transaction window: every entry, in every file, carrying the current
+// correlation key (`core::correlation`), ordered by time.  Clicking a row
+// selects that entry in the timeline; "Filter to transaction" narrows the
+// timeline to these entries.
+//
+// Opened by picking a key in the detail pane or the filters panel.
+//
+// Rule 16: the close button is always enabled.
+// Rule 11: no unbounded allocation — at most MAX_TRANSACTION_ROWS rows.
+
+use crate::app::state::AppState;
+use crate::ui::theme;
+
+/// Maximum number of transaction rows shown.
+const MAX_TRANSACTION_ROWS: usize = 1000;
+
+/// Render the Transaction window (if `state.show_transaction` is true).
+pub fn render(ctx: &egui::Context, state: &mut AppState) {
+    if !state.show_transaction {
+        return;
+    }
+    let Some(key) = state.correlation_key.clone() else {
+        state.show_transaction = false;
+        return;
+    };
+
+    // Same open / close_clicked split as the Log Summary window.
+    let mut open = state.show_transaction;
+    let mut close_clicked = false;
+    let mut filter = false;
+    let mut clicked: Option<u64> = None;
+
+    egui::Window::new("Transaction")
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(true)
+        .min_width(520.0)
+        .min_height(200.0)
+        .default_width(760.0)
+        .default_height(420.0)
+        .default_pos([
+            ctx.screen_rect().width() * 0.5 - 380.0,
+            48.0, // sit just below the menu bar, never off the top edge
+        ])
+        .show(ctx, |ui| {
+            // ----------------------------------------------------------------
+            // Header: key, span and actions
+            // ----------------------------------------------------------------
+            let first_ts = state
+                .transaction
+                .first()
+                .and_then(|&i| state.entries.get(i))
+                .and_then(|e| e.timestamp);
+            let last_ts = state
+                .transaction
+                .iter()
+                .rev()
+                .filter_map(|&i| state.entries.get(i).and_then(|e| e.timestamp))
+                .next();
+            let files: std::collections::HashSet<&std::path::Path> = state
+                .transaction
+                .iter()
+                .filter_map(|&i| state.entries.get(i).map(|e| &*e.source_file))
+                .collect();
+            ui.horizontal(|ui| {
+                ui.strong(key.label());
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} entries in {} files",
+                        state.transaction.len(),
+                        files.len()
+                    ))
+                    .small(),
+                );
+                if let (Some(first), Some(last)) = (first_ts, last_ts) {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "over {}",
+                            crate::core::anomaly::format_duration((last - first).num_seconds())
+                        ))
+                        .weak()
+                        .small(),
+                    );
+                }
+                if ui
+                    .small_button("Filter to transaction")
+                    .on_hover_text("Show only these entries in the timeline")
+                    .clicked()
+                {
+                    filter = true;
+                }
+            });
+
+            ui.add_space(4.0);
+            ui.separator();
+
+            // ----------------------------------------------------------------
+            // Rows: time | +offset | file:line | message
+            // ----------------------------------------------------------------
+            let reserved_bottom: f32 = 44.0;
+            let scroll_max_height = (ui.available_height() - reserved_bottom).max(120.0);
+            egui::ScrollArea::vertical()
+                .id_salt("transaction_scroll")
+                .max_height(scroll_max_height)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    if state.transaction.is_empty() {
+                        ui.label("No loaded entry contains this key.");
+                        return;
+                    }
+                    let selected_id = state.selected_entry().map(|e| e.id);
+                    egui::Grid::new("transaction_grid")
+                        .num_columns(4)
+                        .spacing([12.0, 2.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for &idx in state.transaction.iter().take(MAX_TRANSACTION_ROWS) {
+                                let Some(entry) = state.entries.get(idx) else {
+                                    continue;
+                                };
+                                let ts = entry
+                                    .timestamp
+                                    .map(|t| t.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+                                    .unwrap_or_else(|| "--".to_string());
+                                ui.label(egui::RichText::new(ts).small().monospace());
+
+                                let offset = match (first_ts, entry.timestamp) {
+                                    (Some(first), Some(ts)) => format!(
+                                        "+{:.3}s",
+                                        (ts - first).num_milliseconds() as f64 / 1000.0
+                                    ),
+                                    _ => String::new(),
+                                };
+                                ui.label(egui::RichText::new(offset).small().weak().monospace())
+                                    .on_hover_text("Time since the first entry of the transaction");
+
+                                ui.label(
+                                    egui::RichText::new(format!(
+                                        "{}:{}",
+                                        crate::core::archive::display_name(&entry.source_file),
+                                        entry.line_number
+                                    ))
+                                    .small()
+                                    .color(state.colour_for_file(&entry.source_file)),
+                                )
+                                .on_hover_text(entry.source_file.display().to_string());
+
+                                let colour =
+                                    theme::severity_colour(&entry.severity, state.dark_mode);
+                                let first_line = entry.message().lines().next().unwrap_or("");
+                                if ui
+                                    .add(
+                                        egui::Button::new(
+                                            egui::RichText::new(first_line)
+                                                .monospace()
+                                                .color(colour),
+                                        )
+                                        .selected(selected_id == Some(entry.id))
+                                        .frame(false)
+                                        .truncate(),
+                                    )
+                                    .on_hover_text("Select this entry in the timeline")
+                                    .clicked()
+                                {
+                                    clicked = Some(entry.id);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    if state.transaction.len() > MAX_TRANSACTION_ROWS {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "... and {} more (use Filter to transaction to see all)",
+                                state.transaction.len() - MAX_TRANSACTION_ROWS
+                            ))
+                            .weak()
+                            .small()
+                            .italics(),
+                        );
+                    }
+                });
+
+            ui.add_space(6.0);
+            ui.separator();
+            if ui
+                .button("Close")
+                .on_hover_text("Close the Transaction window (the highlight stays on)")
+                .clicked()
+            {
+                close_clicked = true;
+            }
+        });
+
+    if filter {
+        state.filter_to_transaction();
+    } else if let Some(id) = clicked {
+        if !state.select_entry_by_id(id) {
+            state.status_message =
+                "That entry is hidden by the current filters \u{2014} use Filter to transaction."
+                    .to_string();
+        }
+    }
+
+    // Write back: honour both the title-bar × and the body Close button.
+    state.show_transaction = open && !close_clicked;
+}
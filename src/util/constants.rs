@@ -58,6 +58,10 @@ pub const MAX_SCAN_THREADS: usize = 64;
 /// Default read chunk size in bytes for streaming file reads.
 pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024; // 64 KB
 
+/// Smallest and largest accepted `chunk_size_bytes` in config.toml.
+pub const MIN_CHUNK_SIZE: usize = 4 * 1024; // 4 KB
+pub const MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024; // 16 MB
+
 /// Maximum size of a single log entry in bytes. Entries exceeding
 /// this are truncated to prevent unbounded memory from malformed files.
 pub const DEFAULT_MAX_ENTRY_SIZE: usize = 64 * 1024; // 64 KB
@@ -96,6 +100,84 @@ pub const MIN_MAX_TOTAL_ENTRIES: usize = 10_000;
 /// Maximum user-configurable entry cap (same as the absolute hard limit).
 pub const ABSOLUTE_MAX_TOTAL_ENTRIES: usize = MAX_TOTAL_ENTRIES;
 
+/// Maximum number of structured key/value fields kept on a single entry.
+///
+/// Unmapped JSON keys are retained in `LogEntry::fields` for the detail pane.
+/// A pathological record with thousands of keys would otherwise multiply the
+/// per-entry heap cost; keys beyond this cap are dropped (Rule 11).
+pub const MAX_ENTRY_FIELDS: usize = 64;
+
+/// Maximum distinct strings (thread and component names) one
+/// `core::intern::Interner` shares.  Values beyond it are still stored, just
+/// not shared, so a column of unique IDs cannot grow the table (Rule 11).
+pub const MAX_INTERNED_STRINGS: usize = 4_096;
+
+/// Maximum object nesting depth flattened into dotted field names
+/// (e.g. `http.request.method`).  Objects nested deeper than this are kept
+/// as a compact JSON string under the deepest flattened key.
+pub const MAX_JSON_FIELD_DEPTH: usize = 8;
+
+/// Largest accepted magnitude of a captured `tz_offset`, in minutes.  Real
+/// zones span UTC-12:00 to UTC+14:00; anything beyond 14 h is treated as a
+/// malformed capture rather than silently shifting the entry by days.
+pub const MAX_TZ_OFFSET_MINUTES: i64 = 14 * 60;
+
+// =============================================================================
+// JSON Lines default key lists
+// =============================================================================
+//
+// Used when a profile declares a `[parsing.json]` section but omits one of the
+// key lists.  Keys are matched case-insensitively against the flattened
+// (dot-joined) key path, so `log.level` matches both `{"log":{"level":..}}`
+// and a literal `{"log.level":..}` key.  First key present wins.
+
+/// Default keys searched for the entry timestamp.
+pub const JSON_DEFAULT_TIMESTAMP_KEYS: &[&str] = &[
+    "timestamp",
+    "@timestamp",
+    "ts",
+    "time",
+    "datetime",
+    "date",
+    "@t",
+];
+
+/// Default keys searched for the severity level.
+pub const JSON_DEFAULT_LEVEL_KEYS: &[&str] = &[
+    "level",
+    "severity",
+    "log.level",
+    "lvl",
+    "loglevel",
+    "levelname",
+    "@l",
+];
+
+/// Default keys searched for the message text.
+pub const JSON_DEFAULT_MESSAGE_KEYS: &[&str] =
+    &["message", "msg", "@message", "@m", "@mt", "text", "log"];
+
+/// Default keys searched for the component / logger name.
+pub const JSON_DEFAULT_COMPONENT_KEYS: &[&str] = &[
+    "logger",
+    "logger_name",
+    "log.logger",
+    "component",
+    "module",
+    "name",
+    "source",
+];
+
+/// Default keys searched for the thread / process identifier.
+pub const JSON_DEFAULT_THREAD_KEYS: &[&str] = &[
+    "thread",
+    "thread_name",
+    "threadName",
+    "process.thread.name",
+    "tid",
+    "pid",
+];
+
 // =============================================================================
 // Live tail limits
 // =============================================================================
@@ -160,6 +242,15 @@ pub const DIR_WATCH_MTIME_INTERVAL_MS: u64 = 10_000; // 10 s
 /// the mtime loop regardless of directory size.
 pub const MAX_MTIME_TRACK_FILES_PER_CYCLE: usize = 200;
 
+/// With native change notification, how often live tail still re-checks every
+/// file and the directory watcher still re-walks the tree (ms), to catch
+/// events the OS dropped or coalesced.  Polling mode uses the poll interval.
+pub const FS_WATCH_RESCAN_INTERVAL_MS: u64 = 30_000; // 30 s
+
+/// Maximum change events queued between two watcher wake-ups; beyond this
+/// events are dropped and the watcher does a full re-check instead.
+pub const MAX_PENDING_FS_EVENTS: usize = 4_096;
+
 /// Maximum bytes read from a single file in one poll tick.
 /// Prevents a large burst of new content from stalling the entire poll loop.
 pub const MAX_TAIL_READ_BYTES_PER_TICK: usize = 512 * 1_024; // 512 KiB
@@ -183,6 +274,33 @@ pub const MAX_QUEUED_DIR_WATCHER_FILES: usize = 1_000;
 /// warning (Rule 11 — resource bounds on growing collections).
 pub const MAX_TAIL_PARTIAL_BYTES: usize = MAX_TAIL_READ_BYTES_PER_TICK * 4; // 2 MiB
 
+/// Bytes before the read position that live tail re-reads and compares on
+/// every read of a file.  A mismatch means the file was replaced or truncated
+/// in place; the same bytes identify the copy left by a copytruncate rotation.
+pub const TAIL_ROTATION_FINGERPRINT_BYTES: usize = 512;
+
+/// Maximum sibling files (`app.log.1`, `app.log-20240115`, ...) examined when
+/// live tail looks for where a rotated file's content went.  The most
+/// recently modified siblings are examined first.
+pub const MAX_TAIL_ROTATION_CANDIDATES: usize = 8;
+
+/// Maximum bytes read back from the end of a file to find where a tail
+/// replay (last N lines / last N minutes) starts.  A replay never reaches
+/// further back than this, however many lines or minutes were asked for.
+pub const MAX_TAIL_REPLAY_BYTES: usize = 8 * 1024 * 1024; // 8 MiB
+
+/// Default line count for the "Last N lines" tail start mode.
+pub const DEFAULT_TAIL_REPLAY_LINES: usize = 200;
+
+/// Maximum user-configurable line count for the "Last N lines" start mode.
+pub const MAX_TAIL_REPLAY_LINES: usize = 100_000;
+
+/// Default window for the "Last N minutes" tail start mode.
+pub const DEFAULT_TAIL_REPLAY_MINUTES: u64 = 15;
+
+/// Maximum user-configurable window for the "Last N minutes" start mode.
+pub const MAX_TAIL_REPLAY_MINUTES: u64 = 7 * 24 * 60; // 7 days
+
 /// Default maximum number of live-tail entries held in the rolling ring-buffer.
 ///
 /// When new tail entries would push the tail section of `entries` beyond this
@@ -300,6 +418,17 @@ pub const MIN_CORRELATION_WINDOW_SECS: i64 = 1;
 /// too broad to be meaningful for most log correlation workflows.
 pub const MAX_CORRELATION_WINDOW_SECS: i64 = 3_600;
 
+/// Minimum length in characters of a correlation key (identifier) token.
+/// Shorter tokens match too many unrelated entries.
+pub const MIN_CORRELATION_KEY_LEN: usize = 4;
+
+/// Maximum number of candidate correlation keys offered for one entry.
+pub const MAX_CORRELATION_KEYS_PER_ENTRY: usize = 8;
+
+/// Largest per-file clock offset accepted (seconds).  Clock skew between
+/// hosts is seconds to minutes; a day covers a wrong date setting.
+pub const MAX_CLOCK_OFFSET_SECS: i64 = 86_400;
+
 /// Debounce delay in milliseconds for text filter input.
 pub const DEFAULT_FILTER_DEBOUNCE_MS: u64 = 300;
 
@@ -325,24 +454,24 @@ pub const DEFAULT_LARGE_EXPORT_THRESHOLD: usize = 100_000;
 // File discovery patterns
 // =============================================================================
 
-/// Default include glob patterns for log file discovery.
-#[cfg(target_os = "windows")]
-pub const DEFAULT_INCLUDE_PATTERNS: &[&str] = &["*.log", "*.log.[0-9]*", "*.txt", "*.evtx"];
+/// Longest numeric suffix treated as a rotation number (`app.log.1` ..
+/// `app.log.9999`).  Longer digit runs are dates or version numbers, not
+/// rotation counters.
+pub const MAX_ROTATION_NUMBER_DIGITS: usize = 4;
 
 /// Default include glob patterns for log file discovery.
-#[cfg(not(target_os = "windows"))]
-pub const DEFAULT_INCLUDE_PATTERNS: &[&str] = &["*.log", "*.log.[0-9]*", "*.txt"];
+///
+/// `*.evtx` is included on every platform so exported Windows event logs can
+/// be analysed on Linux and macOS.
+pub const DEFAULT_INCLUDE_PATTERNS: &[&str] = &["*.log", "*.log.[0-9]*", "*.txt", "*.evtx"];
 
 /// Default exclude glob patterns for log file discovery.
-pub const DEFAULT_EXCLUDE_PATTERNS: &[&str] = &[
-    "*.gz",
-    "*.zip",
-    "*.bak",
-    "*.tmp",
-    "node_modules",
-    ".git",
-    "__pycache__",
-];
+///
+/// Archives (`*.gz`, `*.zip`, `*.tar.gz`) are no longer excluded: discovery
+/// lists their members as virtual files.  Add `"*.gz"` / `"*.zip"` here (or in
+/// `config.toml`) to opt out.
+pub const DEFAULT_EXCLUDE_PATTERNS: &[&str] =
+    &["*.bak", "*.tmp", "node_modules", ".git", "__pycache__"];
 
 // =============================================================================
 // Logging
@@ -362,22 +491,158 @@ pub const DEBUG_MAX_LINE_PREVIEW: usize = 200;
 /// Maximum number of entries that can be exported in a single operation.
 pub const MAX_EXPORT_ENTRIES: usize = 5_000_000;
 
+// =============================================================================
+// Filter query language
+// =============================================================================
+
+/// Maximum length in bytes of a filter query typed in the query bar or
+/// restored from a session.  Queries are parsed on every keystroke.
+pub const MAX_QUERY_LENGTH: usize = 4_096;
+
+/// Maximum nesting depth of parentheses and `NOT` in a filter query.  The
+/// parser is recursive; this bound keeps a pathological query from
+/// exhausting the stack.
+pub const MAX_QUERY_DEPTH: usize = 32;
+
+// =============================================================================
+// Log template mining (Patterns view)
+// =============================================================================
+
+/// Minimum share of token positions that must match an existing template
+/// for an entry to join it (Drain similarity threshold).
+pub const PATTERN_SIMILARITY_THRESHOLD: f64 = 0.5;
+
+/// Maximum number of tokens of a message's first line considered when
+/// mining templates.  Longer lines are truncated and end in an ellipsis.
+pub const MAX_PATTERN_TOKENS: usize = 64;
+
+/// Maximum number of templates created by one mining run.  Entries that
+/// would need a new template beyond this are counted as unclustered.
+pub const MAX_PATTERN_CLUSTERS: usize = 5_000;
+
+// =============================================================================
+// Anomaly detection (spikes and silences)
+// =============================================================================
+
+/// Number of time buckets the merged timeline is divided into for anomaly
+/// detection.  The bucket width is the timeline span divided by this,
+/// rounded up to whole minutes.
+pub const ANOMALY_TARGET_BUCKETS: i64 = 120;
+
+/// Narrowest anomaly-detection bucket in seconds.
+pub const ANOMALY_MIN_BUCKET_SECS: i64 = 60;
+
+/// Minimum number of buckets a file or component must span before it has a
+/// baseline; shorter series are not checked for spikes.
+pub const ANOMALY_MIN_SERIES_BUCKETS: usize = 8;
+
+/// Robust z-score (deviation from the median in units of the scaled median
+/// absolute deviation) at or above which a bucket counts as a spike.
+pub const ANOMALY_SPIKE_Z_THRESHOLD: f64 = 4.0;
+
+/// Minimum entry count in a bucket for it to be reported as a spike, so a
+/// handful of errors in an otherwise empty series is not flagged.
+pub const ANOMALY_MIN_SPIKE_COUNT: usize = 5;
+
+/// A quiet period in a file must be at least this many times the file's
+/// median gap between entries to count as a silence.
+pub const ANOMALY_SILENCE_FACTOR: f64 = 10.0;
+
+/// Shortest quiet period in seconds reported as a silence.
+pub const ANOMALY_MIN_SILENCE_SECS: i64 = 300;
+
+/// Minimum number of timestamped entries a file needs before silences in it
+/// are reported.
+pub const ANOMALY_MIN_SILENCE_ENTRIES: usize = 20;
+
+/// Maximum number of findings kept by one analysis run (most significant
+/// first).
+pub const MAX_ANOMALY_FINDINGS: usize = 200;
+
+// =============================================================================
+// Compare mode
+// =============================================================================
+
+/// A message present on both sides of a comparison is reported as changed
+/// when its share of one side is at least this many times its share of the
+/// other.
+pub const COMPARE_CHANGE_RATIO: f64 = 3.0;
+
+/// Minimum occurrences (on the busier side) for a frequency change to be
+/// reported, so one-off messages do not clutter the list.
+pub const COMPARE_MIN_CHANGE_COUNT: usize = 5;
+
+// =============================================================================
+// Live alert rules
+// =============================================================================
+
+/// Maximum number of saved alert rules.
+pub const MAX_ALERT_RULES: usize = 50;
+
+/// Maximum length of an alert rule name in characters.
+pub const MAX_ALERT_RULE_NAME_LEN: usize = 64;
+
+/// Match count a new rule fires at.
+pub const DEFAULT_ALERT_THRESHOLD: usize = 3;
+
+/// Maximum match count of a rule.  A rule remembers one arrival time per
+/// match up to its threshold, so this also bounds its memory.
+pub const MAX_ALERT_THRESHOLD: usize = 1_000;
+
+/// Window a new rule counts matches over (seconds).
+pub const DEFAULT_ALERT_WINDOW_SECS: u64 = 60;
+
+/// Maximum window of a rule (seconds).
+pub const MAX_ALERT_WINDOW_SECS: u64 = 24 * 3_600; // 24 h
+
+/// Maximum firings acted on per batch of tail entries.  Further firings in
+/// the same batch are only counted, so a flood of matches cannot pin
+/// thousands of entries or start thousands of commands.
+pub const MAX_ALERT_FIRINGS_PER_BATCH: usize = 20;
+
+/// Maximum alert commands running at once; a firing while this many are
+/// still running skips its command (the alert log still records it).
+pub const MAX_ALERT_COMMANDS_IN_FLIGHT: usize = 4;
+
+/// An alert command still running after this long is killed (seconds).
+pub const ALERT_COMMAND_TIMEOUT_SECS: u64 = 30;
+
+/// The alert log is moved to `alerts.log.1` once it reaches this size.
+pub const MAX_ALERT_LOG_BYTES: u64 = 5 * 1024 * 1024; // 5 MiB
+
+/// How long the status bar flashes after a rule fires (seconds).
+pub const ALERT_FLASH_SECS: u64 = 10;
+
 // =============================================================================
 // Configuration
 // =============================================================================
 
+// =============================================================================
+// Compressed / archived logs
+// =============================================================================
+
+/// Separator between an archive path and a member name in a virtual
+/// `DiscoveredFile` path, e.g. `bundle.zip!/logs/app.log`.
+pub const ARCHIVE_MEMBER_SEPARATOR: &str = "!/";
+
+/// Maximum members listed from a single archive.  Support bundles can hold
+/// thousands of files; members beyond this cap are skipped with a warning.
+pub const MAX_ARCHIVE_MEMBERS: usize = 5_000;
+
+/// Maximum decompressed size of a single archive member (1 GiB).  Guards
+/// against decompression bombs; reading past it fails with an error.
+pub const MAX_ARCHIVE_MEMBER_BYTES: u64 = 1024 * 1024 * 1024;
+
 // =============================================================================
 // Windows Event Log (.evtx) support
 // =============================================================================
 
 /// Profile ID for the built-in Windows Event Log (.evtx) format.
 /// Used by `core::evtx_parser` and `core::profile::create_evtx_profile()`.
-#[cfg(target_os = "windows")]
 pub const EVTX_PROFILE_ID: &str = "windows-evtx";
 
 /// Maximum EventData key=value pairs extracted from an .evtx event XML.
 /// Prevents extremely long messages from events with many data fields.
-#[cfg(target_os = "windows")]
 pub const EVTX_MAX_DATA_PAIRS: usize = 20;
 
 /// Configuration file name.
@@ -386,5 +651,29 @@ pub const CONFIG_FILE_NAME: &str = "config.toml";
 /// Session persistence file name (stored in the platform data directory).
 pub const SESSION_FILE_NAME: &str = "session.json";
 
+/// On-disk entry store subdirectory (in the platform data directory).
+/// Sessions beyond `max_total_entries` spill their entries there.
+pub const ENTRY_STORE_DIR_NAME: &str = "entry-store";
+
+/// Entry store segments of other processes older than this are treated as
+/// left over from a crash and deleted at startup (7 days).
+pub const STALE_ENTRY_STORE_SECS: u64 = 7 * 24 * 3_600;
+
+/// Saved filter presets file name (stored next to `config.toml`).
+pub const FILTER_PRESETS_FILE_NAME: &str = "filter_presets.toml";
+
+/// Maximum number of saved filter presets.  Each preset is a sidebar button;
+/// imports beyond this cap are dropped with a status-bar note.
+pub const MAX_FILTER_PRESETS: usize = 50;
+
+/// Maximum length of a filter preset name in characters.
+pub const MAX_FILTER_PRESET_NAME_LEN: usize = 64;
+
+/// Saved alert rules file name (stored next to `config.toml`).
+pub const ALERT_RULES_FILE_NAME: &str = "alert_rules.toml";
+
+/// Alert log file name (stored next to `config.toml`): one line per firing.
+pub const ALERT_LOG_FILE_NAME: &str = "alerts.log";
+
 /// User profiles subdirectory name.
 pub const PROFILES_DIR_NAME: &str = "profiles";
@@ -30,6 +30,11 @@ pub enum LogSleuthError {
     /// Configuration loading or validation failed.
     Config(ConfigError),
 
+    /// A background scan reported failure or was cancelled.  The scan
+    /// pipeline reports failures as `ScanProgress::Failed` messages, so only
+    /// the message text is available here.
+    Scan { reason: String },
+
     /// I/O error with path context.
     Io {
         path: PathBuf,
@@ -47,6 +52,7 @@ impl fmt::Display for LogSleuthError {
             Self::Filter(e) => write!(f, "Filter error: {e}"),
             Self::Export(e) => write!(f, "Export error: {e}"),
             Self::Config(e) => write!(f, "Configuration error: {e}"),
+            Self::Scan { reason } => write!(f, "Scan failed: {reason}"),
             Self::Io {
                 path,
                 operation,
@@ -69,6 +75,7 @@ impl std::error::Error for LogSleuthError {
             Self::Filter(e) => Some(e),
             Self::Export(e) => Some(e),
             Self::Config(e) => Some(e),
+            Self::Scan { .. } => None,
             Self::Io { source, .. } => Some(source),
         }
     }
@@ -123,6 +130,14 @@ pub enum ProfileError {
         reason: String,
     },
 
+    /// The `[parsing] timezone` value is not UTC, local, an offset or an IANA
+    /// zone name.
+    InvalidTimezone {
+        profile_id: String,
+        value: String,
+        reason: String,
+    },
+
     /// Duplicate profile ID detected (user profile overriding built-in is OK,
     /// but two user profiles with the same ID is an error).
     DuplicateId {
@@ -186,6 +201,14 @@ impl fmt::Display for ProfileError {
                 f,
                 "Profile '{profile_id}': invalid timestamp format '{format}': {reason}"
             ),
+            Self::InvalidTimezone {
+                profile_id,
+                value,
+                reason,
+            } => write!(
+                f,
+                "Profile '{profile_id}': invalid timezone '{value}': {reason}"
+            ),
             Self::DuplicateId { id, path1, path2 } => write!(
                 f,
                 "Duplicate profile ID '{id}' in '{}' and '{}'",
@@ -393,13 +416,20 @@ impl From<ParseError> for LogSleuthError {
 // ---------------------------------------------------------------------------
 
 /// Errors related to filter operations.
-#[derive(Debug)]
+///
+/// `Clone` so the filter state can keep the last query error for the UI.
+#[derive(Debug, Clone)]
 pub enum FilterError {
     /// User-provided regex is invalid.
     InvalidRegex {
         pattern: String,
         source: regex::Error,
     },
+
+    /// User-provided filter query could not be parsed.  `position` is the
+    /// 0-based character offset of the offending token so the UI can point
+    /// at it.
+    InvalidQuery { position: usize, reason: String },
 }
 
 impl fmt::Display for FilterError {
@@ -408,6 +438,9 @@ impl fmt::Display for FilterError {
             Self::InvalidRegex { pattern, source } => {
                 write!(f, "Invalid filter regex '{pattern}': {source}")
             }
+            Self::InvalidQuery { position, reason } => {
+                write!(f, "Invalid query at column {}: {reason}", position + 1)
+            }
         }
     }
 }
@@ -416,6 +449,7 @@ impl std::error::Error for FilterError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::InvalidRegex { source, .. } => Some(source),
+            Self::InvalidQuery { .. } => None,
         }
     }
 }
@@ -447,6 +481,9 @@ pub enum ExportError {
 
     /// Export would exceed maximum entry count.
     TooManyEntries { count: usize, max: usize },
+
+    /// Reading the exported entries from the on-disk entry store failed.
+    Store { source: io::Error },
 }
 
 impl fmt::Display for ExportError {
@@ -466,6 +503,7 @@ impl fmt::Display for ExportError {
                 "Export of {count} entries exceeds maximum of {max}. \
                  Apply filters to reduce the result set."
             ),
+            Self::Store { source } => write!(f, "Entry store read error: {source}"),
         }
     }
 }
@@ -476,6 +514,7 @@ impl std::error::Error for ExportError {
             Self::Io { source, .. } => Some(source),
             Self::Csv { source, .. } => Some(source),
             Self::Json { source, .. } => Some(source),
+            Self::Store { source } => Some(source),
             _ => None,
         }
     }
@@ -509,6 +548,12 @@ pub enum ConfigError {
 
     /// I/O error reading config file.
     Io { path: PathBuf, source: io::Error },
+
+    /// Serialising a TOML file (e.g. filter presets) failed.
+    TomlSerialize {
+        path: PathBuf,
+        source: toml::ser::Error,
+    },
 }
 
 impl fmt::Display for ConfigError {
@@ -528,6 +573,9 @@ impl fmt::Display for ConfigError {
             Self::Io { path, source } => {
                 write!(f, "Config I/O error '{}': {source}", path.display())
             }
+            Self::TomlSerialize { path, source } => {
+                write!(f, "Cannot serialise '{}': {source}", path.display())
+            }
         }
     }
 }
@@ -537,6 +585,7 @@ impl std::error::Error for ConfigError {
         match self {
             Self::TomlParse { source, .. } => Some(source),
             Self::Io { source, .. } => Some(source),
+            Self::TomlSerialize { source, .. } => Some(source),
             _ => None,
         }
     }
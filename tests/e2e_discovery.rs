@@ -305,7 +305,7 @@ fn e2e_parse_veeam_vbr_sample() {
     // All entries should reference the correct source file.
     for entry in &result.entries {
         assert_eq!(
-            entry.source_file, fixture_path,
+            *entry.source_file, *fixture_path,
             "source_file should be the fixture path"
         );
     }
@@ -423,7 +423,7 @@ fn e2e_veeam_vbr_severity_mapping() {
     assert!(
         error_entries
             .iter()
-            .any(|e| e.message.contains("Failed to create snapshot")),
+            .any(|e| e.message().contains("Failed to create snapshot")),
         "should find the snapshot failure error entry"
     );
 }
@@ -640,13 +640,14 @@ fn make_entry(id: u64, severity: Severity, message: &str, ts_offset_secs: i64) -
         id,
         timestamp: Some(chrono::Utc::now() - chrono::Duration::seconds(ts_offset_secs)),
         severity,
-        message: message.to_string(),
+        message: message.to_string().into(),
         raw_text: message.to_string(),
-        source_file: std::path::PathBuf::from("app.log"),
+        source_file: std::path::PathBuf::from("app.log").into(),
         line_number: id,
         thread: None,
         component: None,
-        profile_id: "test".to_string(),
+        profile_id: "test".to_string().into(),
+        fields: Default::default(),
         file_modified: None,
     }
 }
@@ -798,7 +799,7 @@ fn e2e_parse_veeam_vbo365_sample() {
 
     // All entries should reference the correct source file.
     for entry in &result.entries {
-        assert_eq!(entry.source_file, fixture_path);
+        assert_eq!(*entry.source_file, *fixture_path);
     }
 
     // Severity is inferred from message content for VBO365 (no explicit level field).